@@ -0,0 +1,33 @@
+use booky::lex;
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Number of lookup rounds
+const ROUNDS: usize = 20;
+
+/// Time lookups of all words, printing nanoseconds per lookup
+fn bench<F>(name: &str, words: &[String], mut lookup: F)
+where
+    F: FnMut(&str) -> usize,
+{
+    let start = Instant::now();
+    let mut found = 0;
+    for _ in 0..ROUNDS {
+        for word in words {
+            found += lookup(black_box(word));
+        }
+    }
+    let ns = start.elapsed().as_nanos() / (ROUNDS * words.len()) as u128;
+    println!("{name:>14}: {ns:>4} ns/lookup ({found} found)");
+}
+
+fn main() {
+    let lex = lex::builtin();
+    let mut words: Vec<String> = lex.iter_forms().map(String::from).collect();
+    // capitalized and unknown words
+    words.extend(lex.iter().map(|w| w.lemma().to_uppercase()));
+    words.extend(lex.iter().map(|w| format!("{}zq", w.lemma())));
+    fastrand::shuffle(&mut words);
+    bench("contains", &words, |w| usize::from(lex.contains(w)));
+    bench("word_entries", &words, |w| lex.word_entries(w).len());
+}
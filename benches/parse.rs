@@ -0,0 +1,28 @@
+use booky::lex;
+use booky::tally::WordTally;
+use std::hint::black_box;
+use std::time::Instant;
+
+/// Number of copies of the lexicon text to parse
+const COPIES: usize = 10;
+
+/// Number of parse rounds (best is reported)
+const ROUNDS: usize = 5;
+
+fn main() {
+    let text = lex::builtin_csv().replace([':', ','], " ").repeat(COPIES);
+    let mb = text.len() as f64 / 1_000_000.0;
+    let mut best = 0.0;
+    let mut words = 0;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        let mut tally = WordTally::new();
+        tally.parse_text(black_box(text.as_bytes())).unwrap();
+        let rate = mb / start.elapsed().as_secs_f64();
+        if rate > best {
+            best = rate;
+        }
+        words = tally.len();
+    }
+    println!("{:>14}: {best:>6.2} MB/s ({words} words)", "parse_text");
+}
@@ -6,7 +6,7 @@ use booky::word::{Lexeme, WordClass};
 fn main() -> Result<()> {
     let lex = lex::builtin();
     for word in lex.iter() {
-        if !keep(&lex, word) {
+        if !keep(lex, word) {
             println!("{word:?}");
         }
     }
@@ -16,12 +16,10 @@ fn main() -> Result<()> {
 fn keep(lex: &Lexicon, word: &Lexeme) -> bool {
     if WordClass::Noun == word.word_class() {
         for w in lex.iter() {
-            if WordClass::Noun == w.word_class() {
-                if w != word {
-                    for form in w.forms() {
-                        if form == word.lemma() {
-                            return false;
-                        }
+            if WordClass::Noun == w.word_class() && w != word {
+                for form in w.forms() {
+                    if form == word.lemma() {
+                        return false;
                     }
                 }
             }
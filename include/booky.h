@@ -0,0 +1,55 @@
+#ifndef BOOKY_H
+#define BOOKY_H
+
+/* Generated by cbindgen; do not edit */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ Split text into words
+
+ Each word is on a separate line, prefixed by its kind code and a tab
+ (e.g. `l\tcat`).  The result must be freed with `booky_free`.  NULL is
+ returned if `text` is NULL or not valid UTF-8.
+
+ # Safety
+
+ `text` must be NULL or a valid NUL-terminated string.
+ */
+char *booky_tokenize(const char *text);
+
+/*
+ Look up a word form in the built-in lexicon
+
+ Each matching entry is on a separate line, in lexicon CSV format (e.g.
+ `mouse:N,mice`).  The result must be freed with `booky_free`.  NULL is
+ returned if the word is not found, or `word` is NULL or not valid UTF-8.
+
+ # Safety
+
+ `word` must be NULL or a valid NUL-terminated string.
+ */
+char *booky_lookup(const char *word);
+
+/*
+ Free a string returned by `booky_tokenize` or `booky_lookup`
+
+ # Safety
+
+ `s` must be NULL or a string returned by a `booky` function, which has
+ not already been freed.
+ */
+void booky_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BOOKY_H */
@@ -0,0 +1,95 @@
+use crate::lex::make_word;
+use std::sync::{Arc, LazyLock};
+
+/// Built-in abbreviations (lowercase, with dots)
+const BUILTIN: &[&str] = &[
+    "a.m.", "approx.", "capt.", "cf.", "col.", "dept.", "dr.", "e.g.", "etc.",
+    "gen.", "gov.", "hon.", "i.e.", "inc.", "jr.", "lt.", "ltd.", "messrs.",
+    "mlle.", "mme.", "mr.", "mrs.", "ms.", "mt.", "p.m.", "prof.", "rev.",
+    "sgt.", "sr.", "st.", "vs.",
+];
+
+/// Static abbreviation table
+static ABBREVIATIONS: LazyLock<Arc<Abbreviations>> = LazyLock::new(|| {
+    let mut abbrevs = Abbreviations::new();
+    for abbr in BUILTIN {
+        abbrevs.insert(abbr);
+    }
+    Arc::new(abbrevs)
+});
+
+/// Table of abbreviations ending with a dot
+///
+/// A `Parser` keeps the dots of known abbreviations ("Mr.", "e.g.") as part
+/// of the word, instead of splitting them off as symbols.
+#[derive(Clone, Debug, Default)]
+pub struct Abbreviations {
+    /// Abbreviations (lowercase, sorted)
+    abbrevs: Vec<String>,
+}
+
+/// Get the built-in abbreviation table
+pub fn builtin() -> &'static Abbreviations {
+    &ABBREVIATIONS
+}
+
+/// Get a shared handle to the built-in abbreviation table
+pub fn builtin_shared() -> Arc<Abbreviations> {
+    Arc::clone(&ABBREVIATIONS)
+}
+
+impl Abbreviations {
+    /// Create an empty abbreviation table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert an abbreviation
+    ///
+    /// A dot is appended if it does not end with one (e.g. "Corp").
+    pub fn insert(&mut self, abbr: &str) {
+        let mut abbr = make_word(abbr);
+        if !abbr.ends_with('.') {
+            abbr.push('.');
+        }
+        if let Err(i) = self.abbrevs.binary_search(&abbr) {
+            self.abbrevs.insert(i, abbr);
+        }
+    }
+
+    /// Check if a word is an abbreviation (case insensitive)
+    pub fn contains(&self, word: &str) -> bool {
+        self.abbrevs.binary_search(&make_word(word)).is_ok()
+    }
+
+    /// Check if text is the start of any abbreviation
+    pub fn is_prefix(&self, text: &str) -> bool {
+        let text = make_word(text);
+        let i = self.abbrevs.partition_point(|a| *a < text);
+        self.abbrevs.get(i).is_some_and(|a| a.starts_with(&text))
+    }
+
+    /// Get an iterator of all abbreviations (lowercase)
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.abbrevs.iter().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn abbreviations() {
+        let mut abbrevs = builtin().clone();
+        assert!(abbrevs.contains("Mr."));
+        assert!(abbrevs.contains("E.G."));
+        assert!(!abbrevs.contains("mr"));
+        assert!(abbrevs.is_prefix("e."));
+        assert!(abbrevs.is_prefix("p.m."));
+        assert!(!abbrevs.is_prefix("x."));
+        abbrevs.insert("Corp");
+        assert!(abbrevs.contains("corp."));
+        assert_eq!(abbrevs.iter().count(), BUILTIN.len() + 1);
+    }
+}
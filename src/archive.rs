@@ -0,0 +1,223 @@
+use crate::ingest::Format;
+use crate::{Error, Result};
+use flate2::read::GzDecoder;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+use zip::ZipArchive;
+
+/// Number of bytes checked for binary content
+const SNIFF_LEN: usize = 8192;
+
+/// Archive kind
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    /// Zip archive
+    Zip,
+    /// Tar archive
+    Tar,
+    /// Gzip-compressed tar archive
+    TarGz,
+}
+
+/// File contained in an archive
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    /// Path within archive
+    name: String,
+    /// File contents
+    data: Vec<u8>,
+}
+
+impl Entry {
+    /// Get path within archive
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get file contents
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Get archive kind of a path (by extension)
+fn archive_kind(path: &Path) -> Option<Kind> {
+    let name = path.file_name()?.to_str()?.to_lowercase();
+    if name.ends_with(".zip") {
+        Some(Kind::Zip)
+    } else if name.ends_with(".tar") {
+        Some(Kind::Tar)
+    } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        Some(Kind::TarGz)
+    } else {
+        None
+    }
+}
+
+/// Check if a path is an archive (zip, tar or tar.gz)
+pub fn is_archive(path: &Path) -> bool {
+    archive_kind(path).is_some()
+}
+
+/// Check if an entry is readable text (or a binary document format)
+fn is_readable(name: &str, data: &[u8]) -> bool {
+    let path = Path::new(name);
+    if path
+        .components()
+        .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+        || is_archive(path)
+    {
+        return false;
+    }
+    Format::from_path(path).is_some_and(Format::is_binary)
+        || !data[..data.len().min(SNIFF_LEN)].contains(&0)
+}
+
+/// Read entries from a zip archive
+fn zip_entries<R>(reader: R) -> Result<Vec<Entry>>
+where
+    R: Read + Seek,
+{
+    let mut archive = ZipArchive::new(reader)?;
+    let mut entries = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i)?;
+        if !file.is_file() {
+            continue;
+        }
+        let name = file.name().to_string();
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        entries.push(Entry { name, data });
+    }
+    Ok(entries)
+}
+
+/// Read entries from a tar archive
+fn tar_entries<R>(reader: R) -> Result<Vec<Entry>>
+where
+    R: Read,
+{
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    for file in archive.entries()? {
+        let mut file = file?;
+        if !file.header().entry_type().is_file() {
+            continue;
+        }
+        let name = file.path()?.to_string_lossy().into_owned();
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        entries.push(Entry { name, data });
+    }
+    Ok(entries)
+}
+
+/// Read text file entries from an archive (zip, tar or tar.gz)
+///
+/// Directories, hidden files, nested archives and binary files (other than
+/// document formats) are skipped.  Entries are sorted by name.
+pub fn read_entries(path: &Path) -> Result<Vec<Entry>> {
+    let file = BufReader::new(File::open(path)?);
+    let mut entries = match archive_kind(path) {
+        Some(Kind::Zip) => zip_entries(file)?,
+        Some(Kind::Tar) => tar_entries(file)?,
+        Some(Kind::TarGz) => tar_entries(GzDecoder::new(file))?,
+        None => {
+            return Err(Error::Decode("Unknown archive type".into()));
+        }
+    };
+    entries.retain(|e| is_readable(&e.name, &e.data));
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::{Cursor, Write};
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn zip() {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default();
+        for (name, data) in [
+            ("corpus/b.txt", &b"Second"[..]),
+            ("corpus/a.md", b"# First"),
+            ("corpus/.hidden", b"Skip"),
+            ("corpus/image.png", b"\x89PNG\0\0"),
+        ] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.add_directory("corpus/empty", options).unwrap();
+        let buf = zip.finish().unwrap().into_inner();
+        let mut entries = zip_entries(Cursor::new(buf)).unwrap();
+        assert_eq!(entries.len(), 4);
+        entries.retain(|e| is_readable(&e.name, &e.data));
+        let names: Vec<_> = entries.iter().map(Entry::name).collect();
+        assert_eq!(names, ["corpus/b.txt", "corpus/a.md"]);
+    }
+
+    #[test]
+    fn tar() {
+        let mut tar = tar::Builder::new(Vec::new());
+        for (name, data) in [("b/two.txt", &b"Two"[..]), ("one.txt", b"One")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_cksum();
+            tar.append_data(&mut header, name, data).unwrap();
+        }
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Directory);
+        header.set_size(0);
+        header.set_cksum();
+        tar.append_data(&mut header, "b/", &[][..]).unwrap();
+        let buf = tar.into_inner().unwrap();
+        let entries = tar_entries(&buf[..]).unwrap();
+        let names: Vec<_> = entries.iter().map(Entry::name).collect();
+        assert_eq!(names, ["b/two.txt", "one.txt"]);
+        assert_eq!(entries[1].data(), b"One");
+        assert!(tar_entries(&buf[..700]).is_err());
+        assert!(zip_entries(Cursor::new(&buf)).is_err());
+    }
+
+    #[test]
+    fn kinds() {
+        for (name, kind) in [
+            ("a.zip", Some(Kind::Zip)),
+            ("A.ZIP", Some(Kind::Zip)),
+            ("a.tar", Some(Kind::Tar)),
+            ("a.tar.gz", Some(Kind::TarGz)),
+            ("a.tgz", Some(Kind::TarGz)),
+            ("a.gz", None),
+            ("zip", None),
+            ("", None),
+        ] {
+            assert_eq!(archive_kind(Path::new(name)), kind, "{name}");
+        }
+        for (name, data, readable) in [
+            ("doc.docx", &b"PK\0\0"[..], true),
+            ("notes", b"plain", true),
+            ("blob.bin", b"\0\x01", false),
+            ("inner.zip", b"PK", false),
+            (".git/config", b"x", false),
+            ("a/./b.txt", b"x", true),
+            ("empty.txt", b"", true),
+        ] {
+            assert_eq!(is_readable(name, data), readable, "{name}");
+        }
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert!(matches!(
+            read_entries(&dir.join("Cargo.toml")),
+            Err(Error::Decode(_))
+        ));
+        assert!(matches!(
+            read_entries(&dir.join("missing.zip")),
+            Err(Error::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,310 @@
+use crate::exit;
+use anyhow::Result;
+use argh::{ArgsInfo, CommandInfoWithArgs, FlagInfo, FlagInfoKind, FromArgs};
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Program name
+const NAME: &str = "booky";
+
+/// Generate shell completions (bash, zsh, fish)
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "completions")]
+pub struct CompletionsCmd {
+    /// shell (bash, zsh, fish)
+    #[argh(positional)]
+    shell: Shell,
+}
+
+/// Shell kind
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Shell {
+    /// Bourne-again shell
+    Bash,
+    /// Z shell
+    Zsh,
+    /// Friendly interactive shell
+    Fish,
+}
+
+/// Command with full path of names
+struct Command<'a> {
+    /// Names (including program)
+    path: Vec<&'a str>,
+    /// Command info
+    info: &'a CommandInfoWithArgs,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(val: &str) -> Result<Self, Self::Err> {
+        match val {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            _ => Err(format!("Unknown shell: {val}")),
+        }
+    }
+}
+
+impl CompletionsCmd {
+    /// Run command
+    pub fn run(self) -> Result<()> {
+        let info = crate::Args::get_args_info();
+        let script = match self.shell {
+            Shell::Bash => bash_script(&info)?,
+            Shell::Zsh => zsh_script(&info)?,
+            Shell::Fish => fish_script(&info)?,
+        };
+        print!("{script}");
+        Ok(())
+    }
+}
+
+impl Command<'_> {
+    /// Get identifier for shell scripts
+    fn ident(&self) -> String {
+        self.path.join("_")
+    }
+
+    /// Get visible flags
+    fn flags(&self) -> impl Iterator<Item = &FlagInfo<'_>> {
+        self.info.flags.iter().filter(|f| !f.hidden)
+    }
+
+    /// Get all completion words (flags and sub-commands)
+    fn words(&self) -> Vec<(String, &str)> {
+        let mut words = Vec::new();
+        for flag in self.flags() {
+            words.push((flag.long.to_string(), flag.description));
+        }
+        for sub in &self.info.commands {
+            words.push((sub.name.to_string(), sub.command.description));
+        }
+        words
+    }
+}
+
+/// Collect all commands (depth first)
+fn commands(info: &CommandInfoWithArgs) -> Vec<Command<'_>> {
+    let mut cmds = Vec::new();
+    collect_commands(vec![NAME], info, &mut cmds);
+    cmds
+}
+
+/// Collect a command and its sub-commands
+fn collect_commands<'a>(
+    path: Vec<&'a str>,
+    info: &'a CommandInfoWithArgs,
+    cmds: &mut Vec<Command<'a>>,
+) {
+    cmds.push(Command {
+        path: path.clone(),
+        info,
+    });
+    for sub in &info.commands {
+        let mut p = path.clone();
+        p.push(sub.name);
+        collect_commands(p, &sub.command, cmds);
+    }
+}
+
+/// Make bash completion script
+fn bash_script(info: &CommandInfoWithArgs) -> Result<String> {
+    let cmds = commands(info);
+    let mut out = String::new();
+    writeln!(out, "_{NAME}() {{")?;
+    writeln!(out, "    local cur cmd opts i")?;
+    writeln!(out, "    cur=\"${{COMP_WORDS[COMP_CWORD]}}\"")?;
+    writeln!(out, "    cmd={NAME}")?;
+    writeln!(out, "    for ((i = 1; i < COMP_CWORD; i++)); do")?;
+    writeln!(out, "        case \"$cmd:${{COMP_WORDS[i]}}\" in")?;
+    for cmd in &cmds {
+        let id = cmd.ident();
+        for sub in &cmd.info.commands {
+            writeln!(out, "            {id}:{0}) cmd={id}_{0} ;;", sub.name)?;
+        }
+    }
+    writeln!(out, "        esac")?;
+    writeln!(out, "    done")?;
+    writeln!(out, "    case \"$cmd\" in")?;
+    for cmd in &cmds {
+        let words: Vec<_> = cmd.words().into_iter().map(|(w, _d)| w).collect();
+        writeln!(
+            out,
+            "        {}) opts=\"{}\" ;;",
+            cmd.ident(),
+            words.join(" ")
+        )?;
+    }
+    writeln!(out, "    esac")?;
+    writeln!(out, "    COMPREPLY=($(compgen -W \"$opts\" -- \"$cur\"))")?;
+    writeln!(out, "}}")?;
+    writeln!(out, "complete -o default -F _{NAME} {NAME}")?;
+    Ok(out)
+}
+
+/// Make zsh completion script
+fn zsh_script(info: &CommandInfoWithArgs) -> Result<String> {
+    let cmds = commands(info);
+    let mut out = String::new();
+    writeln!(out, "#compdef {NAME}")?;
+    writeln!(out)?;
+    writeln!(out, "_{NAME}() {{")?;
+    writeln!(out, "    local cmd={NAME} i")?;
+    writeln!(out, "    local -a opts")?;
+    writeln!(out, "    for ((i = 2; i < CURRENT; i++)); do")?;
+    writeln!(out, "        case \"$cmd:${{words[i]}}\" in")?;
+    for cmd in &cmds {
+        let id = cmd.ident();
+        for sub in &cmd.info.commands {
+            writeln!(out, "            {id}:{0}) cmd={id}_{0} ;;", sub.name)?;
+        }
+    }
+    writeln!(out, "        esac")?;
+    writeln!(out, "    done")?;
+    writeln!(out, "    case $cmd in")?;
+    for cmd in &cmds {
+        writeln!(out, "        {}) opts=(", cmd.ident())?;
+        for (word, desc) in cmd.words() {
+            let desc = desc.replace('\'', "'\\''");
+            writeln!(out, "            '{word}:{desc}'")?;
+        }
+        writeln!(out, "        ) ;;")?;
+    }
+    writeln!(out, "    esac")?;
+    writeln!(out, "    _describe -t commands \"$cmd\" opts")?;
+    writeln!(out, "    _files")?;
+    writeln!(out, "}}")?;
+    writeln!(out)?;
+    writeln!(out, "_{NAME} \"$@\"")?;
+    Ok(out)
+}
+
+/// Make fish completion script
+fn fish_script(info: &CommandInfoWithArgs) -> Result<String> {
+    let mut out = String::new();
+    for cmd in commands(info) {
+        let seen: Vec<_> = cmd.path[1..]
+            .iter()
+            .map(|name| format!("__fish_seen_subcommand_from {name}"))
+            .collect();
+        let cond = if seen.is_empty() {
+            "__fish_use_subcommand".to_string()
+        } else {
+            seen.join("; and ")
+        };
+        for flag in cmd.flags() {
+            let long = flag.long.trim_start_matches('-');
+            write!(out, "complete -c {NAME} -n '{cond}' -l {long}")?;
+            if let Some(short) = flag.short {
+                write!(out, " -s {short}")?;
+            }
+            if let FlagInfoKind::Option { .. } = flag.kind {
+                write!(out, " -r")?;
+            }
+            writeln!(out, " -d '{}'", fish_escape(flag.description))?;
+        }
+        let subs: Vec<_> = cmd.info.commands.iter().map(|s| s.name).collect();
+        let mut sub_cond = cond.clone();
+        if !seen.is_empty() && !subs.is_empty() {
+            sub_cond.push_str("; and not __fish_seen_subcommand_from ");
+            sub_cond.push_str(&subs.join(" "));
+        }
+        for sub in &cmd.info.commands {
+            writeln!(
+                out,
+                "complete -c {NAME} -n '{sub_cond}' -f -a {} -d '{}'",
+                sub.name,
+                fish_escape(sub.command.description)
+            )?;
+        }
+    }
+    Ok(out)
+}
+
+/// Escape a fish string (single-quoted)
+fn fish_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Make a man page (roff)
+pub fn man_page() -> Result<String> {
+    let info = crate::Args::get_args_info();
+    let mut out = String::new();
+    let version = env!("CARGO_PKG_VERSION");
+    let upper = NAME.to_uppercase();
+    writeln!(
+        out,
+        ".TH {upper} 1 \"\" \"{NAME} {version}\" \"User Commands\""
+    )?;
+    writeln!(out, ".SH NAME")?;
+    writeln!(
+        out,
+        "{NAME} \\- {}",
+        roff_escape(env!("CARGO_PKG_DESCRIPTION"))
+    )?;
+    writeln!(out, ".SH SYNOPSIS")?;
+    writeln!(out, ".B {NAME}")?;
+    writeln!(out, "[\\fIoptions\\fR] <\\fIcommand\\fR> [<\\fIargs\\fR>]")?;
+    let cmds = commands(&info);
+    for cmd in &cmds {
+        if cmd.path.len() == 1 {
+            writeln!(out, ".SH OPTIONS")?;
+            write_man_flags(&mut out, cmd)?;
+            writeln!(out, ".SH COMMANDS")?;
+            continue;
+        }
+        writeln!(out, ".SS \"{}\"", cmd.path.join(" "))?;
+        writeln!(out, "{}", roff_escape(cmd.info.description))?;
+        for pos in cmd.info.positionals.iter().filter(|p| !p.hidden) {
+            writeln!(out, ".TP")?;
+            writeln!(out, "\\fI{}\\fR", roff_escape(pos.name))?;
+            writeln!(out, "{}", roff_escape(pos.description))?;
+        }
+        write_man_flags(&mut out, cmd)?;
+    }
+    writeln!(out, ".SH EXIT STATUS")?;
+    for (code, desc) in [
+        (exit::OK, "Success"),
+        (
+            exit::FINDINGS,
+            "Findings reported (e.g. validation problems)",
+        ),
+        (exit::USAGE, "Usage error"),
+        (exit::IO, "I/O or parse error"),
+    ] {
+        writeln!(out, ".TP")?;
+        writeln!(out, ".B {code}")?;
+        writeln!(out, "{}", roff_escape(desc))?;
+    }
+    Ok(out)
+}
+
+/// Write man page flags of a command
+fn write_man_flags(out: &mut String, cmd: &Command) -> Result<()> {
+    for flag in cmd.flags() {
+        writeln!(out, ".TP")?;
+        if let Some(short) = flag.short {
+            write!(out, "\\fB\\-{short}\\fR, ")?;
+        }
+        write!(out, "\\fB{}\\fR", roff_escape(flag.long))?;
+        if let FlagInfoKind::Option { arg_name } = flag.kind {
+            write!(out, " \\fI{}\\fR", roff_escape(arg_name))?;
+        }
+        writeln!(out)?;
+        writeln!(out, "{}", roff_escape(flag.description))?;
+    }
+    Ok(())
+}
+
+/// Escape text for roff
+fn roff_escape(s: &str) -> String {
+    let s = s.replace('\\', "\\e").replace('-', "\\-");
+    if s.starts_with('.') || s.starts_with('\'') {
+        format!("\\&{s}")
+    } else {
+        s
+    }
+}
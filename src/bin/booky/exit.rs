@@ -0,0 +1,105 @@
+use serde_json::json;
+use std::fmt;
+use std::process::ExitCode;
+
+/// Exit code: success
+pub const OK: u8 = 0;
+
+/// Exit code: findings reported (e.g. validation problems)
+pub const FINDINGS: u8 = 1;
+
+/// Exit code: usage error
+pub const USAGE: u8 = 2;
+
+/// Exit code: I/O or parse error
+pub const IO: u8 = 3;
+
+/// Usage error (bad arguments)
+#[derive(Debug)]
+pub struct UsageError(pub String);
+
+/// Findings were reported (not printed as an error)
+#[derive(Debug)]
+pub struct Findings(pub usize);
+
+impl fmt::Display for UsageError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.0)
+    }
+}
+
+impl std::error::Error for UsageError {}
+
+impl fmt::Display for Findings {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{} findings", self.0)
+    }
+}
+
+impl std::error::Error for Findings {}
+
+/// Make a usage error
+pub fn usage(msg: impl Into<String>) -> anyhow::Error {
+    UsageError(msg.into()).into()
+}
+
+/// Get exit code for an error
+fn code(err: &anyhow::Error) -> u8 {
+    if err.is::<Findings>() {
+        FINDINGS
+    } else if err.is::<UsageError>() {
+        USAGE
+    } else {
+        IO
+    }
+}
+
+/// Get error kind name
+fn kind_name(code: u8) -> &'static str {
+    match code {
+        FINDINGS => "findings",
+        USAGE => "usage",
+        _ => "io",
+    }
+}
+
+/// Report a usage error, returning the exit code
+pub fn report_usage(msg: &str, json: bool) -> ExitCode {
+    if json {
+        print_error(USAGE, msg, &[]);
+    } else {
+        eprintln!("{msg}");
+    }
+    ExitCode::from(USAGE)
+}
+
+/// Report an error, returning the exit code
+pub fn report(err: anyhow::Error, json: bool) -> ExitCode {
+    let code = code(&err);
+    if code == FINDINGS {
+        return ExitCode::from(code);
+    }
+    if json {
+        let causes: Vec<_> =
+            err.chain().skip(1).map(|e| e.to_string()).collect();
+        print_error(code, &err.to_string(), &causes);
+    } else if code == USAGE {
+        eprintln!("Error: {err}");
+    } else {
+        eprintln!("Error: {err:?}");
+    }
+    ExitCode::from(code)
+}
+
+/// Print an error as a JSON document (to stderr)
+fn print_error(code: u8, message: &str, causes: &[String]) {
+    let doc = json!({
+        "error": {
+            "code": code,
+            "kind": kind_name(code),
+            "message": message,
+            "causes": causes,
+        }
+    });
+    eprintln!("{doc}");
+}
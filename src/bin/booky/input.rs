@@ -0,0 +1,356 @@
+use crate::exit;
+use anyhow::{Context, Result};
+use booky::gutenberg;
+use booky::ingest::{Format, Ingest};
+use booky::lang::{self, Language};
+use booky::tally::WordTally;
+use glob::MatchOptions;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Cursor, IsTerminal, Read, Stdin};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use yansi::Paint;
+
+/// Number of bytes checked for binary content
+const SNIFF_LEN: usize = 8192;
+
+/// Input text preprocessing
+#[derive(Clone, Debug, Default)]
+pub struct Preprocess {
+    /// Input format (detected if not set)
+    pub format: Option<Format>,
+    /// Strip Project Gutenberg boilerplate
+    pub strip_gutenberg: bool,
+    /// Selected columns (CSV / TSV)
+    pub columns: Vec<String>,
+    /// Selected field paths (JSON)
+    pub fields: Vec<String>,
+}
+
+/// Readers of several inputs, read in order as one text
+struct Concat {
+    /// Remaining readers
+    readers: VecDeque<Box<dyn BufRead>>,
+}
+
+impl Preprocess {
+    /// Get text ingester
+    fn ingest(&self) -> Ingest {
+        let mut ingest = Ingest::new();
+        if let Some(format) = self.format {
+            ingest = ingest.format(format);
+        }
+        for column in &self.columns {
+            ingest = ingest.column(column);
+        }
+        for field in &self.fields {
+            ingest = ingest.field(field);
+        }
+        ingest
+    }
+
+    /// Prepare a reader, applying preprocessing
+    ///
+    /// The path (if any) is used for format detection.
+    pub fn prepare<'a, R>(
+        &self,
+        reader: R,
+        path: Option<&Path>,
+    ) -> Result<Box<dyn BufRead + 'a>>
+    where
+        R: BufRead + 'a,
+    {
+        let mut reader = self.ingest().read(reader, path)?;
+        if !self.strip_gutenberg {
+            return Ok(reader);
+        }
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        let text = gutenberg::strip_boilerplate(&text).to_string();
+        Ok(Box::new(Cursor::new(text.into_bytes())))
+    }
+}
+
+/// Expand input arguments (files, directories and glob patterns)
+///
+/// Directories are only walked when `recursive` is set.  Binary files found
+/// by walking or glob matching are skipped (other than archives, PDF or DOCX).
+/// A pattern or directory which contains no files is an error.
+pub fn expand_paths(args: &[String], recursive: bool) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for arg in args {
+        let count = paths.len();
+        if is_pattern(arg) {
+            let options = MatchOptions {
+                require_literal_leading_dot: true,
+                ..MatchOptions::new()
+            };
+            let entries = glob::glob_with(arg, options).map_err(|e| {
+                exit::usage(format!("Bad pattern `{arg}`: {e}"))
+            })?;
+            for entry in entries {
+                let path = entry?;
+                if path.is_dir() {
+                    if recursive {
+                        walk_dir(&path, &mut paths)?;
+                    }
+                } else if is_readable(&path)? {
+                    paths.push(path);
+                }
+            }
+        } else {
+            let path = PathBuf::from(arg);
+            if path.is_dir() {
+                if !recursive {
+                    return Err(exit::usage(format!(
+                        "`{arg}` is a directory (use --recursive)"
+                    )));
+                }
+                walk_dir(&path, &mut paths)?;
+            } else {
+                paths.push(path);
+            }
+        }
+        if paths.len() == count {
+            return Err(anyhow::anyhow!("No files match `{arg}`"));
+        }
+    }
+    Ok(paths)
+}
+
+impl Read for Concat {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let src = self.fill_buf()?;
+        let n = src.len().min(buf.len());
+        buf[..n].copy_from_slice(&src[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for Concat {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while let Some(reader) = self.readers.front_mut() {
+            if !reader.fill_buf()?.is_empty() {
+                break;
+            }
+            self.readers.pop_front();
+        }
+        match self.readers.front_mut() {
+            Some(reader) => reader.fill_buf(),
+            None => Ok(&[]),
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Some(reader) = self.readers.front_mut() {
+            reader.consume(amt);
+        }
+    }
+}
+
+/// Open input files (or stdin if there are none) as one text
+///
+/// Files are read in order, separated by a blank line.
+pub fn open_text(
+    files: &[String],
+    recursive: bool,
+    prep: &Preprocess,
+) -> Result<Box<dyn BufRead>> {
+    if files.is_empty() {
+        return prep.prepare(stdin()?.lock(), None);
+    }
+    let mut readers: VecDeque<Box<dyn BufRead>> = VecDeque::new();
+    for path in expand_paths(files, recursive)? {
+        for (_name, reader) in open_inputs(&path, prep)? {
+            if !readers.is_empty() {
+                readers.push_back(Box::new(&b"\n\n"[..]));
+            }
+            readers.push_back(reader);
+        }
+    }
+    Ok(Box::new(Concat { readers }))
+}
+
+/// Get stdin, which must be redirected
+pub fn stdin() -> Result<Stdin> {
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        return Err(exit::usage("stdin must be redirected"));
+    }
+    Ok(stdin)
+}
+
+/// Clipboard paste commands (tried in order)
+const PASTE_COMMANDS: &[&[&str]] = &[
+    &["pbpaste"],
+    &["wl-paste", "--no-newline"],
+    &["xclip", "-selection", "clipboard", "-o"],
+    &["xsel", "--clipboard", "--output"],
+    &["powershell", "-NoProfile", "-Command", "Get-Clipboard"],
+];
+
+/// Read text from the clipboard
+pub fn clipboard() -> Result<String> {
+    for cmd in PASTE_COMMANDS {
+        if let Ok(out) = Command::new(cmd[0]).args(&cmd[1..]).output()
+            && out.status.success()
+        {
+            return Ok(String::from_utf8_lossy(&out.stdout).into_owned());
+        }
+    }
+    Err(anyhow::anyhow!(
+        "Cannot read clipboard (tried pbpaste, wl-paste, xclip, xsel)"
+    ))
+}
+
+/// Fetch text from a URL
+///
+/// Returns the body and the format from its content type (if known).
+#[cfg(feature = "fetch")]
+pub fn fetch(url: &str) -> Result<(String, Option<Format>)> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(exit::usage(format!("Invalid URL: {url}")));
+    }
+    let resp = ureq::get(url)
+        .call()
+        .with_context(|| format!("Cannot fetch `{url}`"))?;
+    let content_type = resp.content_type().to_ascii_lowercase();
+    let format = match content_type.as_str() {
+        "text/html" | "application/xhtml+xml" => Some(Format::Html),
+        "application/rss+xml" | "application/atom+xml" => Some(Format::Feed),
+        "application/json" => Some(Format::Json),
+        "text/markdown" => Some(Format::Markdown),
+        _ => None,
+    };
+    let body = resp
+        .into_string()
+        .with_context(|| format!("Cannot read `{url}`"))?;
+    Ok((body, format))
+}
+
+/// Check if an argument is a glob pattern
+pub fn is_pattern(arg: &str) -> bool {
+    arg.contains(['*', '?', '['])
+}
+
+/// Walk a directory, adding all text files (skipping hidden entries)
+fn walk_dir(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(dir)
+        .with_context(|| format!("Cannot read `{}`", dir.display()))?
+    {
+        let entry = entry?;
+        if !entry.file_name().to_string_lossy().starts_with('.') {
+            entries.push(entry.path());
+        }
+    }
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            walk_dir(&path, paths)?;
+        } else if is_readable(&path)? {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Check if a file is readable (an archive, a binary document format, or
+/// text with no NUL bytes at start)
+fn is_readable(path: &Path) -> Result<bool> {
+    if Format::from_path(path).is_some_and(Format::is_binary) {
+        return Ok(true);
+    }
+    #[cfg(feature = "archive")]
+    if booky::archive::is_archive(path) {
+        return Ok(true);
+    }
+    let file = File::open(path)
+        .with_context(|| format!("Cannot open `{}`", path.display()))?;
+    let mut buf = Vec::with_capacity(SNIFF_LEN);
+    file.take(SNIFF_LEN as u64).read_to_end(&mut buf)?;
+    Ok(!buf.contains(&0))
+}
+
+/// Open a file for input (or each text file within an archive)
+///
+/// Returns a list of (name, reader) pairs.
+pub fn open_inputs(
+    path: &Path,
+    prep: &Preprocess,
+) -> Result<Vec<(String, Box<dyn BufRead>)>> {
+    #[cfg(feature = "archive")]
+    if booky::archive::is_archive(path) {
+        let entries = booky::archive::read_entries(path)
+            .with_context(|| format!("Cannot read `{}`", path.display()))?;
+        let mut inputs = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let name = format!("{}:{}", path.display(), entry.name());
+            let reader = prep
+                .prepare(
+                    Cursor::new(entry.data().to_vec()),
+                    Some(Path::new(entry.name())),
+                )
+                .with_context(|| format!("Cannot read `{name}`"))?;
+            inputs.push((name, reader));
+        }
+        return Ok(inputs);
+    }
+    let name = path.display().to_string();
+    let file =
+        File::open(path).with_context(|| format!("Cannot open `{name}`"))?;
+    let reader = prep
+        .prepare(BufReader::new(file), Some(path))
+        .with_context(|| format!("Cannot read `{name}`"))?;
+    Ok(vec![(name, reader)])
+}
+
+/// Warn if the start of a text does not look like English
+///
+/// Only buffered input is checked, so nothing is consumed from the reader.
+pub fn check_language<R>(name: &str, reader: &mut R) -> Result<()>
+where
+    R: BufRead + ?Sized,
+{
+    let sample = String::from_utf8_lossy(reader.fill_buf()?).into_owned();
+    if let Some(lang) = lang::detect_language(sample.as_bytes())?
+        && lang != Language::English
+    {
+        eprintln!("{} `{name}` looks like {lang}", "!!!".bright_yellow());
+    }
+    Ok(())
+}
+
+/// Tally one file (merging all files within an archive)
+pub fn tally_file(
+    path: &Path,
+    prep: &Preprocess,
+    tally: &mut WordTally,
+) -> Result<()> {
+    for (name, mut reader) in open_inputs(path, prep)? {
+        check_language(&name, &mut reader)?;
+        tally
+            .parse_text(reader)
+            .with_context(|| format!("Cannot read `{name}`"))?;
+    }
+    Ok(())
+}
+
+/// Process files, in parallel with the `rayon` feature
+///
+/// Results are in the same order as paths.
+pub fn map_files<T, F>(paths: &[PathBuf], f: F) -> Vec<Result<T>>
+where
+    T: Send,
+    F: Fn(&Path) -> Result<T> + Sync,
+{
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        paths.par_iter().map(|path| f(path)).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    paths.iter().map(|path| f(path)).collect()
+}
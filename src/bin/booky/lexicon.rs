@@ -0,0 +1,392 @@
+use crate::exit::{self, Findings};
+use anyhow::{Context, Result, bail};
+use argh::{ArgsInfo, FromArgs};
+use booky::lex;
+use booky::word::{Lexeme, WordAttr, WordClass};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use yansi::Paint;
+
+/// Lexicon maintenance commands
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "lexicon")]
+pub struct LexiconCmd {
+    #[argh(subcommand)]
+    action: Action,
+}
+
+/// Lexicon actions
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand)]
+enum Action {
+    Add(AddAction),
+    Validate(ValidateAction),
+    Sort(SortAction),
+    Dedupe(DedupeAction),
+    Stats(StatsAction),
+}
+
+/// Add entries to a lexicon file (keeping sort order)
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "add")]
+struct AddAction {
+    /// lexicon CSV file (default: built-in)
+    #[argh(option, short = 'f')]
+    file: Option<String>,
+    /// rewrite file in place
+    #[argh(switch, short = 'i')]
+    in_place: bool,
+    /// entries to add (e.g. `blorf:V`)
+    #[argh(positional)]
+    entries: Vec<String>,
+}
+
+/// Validate lexicon entries
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "validate")]
+struct ValidateAction {
+    /// lexicon CSV file (default: built-in)
+    #[argh(option, short = 'f')]
+    file: Option<String>,
+}
+
+/// Sort lexicon entries
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "sort")]
+struct SortAction {
+    /// lexicon CSV file (default: built-in)
+    #[argh(option, short = 'f')]
+    file: Option<String>,
+    /// rewrite file in place
+    #[argh(switch, short = 'i')]
+    in_place: bool,
+}
+
+/// Remove duplicate lexicon entries
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "dedupe")]
+struct DedupeAction {
+    /// lexicon CSV file (default: built-in)
+    #[argh(option, short = 'f')]
+    file: Option<String>,
+    /// rewrite file in place
+    #[argh(switch, short = 'i')]
+    in_place: bool,
+}
+
+/// Print lexicon statistics
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "stats")]
+struct StatsAction {
+    /// lexicon CSV file (default: built-in)
+    #[argh(option, short = 'f')]
+    file: Option<String>,
+}
+
+/// Validation problem
+struct Problem {
+    /// Line number (1-based)
+    number: usize,
+    /// Problem description
+    problem: &'static str,
+    /// Source text
+    line: String,
+    /// Additional note
+    note: Option<String>,
+}
+
+/// Lexicon source line
+struct Entry {
+    /// Line number (1-based)
+    number: usize,
+    /// Source text
+    line: String,
+    /// Parsed lexeme
+    lexeme: Lexeme,
+}
+
+impl LexiconCmd {
+    /// Run command
+    pub fn run(self, json: bool) -> Result<()> {
+        match self.action {
+            Action::Add(act) => act.run(),
+            Action::Validate(act) => act.run(json),
+            Action::Sort(act) => act.run(),
+            Action::Dedupe(act) => act.run(),
+            Action::Stats(act) => act.run(json),
+        }
+    }
+}
+
+/// Read lexicon source text
+fn read_source(file: &Option<String>) -> Result<String> {
+    match file {
+        Some(path) => fs::read_to_string(path)
+            .with_context(|| format!("Cannot read `{path}`")),
+        None => Ok(lex::builtin_csv().to_string()),
+    }
+}
+
+/// Parse lexicon entries, failing on the first bad line
+fn parse_entries(src: &str) -> Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    for (i, line) in src.lines().enumerate() {
+        let Ok(lexeme) = Lexeme::try_from(line) else {
+            bail!("Bad word on line {}: `{line}`", i + 1);
+        };
+        entries.push(Entry {
+            number: i + 1,
+            line: line.to_string(),
+            lexeme,
+        });
+    }
+    Ok(entries)
+}
+
+/// Write lexicon entries to stdout or file
+fn write_entries(
+    entries: &[Entry],
+    file: &Option<String>,
+    in_place: bool,
+) -> Result<()> {
+    if in_place {
+        let Some(path) = file else {
+            return Err(exit::usage(
+                "In-place rewrite requires a lexicon file",
+            ));
+        };
+        let mut out = String::new();
+        for entry in entries {
+            out.push_str(&entry.line);
+            out.push('\n');
+        }
+        fs::write(path, out)
+            .with_context(|| format!("Cannot write `{path}`"))?;
+    } else {
+        for entry in entries {
+            println!("{}", entry.line);
+        }
+    }
+    Ok(())
+}
+
+/// Sort entries by lexeme order
+fn sort_entries(entries: &mut [Entry]) {
+    entries.sort_by(|a, b| a.lexeme.cmp(&b.lexeme));
+}
+
+impl AddAction {
+    /// Run action
+    fn run(self) -> Result<()> {
+        let src = read_source(&self.file)?;
+        let mut entries = parse_entries(&src)?;
+        for line in &self.entries {
+            let Ok(lexeme) = Lexeme::try_from(line.as_str()) else {
+                return Err(exit::usage(format!("Bad word: `{line}`")));
+            };
+            if entries.iter().any(|e| e.lexeme == lexeme) {
+                eprintln!("{} `{line}` already exists", "!!!".bright_yellow());
+                continue;
+            }
+            let i = entries.partition_point(|e| e.lexeme < lexeme);
+            entries.insert(
+                i,
+                Entry {
+                    number: 0,
+                    line: format!("{lexeme:?}"),
+                    lexeme,
+                },
+            );
+        }
+        write_entries(&entries, &self.file, self.in_place)
+    }
+}
+
+impl ValidateAction {
+    /// Run action
+    fn run(self, json: bool) -> Result<()> {
+        let src = read_source(&self.file)?;
+        let problems = validate(&src);
+        if json {
+            let problems: Vec<_> = problems
+                .iter()
+                .map(|p| {
+                    json!({
+                        "line": p.number,
+                        "problem": p.problem,
+                        "text": p.line,
+                        "note": p.note,
+                    })
+                })
+                .collect();
+            crate::print_json(json!({ "problems": problems }));
+            if !problems.is_empty() {
+                return Err(Findings(problems.len()).into());
+            }
+            return Ok(());
+        }
+        for p in &problems {
+            println!(
+                "{:6} {}: {}",
+                p.number.bright_yellow(),
+                p.problem.red(),
+                p.line
+            );
+            if let Some(note) = &p.note {
+                println!("        {} {note}", "=>".dim());
+            }
+        }
+        println!("\nproblems: {}", problems.len().bright_yellow());
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(Findings(problems.len()).into())
+        }
+    }
+}
+
+/// Validate lexicon source lines
+fn validate(src: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut report = |number, problem, line: &str, note| {
+        problems.push(Problem {
+            number,
+            problem,
+            line: line.to_string(),
+            note,
+        });
+    };
+    let mut prev: Option<Lexeme> = None;
+    let mut seen = HashSet::new();
+    let mut classes = HashMap::new();
+    for (i, line) in src.lines().enumerate() {
+        let number = i + 1;
+        let Ok(lexeme) = Lexeme::try_from(line) else {
+            report(number, "parse error", line, None);
+            continue;
+        };
+        if !has_valid_attributes(line) {
+            report(number, "unknown attribute", line, None);
+        }
+        let canonical = format!("{lexeme:?}");
+        if canonical != line {
+            report(number, "round-trip mismatch", line, Some(canonical));
+        }
+        if let Some(p) = &prev
+            && p > &lexeme
+        {
+            report(number, "out of order", line, None);
+        }
+        if !seen.insert(lexeme.clone()) {
+            report(number, "duplicate", line, None);
+        } else {
+            let attrs: Vec<_> = lexeme.attributes().collect();
+            let key = (lexeme.lemma().to_string(), lexeme.word_class(), attrs);
+            if let Some(n) = classes.insert(key, number) {
+                report(number, "conflict", line, Some(format!("line {n}")));
+            }
+        }
+        prev = Some(lexeme);
+    }
+    problems
+}
+
+/// Check if all attributes of a lexicon line are valid
+fn has_valid_attributes(line: &str) -> bool {
+    let lemma = line.split(',').next().unwrap_or_default();
+    let class = lemma.split_once(':').map_or("", |(_, cla)| cla);
+    match class.split_once('.') {
+        Some((_, attr)) => attr.chars().all(|a| WordAttr::try_from(a).is_ok()),
+        None => true,
+    }
+}
+
+impl SortAction {
+    /// Run action
+    fn run(self) -> Result<()> {
+        let src = read_source(&self.file)?;
+        let mut entries = parse_entries(&src)?;
+        sort_entries(&mut entries);
+        write_entries(&entries, &self.file, self.in_place)
+    }
+}
+
+impl DedupeAction {
+    /// Run action
+    fn run(self) -> Result<()> {
+        let src = read_source(&self.file)?;
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for entry in parse_entries(&src)? {
+            if seen.insert(entry.lexeme.clone()) {
+                entries.push(entry);
+            } else {
+                eprintln!(
+                    "{} removed line {}: {}",
+                    "!!!".bright_yellow(),
+                    entry.number,
+                    entry.line
+                );
+            }
+        }
+        write_entries(&entries, &self.file, self.in_place)
+    }
+}
+
+impl StatsAction {
+    /// Run action
+    fn run(self, json: bool) -> Result<()> {
+        let src = read_source(&self.file)?;
+        let entries = parse_entries(&src)?;
+        let mut forms = HashSet::new();
+        let mut irregular = 0;
+        let mut classes: HashMap<WordClass, usize> = HashMap::new();
+        let mut attrs: HashMap<WordAttr, usize> = HashMap::new();
+        for entry in &entries {
+            let lexeme = &entry.lexeme;
+            forms.extend(lexeme.forms().iter().map(|f| f.to_lowercase()));
+            if lexeme.is_irregular() {
+                irregular += 1;
+            }
+            *classes.entry(lexeme.word_class()).or_default() += 1;
+            for attr in lexeme.attributes() {
+                *attrs.entry(attr).or_default() += 1;
+            }
+        }
+        let mut classes: Vec<_> = classes.into_iter().collect();
+        classes.sort();
+        let mut attrs: Vec<_> = attrs.into_iter().collect();
+        attrs.sort();
+        if json {
+            let classes: serde_json::Map<_, _> = classes
+                .iter()
+                .map(|(wc, count)| (format!("{wc:?}"), json!(count)))
+                .collect();
+            let attrs: serde_json::Map<_, _> = attrs
+                .iter()
+                .map(|(attr, count)| (format!("{attr:?}"), json!(count)))
+                .collect();
+            crate::print_json(json!({
+                "lexemes": entries.len(),
+                "forms": forms.len(),
+                "irregular": irregular,
+                "classes": classes,
+                "attributes": attrs,
+            }));
+            return Ok(());
+        }
+        println!("{:7} lexemes", entries.len().bright_yellow());
+        println!("{:7} word forms", forms.len().bright_yellow());
+        println!("{:7} irregular", irregular.bright_yellow());
+        println!();
+        for (wc, count) in classes {
+            println!("{:7} {:2} {wc:?}", count.bright_yellow(), wc.yellow());
+        }
+        println!();
+        for (attr, count) in attrs {
+            println!("{:7} {attr:?}", count.bright_yellow());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,2337 @@
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, EarlyExit, FromArgs};
+use booky::check::{Checker, Misspelling};
+use booky::concordance::Concordance;
+use booky::gazetteer::Gazetteer;
+use booky::harvest::Harvest;
+use booky::hilite;
+use booky::ingest::Format;
+use booky::keyword::{self, WordFreq};
+use booky::kind::Kind;
+use booky::lex::{self, Lexicon, MergePolicy, make_word};
+use booky::meter::{Meter, Scanner};
+use booky::metrics::Readability;
+use booky::ngram::NgramTally;
+use booky::nonsense::{Generator, Template};
+use booky::rhyme::RhymeAnalyzer;
+use booky::spelling::{Region, SpellingTally};
+use booky::stopwords::StopWords;
+use booky::summary::Summarizer;
+use booky::tagger::Tagger;
+use booky::tally::{WordEntry, WordTally};
+use booky::upos::Upos;
+use booky::word::{Lexeme, WordClass, syllables};
+use input::Preprocess;
+use regex::Regex;
+use serde_json::{Value, json};
+use std::env;
+use std::fs;
+use std::io::{
+    BufRead, Cursor, ErrorKind, IsTerminal, Read, Stdin, Write, stdout,
+};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use yansi::{Paint, Style};
+
+mod completions;
+mod exit;
+mod input;
+mod lexicon;
+mod pager;
+mod repl;
+#[cfg(feature = "tui")]
+mod tui;
+
+/// Lexicon with entries added by `--lexicon`
+static LEXICON: OnceLock<Arc<Lexicon>> = OnceLock::new();
+
+/// Gazetteer loaded by `--gazetteer`
+static GAZETTEER: OnceLock<Arc<Gazetteer>> = OnceLock::new();
+
+/// Stop words skipped by `read --no-stopwords`
+static STOPWORDS: OnceLock<Arc<StopWords>> = OnceLock::new();
+
+/// Command-line arguments
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+struct Args {
+    /// output JSON documents
+    #[argh(switch)]
+    json: bool,
+    /// print man page
+    #[argh(switch)]
+    man: bool,
+    /// color output (auto, always, never)
+    #[argh(option, default = "ColorChoice::Auto")]
+    color: ColorChoice,
+    /// lexicon file to add to the built-in lexicon (CSV lines)
+    #[argh(option)]
+    lexicon: Option<String>,
+    /// gazetteer file of names (`name,class` lines) for word tallies
+    #[argh(option)]
+    gazetteer: Option<String>,
+    #[argh(subcommand)]
+    cmd: Option<SubCommand>,
+}
+
+/// Color output choice
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ColorChoice {
+    /// Color when stdout is a terminal (and `NO_COLOR` is not set)
+    Auto,
+    /// Always color
+    Always,
+    /// Never color
+    Never,
+}
+
+/// Entry sort order
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SortOrder {
+    /// Seen count (descending)
+    Count,
+    /// Alphabetical
+    Alpha,
+    /// Word length (descending)
+    Length,
+}
+
+/// Report output format
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Output {
+    /// Terminal text
+    Text,
+    /// JSON document
+    Json,
+    /// CSV lines (with header)
+    Csv,
+}
+
+/// Part-of-speech tag set
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TagSet {
+    /// Word class codes
+    Class,
+    /// Universal Dependencies tags
+    Upos,
+    /// Penn Treebank tags
+    Penn,
+}
+
+/// Word pattern (regular expression)
+#[derive(Clone, Debug)]
+struct WordPattern(Regex);
+
+/// Define a sub-command which reads text input
+///
+/// Input preprocessing options and input files are added to the fields of
+/// the command, which can be converted to a `Preprocess`.
+macro_rules! input_command {
+    (
+        $(#[$meta:meta])*
+        struct $name:ident {
+            $($fields:tt)*
+        }
+    ) => {
+        $(#[$meta])*
+        struct $name {
+            $($fields)*
+            /// input format: text, html, feed, markdown, latex, org, rst,
+            /// csv, tsv, json, srt, vtt, fountain, fountain-dialogue,
+            /// fountain-action (default: detect by file extension or content)
+            #[argh(option)]
+            input_format: Option<Format>,
+            /// strip Project Gutenberg header and footer
+            #[argh(switch, short = 'g')]
+            strip_gutenberg: bool,
+            /// CSV / TSV columns to read (header name or number)
+            #[argh(option)]
+            column: Vec<String>,
+            /// JSON fields to read (e.g. `comments[].text`)
+            #[argh(option)]
+            field: Vec<String>,
+            /// walk directories recursively
+            #[argh(switch, short = 'R')]
+            recursive: bool,
+            /// input files, directories or glob patterns (default: stdin)
+            #[argh(positional)]
+            files: Vec<String>,
+        }
+
+        impl From<&$name> for Preprocess {
+            fn from(cmd: &$name) -> Self {
+                Preprocess {
+                    format: cmd.input_format,
+                    strip_gutenberg: cmd.strip_gutenberg,
+                    columns: cmd.column.clone(),
+                    fields: cmd.field.clone(),
+                }
+            }
+        }
+    };
+}
+
+/// Sub-command enum
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand)]
+enum SubCommand {
+    Hilite(HiliteCmd),
+    Read(ReadCmd),
+    Harvest(HarvestCmd),
+    Word(WordCmd),
+    Tag(TagCmd),
+    Lemma(LemmaCmd),
+    Spelling(SpellingCmd),
+    Keywords(KeywordsCmd),
+    Summary(SummaryCmd),
+    Stats(StatsCmd),
+    Ngrams(NgramsCmd),
+    Kwic(KwicCmd),
+    Scan(ScanCmd),
+    Rhyme(RhymeCmd),
+    Lexicon(lexicon::LexiconCmd),
+    Repl(repl::ReplCmd),
+    Completions(completions::CompletionsCmd),
+    #[cfg(feature = "tui")]
+    Tui(tui::TuiCmd),
+    Nonsense(NonsenseCmd),
+    Suggest(SuggestCmd),
+    Check(CheckCmd),
+    Anagram(AnagramCmd),
+}
+
+input_command! {
+    /// Hilight text from files or stdin
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "hl")]
+    struct HiliteCmd {
+        /// do not pipe output through a pager
+        #[argh(switch, short = 'P')]
+        no_pager: bool,
+    }
+}
+
+input_command! {
+    /// Read text from files or stdin, grouping tokens by kind
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "read")]
+    struct ReadCmd {
+        /// print aggregate results only
+        #[argh(switch, short = 'a')]
+        aggregate: bool,
+        /// read text from the clipboard
+        #[argh(switch)]
+        clipboard: bool,
+        /// read text from a URL
+        #[cfg(feature = "fetch")]
+        #[argh(option)]
+        url: Option<String>,
+        /// token output limit
+        #[argh(option, short = 't', default = "u32::MAX")]
+        tokens: u32,
+        /// sort order (count, alpha, length)
+        #[argh(option, default = "SortOrder::Count")]
+        sort: SortOrder,
+        /// reverse sort
+        #[argh(switch, short = 'v')]
+        reverse: bool,
+        /// minimum seen count
+        #[argh(option, default = "1")]
+        min: usize,
+        /// maximum seen count
+        #[argh(option, default = "usize::MAX")]
+        max: usize,
+        /// only entries with words matching a regular expression
+        #[argh(option, long = "match")]
+        pattern: Option<WordPattern>,
+        /// output token words only
+        #[argh(switch, short = 'w')]
+        word: bool,
+        /// output format: text, json, csv (default: text)
+        #[argh(option, short = 'f', default = "Output::Text")]
+        format: Output,
+        /// include lemmas of lexicon words (JSON / CSV output)
+        #[argh(switch)]
+        lemmas: bool,
+        /// group lexicon words by lemma
+        #[argh(switch)]
+        by_lemma: bool,
+        /// skip stop words (determiners, conjunctions, prepositions, pronouns)
+        #[argh(switch)]
+        no_stopwords: bool,
+        /// token kinds to list (comma-separated codes: l,f,o,r,n,d,c,a,p,w,
+        /// m,h,t,e,s,u or A for all; default: summary of all kinds)
+        #[argh(option, short = 'k')]
+        kinds: Option<String>,
+    }
+}
+
+input_command! {
+    /// Harvest unknown words as candidate lexicon entries
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "harvest")]
+    struct HarvestCmd {
+        /// sample contexts per word
+        #[argh(option, short = 'c', default = "2")]
+        contexts: usize,
+        /// minimum seen count
+        #[argh(option, short = 'm', default = "1")]
+        min: usize,
+        /// output candidate lines only
+        #[argh(switch, short = 'q')]
+        quiet: bool,
+    }
+}
+
+/// Lookup words from lexicon
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "word")]
+struct WordCmd {
+    /// word classes (A,Av,C,D,I,N,P,Pn,V)
+    #[argh(option, short = 'c')]
+    classes: Option<String>,
+    /// list all word forms
+    #[argh(switch, short = 'f')]
+    forms: bool,
+    /// sample random entries
+    #[argh(option, short = 'r')]
+    random: Option<usize>,
+    /// show lexemes (and rules) producing a word form
+    #[argh(option)]
+    form: Option<String>,
+    /// list word forms matching a pattern (`*` and `?` wildcards)
+    #[argh(option, short = 'g')]
+    glob: Option<String>,
+    /// lemma length (characters)
+    #[argh(option)]
+    length: Option<usize>,
+    /// lemma syllable count
+    #[argh(option)]
+    syllables: Option<usize>,
+    /// lemma prefix
+    #[argh(option)]
+    starts_with: Option<String>,
+    /// lemma suffix
+    #[argh(option)]
+    ends_with: Option<String>,
+    /// word to lookup
+    #[argh(positional)]
+    word: Option<String>,
+}
+
+input_command! {
+    /// Tag words from files or stdin by part of speech
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "tag")]
+    struct TagCmd {
+        /// tag set: class, upos, penn (default: class)
+        #[argh(option, short = 't', default = "TagSet::Class")]
+        tagset: TagSet,
+        /// trained tagger weights file
+        #[argh(option, short = 'w')]
+        weights: Option<String>,
+    }
+}
+
+input_command! {
+    /// Lemmatize words from files or stdin (tab-separated columns)
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "lemma")]
+    struct LemmaCmd {
+    }
+}
+
+input_command! {
+    /// Report British / American spelling from files or stdin
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "spelling")]
+    struct SpellingCmd {
+    }
+}
+
+input_command! {
+    /// Extract keywords from files or stdin
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "keywords")]
+    struct KeywordsCmd {
+        /// keyword output limit
+        #[argh(option, short = 'n', default = "20")]
+        count: usize,
+        /// minimum seen count
+        #[argh(option, default = "2")]
+        min: usize,
+        /// reference word frequency file (CSV lines of `word,count`)
+        #[argh(option, short = 'r')]
+        reference: Option<String>,
+    }
+}
+
+input_command! {
+    /// Summarize text from files or stdin with its top sentences
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "summary")]
+    struct SummaryCmd {
+        /// sentence output limit
+        #[argh(option, short = 'n', default = "3")]
+        count: usize,
+    }
+}
+
+input_command! {
+    /// Report readability statistics of text from files or stdin
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "stats")]
+    struct StatsCmd {
+        /// report vocabulary statistics instead of readability
+        #[argh(switch)]
+        vocab: bool,
+        /// print aggregate results only
+        #[argh(switch, short = 'a')]
+        aggregate: bool,
+    }
+}
+
+input_command! {
+    /// Count word bigrams or trigrams from files or stdin
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "ngrams")]
+    struct NgramsCmd {
+        /// words per n-gram (2 or 3)
+        #[argh(option, short = 'n', default = "2")]
+        n: usize,
+        /// n-gram output limit
+        #[argh(option, short = 'c', default = "20")]
+        count: usize,
+        /// minimum seen count
+        #[argh(option, default = "2")]
+        min: usize,
+    }
+}
+
+input_command! {
+    /// Print keyword-in-context lines of a word from files or stdin
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "kwic")]
+    struct KwicCmd {
+        /// context window (words either side)
+        #[argh(option, short = 'w', default = "5")]
+        window: usize,
+        /// match any form of the word's lexemes
+        #[argh(switch, short = 'l')]
+        lexeme: bool,
+        /// keyword
+        #[argh(positional)]
+        word: String,
+    }
+}
+
+input_command! {
+    /// Scan lines of verse from files or stdin for meter
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "scan")]
+    struct ScanCmd {
+        /// target meter (e.g. `iambic-pentameter`, `trochaic-tetrameter`)
+        #[argh(option, short = 'm', default = "Meter::IAMBIC_PENTAMETER")]
+        meter: Meter,
+    }
+}
+
+input_command! {
+    /// Report rhyme schemes and alliteration of a poem from files or stdin,
+    /// or list rhymes of a word
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "rhyme")]
+    struct RhymeCmd {
+        /// maximum rhymes to list (default 20)
+        #[argh(option, short = 'n', default = "20")]
+        count: usize,
+        /// word to find rhymes for (instead of analyzing a poem)
+        #[argh(option, short = 'w')]
+        word: Option<String>,
+    }
+}
+
+input_command! {
+    /// Suggest spelling corrections for unknown words
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "suggest")]
+    struct SuggestCmd {
+        /// maximum edit distance of suggestions
+        #[argh(option, short = 'd', default = "2")]
+        distance: usize,
+        /// maximum suggestions per word
+        #[argh(option, short = 'n', default = "8")]
+        count: usize,
+        /// word to check (repeatable; default: unknown words of input)
+        #[argh(option, short = 'w', long = "word")]
+        words: Vec<String>,
+    }
+}
+
+input_command! {
+    /// Check spelling of unknown words and inconsistent regional spellings
+    #[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+    #[argh(subcommand, name = "check")]
+    struct CheckCmd {
+        /// maximum edit distance of suggestions
+        #[argh(option, short = 'd', default = "2")]
+        distance: usize,
+        /// maximum suggestions per word
+        #[argh(option, short = 'n', default = "3")]
+        count: usize,
+    }
+}
+
+/// Find anagrams of letters in the lexicon
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "anagram")]
+struct AnagramCmd {
+    /// include sub-anagrams (using only some of the letters)
+    #[argh(switch, short = 's')]
+    sub: bool,
+    /// minimum word length (letters)
+    #[argh(option, short = 'm', default = "1")]
+    min: usize,
+    /// word classes (A,Av,C,D,I,N,P,Pn,V)
+    #[argh(option, short = 'c')]
+    classes: Option<String>,
+    /// letters
+    #[argh(positional)]
+    letters: String,
+}
+
+/// Generate nonsense sentences
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "nonsense")]
+struct NonsenseCmd {
+    /// number of sentences
+    #[argh(option, short = 'c', default = "1")]
+    count: usize,
+    /// sentence template of word class codes (default: "D A N V Av")
+    #[argh(option, short = 't', default = "Template::default()")]
+    template: Template,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(val: &str) -> Result<Self, Self::Err> {
+        match val {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!("Unknown color choice: {val}")),
+        }
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(val: &str) -> Result<Self, Self::Err> {
+        match val {
+            "count" => Ok(SortOrder::Count),
+            "alpha" => Ok(SortOrder::Alpha),
+            "length" => Ok(SortOrder::Length),
+            _ => Err(format!("Unknown sort order: {val}")),
+        }
+    }
+}
+
+impl FromStr for Output {
+    type Err = String;
+
+    fn from_str(val: &str) -> Result<Self, Self::Err> {
+        match val {
+            "text" => Ok(Output::Text),
+            "json" => Ok(Output::Json),
+            "csv" => Ok(Output::Csv),
+            _ => Err(format!("Unknown output format: {val}")),
+        }
+    }
+}
+
+impl FromStr for TagSet {
+    type Err = String;
+
+    fn from_str(val: &str) -> Result<Self, Self::Err> {
+        match val {
+            "class" => Ok(TagSet::Class),
+            "upos" => Ok(TagSet::Upos),
+            "penn" => Ok(TagSet::Penn),
+            _ => Err(format!("Unknown tag set: {val}")),
+        }
+    }
+}
+
+impl FromStr for WordPattern {
+    type Err = String;
+
+    fn from_str(val: &str) -> Result<Self, Self::Err> {
+        Regex::new(val).map(WordPattern).map_err(|e| e.to_string())
+    }
+}
+
+impl PartialEq for WordPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+impl ColorChoice {
+    /// Check if color output is enabled
+    fn is_enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => {
+                stdout().is_terminal()
+                    && env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+impl HiliteCmd {
+    /// Run command
+    fn run(self) -> Result<()> {
+        let stdin = if self.files.is_empty() {
+            Some(input::stdin()?)
+        } else {
+            None
+        };
+        if !self.no_pager
+            && stdout().is_terminal()
+            && let Some(mut pager) = pager::spawn()
+            && let Some(pipe) = pager.stdin.take()
+        {
+            let res = self.write_hilite(stdin, pipe);
+            pager.wait()?;
+            // quitting the pager early closes the pipe
+            match res {
+                Err(e) if is_broken_pipe(&e) => {}
+                r => r?,
+            }
+            return Ok(());
+        }
+        self.write_hilite(stdin, stdout().lock())
+    }
+
+    /// Write hilighted text of stdin or files
+    ///
+    /// With more than one file, each is preceded by its name.
+    fn write_hilite<W>(&self, stdin: Option<Stdin>, mut out: W) -> Result<()>
+    where
+        W: Write,
+    {
+        if let Some(stdin) = stdin {
+            hilite::write_hilite_with_lexicon(
+                Preprocess::from(self).prepare(stdin.lock(), None)?,
+                out,
+                shared_lexicon(),
+            )?;
+            return Ok(());
+        }
+        let prep = Preprocess::from(self);
+        let paths = input::expand_paths(&self.files, self.recursive)?;
+        let headers = paths.len() > 1;
+        let pages = input::map_files(&paths, |path| {
+            let mut page = Vec::new();
+            for (name, reader) in input::open_inputs(path, &prep)? {
+                if headers {
+                    writeln!(page, "{}", name.bold())?;
+                }
+                hilite::write_hilite_with_lexicon(
+                    reader,
+                    &mut page,
+                    shared_lexicon(),
+                )
+                .with_context(|| format!("Cannot read `{name}`"))?;
+            }
+            Ok(page)
+        });
+        for page in pages {
+            out.write_all(&page?)?;
+        }
+        Ok(())
+    }
+}
+
+impl ReadCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        let output = if json { Output::Json } else { self.format };
+        let kinds = match &self.kinds {
+            Some(kinds) => parse_kinds(kinds)?,
+            None => Vec::new(),
+        };
+        let files = &self.files;
+        // kinds were once given as a positional argument
+        if let [file] = &files[..]
+            && !Path::new(file).exists()
+            && parse_kinds(file).is_ok()
+        {
+            return Err(exit::usage(format!(
+                "No file `{file}` (token kinds are given with `-k {file}`)"
+            )));
+        }
+        if self.no_stopwords {
+            let stop = StopWords::from_lexicon(current_lexicon());
+            let _ = STOPWORDS.set(Arc::new(stop));
+        }
+        if let Some(mut reader) = self.source()? {
+            if !files.is_empty() {
+                return Err(exit::usage("Cannot read both a source and files"));
+            }
+            input::check_language("source", &mut reader)?;
+            let mut tally = word_tally();
+            tally.parse_text(reader)?;
+            self.write_report(tally, &kinds, output);
+            return Ok(());
+        }
+        if files.is_empty() {
+            let stdin = input::stdin()?;
+            let mut reader =
+                Preprocess::from(&self).prepare(stdin.lock(), None)?;
+            input::check_language("stdin", &mut reader)?;
+            let mut tally = word_tally();
+            tally.parse_text(reader)?;
+            self.write_report(tally, &kinds, output);
+            return Ok(());
+        }
+        let paths = input::expand_paths(files, self.recursive)?;
+        let per_file = paths.len() > 1 && !self.aggregate;
+        let mut total = WordTally::new();
+        let mut reports = Vec::new();
+        if per_file && output == Output::Csv {
+            println!("path,{}", self.csv_header(&kinds));
+        }
+        let prep = Preprocess::from(&self);
+        let tallies = word_tally().tally_each(&paths, |path, tally| {
+            input::tally_file(path, &prep, tally)
+        });
+        for (path, tally) in paths.iter().zip(tallies) {
+            let tally = tally?;
+            if per_file {
+                match output {
+                    Output::Text => {
+                        println!("{}", path.display().bold());
+                        self.write_report(tally.clone(), &kinds, output);
+                        println!();
+                    }
+                    Output::Json => {
+                        let mut doc = self.report_json(tally.clone(), &kinds);
+                        doc["path"] = json!(path.display().to_string());
+                        reports.push(doc);
+                    }
+                    Output::Csv => {
+                        let path = csv_field(&path.display().to_string());
+                        for row in self.report_csv(tally.clone(), &kinds) {
+                            println!("{path},{row}");
+                        }
+                    }
+                }
+            }
+            total.merge(tally);
+        }
+        if !per_file {
+            self.write_report(total, &kinds, output);
+            return Ok(());
+        }
+        match output {
+            Output::Text => {
+                println!("{} ({} files)", "total".bold(), paths.len());
+                self.write_report(total, &kinds, output);
+            }
+            Output::Json => print_json(json!({
+                "files": reports,
+                "total": self.report_json(total, &kinds),
+            })),
+            Output::Csv => {
+                // total rows have an empty path
+                for row in self.report_csv(total, &kinds) {
+                    println!(",{row}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Get reader for clipboard or URL source (if any)
+    fn source(&self) -> Result<Option<Box<dyn BufRead>>> {
+        if self.clipboard {
+            let text = input::clipboard()?;
+            let reader =
+                Preprocess::from(self).prepare(Cursor::new(text), None)?;
+            return Ok(Some(reader));
+        }
+        #[cfg(feature = "fetch")]
+        if let Some(url) = &self.url {
+            let (body, format) = input::fetch(url)?;
+            let mut prep = Preprocess::from(self);
+            prep.format = prep.format.or(format);
+            let path = Path::new(url.split(['?', '#']).next().unwrap_or(url));
+            let reader = prep.prepare(Cursor::new(body), Some(path))?;
+            return Ok(Some(reader));
+        }
+        Ok(None)
+    }
+
+    /// Write report of a tally
+    fn write_report(&self, tally: WordTally, kinds: &[Kind], output: Output) {
+        match output {
+            Output::Text if kinds.is_empty() => self.write_summary(tally),
+            Output::Text => self.write_entries(tally, kinds),
+            Output::Json => print_json(self.report_json(tally, kinds)),
+            Output::Csv => {
+                println!("{}", self.csv_header(kinds));
+                for row in self.report_csv(tally, kinds) {
+                    println!("{row}");
+                }
+            }
+        }
+    }
+
+    /// Get CSV header of a report
+    fn csv_header(&self, kinds: &[Kind]) -> &'static str {
+        match (kinds.is_empty(), self.lemmas) {
+            (true, _) => "kind,code,count",
+            (false, false) => "seen,word,kind",
+            (false, true) => "seen,word,kind,lemma",
+        }
+    }
+
+    /// Make CSV rows of a report
+    fn report_csv(&self, tally: WordTally, kinds: &[Kind]) -> Vec<String> {
+        if kinds.is_empty() {
+            return Kind::all()
+                .iter()
+                .map(|kind| {
+                    format!(
+                        "{kind:?},{},{}",
+                        kind.code(),
+                        tally.count_kind(*kind)
+                    )
+                })
+                .collect();
+        }
+        self.selected_entries(tally, kinds)
+            .into_iter()
+            .map(|entry| {
+                let mut row = format!(
+                    "{},{},{}",
+                    entry.seen(),
+                    csv_field(entry.word()),
+                    entry.kind().code()
+                );
+                if self.lemmas {
+                    row.push(',');
+                    if let Some(lemma) = entry_lemma(&entry) {
+                        row.push_str(&csv_field(lemma));
+                    }
+                }
+                row
+            })
+            .collect()
+    }
+
+    /// Make JSON report of a tally
+    fn report_json(&self, tally: WordTally, kinds: &[Kind]) -> Value {
+        if kinds.is_empty() {
+            self.summary_json(tally)
+        } else {
+            self.entries_json(tally, kinds)
+        }
+    }
+
+    /// Get sorted entries of a tally (filtered by seen count and pattern)
+    fn sorted_entries(&self, tally: WordTally) -> Vec<WordEntry> {
+        let entries = if self.by_lemma {
+            tally.into_lemma_entries()
+        } else {
+            tally.into_entries()
+        };
+        let mut entries: Vec<_> = entries
+            .into_iter()
+            .rev()
+            .filter(|entry| (self.min..=self.max).contains(&entry.seen()))
+            .filter(|entry| {
+                self.pattern
+                    .as_ref()
+                    .is_none_or(|pat| pat.0.is_match(entry.word()))
+            })
+            .collect();
+        match self.sort {
+            SortOrder::Count => (),
+            SortOrder::Alpha => entries.sort_by_cached_key(|entry| {
+                (make_word(entry.word()), entry.word().to_string())
+            }),
+            SortOrder::Length => entries.sort_by(|a, b| {
+                b.word().chars().count().cmp(&a.word().chars().count())
+            }),
+        }
+        if self.reverse {
+            entries.reverse();
+        }
+        entries
+    }
+
+    /// Write entries of selected kinds
+    fn write_entries(&self, tally: WordTally, kinds: &[Kind]) {
+        let entries = self.selected_entries(tally, kinds);
+        for entry in &entries {
+            if self.word {
+                println!("{}", entry.word());
+            } else {
+                println!("{entry}");
+            }
+        }
+        if !self.word {
+            println!("\ncount: {}", entries.len().bright_yellow());
+        }
+    }
+
+    /// Get sorted entries of selected kinds (up to token limit)
+    fn selected_entries(
+        &self,
+        tally: WordTally,
+        kinds: &[Kind],
+    ) -> Vec<WordEntry> {
+        let tokens = self.tokens as usize;
+        // most frequent entries can be selected without a full sort
+        if self.sort == SortOrder::Count
+            && !self.reverse
+            && !self.by_lemma
+            && self.pattern.is_none()
+            && (self.min..=self.max) == (1..=usize::MAX)
+        {
+            return tally.top_k(tokens, kinds).into_iter().cloned().collect();
+        }
+        self.sorted_entries(tally)
+            .into_iter()
+            .filter(|entry| kinds.contains(&entry.kind()))
+            .take(tokens)
+            .collect()
+    }
+
+    /// Make JSON document of entries of selected kinds
+    fn entries_json(&self, tally: WordTally, kinds: &[Kind]) -> Value {
+        let entries: Vec<_> = self
+            .selected_entries(tally, kinds)
+            .into_iter()
+            .map(|entry| {
+                let mut doc = json!({
+                    "seen": entry.seen(),
+                    "word": entry.word(),
+                    "kind": entry.kind().code().to_string(),
+                });
+                if self.lemmas
+                    && let Some(lemma) = entry_lemma(&entry)
+                {
+                    doc["lemma"] = json!(lemma);
+                }
+                doc
+            })
+            .collect();
+        json!({
+            "count": entries.len(),
+            "entries": entries,
+        })
+    }
+
+    /// Write summary of kinds
+    fn write_summary(&self, tally: WordTally) {
+        for kind in Kind::all() {
+            let count = tally.count_kind(*kind);
+            println!(
+                "{:5} {} {kind:?}",
+                count.bright_yellow(),
+                kind.code().yellow()
+            );
+        }
+    }
+
+    /// Make JSON document of kinds summary
+    fn summary_json(&self, tally: WordTally) -> Value {
+        let kinds: Vec<_> = Kind::all()
+            .iter()
+            .map(|kind| {
+                json!({
+                    "kind": format!("{kind:?}"),
+                    "code": kind.code().to_string(),
+                    "count": tally.count_kind(*kind),
+                })
+            })
+            .collect();
+        json!({ "kinds": kinds })
+    }
+}
+
+/// Get the lemma of a lexicon word entry
+fn entry_lemma(entry: &WordEntry) -> Option<&'static str> {
+    if entry.kind() != Kind::Lexicon {
+        return None;
+    }
+    current_lexicon()
+        .lemmatize(entry.word())
+        .first()
+        .map(|(lemma, _wc)| *lemma)
+}
+
+/// Get the lemma of a word with a word class (or any class if not tagged)
+fn class_lemma<'a>(
+    lex: &'a Lexicon,
+    word: &str,
+    wc: Option<WordClass>,
+) -> Option<&'a str> {
+    let lemmas = lex.lemmatize(word);
+    lemmas
+        .iter()
+        .find(|(_lemma, c)| Some(*c) == wc)
+        .or(lemmas.first())
+        .map(|(lemma, _wc)| *lemma)
+}
+
+/// Check if an error is caused by a closed pipe
+fn is_broken_pipe(err: &anyhow::Error) -> bool {
+    err.chain().any(|e| {
+        let io = match e.downcast_ref::<booky::Error>() {
+            Some(booky::Error::Io(io)) => Some(io),
+            _ => e.downcast_ref::<std::io::Error>(),
+        };
+        io.is_some_and(|io| io.kind() == ErrorKind::BrokenPipe)
+    })
+}
+
+/// Quote a CSV field (if needed)
+fn csv_field(val: &str) -> String {
+    if val.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", val.replace('"', "\"\""))
+    } else {
+        val.to_string()
+    }
+}
+
+/// Parse token kinds
+fn parse_kinds(knd: &str) -> Result<Vec<Kind>> {
+    let mut kinds = Vec::new();
+    for kind in knd.split(',') {
+        let kind = match kind.trim() {
+            "A" => return Ok(Kind::all().to_vec()),
+            "l" => Kind::Lexicon,
+            "f" => Kind::Foreign,
+            "o" => Kind::Ordinal,
+            "r" => Kind::Roman,
+            "n" => Kind::Number,
+            "d" => Kind::Date,
+            "c" => Kind::Time,
+            "a" => Kind::Acronym,
+            "p" => Kind::Proper,
+            "w" => Kind::Url,
+            "m" => Kind::Email,
+            "h" => Kind::Hashtag,
+            "t" => Kind::Mention,
+            "e" => Kind::Emoji,
+            "s" => Kind::Symbol,
+            "u" => Kind::Unknown,
+            k => return Err(exit::usage(format!("Unknown kind: {k}"))),
+        };
+        kinds.push(kind);
+    }
+    Ok(kinds)
+}
+
+impl HarvestCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        let mut harvest =
+            Harvest::with_lexicon(self.contexts, shared_lexicon());
+        let prep = Preprocess::from(&self);
+        harvest.parse_text(input::open_text(
+            &self.files,
+            self.recursive,
+            &prep,
+        )?)?;
+        let candidates = harvest
+            .into_candidates()
+            .into_iter()
+            .filter(|cand| cand.seen() >= self.min);
+        if json {
+            let candidates: Vec<_> = candidates
+                .map(|cand| {
+                    json!({
+                        "line": format!("{:?}", cand.lexeme()),
+                        "seen": cand.seen(),
+                        "words": cand.words(),
+                        "forms": cand.lexeme().forms(),
+                        "contexts": cand.contexts(),
+                    })
+                })
+                .collect();
+            print_json(json!({ "candidates": candidates }));
+            return Ok(());
+        }
+        for cand in candidates {
+            if !self.quiet {
+                println!(
+                    "{} {} {}",
+                    "#".dim(),
+                    cand.seen().bright_yellow(),
+                    cand.words().join(", ").bold()
+                );
+                let forms = cand.lexeme().forms().join(" ");
+                println!("{} forms: {}", "#".dim(), forms.dim());
+                for context in cand.contexts() {
+                    println!("{}   {}", "#".dim(), context.italic());
+                }
+            }
+            println!("{:?}", cand.lexeme());
+        }
+        Ok(())
+    }
+}
+
+impl WordCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        if self.forms {
+            let mut forms: Vec<_> = current_lexicon().iter_forms().collect();
+            forms.sort();
+            if json {
+                print_json(json!({ "forms": forms }));
+                return Ok(());
+            }
+            for form in forms {
+                println!("{form}");
+            }
+        } else if let Some(form) = &self.form {
+            self.reverse_lookup(form, json);
+        } else if let Some(pattern) = &self.glob {
+            let lex = current_lexicon();
+            let forms: Vec<_> =
+                lex.find(pattern)
+                    .filter(|form| {
+                        self.classes.is_none()
+                            || lex.word_entries(form).iter().any(|entry| {
+                                self.show_class(entry.word_class())
+                            })
+                    })
+                    .collect();
+            if json {
+                print_json(json!({ "pattern": pattern, "forms": forms }));
+                return Ok(());
+            }
+            for form in forms {
+                println!("{form}");
+            }
+        } else if let Some(n) = self.random {
+            let words =
+                current_lexicon().iter().filter(|word| self.show_word(word));
+            let words = fastrand::choose_multiple(words, n);
+            if json {
+                let entries: Vec<_> =
+                    words.into_iter().map(lexeme_json).collect();
+                print_json(json!({ "entries": entries }));
+                return Ok(());
+            }
+            for word in words {
+                println!("{word:?}");
+            }
+        } else if let Some(word) = &self.word {
+            if json {
+                let entries: Vec<_> = current_lexicon()
+                    .word_entries(word)
+                    .into_iter()
+                    .map(lexeme_json)
+                    .collect();
+                print_json(json!({ "word": word, "entries": entries }));
+                return Ok(());
+            }
+            self.lookup(word)?;
+        } else {
+            // into_iter() sorts the entries
+            let words = current_lexicon()
+                .clone()
+                .into_iter()
+                .filter(|word| self.show_word(word));
+            if json {
+                let entries: Vec<_> = words.map(|w| lexeme_json(&w)).collect();
+                print_json(json!({ "entries": entries }));
+                return Ok(());
+            }
+            for word in words {
+                println!("{word:?}");
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if a word should be shown
+    fn show_word(&self, word: &Lexeme) -> bool {
+        let lemma = word.lemma();
+        self.show_class(word.word_class())
+            && self.length.is_none_or(|n| lemma.chars().count() == n)
+            && self.syllables.is_none_or(|n| syllables(lemma) == n)
+            && self
+                .starts_with
+                .as_ref()
+                .is_none_or(|pre| lemma.starts_with(pre.as_str()))
+            && self
+                .ends_with
+                .as_ref()
+                .is_none_or(|suf| lemma.ends_with(suf.as_str()))
+    }
+
+    /// Check if a word class should be shown
+    fn show_class(&self, wc: WordClass) -> bool {
+        match &self.classes {
+            Some(classes) => {
+                for cl in classes.split(',') {
+                    match WordClass::try_from(cl) {
+                        Ok(cl) => {
+                            if cl == wc {
+                                return true;
+                            }
+                        }
+                        Err(_) => return false,
+                    }
+                }
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Lookup lexemes which produce a word form
+    fn reverse_lookup(&self, form: &str, json: bool) {
+        let key = make_word(form);
+        let mut found = Vec::new();
+        for word in current_lexicon().word_entries(form) {
+            for f in word.forms().iter().filter(|f| make_word(f) == key) {
+                if let Some(rule) = word.form_rule(f) {
+                    found.push((word, f, rule));
+                }
+            }
+        }
+        if json {
+            let entries: Vec<_> = found
+                .iter()
+                .map(|(word, f, rule)| {
+                    json!({
+                        "form": f,
+                        "rule": format!("{rule:?}"),
+                        "lexeme": lexeme_json(word),
+                    })
+                })
+                .collect();
+            print_json(json!({ "form": form, "entries": entries }));
+            return;
+        }
+        if found.is_empty() {
+            println!("`{form}` not found");
+        }
+        for (word, f, rule) in found {
+            println!(
+                "{} {} {:?} ({rule})",
+                f.bright_yellow().italic(),
+                "<=".dim(),
+                word.bold()
+            );
+        }
+    }
+
+    /// Lookup a word form
+    fn lookup(&self, word: &str) -> Result<()> {
+        let lex = current_lexicon();
+        if lex.contains(word) {
+            for w in lex.word_entries(word) {
+                for f in w.forms() {
+                    let mut style = if f == word {
+                        Style::new().bright_yellow().italic()
+                    } else {
+                        Style::new()
+                    };
+                    if f == w.lemma() {
+                        style = style.bold();
+                        print!("{}:{} ", f.paint(style), w.word_class().bold());
+                    } else {
+                        print!("{} ", f.paint(style));
+                    }
+                }
+                println!();
+            }
+        } else {
+            println!("`{word}` not found");
+        }
+        Ok(())
+    }
+}
+
+impl TagCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        let mut tagger = Tagger::with_lexicon(shared_lexicon());
+        if let Some(path) = &self.weights {
+            let src = std::fs::read_to_string(path)
+                .with_context(|| format!("Cannot read `{path}`"))?;
+            tagger = tagger.load_weights(&src)?;
+        }
+        let prep = Preprocess::from(&self);
+        let reader = input::open_text(&self.files, self.recursive, &prep)?;
+        let tagged: Vec<(String, Option<String>)> = match self.tagset {
+            TagSet::Class => tagger
+                .tag_text(reader)?
+                .into_iter()
+                .map(|(w, wc)| (w, wc.map(|wc| wc.to_string())))
+                .collect(),
+            TagSet::Upos => tagger
+                .tag_text(reader)?
+                .into_iter()
+                .map(|(w, wc)| {
+                    let tag = upos_tag(&w, wc).map(|t| t.to_string());
+                    (w, tag)
+                })
+                .collect(),
+            TagSet::Penn => tagger
+                .tag_text_penn(reader)?
+                .into_iter()
+                .map(|(w, t)| (w, t.map(|t| t.to_string())))
+                .collect(),
+        };
+        if json {
+            let tokens: Vec<_> = tagged
+                .iter()
+                .map(|(word, tag)| json!({ "word": word, "tag": tag }))
+                .collect();
+            print_json(json!({ "tokens": tokens }));
+        } else {
+            for (word, tag) in tagged {
+                println!("{word}\t{}", tag.as_deref().unwrap_or("-"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LemmaCmd {
+    /// Run command
+    ///
+    /// Each word is printed on a line with its number in the sentence, word
+    /// form, lemma and word class (`_` if unknown), with a blank line after
+    /// each sentence.
+    fn run(self, json: bool) -> Result<()> {
+        let tagger = Tagger::with_lexicon(shared_lexicon());
+        let prep = Preprocess::from(&self);
+        let reader = input::open_text(&self.files, self.recursive, &prep)?;
+        let lex = current_lexicon();
+        let sentences = tagger.tag_sentences(reader)?;
+        if json {
+            let sentences: Vec<Vec<_>> = sentences
+                .iter()
+                .map(|words| {
+                    words
+                        .iter()
+                        .map(|(word, wc)| {
+                            json!({
+                                "word": word,
+                                "lemma": class_lemma(lex, word, *wc),
+                                "class": wc.map(|wc| wc.to_string()),
+                            })
+                        })
+                        .collect()
+                })
+                .collect();
+            print_json(json!({ "sentences": sentences }));
+            return Ok(());
+        }
+        for words in sentences {
+            for (i, (word, wc)) in words.iter().enumerate() {
+                let lemma = class_lemma(lex, word, *wc).unwrap_or("_");
+                let wc = wc.map_or("_".to_string(), |wc| wc.to_string());
+                println!("{}\t{word}\t{lemma}\t{wc}", i + 1);
+            }
+            println!();
+        }
+        Ok(())
+    }
+}
+
+impl SpellingCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        let prep = Preprocess::from(&self);
+        let reader = input::open_text(&self.files, self.recursive, &prep)?;
+        let mut tally = SpellingTally::with_lexicon(shared_lexicon());
+        tally.parse_text(reader)?;
+        let lean = tally.lean().map(|r| r.to_string());
+        let british = tally.count(Region::British);
+        let american = tally.count(Region::American);
+        if json {
+            let words: Vec<_> = tally
+                .entries()
+                .iter()
+                .map(|e| {
+                    json!({
+                        "word": e.word(),
+                        "region": e.spelling().region().to_string(),
+                        "counterpart": e.spelling().counterpart(),
+                        "seen": e.seen(),
+                    })
+                })
+                .collect();
+            let inconsistencies: Vec<_> = tally
+                .inconsistencies()
+                .iter()
+                .map(|(uk, us)| json!({ "british": uk, "american": us }))
+                .collect();
+            print_json(json!({
+                "lean": lean,
+                "british": british,
+                "american": american,
+                "words": words,
+                "inconsistencies": inconsistencies,
+            }));
+            return Ok(());
+        }
+        let lean = lean.unwrap_or_else(|| "Neither".to_string());
+        println!("{} ({british} British, {american} American)", lean.bold());
+        for entry in tally.entries() {
+            let spelling = entry.spelling();
+            let code = match spelling.region() {
+                Region::British => 'B',
+                Region::American => 'A',
+            };
+            println!(
+                "{:5} {} {} ({})",
+                entry.seen().bright().yellow(),
+                code.yellow(),
+                entry.word(),
+                spelling.counterpart().dim(),
+            );
+        }
+        for (uk, us) in tally.inconsistencies() {
+            println!("{} {uk} / {us}", "!!!".bright_yellow());
+        }
+        Ok(())
+    }
+}
+
+impl KeywordsCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        let reference = match &self.reference {
+            Some(path) => {
+                let src = std::fs::read_to_string(path)
+                    .with_context(|| format!("Cannot read `{path}`"))?;
+                WordFreq::from_csv(&src)?
+            }
+            None => keyword::builtin().clone(),
+        };
+        let prep = Preprocess::from(&self);
+        let reader = input::open_text(&self.files, self.recursive, &prep)?;
+        let mut tally = word_tally();
+        tally.parse_text(reader)?;
+        let keywords: Vec<_> = reference
+            .keywords(&tally)
+            .into_iter()
+            .filter(|k| k.seen() >= self.min)
+            .take(self.count)
+            .collect();
+        if json {
+            let keywords: Vec<_> = keywords
+                .iter()
+                .map(|k| {
+                    json!({
+                        "word": k.word(),
+                        "seen": k.seen(),
+                        "score": k.score(),
+                    })
+                })
+                .collect();
+            print_json(json!({ "keywords": keywords }));
+            return Ok(());
+        }
+        for k in keywords {
+            println!(
+                "{:5} {:8.1} {}",
+                k.seen().bright().yellow(),
+                k.score().dim(),
+                k.word()
+            );
+        }
+        Ok(())
+    }
+}
+
+impl SummaryCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        let prep = Preprocess::from(&self);
+        let mut text = String::new();
+        input::open_text(&self.files, self.recursive, &prep)?
+            .read_to_string(&mut text)?;
+        let summary = Summarizer::with_lexicon(shared_lexicon())
+            .summarize(&text, self.count)?;
+        if json {
+            let sentences: Vec<_> = summary
+                .iter()
+                .map(|s| {
+                    json!({
+                        "number": s.number(),
+                        "score": s.score(),
+                        "text": &text[s.span()],
+                    })
+                })
+                .collect();
+            print_json(json!({ "sentences": sentences }));
+            return Ok(());
+        }
+        for sentence in summary {
+            let words: Vec<_> =
+                text[sentence.span()].split_whitespace().collect();
+            println!("{}", words.join(" "));
+        }
+        Ok(())
+    }
+}
+
+/// Statistics report of `stats` command
+enum StatsReport {
+    /// Readability statistics
+    Readability(Readability),
+    /// Vocabulary statistics
+    Vocab(WordTally),
+}
+
+impl StatsReport {
+    /// Parse text from a reader
+    fn parse_text<R>(&mut self, reader: R) -> booky::Result<()>
+    where
+        R: BufRead,
+    {
+        match self {
+            StatsReport::Readability(stats) => stats.parse_text(reader),
+            StatsReport::Vocab(tally) => tally.parse_text(reader),
+        }
+    }
+
+    /// Merge another report into this one
+    fn merge(&mut self, other: StatsReport) {
+        match (self, other) {
+            (StatsReport::Readability(stats), StatsReport::Readability(o)) => {
+                stats.merge(&o)
+            }
+            (StatsReport::Vocab(tally), StatsReport::Vocab(o)) => {
+                tally.merge(o)
+            }
+            _ => (),
+        }
+    }
+
+    /// Write report
+    fn write(&self, json: bool) {
+        if json {
+            print_json(self.to_json());
+            return;
+        }
+        match self {
+            StatsReport::Readability(stats) => write_readability(stats),
+            StatsReport::Vocab(tally) => write_vocab(tally),
+        }
+    }
+
+    /// Make JSON document of report
+    fn to_json(&self) -> Value {
+        match self {
+            StatsReport::Readability(stats) => readability_json(stats),
+            StatsReport::Vocab(tally) => vocab_json(tally),
+        }
+    }
+}
+
+/// Get readability scores
+fn readability_scores(stats: &Readability) -> [(&'static str, Option<f64>); 6] {
+    [
+        ("avg_sentence_length", stats.avg_sentence_length()),
+        ("avg_word_length", stats.avg_word_length()),
+        ("flesch_reading_ease", stats.flesch_reading_ease()),
+        ("flesch_kincaid", stats.flesch_kincaid()),
+        ("gunning_fog", stats.gunning_fog()),
+        ("smog", stats.smog()),
+    ]
+}
+
+/// Make JSON document of readability statistics
+fn readability_json(stats: &Readability) -> Value {
+    let mut doc = json!({
+        "sentences": stats.sentences(),
+        "words": stats.words(),
+        "syllables": stats.syllables(),
+        "polysyllables": stats.polysyllables(),
+    });
+    for (name, score) in readability_scores(stats) {
+        doc[name] = json!(score);
+    }
+    doc
+}
+
+/// Write readability statistics
+fn write_readability(stats: &Readability) {
+    let counts = [
+        ("sentences", stats.sentences()),
+        ("words", stats.words()),
+        ("syllables", stats.syllables()),
+        ("polysyllables", stats.polysyllables()),
+    ];
+    for (name, count) in counts {
+        println!("{:20} {}", name.bold(), count.bright().yellow());
+    }
+    for (name, score) in readability_scores(stats) {
+        let score = score.map_or("-".to_string(), |s| format!("{s:.2}"));
+        let name = name.replace('_', " ");
+        println!("{:20} {}", name.bold(), score.yellow());
+    }
+}
+
+/// Make JSON document of vocabulary statistics
+fn vocab_json(tally: &WordTally) -> Value {
+    let ranks: Vec<_> = tally
+        .rank_frequency()
+        .into_iter()
+        .map(|(rank, entry)| {
+            json!({
+                "rank": rank,
+                "seen": entry.seen(),
+                "word": entry.word(),
+            })
+        })
+        .collect();
+    json!({
+        "tokens": tally.token_count(),
+        "types": tally.type_count(),
+        "hapax_legomena": tally.hapax_count(),
+        "type_token_ratio": tally.type_token_ratio(),
+        "growth": tally.vocabulary_growth(),
+        "ranks": ranks,
+    })
+}
+
+/// Write vocabulary statistics
+fn write_vocab(tally: &WordTally) {
+    let counts = [
+        ("tokens", tally.token_count()),
+        ("types", tally.type_count()),
+        ("hapax legomena", tally.hapax_count()),
+    ];
+    for (name, count) in counts {
+        println!("{:20} {}", name.bold(), count.bright().yellow());
+    }
+    let ttr = tally.type_token_ratio();
+    let ttr = ttr.map_or("-".to_string(), |r| format!("{r:.4}"));
+    println!("{:20} {}", "type token ratio".bold(), ttr.yellow());
+    println!("\n{}", "growth (tokens, types)".bold());
+    for (tokens, types) in tally.vocabulary_growth() {
+        println!("{tokens:8} {:6}", types.bright().yellow());
+    }
+}
+
+impl StatsCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        if self.files.is_empty() {
+            let stdin = input::stdin()?;
+            let mut report = self.new_report();
+            report.parse_text(
+                Preprocess::from(&self).prepare(stdin.lock(), None)?,
+            )?;
+            report.write(json);
+            return Ok(());
+        }
+        let prep = Preprocess::from(&self);
+        let paths = input::expand_paths(&self.files, self.recursive)?;
+        let per_file = paths.len() > 1 && !self.aggregate;
+        let mut total = self.new_report();
+        if !per_file && self.vocab {
+            // parse into total in order, keeping its vocabulary growth curve
+            for path in &paths {
+                self.parse_path(&mut total, path, &prep)?;
+            }
+            total.write(json);
+            return Ok(());
+        }
+        let mut reports = Vec::new();
+        let parsed = input::map_files(&paths, |path| {
+            let mut report = self.new_report();
+            self.parse_path(&mut report, path, &prep)?;
+            Ok(report)
+        });
+        for (path, report) in paths.iter().zip(parsed) {
+            let report = report?;
+            if !per_file {
+                total.merge(report);
+                continue;
+            }
+            if json {
+                let mut doc = report.to_json();
+                doc["path"] = json!(path.display().to_string());
+                reports.push(doc);
+            } else {
+                println!("{}", path.display().bold());
+                report.write(false);
+                println!();
+            }
+            if !self.vocab {
+                total.merge(report);
+            }
+        }
+        if self.vocab {
+            // merged tallies have no growth curve, so parse the total in order
+            for path in &paths {
+                self.parse_path(&mut total, path, &prep)?;
+            }
+        }
+        if !per_file {
+            total.write(json);
+        } else if json {
+            print_json(json!({
+                "files": reports,
+                "total": total.to_json(),
+            }));
+        } else {
+            println!("{} ({} files)", "total".bold(), paths.len());
+            total.write(false);
+        }
+        Ok(())
+    }
+
+    /// Parse all inputs of a path into a report
+    fn parse_path(
+        &self,
+        report: &mut StatsReport,
+        path: &Path,
+        prep: &Preprocess,
+    ) -> Result<()> {
+        for (name, mut reader) in input::open_inputs(path, prep)? {
+            input::check_language(&name, &mut reader)?;
+            report
+                .parse_text(reader)
+                .with_context(|| format!("Cannot read `{name}`"))?;
+        }
+        Ok(())
+    }
+
+    /// Create an empty report
+    fn new_report(&self) -> StatsReport {
+        if self.vocab {
+            StatsReport::Vocab(word_tally())
+        } else {
+            StatsReport::Readability(
+                Readability::with_lexicon(shared_lexicon()),
+            )
+        }
+    }
+}
+
+impl NgramsCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        if !(2..=3).contains(&self.n) {
+            return Err(exit::usage("N-grams must be 2 or 3 words"));
+        }
+        let prep = Preprocess::from(&self);
+        let reader = input::open_text(&self.files, self.recursive, &prep)?;
+        let mut tally = NgramTally::with_lexicon(shared_lexicon());
+        tally.parse_text(reader)?;
+        let entries = tally
+            .into_ngram_entries(self.n)
+            .into_iter()
+            .filter(|e| e.seen() >= self.min)
+            .take(self.count);
+        if json {
+            let ngrams: Vec<_> = entries
+                .map(|e| json!({ "words": e.words(), "seen": e.seen() }))
+                .collect();
+            print_json(json!({ "ngrams": ngrams }));
+        } else {
+            for entry in entries {
+                println!("{entry}");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl KwicCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        let prep = Preprocess::from(&self);
+        let mut text = String::new();
+        input::open_text(&self.files, self.recursive, &prep)?
+            .read_to_string(&mut text)?;
+        let lines = Concordance::with_lexicon(shared_lexicon())
+            .window(self.window)
+            .lexeme(self.lexeme)
+            .find(&text, &self.word)?;
+        if json {
+            let lines: Vec<_> = lines
+                .iter()
+                .map(|l| {
+                    json!({
+                        "line": l.line(),
+                        "column": l.column(),
+                        "left": l.left(),
+                        "keyword": l.keyword(),
+                        "right": l.right(),
+                    })
+                })
+                .collect();
+            print_json(json!({ "lines": lines }));
+            return Ok(());
+        }
+        let width = lines
+            .iter()
+            .map(|l| l.left().chars().count())
+            .max()
+            .unwrap_or(0);
+        for l in lines {
+            let pos = format!("{:>9}", format!("{}:{}", l.line(), l.column()));
+            let left = format!("{:>width$}", l.left());
+            println!(
+                "{} {left}{}{}",
+                pos.dim(),
+                l.keyword().bold().bright_yellow(),
+                l.right(),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl ScanCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        let prep = Preprocess::from(&self);
+        let reader = input::open_text(&self.files, self.recursive, &prep)?;
+        let lines =
+            Scanner::with_lexicon(shared_lexicon()).scan_text(reader)?;
+        if json {
+            let lines: Vec<_> = lines
+                .iter()
+                .map(|line| {
+                    let stresses: String =
+                        line.stresses().iter().map(|s| s.to_string()).collect();
+                    let breaks: Vec<_> = line
+                        .breaks(self.meter)
+                        .iter()
+                        .map(|b| b.to_string())
+                        .collect();
+                    json!({
+                        "text": line.text(),
+                        "syllables": line.syllables(),
+                        "stresses": stresses,
+                        "meter": line.detect_meter().map(|m| m.to_string()),
+                        "breaks": breaks,
+                    })
+                })
+                .collect();
+            print_json(json!({
+                "meter": self.meter.to_string(),
+                "lines": lines,
+            }));
+            return Ok(());
+        }
+        let mut fits = 0;
+        for line in &lines {
+            let stresses: String =
+                line.stresses().iter().map(|s| s.to_string()).collect();
+            println!(
+                "{:3} {:16} {}",
+                line.syllables().bright().yellow(),
+                stresses.dim(),
+                line.text()
+            );
+            let breaks = line.breaks(self.meter);
+            if breaks.is_empty() {
+                fits += 1;
+            }
+            for brk in breaks {
+                println!("    {} {brk}", "!!!".bright_yellow());
+            }
+        }
+        println!("{fits} of {} lines in {}", lines.len(), self.meter.bold());
+        Ok(())
+    }
+}
+
+impl RhymeCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        if let Some(word) = &self.word {
+            let mut rhymes = current_lexicon().rhymes(word);
+            rhymes.truncate(self.count);
+            if json {
+                print_json(json!({ "word": word, "rhymes": rhymes }));
+                return Ok(());
+            }
+            for rhyme in rhymes {
+                println!("{rhyme}");
+            }
+            return Ok(());
+        }
+        let prep = Preprocess::from(&self);
+        let reader = input::open_text(&self.files, self.recursive, &prep)?;
+        let stanzas =
+            RhymeAnalyzer::with_lexicon(shared_lexicon()).analyze(reader)?;
+        if json {
+            let stanzas: Vec<_> = stanzas
+                .iter()
+                .map(|stanza| {
+                    let alliterations: Vec<_> = stanza
+                        .alliterations()
+                        .iter()
+                        .map(|a| {
+                            json!({
+                                "line": a.line(),
+                                "sound": a.sound(),
+                                "words": a.words(),
+                            })
+                        })
+                        .collect();
+                    json!({
+                        "scheme": stanza.scheme(),
+                        "lines": stanza.lines(),
+                        "alliterations": alliterations,
+                    })
+                })
+                .collect();
+            print_json(json!({ "stanzas": stanzas }));
+            return Ok(());
+        }
+        for (i, stanza) in stanzas.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            println!("{}", stanza.scheme().bold());
+            for (n, (line, letter)) in stanza
+                .lines()
+                .iter()
+                .zip(stanza.scheme().chars())
+                .enumerate()
+            {
+                println!("{} {line}", letter.bright().yellow());
+                for run in
+                    stanza.alliterations().iter().filter(|a| a.line() == n)
+                {
+                    let words = run.words().join(" ");
+                    println!(
+                        "  {} {}: {}",
+                        "~".dim(),
+                        run.sound(),
+                        words.dim()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Get the UPOS tag of a word with a word class
+fn upos_tag(word: &str, wc: Option<WordClass>) -> Option<Upos> {
+    let entries = current_lexicon().word_entries(word);
+    if entries.is_empty() {
+        return Upos::from_kind(Kind::from(word), word);
+    }
+    let wc = wc?;
+    entries
+        .iter()
+        .find(|entry| entry.word_class() == wc)
+        .map(|entry| Upos::from(*entry))
+}
+
+/// Get the lexicon (built-in, plus any `--lexicon` entries)
+fn current_lexicon() -> &'static Lexicon {
+    LEXICON.get_or_init(cached_builtin)
+}
+
+/// Create a word tally with the lexicon (and any `--gazetteer` names or
+/// stop words)
+fn word_tally() -> WordTally {
+    let mut tally = WordTally::with_lexicon(shared_lexicon());
+    if let Some(gaz) = GAZETTEER.get() {
+        tally = tally.gazetteer(Arc::clone(gaz));
+    }
+    if let Some(stop) = STOPWORDS.get() {
+        tally = tally.stopwords(Arc::clone(stop));
+    }
+    tally
+}
+
+/// Load a gazetteer file, adding its names to months and weekdays
+fn load_gazetteer(path: &str) -> Result<()> {
+    let mut gaz = Gazetteer::calendar();
+    let file = fs::File::open(path)
+        .with_context(|| format!("Cannot open gazetteer `{path}`"))?;
+    gaz.load(std::io::BufReader::new(file))
+        .with_context(|| format!("Cannot load gazetteer `{path}`"))?;
+    // only called once, before any command runs
+    let _ = GAZETTEER.set(Arc::new(gaz));
+    Ok(())
+}
+
+/// Get a shared handle to the lexicon
+fn shared_lexicon() -> Arc<Lexicon> {
+    Arc::clone(LEXICON.get_or_init(cached_builtin))
+}
+
+/// Get the built-in lexicon, using a cache file when possible
+fn cached_builtin() -> Arc<Lexicon> {
+    let Some(path) = cache_path() else {
+        return lex::builtin_shared();
+    };
+    if let Ok(lex) = Lexicon::from_cache(&path) {
+        return Arc::new(lex);
+    }
+    let lex = lex::builtin_shared();
+    // the cache is only an optimization, so errors are ignored;
+    // write to a temporary file first, in case of concurrent runs
+    let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+    if let Some(dir) = path.parent()
+        && fs::create_dir_all(dir).is_ok()
+        && lex
+            .to_cache(&tmp)
+            .and_then(|()| Ok(fs::rename(&tmp, &path)?))
+            .is_err()
+    {
+        let _ = fs::remove_file(&tmp);
+    }
+    lex
+}
+
+/// Get the path of the built-in lexicon cache file
+///
+/// The file name includes the version and a hash of the built-in lexicon
+/// source, so a stale cache is never used.  Set `BOOKY_NO_CACHE` to skip it.
+fn cache_path() -> Option<PathBuf> {
+    if env::var_os("BOOKY_NO_CACHE").is_some() {
+        return None;
+    }
+    let dir = env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| {
+            env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache"))
+        })?;
+    // FNV-1a hash
+    let hash = lex::builtin_csv().bytes().fold(0x811c_9dc5_u32, |h, b| {
+        (h ^ u32::from(b)).wrapping_mul(0x0100_0193)
+    });
+    let name = format!("lexicon-{}-{hash:08x}.bin", env!("CARGO_PKG_VERSION"));
+    Some(dir.join("booky").join(name))
+}
+
+/// Load a lexicon file, adding its entries to the built-in lexicon
+fn load_lexicon(path: &str) -> Result<()> {
+    let extra = Lexicon::from_path(path)
+        .with_context(|| format!("Cannot load lexicon `{path}`"))?;
+    let mut lex = Arc::unwrap_or_clone(cached_builtin());
+    lex.merge(extra, MergePolicy::KeepLast);
+    // only called once, before any command runs
+    let _ = LEXICON.set(Arc::new(lex));
+    Ok(())
+}
+
+/// Print a JSON document
+fn print_json(doc: Value) {
+    println!("{doc}");
+}
+
+/// Make a JSON value for a lexeme
+fn lexeme_json(word: &Lexeme) -> Value {
+    let attributes: Vec<_> =
+        word.attributes().map(|a| format!("{a:?}")).collect();
+    let inflections: Vec<_> = word
+        .labeled_forms()
+        .iter()
+        .map(
+            |f| json!({ "text": f.text(), "feature": f.feature().to_string() }),
+        )
+        .collect();
+    json!({
+        "lemma": word.lemma(),
+        "class": word.word_class().to_string(),
+        "attributes": attributes,
+        "forms": word.forms(),
+        "inflections": inflections,
+    })
+}
+
+impl NonsenseCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        let mut generator = Generator::with_lexicon(shared_lexicon());
+        let sentences: Vec<_> = (0..self.count)
+            .map(|_i| generator.sentence(&self.template))
+            .collect();
+        if json {
+            print_json(json!({ "sentences": sentences }));
+        } else {
+            for sentence in sentences {
+                println!("{sentence}");
+            }
+        }
+        Ok(())
+    }
+}
+
+impl SuggestCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        let words = if self.words.is_empty() {
+            self.unknown_words()?
+        } else {
+            self.words.clone()
+        };
+        let lex = current_lexicon();
+        let mut all = Vec::new();
+        for word in words {
+            let mut suggestions = lex.suggest(&word, self.distance);
+            suggestions.truncate(self.count);
+            if json {
+                let suggestions: Vec<_> = suggestions
+                    .iter()
+                    .map(|s| json!({ "word": s.word(), "distance": s.distance() }))
+                    .collect();
+                all.push(json!({ "word": word, "suggestions": suggestions }));
+            } else {
+                print!("{}", word.bold());
+                for s in suggestions {
+                    let distance = format!("({})", s.distance());
+                    print!(" {}{}", s.word().italic(), distance.dim());
+                }
+                println!();
+            }
+        }
+        if json {
+            print_json(json!({ "words": all }));
+        }
+        Ok(())
+    }
+
+    /// Get unknown words from input (most frequent first)
+    fn unknown_words(&self) -> Result<Vec<String>> {
+        let prep = Preprocess::from(self);
+        let reader = input::open_text(&self.files, self.recursive, &prep)?;
+        let mut tally = word_tally();
+        tally.parse_text(reader)?;
+        Ok(tally
+            .into_entries()
+            .into_iter()
+            .rev()
+            .filter(|e| e.kind() == Kind::Unknown)
+            .map(|e| e.word().to_string())
+            .collect())
+    }
+}
+
+impl CheckCmd {
+    /// Run command
+    ///
+    /// Exits with code 1 if any problems are found.
+    fn run(self, json: bool) -> Result<()> {
+        let mut checker = Checker::with_lexicon(shared_lexicon())
+            .max_distance(self.distance)
+            .max_suggestions(self.count);
+        if let Some(gaz) = GAZETTEER.get() {
+            checker = checker.gazetteer(Arc::clone(gaz));
+        }
+        let mut checked: Vec<(Option<String>, Vec<Misspelling>)> = Vec::new();
+        if self.files.is_empty() {
+            let stdin = input::stdin()?;
+            let reader = Preprocess::from(&self).prepare(stdin.lock(), None)?;
+            checked.push((None, checker.check_text(reader)?));
+        }
+        let prep = Preprocess::from(&self);
+        let paths = input::expand_paths(&self.files, self.recursive)?;
+        let results = input::map_files(&paths, |path| {
+            let mut checker = checker.clone();
+            let mut checked = Vec::new();
+            for (name, reader) in input::open_inputs(path, &prep)? {
+                let misspellings = checker
+                    .check_text(reader)
+                    .with_context(|| format!("Cannot read `{name}`"))?;
+                checked.push((Some(name), misspellings));
+            }
+            Ok((checked, checker))
+        });
+        for res in results {
+            let (file_checked, file_checker) = res?;
+            checked.extend(file_checked);
+            checker.merge(file_checker);
+        }
+        let inconsistencies = checker.inconsistencies();
+        let findings = inconsistencies.len()
+            + checked.iter().map(|(_name, m)| m.len()).sum::<usize>();
+        if json {
+            let mut misspellings = Vec::new();
+            for (name, checked) in &checked {
+                for m in checked {
+                    let suggestions: Vec<_> =
+                        m.suggestions().iter().map(|s| s.word()).collect();
+                    misspellings.push(json!({
+                        "file": name,
+                        "word": m.word(),
+                        "line": m.line(),
+                        "column": m.column(),
+                        "suggestions": suggestions,
+                    }));
+                }
+            }
+            let inconsistencies: Vec<_> = inconsistencies
+                .iter()
+                .map(|(uk, us)| json!({ "british": uk, "american": us }))
+                .collect();
+            print_json(json!({
+                "misspellings": misspellings,
+                "inconsistencies": inconsistencies,
+            }));
+        } else {
+            for (name, checked) in &checked {
+                for m in checked {
+                    let loc = match name {
+                        Some(name) => {
+                            format!("{name}:{}:{}", m.line(), m.column())
+                        }
+                        None => format!("{}:{}", m.line(), m.column()),
+                    };
+                    print!("{} {}", loc.dim(), m.word().bold());
+                    for s in m.suggestions() {
+                        print!(" {}", s.word().italic());
+                    }
+                    println!();
+                }
+            }
+            for (uk, us) in &inconsistencies {
+                println!("{} {uk} / {us}", "!!!".bright_yellow());
+            }
+        }
+        if findings > 0 {
+            return Err(exit::Findings(findings).into());
+        }
+        Ok(())
+    }
+}
+
+impl AnagramCmd {
+    /// Run command
+    fn run(self, json: bool) -> Result<()> {
+        let classes = match &self.classes {
+            Some(classes) => classes
+                .split(',')
+                .map(WordClass::try_from)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| exit::usage(format!("Bad classes: {classes}")))?,
+            None => Vec::new(),
+        };
+        let lex = current_lexicon();
+        let entries = if self.sub {
+            lex.sub_anagrams(&self.letters)
+        } else {
+            lex.anagrams(&self.letters)
+        };
+        let mut words: Vec<&str> = Vec::new();
+        for entry in entries {
+            let lemma = entry.lemma();
+            if (classes.is_empty() || classes.contains(&entry.word_class()))
+                && lemma.chars().filter(|c| c.is_alphabetic()).count()
+                    >= self.min
+                && !words.contains(&lemma)
+            {
+                words.push(lemma);
+            }
+        }
+        if json {
+            print_json(json!({ "letters": self.letters, "words": words }));
+            return Ok(());
+        }
+        for word in words {
+            println!("{word}");
+        }
+        Ok(())
+    }
+}
+
+/// Run sub-command
+fn run(args: Args) -> Result<()> {
+    if let Some(path) = &args.lexicon {
+        load_lexicon(path)?;
+    }
+    if let Some(path) = &args.gazetteer {
+        load_gazetteer(path)?;
+    }
+    match args.cmd {
+        Some(SubCommand::Hilite(cmd)) => cmd.run()?,
+        Some(SubCommand::Read(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Harvest(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Word(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Tag(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Lemma(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Spelling(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Keywords(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Summary(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Stats(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Ngrams(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Kwic(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Scan(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Rhyme(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Lexicon(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Repl(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Completions(cmd)) => cmd.run()?,
+        #[cfg(feature = "tui")]
+        Some(SubCommand::Tui(cmd)) => cmd.run()?,
+        Some(SubCommand::Nonsense(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Suggest(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Check(cmd)) => cmd.run(args.json)?,
+        Some(SubCommand::Anagram(cmd)) => cmd.run(args.json)?,
+        None if args.man => print!("{}", completions::man_page()?),
+        None => {
+            let help = match Args::from_args(&["booky"], &["--help"]) {
+                Err(e) => e.output,
+                Ok(_) => String::new(),
+            };
+            return Err(exit::usage(help.trim_end()));
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let argv: Vec<String> = env::args().collect();
+    let strs: Vec<&str> = argv.iter().skip(1).map(String::as_str).collect();
+    // `--json` may be needed before arguments are parsed
+    let json = strs.contains(&"--json");
+    let args = match Args::from_args(&["booky"], &strs) {
+        Ok(args) => args,
+        Err(EarlyExit {
+            output,
+            status: Ok(()),
+        }) => {
+            println!("{output}");
+            return ExitCode::from(exit::OK);
+        }
+        Err(EarlyExit {
+            output,
+            status: Err(()),
+        }) => return exit::report_usage(output.trim_end(), json),
+    };
+    if !args.color.is_enabled() {
+        yansi::disable();
+    }
+    let json = args.json;
+    match run(args) {
+        Ok(()) => ExitCode::from(exit::OK),
+        Err(err) => exit::report(err, json),
+    }
+}
@@ -0,0 +1,23 @@
+use std::env;
+use std::process::{Child, Command, Stdio};
+
+/// Default pager command
+const DEFAULT_PAGER: &str = "less";
+
+/// Spawn a pager process (`$PAGER` or `less`), with piped stdin
+///
+/// When `LESS` is not set, it is set to `FRX` so that short output is not
+/// paged and colors are passed through.
+pub fn spawn() -> Option<Child> {
+    let pager = env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_PAGER.to_string());
+    let mut parts = pager.split_whitespace();
+    let mut cmd = Command::new(parts.next()?);
+    cmd.args(parts).stdin(Stdio::piped());
+    if env::var_os("LESS").is_none() {
+        cmd.env("LESS", "FRX");
+    }
+    cmd.spawn().ok()
+}
@@ -0,0 +1,128 @@
+use anyhow::Result;
+use argh::{ArgsInfo, FromArgs};
+use booky::kind::Kind;
+use booky::lex::make_word;
+use booky::word::syllables;
+use serde_json::json;
+use std::io::{BufRead, Write, stdin, stdout};
+use yansi::Paint;
+
+/// Maximum number of suggestions
+const MAX_SUGGESTIONS: usize = 8;
+
+/// Interactive word lookup prompt
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "repl")]
+pub struct ReplCmd {
+    /// maximum edit distance of suggestions
+    #[argh(option, short = 'd', default = "2")]
+    distance: usize,
+}
+
+impl ReplCmd {
+    /// Run command
+    pub fn run(self, json: bool) -> Result<()> {
+        let stdin = stdin();
+        loop {
+            if !json {
+                print!("{} ", ">".bright_green().bold());
+                stdout().flush()?;
+            }
+            let mut line = String::new();
+            if stdin.lock().read_line(&mut line)? == 0 {
+                if !json {
+                    println!();
+                }
+                break;
+            }
+            match line.trim() {
+                ":q" | ":quit" => break,
+                words => {
+                    for word in words.split_whitespace() {
+                        if json {
+                            self.lookup_json(word);
+                        } else {
+                            self.lookup(word);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Lookup a word
+    fn lookup(&self, word: &str) {
+        let kind = word_kind(word);
+        let syllables = syllables(word);
+        println!(
+            "{} {} {kind:?} {} {}",
+            word.bold(),
+            kind.code().yellow(),
+            syllables.bright_yellow(),
+            if syllables == 1 {
+                "syllable"
+            } else {
+                "syllables"
+            },
+        );
+        for w in crate::current_lexicon().word_entries(word) {
+            print!("  {}:{} ", w.lemma().bold(), w.word_class().bold());
+            for form in w.forms().iter().filter(|f| *f != w.lemma()) {
+                if make_word(form) == make_word(word) {
+                    print!("{} ", form.bright_yellow().italic());
+                } else {
+                    print!("{form} ");
+                }
+            }
+            println!();
+        }
+        if kind == Kind::Unknown {
+            let suggestions = suggest(word, self.distance);
+            if !suggestions.is_empty() {
+                println!("  {} {}", "?".dim(), suggestions.join(" ").italic());
+            }
+        }
+    }
+
+    /// Lookup a word (JSON output)
+    fn lookup_json(&self, word: &str) {
+        let kind = word_kind(word);
+        let entries: Vec<_> = crate::current_lexicon()
+            .word_entries(word)
+            .into_iter()
+            .map(crate::lexeme_json)
+            .collect();
+        let suggestions = if kind == Kind::Unknown {
+            suggest(word, self.distance)
+        } else {
+            Vec::new()
+        };
+        crate::print_json(json!({
+            "word": word,
+            "kind": format!("{kind:?}"),
+            "syllables": syllables(word),
+            "entries": entries,
+            "suggestions": suggestions,
+        }));
+    }
+}
+
+/// Get the kind of a word
+fn word_kind(word: &str) -> Kind {
+    if crate::current_lexicon().contains(word) {
+        Kind::Lexicon
+    } else {
+        Kind::from(word)
+    }
+}
+
+/// Suggest lexicon forms similar to a word
+fn suggest(word: &str, distance: usize) -> Vec<String> {
+    crate::current_lexicon()
+        .suggest(word, distance)
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|s| s.word().to_string())
+        .collect()
+}
@@ -0,0 +1,387 @@
+use anyhow::{Context, Result};
+use argh::{ArgsInfo, FromArgs};
+use booky::kind::Kind;
+use booky::lex::make_word;
+use booky::tally::WordEntry;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, List, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::fs;
+use std::io::Read;
+
+/// Maximum number of occurrences listed
+const MAX_OCCURRENCES: usize = 1000;
+
+/// Browse a word tally interactively
+#[derive(ArgsInfo, FromArgs, Debug, PartialEq)]
+#[argh(subcommand, name = "tui")]
+pub struct TuiCmd {
+    /// input files (default: stdin)
+    #[argh(positional)]
+    files: Vec<String>,
+}
+
+/// Entry sort order
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SortOrder {
+    /// Seen count (descending)
+    Count,
+    /// Alphabetical
+    Alpha,
+    /// Word length (descending)
+    Length,
+}
+
+/// Input focus
+#[derive(Clone, Copy, PartialEq)]
+enum Focus {
+    /// Word entry list
+    Entries,
+    /// Occurrence list
+    Occurrences,
+    /// Filter text input
+    Filter,
+}
+
+/// Tally browser state
+struct Browser {
+    /// Source text
+    text: String,
+    /// All tally entries
+    entries: Vec<WordEntry>,
+    /// Indices of visible entries
+    visible: Vec<usize>,
+    /// Sort order
+    sort: SortOrder,
+    /// Reverse sort
+    reverse: bool,
+    /// Kind filter
+    kind: Option<Kind>,
+    /// Word filter text
+    filter: String,
+    /// Input focus
+    focus: Focus,
+    /// Entry list state
+    list: ListState,
+    /// Occurrences of selected word (line number, text)
+    occurrences: Vec<(usize, String)>,
+    /// Occurrence list state
+    occ_list: ListState,
+}
+
+impl TuiCmd {
+    /// Run command
+    pub fn run(self) -> Result<()> {
+        let text = self.read_text()?;
+        let mut tally = crate::word_tally();
+        tally.parse_text(text.as_bytes())?;
+        let mut browser = Browser::new(text, tally.into_entries());
+        let terminal = ratatui::init();
+        let res = browser.run(terminal);
+        ratatui::restore();
+        res
+    }
+
+    /// Read all input text
+    fn read_text(&self) -> Result<String> {
+        let mut text = String::new();
+        if self.files.is_empty() {
+            crate::input::stdin()?.lock().read_to_string(&mut text)?;
+        }
+        for path in &self.files {
+            let t = fs::read_to_string(path)
+                .with_context(|| format!("Cannot read `{path}`"))?;
+            text.push_str(&t);
+            text.push('\n');
+        }
+        Ok(text)
+    }
+}
+
+impl SortOrder {
+    /// Get next sort order
+    fn next(self) -> Self {
+        match self {
+            SortOrder::Count => SortOrder::Alpha,
+            SortOrder::Alpha => SortOrder::Length,
+            SortOrder::Length => SortOrder::Count,
+        }
+    }
+}
+
+/// Get next kind filter
+fn next_kind(kind: Option<Kind>) -> Option<Kind> {
+    let all = Kind::all();
+    match kind {
+        None => all.first().copied(),
+        Some(k) => {
+            let i = all.iter().position(|a| *a == k).unwrap_or_default();
+            all.get(i + 1).copied()
+        }
+    }
+}
+
+impl Browser {
+    /// Create a new tally browser
+    fn new(text: String, entries: Vec<WordEntry>) -> Self {
+        let mut browser = Browser {
+            text,
+            entries,
+            visible: Vec::new(),
+            sort: SortOrder::Count,
+            reverse: false,
+            kind: None,
+            filter: String::new(),
+            focus: Focus::Entries,
+            list: ListState::default(),
+            occurrences: Vec::new(),
+            occ_list: ListState::default(),
+        };
+        browser.refresh();
+        browser
+    }
+
+    /// Run browser event loop
+    fn run(&mut self, mut terminal: DefaultTerminal) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && !self.handle_key(key.code)
+            {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Refresh visible entries
+    fn refresh(&mut self) {
+        let filter = make_word(&self.filter);
+        let mut visible: Vec<usize> = (0..self.entries.len())
+            .filter(|i| {
+                let entry = &self.entries[*i];
+                self.kind.is_none_or(|k| k == entry.kind())
+                    && make_word(entry.word()).contains(&filter)
+            })
+            .collect();
+        let entries = &self.entries;
+        match self.sort {
+            SortOrder::Count => visible.sort_by(|a, b| {
+                let (a, b) = (&entries[*a], &entries[*b]);
+                b.seen().cmp(&a.seen()).then_with(|| a.word().cmp(b.word()))
+            }),
+            SortOrder::Alpha => visible.sort_by_cached_key(|i| {
+                (make_word(entries[*i].word()), entries[*i].word())
+            }),
+            SortOrder::Length => visible.sort_by(|a, b| {
+                let (a, b) = (entries[*a].word(), entries[*b].word());
+                b.chars().count().cmp(&a.chars().count()).then(a.cmp(b))
+            }),
+        }
+        if self.reverse {
+            visible.reverse();
+        }
+        self.visible = visible;
+        let selected = match self.list.selected() {
+            Some(i) => i.min(self.visible.len().saturating_sub(1)),
+            None => 0,
+        };
+        self.list
+            .select((!self.visible.is_empty()).then_some(selected));
+        self.find_occurrences();
+    }
+
+    /// Get selected entry
+    fn selected(&self) -> Option<&WordEntry> {
+        let i = self.list.selected()?;
+        self.visible.get(i).map(|i| &self.entries[*i])
+    }
+
+    /// Find occurrences of selected word
+    fn find_occurrences(&mut self) {
+        self.occurrences.clear();
+        self.occ_list.select(None);
+        let Some(word) = self.selected().map(|e| make_word(e.word())) else {
+            return;
+        };
+        for (i, line) in self.text.lines().enumerate() {
+            if contains_word(&make_word(line), &word) {
+                self.occurrences.push((i + 1, line.trim().to_string()));
+                if self.occurrences.len() >= MAX_OCCURRENCES {
+                    break;
+                }
+            }
+        }
+        if !self.occurrences.is_empty() {
+            self.occ_list.select(Some(0));
+        }
+    }
+
+    /// Handle a key press (returns false to quit)
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        if self.focus == Focus::Filter {
+            match code {
+                KeyCode::Enter => self.focus = Focus::Entries,
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.focus = Focus::Entries;
+                }
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Char(c) => self.filter.push(c),
+                _ => return true,
+            }
+            self.refresh();
+            return true;
+        }
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('s') => {
+                self.sort = self.sort.next();
+                self.refresh();
+            }
+            KeyCode::Char('r') => {
+                self.reverse = !self.reverse;
+                self.refresh();
+            }
+            KeyCode::Char('k') => {
+                self.kind = next_kind(self.kind);
+                self.refresh();
+            }
+            KeyCode::Char('/') => self.focus = Focus::Filter,
+            KeyCode::Tab => {
+                self.focus = match self.focus {
+                    Focus::Entries => Focus::Occurrences,
+                    _ => Focus::Entries,
+                };
+            }
+            KeyCode::Up => self.move_selection(-1),
+            KeyCode::Down => self.move_selection(1),
+            KeyCode::PageUp => self.move_selection(-20),
+            KeyCode::PageDown => self.move_selection(20),
+            KeyCode::Home => self.move_selection(isize::MIN),
+            KeyCode::End => self.move_selection(isize::MAX),
+            _ => (),
+        }
+        true
+    }
+
+    /// Move selection of focused list
+    fn move_selection(&mut self, delta: isize) {
+        let (state, len) = match self.focus {
+            Focus::Occurrences => (&mut self.occ_list, self.occurrences.len()),
+            _ => (&mut self.list, self.visible.len()),
+        };
+        if len == 0 {
+            return;
+        }
+        let i = state.selected().unwrap_or_default();
+        let i = i.saturating_add_signed(delta).min(len - 1);
+        state.select(Some(i));
+        if self.focus == Focus::Entries {
+            self.find_occurrences();
+        }
+    }
+
+    /// Draw browser
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)])
+                .areas(frame.area());
+        let [left, right] = Layout::horizontal([
+            Constraint::Percentage(35),
+            Constraint::Fill(1),
+        ])
+        .areas(main);
+        let [lookup, occ] =
+            Layout::vertical([Constraint::Length(8), Constraint::Fill(1)])
+                .areas(right);
+        let highlight = Style::new().add_modifier(Modifier::REVERSED);
+        let items = self.visible.iter().map(|i| {
+            let entry = &self.entries[*i];
+            let kind = entry.kind().code();
+            format!("{:6} {kind} {}", entry.seen(), entry.word())
+        });
+        let kind = match self.kind {
+            Some(k) => format!("{k:?}"),
+            None => "All".to_string(),
+        };
+        let title = format!(
+            " {} words │ sort: {:?}{} │ kind: {kind} ",
+            self.visible.len(),
+            self.sort,
+            if self.reverse { " (rev)" } else { "" },
+        );
+        let list = List::new(items)
+            .block(focus_block(title, self.focus == Focus::Entries))
+            .highlight_style(highlight);
+        frame.render_stateful_widget(list, left, &mut self.list);
+        let lookup_lines = self.lookup_lines();
+        let para = Paragraph::new(lookup_lines).block(Block::bordered());
+        frame.render_widget(para, lookup);
+        let items = self
+            .occurrences
+            .iter()
+            .map(|(n, line)| format!("{n:6}: {line}"));
+        let title = format!(" {} occurrences ", self.occurrences.len());
+        let list = List::new(items)
+            .block(focus_block(title, self.focus == Focus::Occurrences))
+            .highlight_style(highlight);
+        frame.render_stateful_widget(list, occ, &mut self.occ_list);
+        let help = if self.focus == Focus::Filter {
+            Line::from(format!("filter: {}▏", self.filter)).bold()
+        } else {
+            Line::from(
+                "q quit │ ↑↓ move │ s sort │ r reverse │ k kind │ / filter \
+                 │ tab occurrences",
+            )
+            .dim()
+        };
+        frame.render_widget(help, status);
+    }
+
+    /// Make lexicon lookup lines for selected word
+    fn lookup_lines(&self) -> Vec<Line<'static>> {
+        let Some(entry) = self.selected() else {
+            return Vec::new();
+        };
+        let mut lines = vec![
+            Line::from(format!(
+                "{}  ({:?}, seen {})",
+                entry.word(),
+                entry.kind(),
+                entry.seen()
+            ))
+            .bold(),
+        ];
+        for lexeme in crate::current_lexicon().word_entries(entry.word()) {
+            lines.push(Line::from(format!(
+                "{}:{}  {}",
+                lexeme.lemma(),
+                lexeme.word_class(),
+                lexeme.forms().join(" ")
+            )));
+        }
+        lines
+    }
+}
+
+/// Make a block, highlighted when focused
+fn focus_block(title: String, focused: bool) -> Block<'static> {
+    let block = Block::bordered().title(title);
+    if focused { block.yellow() } else { block }
+}
+
+/// Check if a line contains a whole word
+fn contains_word(line: &str, word: &str) -> bool {
+    line.match_indices(word).any(|(i, w)| {
+        let before = line[..i].chars().next_back();
+        let after = line[i + w.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric)
+            && !after.is_some_and(char::is_alphanumeric)
+    })
+}
@@ -0,0 +1,218 @@
+use crate::{Error, Result};
+
+/// Binary cache writer
+#[derive(Default)]
+pub(crate) struct Writer {
+    /// Encoded bytes
+    buf: Vec<u8>,
+}
+
+/// Binary cache reader
+pub(crate) struct Reader<'a> {
+    /// Remaining bytes
+    buf: &'a [u8],
+}
+
+/// Make a cache decoding error
+pub(crate) fn invalid() -> Error {
+    Error::Decode("Invalid lexicon cache".to_string())
+}
+
+impl Writer {
+    /// Create a new cache writer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write raw bytes
+    pub fn bytes(&mut self, b: &[u8]) {
+        self.buf.extend_from_slice(b);
+    }
+
+    /// Write a `u32` value (little-endian)
+    pub fn u32(&mut self, v: u32) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    /// Write a length (as `u32`)
+    pub fn len(&mut self, len: usize) {
+        self.u32(len as u32);
+    }
+
+    /// Write a string (length first)
+    pub fn str(&mut self, s: &str) {
+        self.len(s.len());
+        self.bytes(s.as_bytes());
+    }
+
+    /// Write a slice of strings (count first)
+    pub fn strs(&mut self, strs: &[String]) {
+        self.len(strs.len());
+        for s in strs {
+            self.str(s);
+        }
+    }
+
+    /// Get encoded bytes
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+impl<'a> Reader<'a> {
+    /// Create a new cache reader
+    pub fn new(buf: &'a [u8]) -> Self {
+        Reader { buf }
+    }
+
+    /// Read raw bytes
+    pub fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.buf.len() {
+            return Err(invalid());
+        }
+        let (b, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Ok(b)
+    }
+
+    /// Read a `u32` value (little-endian)
+    pub fn u32(&mut self) -> Result<u32> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Read a length, checking that enough bytes remain for its items
+    pub fn len(&mut self, item_size: usize) -> Result<usize> {
+        let len = self.u32()? as usize;
+        if len.saturating_mul(item_size) > self.buf.len() {
+            return Err(invalid());
+        }
+        Ok(len)
+    }
+
+    /// Read a string
+    pub fn str(&mut self) -> Result<&'a str> {
+        let len = self.len(1)?;
+        str::from_utf8(self.bytes(len)?).map_err(|_| invalid())
+    }
+
+    /// Read a Vec of strings
+    pub fn strs(&mut self) -> Result<Vec<String>> {
+        let len = self.len(4)?;
+        (0..len).map(|_i| Ok(self.str()?.to_string())).collect()
+    }
+
+    /// Check that all bytes have been read
+    pub fn finish(self) -> Result<()> {
+        if self.buf.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::lex::{self, Lexicon};
+    use std::path::PathBuf;
+
+    /// Get a scratch cache path for one test
+    fn cache_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("booky-{}-{name}.cache", std::process::id()))
+    }
+
+    /// Load a cache file, falling back to the built-in lexicon
+    fn load_or_builtin(path: &PathBuf) -> (bool, Lexicon) {
+        match Lexicon::from_cache(path) {
+            Ok(lex) => (true, lex),
+            Err(_) => (false, lex::builtin().clone()),
+        }
+    }
+
+    /// Write a small lexicon cache, returning its bytes
+    fn write_small(path: &PathBuf) -> Vec<u8> {
+        let lex = Lexicon::from_csv("blorf:V\nsnark:N\nrun:V,ran,run").unwrap();
+        lex.to_cache(path).unwrap();
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    fn values() {
+        let mut w = Writer::new();
+        w.u32(0xDEAD_BEEF);
+        w.str("blörf");
+        w.strs(&["a".to_string(), String::new()]);
+        let buf = w.into_bytes();
+        let mut r = Reader::new(&buf);
+        assert_eq!(r.u32().unwrap(), 0xDEAD_BEEF);
+        assert_eq!(r.str().unwrap(), "blörf");
+        assert_eq!(r.strs().unwrap(), ["a", ""]);
+        r.finish().unwrap();
+    }
+
+    #[test]
+    fn bad_values() {
+        // length larger than remaining bytes
+        let mut r = Reader::new(&[9, 0, 0, 0, b'a']);
+        assert!(matches!(r.str(), Err(Error::Decode(_))));
+        // invalid UTF-8
+        let mut r = Reader::new(&[1, 0, 0, 0, 0xFF]);
+        assert!(r.str().is_err());
+        // too few bytes for a u32
+        assert!(Reader::new(&[1, 2, 3]).u32().is_err());
+        // bytes left over
+        assert!(Reader::new(&[0]).finish().is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = cache_path("round-trip");
+        write_small(&path);
+        let (cached, lex) = load_or_builtin(&path);
+        assert!(cached);
+        assert!(lex.contains("blorfed") && lex.contains("ran"));
+        assert!(!lex.contains("the"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn truncated() {
+        let path = cache_path("truncated");
+        let buf = write_small(&path);
+        for len in 0..buf.len() {
+            std::fs::write(&path, &buf[..len]).unwrap();
+            let (cached, lex) = load_or_builtin(&path);
+            assert!(!cached, "{len}");
+            assert!(lex.contains("the"));
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn mismatch() {
+        let path = cache_path("mismatch");
+        let buf = write_small(&path);
+        let magic = b"booky-lexicon".len();
+        // another cache version
+        let mut other = buf.clone();
+        other[magic] = other[magic].wrapping_add(1);
+        std::fs::write(&path, &other).unwrap();
+        let (cached, lex) = load_or_builtin(&path);
+        assert!(!cached && lex.contains("the"));
+        // another file type
+        let mut other = buf.clone();
+        other[0] = b'B';
+        std::fs::write(&path, &other).unwrap();
+        assert!(!load_or_builtin(&path).0);
+        // corrupt form index (last link points past the end)
+        let mut other = buf.clone();
+        let end = other.len();
+        other[end - 8..end - 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(&path, &other).unwrap();
+        assert!(!load_or_builtin(&path).0);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
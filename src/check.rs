@@ -0,0 +1,291 @@
+use crate::Result;
+use crate::gazetteer::Gazetteer;
+use crate::kind::Kind;
+use crate::lex::{self, Lexicon, make_word};
+use crate::parse::{Chunk, Parser};
+use crate::spelling::SpellingTally;
+use crate::suggest::Suggestion;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::sync::Arc;
+
+/// Default maximum edit distance of suggestions
+const MAX_DISTANCE: usize = 2;
+
+/// Default maximum suggestions per word
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Unknown word found by a spelling check
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Misspelling {
+    /// Word as it appears in the text
+    word: String,
+    /// Line number (starting at 1)
+    line: usize,
+    /// Column number (characters, starting at 1)
+    column: usize,
+    /// Suggested corrections (nearest first)
+    suggestions: Vec<Suggestion>,
+}
+
+/// Spelling and consistency checker
+///
+/// Words not found in the lexicon are reported with suggested corrections,
+/// and regional spellings are tallied across all checked texts to find
+/// words spelled both ways (e.g. "colour" and "color").
+#[derive(Clone)]
+pub struct Checker {
+    /// Maximum edit distance of suggestions
+    max_distance: usize,
+    /// Maximum suggestions per word
+    max_suggestions: usize,
+    /// Suggestions of words already checked
+    suggestions: HashMap<String, Vec<Suggestion>>,
+    /// Regional spelling tally
+    spelling: SpellingTally,
+    /// Gazetteer for finding names
+    gazetteer: Option<Arc<Gazetteer>>,
+    /// Lexicon for parsing (built-in if not set)
+    lex: Option<Arc<Lexicon>>,
+}
+
+impl Misspelling {
+    /// Get the word as it appears in the text
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// Get line number (starting at 1)
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get column number (characters, starting at 1)
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Get suggested corrections (nearest first)
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions[..]
+    }
+}
+
+impl Default for Checker {
+    fn default() -> Self {
+        Checker {
+            max_distance: MAX_DISTANCE,
+            max_suggestions: MAX_SUGGESTIONS,
+            suggestions: HashMap::new(),
+            spelling: SpellingTally::new(),
+            gazetteer: None,
+            lex: None,
+        }
+    }
+}
+
+impl Checker {
+    /// Create a new checker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new checker with a shared lexicon
+    pub fn with_lexicon(lex: Arc<Lexicon>) -> Self {
+        Checker {
+            lex: Some(lex),
+            ..Default::default()
+        }
+    }
+
+    /// Set the maximum edit distance of suggestions (default 2)
+    pub fn max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = max_distance;
+        self.suggestions.clear();
+        self
+    }
+
+    /// Set the maximum suggestions per word (default 3)
+    pub fn max_suggestions(mut self, max_suggestions: usize) -> Self {
+        self.max_suggestions = max_suggestions;
+        self.suggestions.clear();
+        self
+    }
+
+    /// Use a gazetteer to find names (`Kind::Proper`), which are not checked
+    pub fn gazetteer(mut self, gazetteer: Arc<Gazetteer>) -> Self {
+        self.gazetteer = Some(gazetteer);
+        self
+    }
+
+    /// Check text from a reader
+    ///
+    /// Returns unknown words, in order of appearance.  Capitalized words
+    /// starting a sentence are not reported if they appear as names within
+    /// a sentence anywhere in the text.
+    pub fn check_text<R>(&mut self, reader: R) -> Result<Vec<Misspelling>>
+    where
+        R: BufRead,
+    {
+        let lex = self.lex.clone().unwrap_or_else(lex::builtin_shared);
+        let mut parser = Parser::with_lexicon(reader, Arc::clone(&lex));
+        if let Some(gaz) = &self.gazetteer {
+            parser = parser.gazetteer(Arc::clone(gaz));
+        }
+        let mut unknown = Vec::new();
+        for token in parser.by_ref() {
+            let token = token?;
+            if token.chunk() != Chunk::Text {
+                continue;
+            }
+            match token.kind() {
+                Kind::Lexicon => self.spelling.tally_word(&lex, token.text()),
+                Kind::Unknown if is_checkable(token.text()) => unknown.push((
+                    token.text().to_string(),
+                    token.line(),
+                    token.column(),
+                )),
+                _ => (),
+            }
+        }
+        // names are only known after they appear within a sentence
+        unknown.retain(|(word, _, _)| !parser.is_name(word));
+        let misspellings = unknown
+            .into_iter()
+            .map(|(word, line, column)| {
+                let suggestions = self.suggest(&lex, &word);
+                Misspelling {
+                    word,
+                    line,
+                    column,
+                    suggestions,
+                }
+            })
+            .collect();
+        Ok(misspellings)
+    }
+
+    /// Get suggested corrections for a word
+    fn suggest(&mut self, lex: &Lexicon, word: &str) -> Vec<Suggestion> {
+        let key = make_word(word);
+        if let Some(suggestions) = self.suggestions.get(&key) {
+            return suggestions.clone();
+        }
+        let mut suggestions = lex.suggest(&key, self.max_distance);
+        suggestions.truncate(self.max_suggestions);
+        self.suggestions.insert(key, suggestions.clone());
+        suggestions
+    }
+
+    /// Merge another checker's regional spelling tally into this one
+    ///
+    /// This allows texts to be checked in parallel by clones of a checker.
+    pub fn merge(&mut self, other: Checker) {
+        self.spelling.merge(other.spelling);
+        self.suggestions.extend(other.suggestions);
+    }
+
+    /// Get the regional spelling tally of all checked texts
+    pub fn spelling(&self) -> &SpellingTally {
+        &self.spelling
+    }
+
+    /// Get inconsistent spellings (words spelled both ways)
+    ///
+    /// Returns a list of (British, American) word pairs.
+    pub fn inconsistencies(&self) -> Vec<(&str, &str)> {
+        self.spelling.inconsistencies()
+    }
+}
+
+/// Check if an unknown word should be checked
+///
+/// Single letters and words containing digits or symbols are skipped.
+fn is_checkable(word: &str) -> bool {
+    word.chars().count() > 1
+        && word
+            .chars()
+            .all(|c| c.is_alphabetic() || lex::is_apostrophe(c))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check() {
+        let mut checker = Checker::new();
+        let text =
+            "The colour of the\nhouse was beautyful, the color of grass.";
+        let misspellings = checker.check_text(text.as_bytes()).unwrap();
+        assert_eq!(misspellings.len(), 1);
+        let miss = &misspellings[0];
+        assert_eq!(
+            (miss.word(), miss.line(), miss.column()),
+            ("beautyful", 2, 11)
+        );
+        assert!(miss.suggestions().iter().any(|s| s.word() == "beautiful"));
+        assert_eq!(checker.inconsistencies(), [("colour", "color")]);
+    }
+
+    #[test]
+    fn names() {
+        let mut checker = Checker::new();
+        let text = "Zyxwell went home. Then Zyxwell left.";
+        assert!(checker.check_text(text.as_bytes()).unwrap().is_empty());
+        let text = "Zyxwell went home. Then he left.";
+        let misspellings = checker.check_text(text.as_bytes()).unwrap();
+        assert_eq!(misspellings[0].word(), "Zyxwell");
+    }
+
+    #[test]
+    fn checkable() {
+        assert!(is_checkable("teh") && is_checkable("isn’t"));
+        assert!(!is_checkable("x") && !is_checkable("b4"));
+        assert!(!is_checkable("a-b") && !is_checkable(""));
+        let mut checker = Checker::new();
+        let text = "x b4 https://example.com/teh name@exmple.com #teh 3rd";
+        assert!(checker.check_text(text.as_bytes()).unwrap().is_empty());
+        assert!(checker.check_text("".as_bytes()).unwrap().is_empty());
+        assert!(checker.check_text(&b"caf\xE9"[..]).is_err());
+    }
+
+    #[test]
+    fn options() {
+        let text = "beautyful and beautyful";
+        let mut checker = Checker::new().max_suggestions(1);
+        let misspellings = checker.check_text(text.as_bytes()).unwrap();
+        assert_eq!(misspellings.len(), 2);
+        assert_eq!(misspellings[1].column(), 15);
+        assert_eq!(
+            misspellings[0].suggestions(),
+            misspellings[1].suggestions()
+        );
+        assert_eq!(misspellings[0].suggestions().len(), 1);
+        let mut checker = Checker::new().max_distance(0);
+        let misspellings = checker.check_text(text.as_bytes()).unwrap();
+        assert!(misspellings[0].suggestions().is_empty());
+        let lex = Arc::new(Lexicon::from_csv("beautyful:A").unwrap());
+        let mut checker = Checker::with_lexicon(lex);
+        let misspellings = checker.check_text(text.as_bytes()).unwrap();
+        assert_eq!(misspellings.len(), 1);
+        assert_eq!(misspellings[0].word(), "and");
+        let mut gaz = Gazetteer::default();
+        gaz.load("Zyxwell".as_bytes()).unwrap();
+        let mut checker = Checker::new().gazetteer(Arc::new(gaz));
+        let text = "Zyxwell went home.";
+        assert!(checker.check_text(text.as_bytes()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn merge() {
+        let mut checker = Checker::new();
+        checker.check_text("The colour.".as_bytes()).unwrap();
+        let mut other = checker.clone();
+        other.check_text("The color.".as_bytes()).unwrap();
+        assert!(checker.inconsistencies().is_empty());
+        checker.merge(other);
+        assert_eq!(checker.inconsistencies(), [("colour", "color")]);
+    }
+}
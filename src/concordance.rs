@@ -0,0 +1,234 @@
+use crate::Result;
+use crate::lex::{self, Lexicon, make_word};
+use crate::parse::{Chunk, Parser};
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Default context window (words either side)
+const WINDOW: usize = 5;
+
+/// Keyword-in-context line
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KwicLine {
+    /// Line number (starting at 1)
+    line: usize,
+    /// Column number (characters, starting at 1)
+    column: usize,
+    /// Byte span of keyword in text
+    span: Range<usize>,
+    /// Context before keyword
+    left: String,
+    /// Keyword as found in text
+    keyword: String,
+    /// Context after keyword
+    right: String,
+}
+
+/// Concordance builder (keyword in context)
+#[derive(Clone)]
+pub struct Concordance {
+    /// Lexicon for parsing (built-in if not set)
+    lex: Option<Arc<Lexicon>>,
+    /// Context window (words either side)
+    window: usize,
+    /// Match any form of a lexeme
+    lexeme: bool,
+}
+
+impl KwicLine {
+    /// Get line number (starting at 1)
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get column number (characters, starting at 1)
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Get byte span of keyword in text
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Get context before keyword
+    pub fn left(&self) -> &str {
+        &self.left
+    }
+
+    /// Get keyword as found in text
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+
+    /// Get context after keyword
+    pub fn right(&self) -> &str {
+        &self.right
+    }
+}
+
+impl Default for Concordance {
+    fn default() -> Self {
+        Concordance {
+            lex: None,
+            window: WINDOW,
+            lexeme: false,
+        }
+    }
+}
+
+impl Concordance {
+    /// Create a new concordance builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new concordance builder with a shared lexicon
+    pub fn with_lexicon(lex: Arc<Lexicon>) -> Self {
+        Concordance {
+            lex: Some(lex),
+            ..Default::default()
+        }
+    }
+
+    /// Set context window (words either side)
+    pub fn window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Match any form of a lexeme (e.g. "ran" for "run")
+    pub fn lexeme(mut self, lexeme: bool) -> Self {
+        self.lexeme = lexeme;
+        self
+    }
+
+    /// Find every occurrence of a word in a text
+    pub fn find(&self, text: &str, word: &str) -> Result<Vec<KwicLine>> {
+        let lex = self.lex.clone().unwrap_or_else(lex::builtin_shared);
+        let mut keys = HashSet::from([make_word(word)]);
+        if self.lexeme {
+            for entry in lex.word_entries(word) {
+                keys.extend(entry.forms().iter().map(|f| make_word(f)));
+            }
+        }
+        let mut words = Vec::new();
+        for token in Parser::with_lexicon(text.as_bytes(), lex) {
+            let token = token?;
+            if token.chunk() == Chunk::Text
+                && token.text().chars().any(char::is_alphanumeric)
+            {
+                words.push(token);
+            }
+        }
+        let mut lines = Vec::new();
+        for (i, word) in words.iter().enumerate() {
+            if !keys.contains(&make_word(word.text())) {
+                continue;
+            }
+            let span = word.span();
+            let first = words[i.saturating_sub(self.window)].span();
+            let last = words[(i + self.window).min(words.len() - 1)].span();
+            lines.push(KwicLine {
+                line: word.line(),
+                column: word.column(),
+                left: collapse(&text[first.start..span.start]),
+                keyword: word.text().to_string(),
+                right: collapse(&text[span.end..last.end]),
+                span,
+            });
+        }
+        Ok(lines)
+    }
+}
+
+/// Collapse whitespace (including newlines) to single spaces
+fn collapse(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            space = true;
+        } else {
+            if space {
+                out.push(' ');
+                space = false;
+            }
+            out.push(c);
+        }
+    }
+    if space {
+        out.push(' ');
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn kwic() {
+        let text = "The dog ran home.\nA cat runs,\n  and the dog  runs too.";
+        let lines = Concordance::new().window(2).find(text, "runs").unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].line(), 2);
+        assert_eq!(lines[0].column(), 7);
+        assert_eq!(lines[0].left(), "A cat ");
+        assert_eq!(lines[0].right(), ", and the");
+        assert_eq!(lines[1].left(), "the dog ");
+        assert_eq!(lines[1].keyword(), "runs");
+        assert_eq!(lines[1].right(), " too");
+        assert_eq!(&text[lines[1].span()], "runs");
+        let lines = Concordance::new()
+            .window(1)
+            .lexeme(true)
+            .find(text, "run")
+            .unwrap();
+        let found: Vec<_> = lines.iter().map(|l| l.keyword()).collect();
+        assert_eq!(found, ["ran", "runs", "runs"]);
+        assert_eq!(lines[0].left(), "dog ");
+        let text = "Runs\t\trun, RUNS!\n42 runs.";
+        let lines = Concordance::new().window(0).find(text, "runs").unwrap();
+        let found: Vec<_> = lines
+            .iter()
+            .map(|l| (l.line(), l.column(), l.left(), l.keyword(), l.right()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (1, 1, "", "Runs", ""),
+                (1, 12, "", "RUNS", ""),
+                (2, 4, "", "runs", ""),
+            ]
+        );
+        let lines = Concordance::new().window(9).find(text, "42").unwrap();
+        assert_eq!(lines[0].left(), "Runs run, RUNS! ");
+        assert_eq!(lines[0].right(), " runs");
+        assert!(Concordance::new().find(text, "walk").unwrap().is_empty());
+        assert!(Concordance::new().find("", "runs").unwrap().is_empty());
+        assert!(Concordance::new().find(text, "!").unwrap().is_empty());
+        // unknown words match only themselves
+        let lines = Concordance::new()
+            .lexeme(true)
+            .find("blorf blorfs", "blorf")
+            .unwrap();
+        assert_eq!(lines.len(), 1);
+        let lex = Lexicon::from_csv("blorf:V").unwrap();
+        let lines = Concordance::with_lexicon(Arc::new(lex))
+            .lexeme(true)
+            .find("blorf blorfed blorfs", "blorfing")
+            .unwrap();
+        assert_eq!(lines.len(), 3);
+        for (text, collapsed) in [
+            ("", ""),
+            (" ", " "),
+            ("\n a \t b\n\n", " a b "),
+            ("a  ", "a "),
+        ] {
+            assert_eq!(collapse(text), collapsed, "{text:?}");
+        }
+    }
+}
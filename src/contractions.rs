@@ -1,4 +1,7 @@
 use crate::lex::is_apostrophe;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// Word contractions
 enum Contraction {
@@ -45,8 +48,9 @@ impl Contraction {
         match self {
             Contraction::Full(c, a, b) => {
                 if equals_contraction(c, word) {
-                    words.push(a.to_string());
+                    // pushed in reverse, since the last word is popped first
                     words.push(b.to_string());
+                    words.push(a.to_string());
                     return true;
                 }
             }
@@ -104,7 +108,7 @@ fn equals_contraction(part: &str, word: &str) -> bool {
     true
 }
 
-/// Split contractions
+/// Split contractions (in word order)
 pub fn split(word: &str) -> Vec<String> {
     let mut words = vec![word.to_string()];
     let mut ex = Vec::with_capacity(2);
@@ -125,3 +129,15 @@ fn split_contraction(words: &mut Vec<String>, word: &str) -> bool {
     }
     false
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn contractions() {
+        assert_eq!(split("can’t"), ["can", "not"]);
+        assert_eq!(split("they'd've"), ["they", "would", "have"]);
+        assert_eq!(split("runnin’"), ["running"]);
+    }
+}
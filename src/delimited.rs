@@ -0,0 +1,142 @@
+use crate::{Error, Result};
+use std::io::BufRead;
+
+/// Parse records from delimited text (CSV or TSV)
+///
+/// Fields may be quoted with `"`, containing delimiters, line breaks or
+/// doubled quotes.
+pub fn parse_records<R>(
+    mut reader: R,
+    delimiter: char,
+) -> Result<Vec<Vec<String>>>
+where
+    R: BufRead,
+{
+    let mut src = String::new();
+    reader.read_to_string(&mut src)?;
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = src.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            if c == '"' {
+                if chars.next_if_eq(&'"').is_some() {
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            } else {
+                field.push(c);
+            }
+        } else if c == '"' && field.is_empty() {
+            quoted = true;
+        } else if c == delimiter {
+            record.push(std::mem::take(&mut field));
+        } else if c == '\n' {
+            record.push(std::mem::take(&mut field));
+            records.push(std::mem::take(&mut record));
+        } else if c != '\r' {
+            field.push(c);
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Find index of a column by header name or number (starting from 1)
+fn column_index(header: &[String], column: &str) -> Result<usize> {
+    if let Some(i) = header.iter().position(|h| h.trim() == column) {
+        return Ok(i);
+    }
+    match column.parse::<usize>() {
+        Ok(n) if n > 0 => Ok(n - 1),
+        _ => Err(Error::Select(format!("unknown column `{column}`"))),
+    }
+}
+
+/// Extract text of selected columns from delimited text (CSV or TSV)
+///
+/// The first record is a header, and columns are selected by name or number
+/// (starting from 1).  If no columns are selected, all are included.  Each
+/// field is separated by a blank line.
+pub fn extract_columns<R>(
+    reader: R,
+    delimiter: char,
+    columns: &[String],
+) -> Result<String>
+where
+    R: BufRead,
+{
+    let records = parse_records(reader, delimiter)?;
+    let Some((header, records)) = records.split_first() else {
+        return Ok(String::new());
+    };
+    let indices = if columns.is_empty() {
+        (0..header.len()).collect()
+    } else {
+        columns
+            .iter()
+            .map(|c| column_index(header, c))
+            .collect::<Result<Vec<_>>>()?
+    };
+    let mut text = String::new();
+    for record in records {
+        for i in &indices {
+            if let Some(field) = record.get(*i)
+                && !field.trim().is_empty()
+            {
+                text.push_str(field.trim());
+                text.push_str("\n\n");
+            }
+        }
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn csv() {
+        let csv = "id,body,score\r\n1,\"Hello, \"\"world\"\"\",5\r\n\
+            2,\"Two\nlines\",3\n3,,1\n";
+        let records = parse_records(csv.as_bytes(), ',').unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[1], ["1", "Hello, \"world\"", "5"]);
+        let cols = ["body".to_string()];
+        let text = extract_columns(csv.as_bytes(), ',', &cols).unwrap();
+        assert_eq!(text, "Hello, \"world\"\n\nTwo\nlines\n\n");
+        let cols = ["3".to_string()];
+        let text = extract_columns(csv.as_bytes(), ',', &cols).unwrap();
+        assert_eq!(text, "5\n\n3\n\n1\n\n");
+        let cols = ["title".to_string()];
+        assert!(extract_columns(csv.as_bytes(), ',', &cols).is_err());
+        let records = parse_records("a\tb \"c\"\t\"d\te\"".as_bytes(), '\t');
+        assert_eq!(records.unwrap(), [["a", "b \"c\"", "d\te"]]);
+        // an unterminated quote runs to the end of input
+        let records = parse_records("x,\"y\nz".as_bytes(), ',').unwrap();
+        assert_eq!(records, [["x", "y\nz"]]);
+        let records = parse_records("a,\n,\n".as_bytes(), ',').unwrap();
+        assert_eq!(records, [["a", ""], ["", ""]]);
+        assert!(parse_records("".as_bytes(), ',').unwrap().is_empty());
+        assert!(parse_records(&[b'a', 0xFF][..], ',').is_err());
+        let cols = ["0".to_string()];
+        assert!(matches!(
+            extract_columns("a\n1\n".as_bytes(), ',', &cols),
+            Err(Error::Select(_))
+        ));
+        // missing fields and empty input are skipped
+        let cols = ["9".to_string()];
+        let text = extract_columns("a\n1\n".as_bytes(), ',', &cols);
+        assert_eq!(text.unwrap(), "");
+        assert_eq!(extract_columns("".as_bytes(), ',', &[]).unwrap(), "");
+        let text = extract_columns("a,b\n 1 ,\n".as_bytes(), ',', &[]);
+        assert_eq!(text.unwrap(), "1\n\n");
+    }
+}
@@ -0,0 +1,70 @@
+use crate::Result;
+use html_escape::decode_html_entities;
+use regex::Regex;
+use std::io::{Cursor, Read};
+use std::sync::LazyLock;
+use zip::ZipArchive;
+
+/// Text runs, tabs, breaks and paragraph ends
+static MARKUP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"<w:t(?:\s[^>]*)?>([^<]*)</w:t>|<w:(tab|br|cr)\s*/>|</w:p>")
+        .unwrap()
+});
+
+/// Main document part
+const DOCUMENT: &str = "word/document.xml";
+
+/// Extract paragraph text from WordprocessingML
+fn document_text(xml: &str) -> String {
+    let mut text = String::new();
+    for cap in MARKUP.captures_iter(xml) {
+        if let Some(t) = cap.get(1) {
+            text.push_str(&decode_html_entities(t.as_str()));
+        } else if cap.get(2).is_some_and(|m| m.as_str() == "tab") {
+            text.push('\t');
+        } else {
+            text.push('\n');
+        }
+    }
+    text
+}
+
+/// Extract paragraph text from a Word document (.docx)
+///
+/// Each paragraph is written on a separate line.
+pub fn extract_text<R>(mut reader: R) -> Result<String>
+where
+    R: Read,
+{
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf)?;
+    let mut archive = ZipArchive::new(Cursor::new(buf))?;
+    let mut xml = String::new();
+    archive.by_name(DOCUMENT)?.read_to_string(&mut xml)?;
+    Ok(document_text(&xml))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn extract() {
+        let xml = "<w:document><w:body>\
+            <w:p><w:r><w:t>Hello</w:t></w:r>\
+            <w:r><w:t xml:space=\"preserve\"> big &amp; </w:t></w:r>\
+            <w:r><w:delText>old</w:delText><w:t>world</w:t></w:r></w:p>\
+            <w:p><w:r><w:t>A</w:t><w:tab/><w:t>B</w:t></w:r></w:p>\
+            </w:body></w:document>";
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file(DOCUMENT, SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(xml.as_bytes()).unwrap();
+        let buf = zip.finish().unwrap().into_inner();
+        let text = extract_text(&buf[..]).unwrap();
+        assert_eq!(text, "Hello big & world\nA\tB\n");
+    }
+}
@@ -0,0 +1,215 @@
+use crate::html::Extractor;
+use crate::{Error, Result};
+use html_escape::decode_html_entities;
+use regex::Regex;
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Seek};
+use std::sync::LazyLock;
+use zip::ZipArchive;
+
+/// Container document (locates the package document)
+const CONTAINER: &str = "META-INF/container.xml";
+
+/// Root file path in container document
+static ROOTFILE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"<(?:\w+:)?rootfile\s[^>]*full-path\s*=\s*["']([^"']+)"#)
+        .unwrap()
+});
+
+/// Manifest items in package document
+static ITEM: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<(?:\w+:)?item\s[^>]*>").unwrap());
+
+/// Spine item references in package document
+static ITEMREF: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"<(?:\w+:)?itemref\s[^>]*idref\s*=\s*["']([^"']+)"#).unwrap()
+});
+
+/// Attribute value (name, value)
+static ATTR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"([\w-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap()
+});
+
+/// Make an EPUB decoding error
+fn invalid(msg: &str) -> Error {
+    Error::Decode(format!("Invalid EPUB: {msg}"))
+}
+
+/// Read a file from an archive as a string
+fn read_file<R>(archive: &mut ZipArchive<R>, name: &str) -> Result<String>
+where
+    R: Read + Seek,
+{
+    let mut text = String::new();
+    archive.by_name(name)?.read_to_string(&mut text)?;
+    Ok(text)
+}
+
+/// Get the attributes of a tag
+fn attributes(tag: &str) -> HashMap<&str, String> {
+    ATTR.captures_iter(tag)
+        .filter_map(|cap| {
+            let val = cap.get(2).or(cap.get(3))?;
+            Some((cap.get(1)?.as_str(), decode_html_entities(val.as_str())))
+        })
+        .map(|(name, val)| (name, val.into_owned()))
+        .collect()
+}
+
+/// Resolve a relative href against the package document directory
+fn resolve(dir: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or(href).replace("%20", " ");
+    let mut parts: Vec<&str> =
+        dir.split('/').filter(|p| !p.is_empty()).collect();
+    for part in href.split('/') {
+        match part {
+            "" | "." => (),
+            ".." => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+/// Get the paths of spine documents, in reading order
+fn spine_paths(opf: &str, dir: &str) -> Vec<String> {
+    let mut items = HashMap::new();
+    for item in ITEM.find_iter(opf) {
+        let attrs = attributes(item.as_str());
+        if let (Some(id), Some(href)) = (attrs.get("id"), attrs.get("href")) {
+            items.insert(id.clone(), resolve(dir, href));
+        }
+    }
+    ITEMREF
+        .captures_iter(opf)
+        .filter_map(|cap| items.get(&cap[1]).cloned())
+        .collect()
+}
+
+/// Extract chapter text from an EPUB book
+///
+/// Documents are read in spine (reading) order, with the text of each
+/// extracted as HTML.
+pub fn extract_text<R>(mut reader: R) -> Result<String>
+where
+    R: Read,
+{
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf)?;
+    let mut archive = ZipArchive::new(Cursor::new(buf))?;
+    let container = read_file(&mut archive, CONTAINER)?;
+    let opf_path = ROOTFILE
+        .captures(&container)
+        .map(|cap| cap[1].to_string())
+        .ok_or_else(|| invalid("no package document"))?;
+    let opf = read_file(&mut archive, &opf_path)?;
+    let dir = opf_path.rsplit_once('/').map_or("", |(dir, _)| dir);
+    let extractor = Extractor::new();
+    let mut text = String::new();
+    for path in spine_paths(&opf, dir) {
+        let chapter = read_file(&mut archive, &path)?;
+        text.push_str(&extractor.extract_text(chapter.as_bytes())?);
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    /// Make an EPUB archive from (name, data) files
+    fn make_epub(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data) in files {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(data.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    /// Container document for a package document path
+    fn container(path: &str) -> String {
+        format!("<container><rootfile full-path='{path}'/></container>")
+    }
+
+    #[test]
+    fn extract() {
+        let files = [
+            ("mimetype", "application/epub+zip"),
+            (
+                CONTAINER,
+                "<container><rootfiles>\
+                <rootfile full-path=\"OEBPS/content.opf\"/>\
+                </rootfiles></container>",
+            ),
+            (
+                "OEBPS/content.opf",
+                "<package><manifest>\
+                <item id=\"c2\" href=\"text/two.xhtml\"/>\
+                <item href='text/one.xhtml' id='c1'/>\
+                <item id=\"css\" href=\"style.css\"/>\
+                </manifest><spine>\
+                <itemref idref=\"c1\"/><itemref idref=\"c2\"/>\
+                </spine></package>",
+            ),
+            (
+                "OEBPS/text/one.xhtml",
+                "<html><body><h1>One</h1><p>It was a dark night.</p>\
+                </body></html>",
+            ),
+            (
+                "OEBPS/text/two.xhtml",
+                "<html><body><p>The end.</p></body></html>",
+            ),
+        ];
+        let buf = make_epub(&files);
+        let text = extract_text(&buf[..]).unwrap();
+        assert_eq!(text, "One\nIt was a dark night.\n\nThe end.\n\n");
+        assert_eq!(
+            resolve("OEBPS/text", "../img/a%20b.png#x"),
+            "OEBPS/img/a b.png"
+        );
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(resolve("", "ch1.xhtml"), "ch1.xhtml");
+        assert_eq!(resolve("a/b", "./c/../d.xhtml"), "a/b/d.xhtml");
+        assert_eq!(resolve("a", "../../../x.xhtml"), "x.xhtml");
+        let opf = "<opf:item id=\"a\" href=\"a&amp;b.xhtml\"/>\
+            <item href=\"nope.xhtml\"/>\
+            <itemref idref=\"missing\"/><opf:itemref idref='a'/>";
+        assert_eq!(spine_paths(opf, "OPS"), ["OPS/a&b.xhtml"]);
+    }
+
+    #[test]
+    fn bad_books() {
+        let decode =
+            |buf: &[u8]| matches!(extract_text(buf), Err(Error::Decode(_)));
+        assert!(decode(b"not a zip file"));
+        assert!(decode(&make_epub(&[("mimetype", "application/epub+zip")])));
+        let no_root = make_epub(&[(CONTAINER, "<container/>")]);
+        let err = extract_text(&no_root[..]).unwrap_err();
+        assert!(err.to_string().contains("no package document"));
+        let no_opf = make_epub(&[(CONTAINER, &container("content.opf"))]);
+        assert!(decode(&no_opf));
+        // a missing chapter is an error, but an empty spine is not
+        let opf = "<item id='c' href='c.xhtml'/><itemref idref='c'/>";
+        let no_chapter = make_epub(&[
+            (CONTAINER, &container("content.opf")),
+            ("content.opf", opf),
+        ]);
+        assert!(decode(&no_chapter));
+        let empty = make_epub(&[
+            (CONTAINER, &container("content.opf")),
+            ("content.opf", "<package/>"),
+        ]);
+        assert_eq!(extract_text(&empty[..]).unwrap(), "");
+    }
+}
@@ -0,0 +1,74 @@
+use alloc::string::String;
+#[cfg(feature = "std")]
+use std::io;
+
+/// Booky error
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// I/O error
+    #[cfg(feature = "std")]
+    #[error(transparent)]
+    Io(io::Error),
+    /// Input decoding error (invalid UTF-8, malformed document, etc.)
+    #[error("Decode error: {0}")]
+    Decode(String),
+    /// Invalid input selection (column, field path, etc.)
+    #[error("Invalid selection: {0}")]
+    Select(String),
+    /// Invalid word class
+    #[error("Invalid word class: `{0}`")]
+    WordClass(String),
+    /// Invalid word attribute
+    #[error("Invalid word attribute: `{0}`")]
+    WordAttr(char),
+    /// Invalid part-of-speech tag
+    #[error("Invalid tag: `{0}`")]
+    Tag(String),
+    /// Invalid meter name
+    #[error("Invalid meter: `{0}`")]
+    Meter(String),
+    /// Invalid lexeme
+    #[error("Invalid lexeme: `{0}`")]
+    Lexeme(String),
+    /// Invalid lexicon line
+    #[error("Bad word on line {line}: `{text}`")]
+    Lexicon {
+        /// Line number (starting from 1)
+        line: usize,
+        /// Line text
+        text: String,
+    },
+}
+
+/// Booky result
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[cfg(feature = "std")]
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        match err.io_error_kind() {
+            Some(_) => Error::Io(err.into()),
+            None => Error::Decode(err.to_string()),
+        }
+    }
+}
+
+#[cfg(any(feature = "archive", feature = "docx", feature = "epub"))]
+impl From<zip::result::ZipError> for Error {
+    fn from(err: zip::result::ZipError) -> Self {
+        match err {
+            zip::result::ZipError::Io(e) => e.into(),
+            _ => Error::Decode(err.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::InvalidData => Error::Decode(err.to_string()),
+            _ => Error::Io(err),
+        }
+    }
+}
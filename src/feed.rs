@@ -0,0 +1,175 @@
+use crate::Result;
+use crate::html;
+use html_escape::decode_html_entities;
+use regex::Regex;
+use std::io::BufRead;
+use std::sync::LazyLock;
+
+/// RSS item or Atom entry
+static ENTRY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?s)<item(?:\s[^>]*)?>(.*?)</item>|<entry(?:\s[^>]*)?>(.*?)</entry>",
+    )
+    .unwrap()
+});
+
+/// Start tag of a text field
+static FIELD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"<(title|description|summary|content:encoded|content)(\s[^>]*)?>",
+    )
+    .unwrap()
+});
+
+/// Feed entry (RSS item or Atom entry)
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entry {
+    /// Title
+    title: String,
+    /// Summary (or RSS description)
+    summary: String,
+    /// Full content
+    content: String,
+}
+
+impl Entry {
+    /// Get title
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get summary
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    /// Get full content
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+/// Decode a field value to plain text
+fn field_text(attrs: &str, value: &str) -> Result<String> {
+    let value = value.trim();
+    let markup = match value
+        .strip_prefix("<![CDATA[")
+        .and_then(|v| v.strip_suffix("]]>"))
+    {
+        Some(cdata) => cdata.to_string(),
+        None => decode_html_entities(value).into_owned(),
+    };
+    // Atom text constructs are plain unless `type` is html / xhtml
+    if attrs.contains("type=\"text\"") || attrs.contains("type='text'") {
+        return Ok(markup);
+    }
+    let text = html::extract_text(markup.as_bytes())?;
+    Ok(text.trim().to_string())
+}
+
+/// Parse entry fields from an item / entry element
+fn parse_entry(src: &str) -> Result<Entry> {
+    let mut entry = Entry::default();
+    let mut rest = src;
+    while let Some(cap) = FIELD.captures(rest) {
+        let name = cap.get(1).map_or("", |m| m.as_str());
+        let attrs = cap.get(2).map_or("", |m| m.as_str());
+        rest = &rest[cap.get(0).map_or(0, |m| m.end())..];
+        if attrs.ends_with('/') {
+            continue;
+        }
+        let end = format!("</{name}>");
+        let Some(i) = rest.find(&end) else {
+            break;
+        };
+        let text = field_text(attrs, &rest[..i])?;
+        rest = &rest[i + end.len()..];
+        let field = match name {
+            "title" => &mut entry.title,
+            "description" | "summary" => &mut entry.summary,
+            _ => &mut entry.content,
+        };
+        if field.is_empty() {
+            *field = text;
+        }
+    }
+    Ok(entry)
+}
+
+/// Parse entries from an RSS or Atom feed
+pub fn parse_entries<R>(mut reader: R) -> Result<Vec<Entry>>
+where
+    R: BufRead,
+{
+    let mut src = String::new();
+    reader.read_to_string(&mut src)?;
+    let mut entries = Vec::new();
+    for cap in ENTRY.captures_iter(&src) {
+        if let Some(m) = cap.get(1).or_else(|| cap.get(2)) {
+            entries.push(parse_entry(m.as_str())?);
+        }
+    }
+    Ok(entries)
+}
+
+/// Extract text from an RSS or Atom feed
+///
+/// Each entry's title is followed by its content (or summary, if there is no
+/// content), separated by blank lines.
+pub fn extract_text<R>(reader: R) -> Result<String>
+where
+    R: BufRead,
+{
+    let mut text = String::new();
+    for entry in parse_entries(reader)? {
+        let body = if entry.content.is_empty() {
+            &entry.summary
+        } else {
+            &entry.content
+        };
+        for field in [&entry.title, body] {
+            if !field.is_empty() {
+                text.push_str(field);
+                text.push_str("\n\n");
+            }
+        }
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rss() {
+        let rss = "<?xml version=\"1.0\"?><rss><channel><title>Blog</title>\
+            <item><title>First &amp; best</title>\
+            <description>&lt;p&gt;Short &lt;b&gt;one&lt;/b&gt;&lt;/p&gt;\
+            </description></item>\
+            <item><title>Second</title><description>Skip</description>\
+            <content:encoded><![CDATA[<p>Full <i>text</i></p>]]>\
+            </content:encoded></item></channel></rss>";
+        let entries = parse_entries(rss.as_bytes()).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].title(), "First & best");
+        assert_eq!(entries[0].summary(), "Short one");
+        assert_eq!(entries[1].content(), "Full text");
+        let text = extract_text(rss.as_bytes()).unwrap();
+        assert_eq!(
+            text,
+            "First & best\n\nShort one\n\nSecond\n\nFull text\n\n"
+        );
+    }
+
+    #[test]
+    fn atom() {
+        let atom = "<feed xmlns=\"http://www.w3.org/2005/Atom\">\
+            <title>Feed</title><entry><title type=\"text\">A &lt; B</title>\
+            <link href=\"https://x.io\"/><summary type=\"html\">\
+            &lt;em&gt;Hi&lt;/em&gt; there</summary></entry></feed>";
+        let text = extract_text(atom.as_bytes()).unwrap();
+        assert_eq!(text, "A < B\n\nHi there\n\n");
+    }
+}
@@ -0,0 +1,113 @@
+use crate::lex;
+use crate::parse::{Chunk, Parser};
+use std::ffi::{CStr, CString, c_char};
+use std::ptr;
+
+/// Convert a C string argument to `&str`
+///
+/// # Safety
+///
+/// `s` must be NULL or a valid NUL-terminated string.
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+    unsafe { CStr::from_ptr(s) }.to_str().ok()
+}
+
+/// Convert a `String` into a C string (to be freed with `booky_free`)
+fn into_raw(s: String) -> *mut c_char {
+    CString::new(s).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Split text into words
+///
+/// Each word is on a separate line, prefixed by its kind code and a tab
+/// (e.g. `l\tcat`).  The result must be freed with `booky_free`.  NULL is
+/// returned if `text` is NULL or not valid UTF-8.
+///
+/// # Safety
+///
+/// `text` must be NULL or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn booky_tokenize(text: *const c_char) -> *mut c_char {
+    let Some(text) = (unsafe { to_str(text) }) else {
+        return ptr::null_mut();
+    };
+    let mut lines = String::new();
+    for token in Parser::new(text.as_bytes()).flatten() {
+        if token.chunk() == Chunk::Text {
+            lines.push(token.kind().code());
+            lines.push('\t');
+            lines.push_str(token.text());
+            lines.push('\n');
+        }
+    }
+    into_raw(lines)
+}
+
+/// Look up a word form in the built-in lexicon
+///
+/// Each matching entry is on a separate line, in lexicon CSV format (e.g.
+/// `mouse:N,mice`).  The result must be freed with `booky_free`.  NULL is
+/// returned if the word is not found, or `word` is NULL or not valid UTF-8.
+///
+/// # Safety
+///
+/// `word` must be NULL or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn booky_lookup(word: *const c_char) -> *mut c_char {
+    let Some(word) = (unsafe { to_str(word) }) else {
+        return ptr::null_mut();
+    };
+    let entries = lex::builtin().word_entries(word);
+    if entries.is_empty() {
+        return ptr::null_mut();
+    }
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&format!("{entry:?}\n"));
+    }
+    into_raw(lines)
+}
+
+/// Free a string returned by `booky_tokenize` or `booky_lookup`
+///
+/// # Safety
+///
+/// `s` must be NULL or a string returned by a `booky` function, which has
+/// not already been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn booky_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Take an owned C string
+    fn take(s: *mut c_char) -> Option<String> {
+        if s.is_null() {
+            return None;
+        }
+        let val = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_string();
+        unsafe { booky_free(s) };
+        Some(val)
+    }
+
+    #[test]
+    fn ffi() {
+        let text = CString::new("So Sam ran, blorf!").unwrap();
+        let words = take(unsafe { booky_tokenize(text.as_ptr()) });
+        assert_eq!(words.unwrap(), "l\tSo\np\tSam\nl\tran\nu\tblorf\n");
+        let word = CString::new("Mice").unwrap();
+        let entries = take(unsafe { booky_lookup(word.as_ptr()) });
+        assert_eq!(entries.unwrap(), "mouse:N,mice\n");
+        let word = CString::new("blorf").unwrap();
+        assert_eq!(take(unsafe { booky_lookup(word.as_ptr()) }), None);
+        assert_eq!(take(unsafe { booky_tokenize(ptr::null()) }), None);
+    }
+}
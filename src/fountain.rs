@@ -0,0 +1,252 @@
+use crate::Result;
+use regex::Regex;
+use std::io::BufRead;
+use std::sync::LazyLock;
+
+/// Boneyard (comments) and notes
+static BONEYARD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)/\*.*?\*/|\[\[.*?\]\]").unwrap());
+
+/// Title page key line
+static TITLE_KEY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z][A-Za-z ]*:").unwrap());
+
+/// Scene heading prefixes (uppercase)
+const SCENE_PREFIXES: &[&str] =
+    &["INT./EXT", "INT/EXT", "I/E", "INT", "EXT", "EST"];
+
+/// Screenplay element
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Element {
+    /// Scene heading (e.g. `INT. HOUSE - DAY`)
+    SceneHeading,
+    /// Action (description)
+    Action,
+    /// Character name cue
+    Character,
+    /// Parenthetical direction within dialogue
+    Parenthetical,
+    /// Dialogue
+    Dialogue,
+    /// Lyrics
+    Lyric,
+    /// Transition (e.g. `CUT TO:`)
+    Transition,
+    /// Section or synopsis
+    Outline,
+}
+
+/// Check if a line is a scene heading
+fn is_scene_heading(line: &str) -> bool {
+    let upper = line.to_uppercase();
+    SCENE_PREFIXES.iter().any(|p| {
+        upper
+            .strip_prefix(p)
+            .is_some_and(|rest| rest.starts_with(['.', ' ']))
+    })
+}
+
+/// Check if a line has no lowercase letters (and some uppercase)
+fn is_upper(line: &str) -> bool {
+    line.chars().any(char::is_uppercase)
+        && !line.chars().any(char::is_lowercase)
+}
+
+/// Check if a line is a character cue (ignoring extensions)
+fn is_character(line: &str) -> bool {
+    let name = line.trim_end_matches('^');
+    let name = name.split('(').next().unwrap_or(name);
+    is_upper(name)
+}
+
+/// Remove emphasis markers from text
+fn plain(text: &str) -> String {
+    text.replace(['*', '_'], "").trim().to_string()
+}
+
+/// Parse screenplay elements from Fountain source
+pub fn parse_elements<R>(mut reader: R) -> Result<Vec<(Element, String)>>
+where
+    R: BufRead,
+{
+    let mut src = String::new();
+    reader.read_to_string(&mut src)?;
+    let src = BONEYARD.replace_all(&src, "");
+    let lines: Vec<&str> = src.lines().map(str::trim_end).collect();
+    let mut i = 0;
+    // skip title page
+    if lines.first().is_some_and(|l| TITLE_KEY.is_match(l)) {
+        while i < lines.len() && !lines[i].trim().is_empty() {
+            i += 1;
+        }
+    }
+    let mut elements = Vec::new();
+    let mut dialogue = false;
+    for j in i..lines.len() {
+        let line = lines[j].trim();
+        if line.is_empty() {
+            dialogue = false;
+            continue;
+        }
+        let prev_blank = j == 0 || lines[j - 1].trim().is_empty();
+        let next_blank = lines.get(j + 1).is_none_or(|l| l.trim().is_empty());
+        let (elem, text) = if dialogue {
+            if line.starts_with('(') && line.ends_with(')') {
+                (Element::Parenthetical, line)
+            } else if let Some(lyric) = line.strip_prefix('~') {
+                (Element::Lyric, lyric)
+            } else {
+                (Element::Dialogue, line)
+            }
+        } else if line.starts_with('#') || line.starts_with('=') {
+            (Element::Outline, line.trim_start_matches(['#', '=']))
+        } else if let Some(action) = line.strip_prefix('!') {
+            (Element::Action, action)
+        } else if let Some(h) = line.strip_prefix('.')
+            && !h.starts_with('.')
+        {
+            (Element::SceneHeading, h)
+        } else if prev_blank && is_scene_heading(line) {
+            (Element::SceneHeading, line)
+        } else if let Some(t) = line.strip_prefix('>') {
+            match t.strip_suffix('<') {
+                Some(centered) => (Element::Action, centered),
+                None => (Element::Transition, t),
+            }
+        } else if prev_blank
+            && next_blank
+            && is_upper(line)
+            && line.ends_with("TO:")
+        {
+            (Element::Transition, line)
+        } else if let Some(name) = line.strip_prefix('@') {
+            dialogue = true;
+            (Element::Character, name)
+        } else if prev_blank && !next_blank && is_character(line) {
+            dialogue = true;
+            (Element::Character, line)
+        } else if let Some(lyric) = line.strip_prefix('~') {
+            (Element::Lyric, lyric)
+        } else {
+            (Element::Action, line)
+        };
+        elements.push((elem, plain(text)));
+    }
+    Ok(elements)
+}
+
+/// Extract text of selected elements from Fountain source
+///
+/// Each element is written on a separate line.
+pub fn extract_text<R>(reader: R, select: &[Element]) -> Result<String>
+where
+    R: BufRead,
+{
+    let mut text = String::new();
+    for (elem, t) in parse_elements(reader)? {
+        if select.contains(&elem) {
+            text.push_str(&t);
+            text.push('\n');
+        }
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let src = "Title: Test\nAuthor: Me\n\n\
+            INT. KITCHEN - DAY\n\n\
+            Bob enters. /* cut this */\n\n\
+            BOB (V.O.)\n(quietly)\nWhere is *everyone*?\n\n\
+            CUT TO:\n\n\
+            .FLASHBACK\n";
+        let elems = parse_elements(src.as_bytes()).unwrap();
+        assert_eq!(
+            elems,
+            vec![
+                (Element::SceneHeading, "INT. KITCHEN - DAY".to_string()),
+                (Element::Action, "Bob enters.".to_string()),
+                (Element::Character, "BOB (V.O.)".to_string()),
+                (Element::Parenthetical, "(quietly)".to_string()),
+                (Element::Dialogue, "Where is everyone?".to_string()),
+                (Element::Transition, "CUT TO:".to_string()),
+                (Element::SceneHeading, "FLASHBACK".to_string()),
+            ]
+        );
+        let text = extract_text(src.as_bytes(), &[Element::Dialogue]).unwrap();
+        assert_eq!(text, "Where is everyone?\n");
+        // forced action is never a character cue
+        let elems = parse_elements("!BOOM\nIt shakes.".as_bytes()).unwrap();
+        assert_eq!(elems[0], (Element::Action, "BOOM".to_string()));
+        assert!(parse_elements("".as_bytes()).unwrap().is_empty());
+        // a title page only
+        let src = "Title: Only\nCredit: Me";
+        assert!(parse_elements(src.as_bytes()).unwrap().is_empty());
+        // without a key on the first line, there is no title page
+        let src = "Note, to self: call\nhome";
+        let text = extract_text(src.as_bytes(), &[Element::Action]).unwrap();
+        assert_eq!(text, "Note, to self: call\nhome\n");
+        assert!(extract_text("x".as_bytes(), &[]).unwrap().is_empty());
+        assert!(parse_elements(&[b'x', 0xFF][..]).is_err());
+        for (line, heading) in [
+            ("int. house", true),
+            ("EXT HOUSE", true),
+            ("I/E. CAR", true),
+            ("EST. CITY", true),
+            ("INTERIOR", false),
+            ("ESTATE SALE", false),
+        ] {
+            assert_eq!(is_scene_heading(line), heading, "{line}");
+        }
+        for (line, character) in [
+            ("BOB", true),
+            ("BOB (cont'd)", true),
+            ("BOB ^", true),
+            ("R2-D2", true),
+            ("Bob", false),
+            ("(V.O.)", false),
+            ("42", false),
+        ] {
+            assert_eq!(is_character(line), character, "{line}");
+        }
+    }
+
+    #[test]
+    fn forced() {
+        let src = "# Act One\n= Synopsis\n\n\
+            @McCoy\n~La la la\nHi. [[a note]]\n\n\
+            !EXT. STREET\nINT. HOUSE\n\n\
+            > THE END <\n> FADE OUT\n\n\
+            ...and then\n~Sing /* a\nlong\ncut */along\n\n\
+            BANG!\n\nSMASH TO:\n\nALICE\n";
+        let elems: Vec<_> = parse_elements(src.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(|(e, t)| (e, t.to_string()))
+            .collect();
+        let expected = [
+            (Element::Outline, "Act One"),
+            (Element::Outline, "Synopsis"),
+            (Element::Character, "McCoy"),
+            (Element::Lyric, "La la la"),
+            (Element::Dialogue, "Hi."),
+            (Element::Action, "EXT. STREET"),
+            (Element::Action, "INT. HOUSE"),
+            (Element::Action, "THE END"),
+            (Element::Transition, "FADE OUT"),
+            (Element::Action, "...and then"),
+            (Element::Lyric, "Sing along"),
+            (Element::Action, "BANG!"),
+            (Element::Transition, "SMASH TO:"),
+            (Element::Action, "ALICE"),
+        ];
+        let expected: Vec<_> =
+            expected.iter().map(|(e, t)| (*e, t.to_string())).collect();
+        assert_eq!(elems, expected);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::lex::make_word;
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+use std::path::Path;
+
+/// Months of the year
+const MONTHS: &[&str] = &[
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Days of the week
+const WEEKDAYS: &[&str] = &[
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Named entity class
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Entity {
+    /// Person name
+    Person,
+    /// Place name
+    Place,
+    /// Organization name
+    Organization,
+    /// Month of the year
+    Month,
+    /// Day of the week
+    Weekday,
+    /// Other name
+    Other,
+}
+
+/// Gazetteer of named entities (proper nouns)
+///
+/// Names are single words, matched only when capitalized: "May" is a month,
+/// but "may" is not.  A `Parser` with a gazetteer gives names the kind
+/// `Kind::Proper`, even if they are also lexicon words.
+#[derive(Clone, Debug, Default)]
+pub struct Gazetteer {
+    /// Entity classes, keyed by lowercase name
+    names: HashMap<String, Entity>,
+}
+
+impl fmt::Display for Entity {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let ent = match self {
+            Entity::Person => "person",
+            Entity::Place => "place",
+            Entity::Organization => "organization",
+            Entity::Month => "month",
+            Entity::Weekday => "weekday",
+            Entity::Other => "other",
+        };
+        write!(fmt, "{ent}")
+    }
+}
+
+impl Entity {
+    /// Parse an entity class name (case insensitive)
+    fn parse(ent: &str) -> Option<Self> {
+        match ent.trim().to_lowercase().as_str() {
+            "person" => Some(Entity::Person),
+            "place" => Some(Entity::Place),
+            "organization" => Some(Entity::Organization),
+            "month" => Some(Entity::Month),
+            "weekday" => Some(Entity::Weekday),
+            "other" => Some(Entity::Other),
+            _ => None,
+        }
+    }
+}
+
+impl Gazetteer {
+    /// Create an empty gazetteer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a gazetteer of months and weekdays
+    pub fn calendar() -> Self {
+        let mut gaz = Gazetteer::new();
+        for month in MONTHS {
+            gaz.insert(month, Entity::Month);
+        }
+        for day in WEEKDAYS {
+            gaz.insert(day, Entity::Weekday);
+        }
+        gaz
+    }
+
+    /// Load names from a reader
+    ///
+    /// Each line has a name and entity class, separated by a comma (e.g.
+    /// `Paris,place`).  The class defaults to `other` if omitted.  Blank
+    /// lines and lines starting with `#` are skipped.
+    pub fn from_reader<R>(reader: R) -> Result<Self>
+    where
+        R: BufRead,
+    {
+        let mut gaz = Gazetteer::new();
+        gaz.load(reader)?;
+        Ok(gaz)
+    }
+
+    /// Load names from a file (see `from_reader`)
+    pub fn from_path<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let file = std::fs::File::open(path)?;
+        Gazetteer::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Load more names from a reader (see `from_reader`)
+    pub fn load<R>(&mut self, reader: R) -> Result<()>
+    where
+        R: BufRead,
+    {
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let text = line.trim();
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let bad = || Error::Lexicon {
+                line: i + 1,
+                text: line.clone(),
+            };
+            let (name, ent) = match text.split_once(',') {
+                Some((name, ent)) => {
+                    (name.trim(), Entity::parse(ent).ok_or_else(bad)?)
+                }
+                None => (text, Entity::Other),
+            };
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(bad());
+            }
+            self.insert(name, ent);
+        }
+        Ok(())
+    }
+
+    /// Insert a name
+    pub fn insert(&mut self, name: &str, ent: Entity) {
+        self.names.insert(make_word(name), ent);
+    }
+
+    /// Get the entity class of a word, if it is a capitalized name
+    pub fn get(&self, word: &str) -> Option<Entity> {
+        if word.chars().next().is_some_and(char::is_uppercase) {
+            self.names.get(&make_word(word)).copied()
+        } else {
+            None
+        }
+    }
+
+    /// Check if a word is a capitalized name
+    pub fn contains(&self, word: &str) -> bool {
+        self.get(word).is_some()
+    }
+
+    /// Get the number of names
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Check if the gazetteer is empty
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn gazetteer() {
+        let mut gaz = Gazetteer::calendar();
+        assert_eq!(gaz.get("May"), Some(Entity::Month));
+        assert_eq!(gaz.get("may"), None);
+        assert_eq!(gaz.get("SUNDAY"), Some(Entity::Weekday));
+        gaz.load("# names\nParis, place\n\nBill,Person\nAcme".as_bytes())
+            .unwrap();
+        assert_eq!(gaz.len(), 22);
+        assert_eq!(gaz.get("Paris"), Some(Entity::Place));
+        assert_eq!(gaz.get("Bill"), Some(Entity::Person));
+        assert_eq!(gaz.get("Acme"), Some(Entity::Other));
+        assert!(!gaz.contains("bill"));
+        assert!(matches!(
+            Gazetteer::from_reader("Rome,place\nNew York,place".as_bytes()),
+            Err(Error::Lexicon { line: 2, .. })
+        ));
+        assert!(Gazetteer::from_reader("Rome,city".as_bytes()).is_err());
+        let gaz = Gazetteer::new();
+        assert!(gaz.is_empty());
+        assert_eq!(gaz.get(""), None);
+        let gaz = Gazetteer::from_reader(
+            "  # indented comment\n\t\n Zoë , OTHER \nzoë,person\nÉmile,\
+            person\nO’Brien,person\n"
+                .as_bytes(),
+        )
+        .unwrap();
+        // names are case insensitive, and later entries replace earlier
+        assert_eq!(gaz.len(), 3);
+        assert_eq!(gaz.get("Zoë"), Some(Entity::Person));
+        assert_eq!(gaz.get("ZOË"), Some(Entity::Person));
+        assert_eq!(gaz.get("Émile"), Some(Entity::Person));
+        assert_eq!(gaz.get("émile"), None);
+        assert_eq!(gaz.get("O'Brien"), Some(Entity::Person));
+        assert_eq!(gaz.get("1st"), None);
+        for (text, line) in [
+            (",place", 1),
+            ("Rome,", 1),
+            ("\nRome,place,extra", 2),
+            ("Rome,place\n\nSão\tPaulo", 3),
+        ] {
+            assert!(
+                matches!(
+                    Gazetteer::from_reader(text.as_bytes()),
+                    Err(Error::Lexicon { line: l, .. }) if l == line
+                ),
+                "{text:?}"
+            );
+        }
+        assert!(Gazetteer::from_reader(&[b'A', 0xFF][..]).is_err());
+        assert!(matches!(
+            Gazetteer::from_path("/nonexistent/names.csv"),
+            Err(Error::Io(_))
+        ));
+        for ent in [
+            Entity::Person,
+            Entity::Place,
+            Entity::Organization,
+            Entity::Month,
+            Entity::Weekday,
+            Entity::Other,
+        ] {
+            assert_eq!(
+                Entity::parse(&ent.to_string().to_uppercase()),
+                Some(ent)
+            );
+        }
+    }
+}
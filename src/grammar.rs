@@ -0,0 +1,508 @@
+use crate::Result;
+use crate::lex::Lexicon;
+use crate::parse::{SpanWord, span_sentences};
+use crate::penn::Penn;
+use crate::tagger::Tagger;
+use crate::word::{FormRule, WordAttr, WordClass};
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Determiners used with singular nouns
+const SINGULAR_DETERMINERS: &[&str] =
+    &["a", "an", "another", "each", "every", "this"];
+
+/// Determiners used with plural nouns
+const PLURAL_DETERMINERS: &[&str] =
+    &["both", "few", "many", "several", "these", "those"];
+
+/// Pronouns used with third person singular verbs
+const SINGULAR_PRONOUNS: &[&str] = &["he", "she", "it"];
+
+/// Pronouns used with base form verbs
+const PLURAL_PRONOUNS: &[&str] = &["i", "you", "we", "they"];
+
+/// Words which are correctly doubled (e.g. "had had")
+const DOUBLES: &[&str] = &["had", "that"];
+
+/// Words starting with a vowel letter and a consonant sound
+const CONSONANT_SOUNDS: &[&str] = &[
+    "eu", "ewe", "one", "once", "ubiq", "uni", "ure", "use", "usu", "uti",
+];
+
+/// Words starting with a silent `h`
+const SILENT_H: &[&str] = &["heir", "honest", "honor", "honour", "hour"];
+
+/// Letters with names starting with a vowel sound
+const VOWEL_LETTERS: &str = "AEFHILMNORSX";
+
+/// Kind of grammar problem
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Problem {
+    /// Subject-verb agreement (e.g. "the dogs barks")
+    Agreement,
+    /// Indefinite article (e.g. "a apple")
+    Article,
+    /// Determiner-noun number (e.g. "these book")
+    Number,
+    /// Doubled word (e.g. "the the")
+    Doubled,
+}
+
+/// Grammar finding
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Finding {
+    /// Byte span in text
+    span: Range<usize>,
+    /// Kind of problem
+    problem: Problem,
+    /// Description of problem
+    message: String,
+}
+
+/// Grammar checker
+#[derive(Clone, Default)]
+pub struct GrammarChecker {
+    /// Part-of-speech tagger
+    tagger: Tagger,
+}
+
+/// Grammatical number of a noun form
+#[derive(Clone, Copy, Default)]
+struct Number {
+    /// Form can be singular
+    singular: bool,
+    /// Form can be plural
+    plural: bool,
+}
+
+/// Sentence being checked
+struct Sentence<'a> {
+    /// Word lexicon
+    lex: &'a Lexicon,
+    /// Full text
+    text: &'a str,
+    /// Words in sentence
+    words: &'a [SpanWord],
+    /// Lowercase words
+    lower: Vec<String>,
+    /// Tagged word classes
+    tags: Vec<Option<WordClass>>,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{self:?}")
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}: {}", self.problem, self.message)
+    }
+}
+
+impl Finding {
+    /// Create a new finding
+    fn new(span: Range<usize>, problem: Problem, message: String) -> Self {
+        Finding {
+            span,
+            problem,
+            message,
+        }
+    }
+
+    /// Get byte span in text
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Get kind of problem
+    pub fn problem(&self) -> Problem {
+        self.problem
+    }
+
+    /// Get description of problem
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl GrammarChecker {
+    /// Create a new grammar checker (using the built-in lexicon)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new grammar checker with a tagger
+    pub fn with_tagger(tagger: Tagger) -> Self {
+        GrammarChecker { tagger }
+    }
+
+    /// Check the grammar of a text
+    ///
+    /// Findings are sorted by position in the text.
+    pub fn check(&self, text: &str) -> Result<Vec<Finding>> {
+        let lex = self.tagger.lexicon();
+        let mut findings = Vec::new();
+        for words in span_sentences(text, Arc::clone(lex))? {
+            let refs: Vec<_> = words.iter().map(|w| w.text.as_str()).collect();
+            let sentence = Sentence {
+                lex,
+                text,
+                words: &words,
+                lower: refs.iter().map(|w| w.to_lowercase()).collect(),
+                tags: self.tagger.tag_words(&refs),
+            };
+            sentence.check(&mut findings);
+        }
+        findings.sort_by_key(|f| f.span.start);
+        Ok(findings)
+    }
+}
+
+impl Sentence<'_> {
+    /// Check all words of the sentence
+    fn check(&self, findings: &mut Vec<Finding>) {
+        for i in 0..self.words.len() {
+            findings.extend(self.check_doubled(i));
+            findings.extend(self.check_article(i));
+            findings.extend(self.check_number(i));
+            findings.extend(self.check_agreement(i));
+        }
+    }
+
+    /// Get span from one word to another
+    fn span(&self, i: usize, j: usize) -> Range<usize> {
+        self.words[i].span.start..self.words[j].span.end
+    }
+
+    /// Check if a word could be tagged as a word class
+    fn could_be(&self, i: usize, wc: WordClass) -> bool {
+        match self.tags[i] {
+            Some(tag) => tag == wc,
+            None => self
+                .lex
+                .word_entries(&self.words[i].text)
+                .iter()
+                .any(|e| e.word_class() == wc),
+        }
+    }
+
+    /// Check for a doubled word
+    fn check_doubled(&self, i: usize) -> Option<Finding> {
+        let j = i + 1;
+        let word = self.lower.get(j)?;
+        if *word != self.lower[i] || DOUBLES.contains(&word.as_str()) {
+            return None;
+        }
+        let between =
+            &self.text[self.words[i].span.end..self.words[j].span.start];
+        if !between.chars().all(char::is_whitespace) {
+            return None;
+        }
+        let msg = format!("doubled word `{}`", self.words[j].text);
+        Some(Finding::new(self.span(i, j), Problem::Doubled, msg))
+    }
+
+    /// Check an indefinite article
+    fn check_article(&self, i: usize) -> Option<Finding> {
+        let article = self.lower[i].as_str();
+        if article != "a" && article != "an" {
+            return None;
+        }
+        let next = &self.words.get(i + 1)?.text;
+        let expected = match starts_with_vowel_sound(next)? {
+            true => "an",
+            false => "a",
+        };
+        if article == expected {
+            return None;
+        }
+        let msg = format!("use `{expected}` before `{next}`");
+        Some(Finding::new(self.span(i, i + 1), Problem::Article, msg))
+    }
+
+    /// Check number of a determiner and noun
+    fn check_number(&self, i: usize) -> Option<Finding> {
+        let det = self.lower[i].as_str();
+        let plural = if PLURAL_DETERMINERS.contains(&det) {
+            true
+        } else if SINGULAR_DETERMINERS.contains(&det) {
+            false
+        } else {
+            return None;
+        };
+        let mut j = i + 1;
+        // demonstratives may be separated from the noun by adjectives
+        while matches!(det, "this" | "these" | "those")
+            && self.tags.get(j) == Some(&Some(WordClass::Adjective))
+        {
+            j += 1;
+        }
+        let noun = &self.words.get(j)?.text;
+        if !self.could_be(j, WordClass::Noun) {
+            return None;
+        }
+        let number = self.noun_number(noun);
+        let conflict = if plural {
+            number.singular && !number.plural
+        } else {
+            number.plural && !number.singular
+        };
+        if !conflict {
+            return None;
+        }
+        let msg = match plural {
+            true => {
+                format!("`{}` with singular noun `{noun}`", self.words[i].text)
+            }
+            false => {
+                format!("`{}` with plural noun `{noun}`", self.words[i].text)
+            }
+        };
+        Some(Finding::new(self.span(i, j), Problem::Number, msg))
+    }
+
+    /// Check agreement of a subject and verb
+    fn check_agreement(&self, i: usize) -> Option<Finding> {
+        let verb = i + 1;
+        let word = &self.words.get(verb)?.text;
+        let subject = self.lower[i].as_str();
+        let pronoun = SINGULAR_PRONOUNS.contains(&subject)
+            || PLURAL_PRONOUNS.contains(&subject);
+        let plural = if pronoun {
+            // after a verb, the pronoun is an object ("let it go") or the
+            // subject of a question ("does he go")
+            if i > 0 && self.could_be(i - 1, WordClass::Verb) {
+                return None;
+            }
+            PLURAL_PRONOUNS.contains(&subject)
+        } else if i > 0
+            && self.could_be(i - 1, WordClass::Determiner)
+            && self.could_be(i, WordClass::Noun)
+        {
+            let number = self.noun_number(&self.words[i].text);
+            if number.singular == number.plural {
+                return None;
+            }
+            number.plural
+        } else {
+            return None;
+        };
+        // a subject pronoun is rarely followed by a noun
+        let is_verb = match pronoun {
+            true => self.could_be(verb, WordClass::Verb),
+            false => self.is_verb(verb),
+        };
+        if !is_verb {
+            return None;
+        }
+        let tags = self.verb_tags(word);
+        let conflict = if plural {
+            tags.contains(&Penn::Vbz)
+        } else {
+            tags.contains(&Penn::Vb)
+                && !tags.iter().any(|t| matches!(t, Penn::Vbd | Penn::Vbn))
+                && subject != "i"
+        };
+        if !conflict || (subject == "i" && word.to_lowercase() == "am") {
+            return None;
+        }
+        let msg = match plural {
+            true => format!(
+                "plural subject `{}` with verb `{word}`",
+                self.words[i].text
+            ),
+            false => format!(
+                "singular subject `{}` with verb `{word}`",
+                self.words[i].text
+            ),
+        };
+        Some(Finding::new(self.span(i, verb), Problem::Agreement, msg))
+    }
+
+    /// Check if a word is used as a verb
+    ///
+    /// An untagged word is only considered a verb when its other entries are
+    /// nouns, and it is not followed by a word which could be a verb.
+    fn is_verb(&self, i: usize) -> bool {
+        match self.tags[i] {
+            Some(wc) => wc == WordClass::Verb,
+            None => {
+                let entries = self.lex.word_entries(&self.words[i].text);
+                entries.iter().any(|e| e.word_class() == WordClass::Verb)
+                    && entries.iter().all(|e| {
+                        matches!(
+                            e.word_class(),
+                            WordClass::Noun | WordClass::Verb
+                        )
+                    })
+                    && (i + 1 == self.words.len()
+                        || !self.could_be(i + 1, WordClass::Verb))
+            }
+        }
+    }
+
+    /// Get Penn tags of a word's verb forms
+    fn verb_tags(&self, word: &str) -> Vec<Penn> {
+        self.lex
+            .word_entries(word)
+            .iter()
+            .filter(|e| e.word_class() == WordClass::Verb)
+            .map(|e| Penn::from_lexeme(e, word))
+            .collect()
+    }
+
+    /// Get the grammatical number of a noun form
+    fn noun_number(&self, word: &str) -> Number {
+        let lower = word.to_lowercase();
+        let mut number = Number::default();
+        for e in self.lex.word_entries(word) {
+            if e.word_class() != WordClass::Noun {
+                continue;
+            }
+            if e.attributes().any(|a| a == WordAttr::PluraleTantum) {
+                number.plural = true;
+                continue;
+            }
+            match e.form_rule(&lower) {
+                Some(FormRule::Lemma | FormRule::Variant) => {
+                    number.singular = true;
+                }
+                Some(FormRule::Plural) => number.plural = true,
+                _ => (),
+            }
+            if e.irregular_position(&lower).is_some() {
+                number.plural = true;
+            }
+        }
+        number
+    }
+}
+
+/// Check if a word starts with a vowel sound
+///
+/// Returns `None` if it cannot be determined (e.g. a symbol).
+pub(crate) fn starts_with_vowel_sound(word: &str) -> Option<bool> {
+    let first = word.chars().next()?;
+    let is_acronym = word.chars().count() > 1
+        && word.chars().all(|c| c.is_uppercase() || c == '.');
+    if is_acronym {
+        return Some(VOWEL_LETTERS.contains(first));
+    }
+    if first.is_ascii_digit() {
+        let digits: String =
+            word.chars().take_while(char::is_ascii_digit).collect();
+        return Some(first == '8' || matches!(digits.as_str(), "11" | "18"));
+    }
+    let lower = word.to_lowercase();
+    if SILENT_H.iter().any(|h| lower.starts_with(h)) {
+        return Some(true);
+    }
+    if CONSONANT_SOUNDS.iter().any(|c| lower.starts_with(c)) {
+        return Some(false);
+    }
+    let c = lower.chars().next()?;
+    if !c.is_alphabetic() {
+        return None;
+    }
+    Some(matches!(c, 'a' | 'e' | 'i' | 'o' | 'u'))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Check text, returning problems and the text of their spans
+    fn check(text: &str) -> Vec<(Problem, &str)> {
+        GrammarChecker::new()
+            .check(text)
+            .unwrap()
+            .into_iter()
+            .map(|f| (f.problem(), &text[f.span()]))
+            .collect()
+    }
+
+    #[test]
+    fn grammar() {
+        assert_eq!(
+            check("The dogs barks. I saw a apple and an hour."),
+            [
+                (Problem::Agreement, "dogs barks"),
+                (Problem::Article, "a apple"),
+            ]
+        );
+        assert_eq!(
+            check("These book is on the the table. He go home."),
+            [
+                (Problem::Number, "These book"),
+                (Problem::Doubled, "the the"),
+                (Problem::Agreement, "He go"),
+            ]
+        );
+        assert_eq!(
+            check(
+                "The dog barks. These sheep graze, and a university \
+                opened an FBI office. Does he go? He had had enough."
+            ),
+            []
+        );
+        assert!(check("").is_empty());
+        assert!(check("a").is_empty());
+        assert!(check("the").is_empty());
+        // doubled words must be separated only by whitespace
+        assert_eq!(check("It is is.")[0], (Problem::Doubled, "is is"));
+        assert_eq!(check("The\nthe end.")[0], (Problem::Doubled, "The\nthe"));
+        assert!(check("Go, go now. That that is.").is_empty());
+        // sentences are checked separately
+        assert!(check("I saw the! The end.").is_empty());
+        assert!(check("Let it go. Does she know?").is_empty());
+        assert_eq!(
+            check("Many dog ran, and every cats."),
+            [
+                (Problem::Number, "Many dog"),
+                (Problem::Number, "every cats")
+            ]
+        );
+        assert_eq!(
+            check("An hour, an uniform."),
+            [(Problem::Article, "an uniform")]
+        );
+    }
+
+    #[test]
+    fn vowel_sounds() {
+        let sounds = [
+            ("apple", Some(true)),
+            ("Hour", Some(true)),
+            ("honestly", Some(true)),
+            ("house", Some(false)),
+            ("European", Some(false)),
+            ("one", Some(false)),
+            ("umbrella", Some(true)),
+            ("FBI", Some(true)),
+            ("U.S.", Some(false)),
+            ("8th", Some(true)),
+            ("11", Some(true)),
+            ("110", Some(false)),
+            ("$5", None),
+            ("", None),
+        ];
+        for (word, sound) in sounds {
+            assert_eq!(starts_with_vowel_sound(word), sound, "{word}");
+        }
+    }
+
+    #[test]
+    fn findings() {
+        let findings = GrammarChecker::new().check("It is a error.").unwrap();
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.span(), 6..13);
+        assert_eq!(finding.message(), "use `an` before `error`");
+        assert_eq!(finding.to_string(), "Article: use `an` before `error`");
+    }
+}
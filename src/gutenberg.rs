@@ -0,0 +1,63 @@
+/// Markers at end of header (after leading `*` removed, uppercase)
+const START_MARKERS: &[&str] = &[
+    "START OF THE PROJECT GUTENBERG",
+    "START OF THIS PROJECT GUTENBERG",
+    "END*THE SMALL PRINT",
+];
+
+/// Markers at start of footer (after leading `*` removed, uppercase)
+const END_MARKERS: &[&str] = &[
+    "END OF THE PROJECT GUTENBERG",
+    "END OF THIS PROJECT GUTENBERG",
+    "END OF PROJECT GUTENBERG",
+];
+
+/// Check if a line starts with one of a set of markers
+fn is_marker(line: &str, markers: &[&str]) -> bool {
+    let line = line.trim().trim_start_matches('*').trim_start();
+    let line = line.to_uppercase();
+    markers.iter().any(|m| line.starts_with(m))
+}
+
+/// Strip Project Gutenberg boilerplate from a text
+///
+/// The header (up to the `*** START OF ...` line) and the footer (from the
+/// `*** END OF ...` line, including the license) are removed.  Text without
+/// markers is returned unchanged.
+pub fn strip_boilerplate(text: &str) -> &str {
+    let mut start = None;
+    let mut end = text.len();
+    let mut pos = 0;
+    for line in text.split_inclusive('\n') {
+        let next = pos + line.len();
+        if start.is_none() && is_marker(line, START_MARKERS) {
+            start = Some(next);
+        } else if is_marker(line, END_MARKERS) {
+            end = pos;
+            break;
+        }
+        pos = next;
+    }
+    &text[start.unwrap_or_default()..end]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn strip() {
+        let text = "The Project Gutenberg eBook of Stuff\n\
+            *** START OF THE PROJECT GUTENBERG EBOOK STUFF ***\n\
+            Once upon a time.\n\
+            The end.\n\
+            *** END OF THE PROJECT GUTENBERG EBOOK STUFF ***\n\
+            Section 1. General Terms of Use\n";
+        assert_eq!(strip_boilerplate(text), "Once upon a time.\nThe end.\n");
+        let text = "*END*THE SMALL PRINT! FOR PUBLIC DOMAIN ETEXTS*END*\n\
+            Chapter I\n\
+            End of the Project Gutenberg Etext of Stuff\n";
+        assert_eq!(strip_boilerplate(text), "Chapter I\n");
+        assert_eq!(strip_boilerplate("No markers\n"), "No markers\n");
+    }
+}
@@ -0,0 +1,421 @@
+use crate::Result;
+use crate::kind::Kind;
+use crate::lex::{self, Lexicon, make_word};
+use crate::parse::{Chunk, Parser};
+use crate::word::Lexeme;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::sync::Arc;
+
+/// Context width (characters on each side of a word)
+const CONTEXT_WIDTH: usize = 30;
+
+/// Derivational suffixes of adverbs
+const ADVERB_SUFFIXES: &[&str] = &["ly", "wise", "ward", "wards"];
+
+/// Derivational suffixes of adjectives
+const ADJECTIVE_SUFFIXES: &[&str] = &[
+    "able", "al", "esque", "ful", "ible", "ic", "ish", "ive", "less", "like",
+    "ous", "y",
+];
+
+/// Derivational suffixes of verbs
+const VERB_SUFFIXES: &[&str] = &["ify", "ise", "ize"];
+
+/// Unknown word harvested from a text
+struct HarvestEntry {
+    /// Seen count
+    seen: usize,
+    /// Word (first variant seen)
+    word: String,
+    /// Sample contexts
+    contexts: Vec<String>,
+}
+
+/// Context waiting for following text
+struct PendingContext {
+    /// Harvest key
+    key: String,
+    /// Context text
+    text: String,
+    /// Count of characters after word
+    after: usize,
+}
+
+/// Candidate lexicon entry for unknown words
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Candidate {
+    /// Guessed lexeme
+    lexeme: Lexeme,
+    /// Total seen count
+    seen: usize,
+    /// Unknown words matching candidate
+    words: Vec<String>,
+    /// Sample contexts
+    contexts: Vec<String>,
+}
+
+/// Unknown word harvester
+#[derive(Default)]
+pub struct Harvest {
+    /// Maximum number of contexts per word
+    max_contexts: usize,
+    /// Harvested words
+    words: HashMap<String, HarvestEntry>,
+    /// Recent text (preceding context)
+    recent: String,
+    /// Contexts waiting for following text
+    pending: Vec<PendingContext>,
+    /// Lexicon for parsing (built-in if not set)
+    lex: Option<Arc<Lexicon>>,
+}
+
+impl Candidate {
+    /// Get guessed lexeme
+    pub fn lexeme(&self) -> &Lexeme {
+        &self.lexeme
+    }
+
+    /// Get total seen count
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// Get unknown words matching candidate
+    pub fn words(&self) -> &[String] {
+        &self.words[..]
+    }
+
+    /// Get sample contexts
+    pub fn contexts(&self) -> &[String] {
+        &self.contexts[..]
+    }
+}
+
+impl Harvest {
+    /// Create a new harvest, keeping sample contexts of each word
+    pub fn new(max_contexts: usize) -> Self {
+        Harvest {
+            max_contexts,
+            ..Default::default()
+        }
+    }
+
+    /// Create a new harvest with a shared lexicon
+    ///
+    /// Words in the lexicon are not harvested.
+    pub fn with_lexicon(max_contexts: usize, lex: Arc<Lexicon>) -> Self {
+        Harvest {
+            max_contexts,
+            lex: Some(lex),
+            ..Default::default()
+        }
+    }
+
+    /// Parse text from a reader
+    pub fn parse_text<R>(&mut self, reader: R) -> Result<()>
+    where
+        R: BufRead,
+    {
+        let lex = self.lex.clone().unwrap_or_else(lex::builtin_shared);
+        for token in Parser::with_lexicon(reader, lex) {
+            let token = token?;
+            let text = match token.chunk() {
+                Chunk::Boundary => " ",
+                _ => token.text(),
+            };
+            self.extend_contexts(text);
+            if token.kind() == Kind::Unknown && is_harvestable(text) {
+                self.harvest_word(text);
+            }
+            self.push_recent(text);
+        }
+        self.finish_contexts();
+        self.recent.clear();
+        Ok(())
+    }
+
+    /// Harvest an unknown word
+    fn harvest_word(&mut self, word: &str) {
+        let key = make_word(word);
+        let entry = self.words.entry(key.clone()).or_insert(HarvestEntry {
+            seen: 0,
+            word: word.to_string(),
+            contexts: Vec::new(),
+        });
+        entry.seen += 1;
+        let waiting = self.pending.iter().filter(|p| p.key == key).count();
+        if entry.contexts.len() + waiting < self.max_contexts {
+            let mut before = tail(&self.recent, CONTEXT_WIDTH);
+            if before.len() < self.recent.len() {
+                // skip partial word at start
+                before = before.split_once(' ').map_or(before, |(_, b)| b);
+            }
+            let mut text = before.to_string();
+            text.push_str(word);
+            self.pending.push(PendingContext {
+                key,
+                text,
+                after: 0,
+            });
+        }
+    }
+
+    /// Push text to recent buffer
+    fn push_recent(&mut self, text: &str) {
+        if text == " " && self.recent.ends_with(' ') {
+            return;
+        }
+        self.recent.push_str(text);
+        if self.recent.len() > CONTEXT_WIDTH * 8 {
+            self.recent = tail(&self.recent, CONTEXT_WIDTH).to_string();
+        }
+    }
+
+    /// Extend pending contexts with following text
+    fn extend_contexts(&mut self, text: &str) {
+        for pc in self.pending.iter_mut() {
+            if text != " " || !pc.text.ends_with(' ') {
+                pc.text.push_str(text);
+                pc.after += text.chars().count();
+            }
+        }
+        let (done, pending) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|pc| {
+                pc.after >= CONTEXT_WIDTH && pc.text.ends_with(' ')
+            });
+        self.pending = pending;
+        self.add_contexts(done);
+    }
+
+    /// Finish all pending contexts
+    fn finish_contexts(&mut self) {
+        let done = std::mem::take(&mut self.pending);
+        self.add_contexts(done);
+    }
+
+    /// Add finished contexts to entries
+    fn add_contexts(&mut self, done: Vec<PendingContext>) {
+        for pc in done {
+            if let Some(entry) = self.words.get_mut(&pc.key) {
+                entry.contexts.push(pc.text.trim().to_string());
+            }
+        }
+    }
+
+    /// Get the number of harvested words
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Check if harvest is empty
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Get a Vec of candidates, sorted by seen count (descending)
+    pub fn into_candidates(self) -> Vec<Candidate> {
+        let mut candidates: HashMap<String, Candidate> = HashMap::new();
+        for (key, entry) in self.words {
+            let Some(lexeme) = guess_lexeme(&key) else {
+                continue;
+            };
+            let line = format!("{lexeme:?}");
+            let cand = candidates.entry(line).or_insert(Candidate {
+                lexeme,
+                seen: 0,
+                words: Vec::new(),
+                contexts: Vec::new(),
+            });
+            cand.seen += entry.seen;
+            cand.words.push(entry.word);
+            cand.contexts.extend(entry.contexts);
+        }
+        let mut candidates: Vec<_> = candidates.into_values().collect();
+        for cand in candidates.iter_mut() {
+            cand.words.sort();
+        }
+        candidates.sort_by(|a, b| {
+            b.seen.cmp(&a.seen).then_with(|| a.lexeme.cmp(&b.lexeme))
+        });
+        candidates
+    }
+}
+
+/// Get the tail of a string (up to a number of characters)
+fn tail(s: &str, n: usize) -> &str {
+    if n == 0 {
+        return "";
+    }
+    match s.char_indices().rev().nth(n - 1) {
+        Some((i, _c)) => &s[i..],
+        None => s,
+    }
+}
+
+/// Check if an unknown word should be harvested
+fn is_harvestable(word: &str) -> bool {
+    word.chars().count() > 1 && word.chars().all(char::is_alphabetic)
+}
+
+/// Guess a lexeme which generates a word form
+fn guess_lexeme(word: &str) -> Option<Lexeme> {
+    for line in guess_lines(word) {
+        if let Ok(lexeme) = Lexeme::try_from(line.as_str())
+            && lexeme.forms().iter().any(|f| f == word)
+        {
+            return Some(lexeme);
+        }
+    }
+    None
+}
+
+/// Make candidate lexicon lines for a word, most likely first
+fn guess_lines(word: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(root) = strip_root(word, "ing") {
+        lines.push(format!("{root}:V"));
+        lines.push(format!("{root}e:V"));
+        lines.push(format!("{}:V", undouble(root)));
+    }
+    if let Some(root) = strip_root(word, "ied") {
+        lines.push(format!("{root}y:V"));
+    }
+    if let Some(root) = strip_root(word, "ed") {
+        lines.push(format!("{root}:V"));
+        lines.push(format!("{root}e:V"));
+        lines.push(format!("{}:V", undouble(root)));
+    }
+    if let Some(root) = strip_root(word, "iest") {
+        lines.push(format!("{root}y:A.c"));
+    }
+    if let Some(root) = strip_root(word, "est") {
+        lines.push(format!("{root}:A.c"));
+        lines.push(format!("{root}e:A.c"));
+        lines.push(format!("{}:A.c", undouble(root)));
+    }
+    if let Some(root) = strip_root(word, "ies") {
+        lines.push(format!("{root}y:N"));
+        lines.push(format!("{root}y:V"));
+    }
+    if !(word.ends_with("ss") || word.ends_with("us") || word.ends_with("is")) {
+        if let Some(root) = strip_root(word, "es") {
+            lines.push(format!("{root}:N"));
+            lines.push(format!("{root}:V"));
+        }
+        if let Some(root) = strip_root(word, "s") {
+            lines.push(format!("{root}:N"));
+            lines.push(format!("{root}:V"));
+        }
+    }
+    if ADVERB_SUFFIXES
+        .iter()
+        .any(|s| strip_root(word, s).is_some())
+    {
+        lines.push(format!("{word}:Av"));
+    }
+    if ADJECTIVE_SUFFIXES
+        .iter()
+        .any(|s| strip_root(word, s).is_some())
+    {
+        lines.push(format!("{word}:A"));
+    }
+    if VERB_SUFFIXES.iter().any(|s| strip_root(word, s).is_some()) {
+        lines.push(format!("{word}:V"));
+    }
+    lines.push(format!("{word}:N"));
+    lines
+}
+
+/// Strip a suffix, leaving a root of at least 3 characters
+fn strip_root<'a>(word: &'a str, suffix: &str) -> Option<&'a str> {
+    word.strip_suffix(suffix).filter(|r| r.chars().count() >= 3)
+}
+
+/// Remove a doubled final consonant from a root
+fn undouble(root: &str) -> &str {
+    let mut chars = root.chars().rev();
+    match (chars.next(), chars.next()) {
+        (Some(a), Some(b)) if a == b && !"aeiouy".contains(a) => {
+            &root[..root.len() - a.len_utf8()]
+        }
+        _ => root,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn guess() {
+        let guess = |w| format!("{:?}", guess_lexeme(w).unwrap());
+        assert_eq!(guess("blorfing"), "blorf:V");
+        assert_eq!(guess("snorfed"), "snorf:V");
+        assert_eq!(guess("glorps"), "glorp:N");
+        assert_eq!(guess("flimbly"), "flimbly:Av");
+        assert_eq!(guess("glumptious"), "glumptious:A");
+        assert_eq!(guess("frizzies"), "frizzy:N");
+        assert_eq!(guess("kwass"), "kwass:N");
+        assert_eq!(guess("zzing"), "zzing:N");
+    }
+
+    #[test]
+    fn helpers() {
+        assert_eq!(tail("hello", 3), "llo");
+        assert_eq!(tail("héllo", 4), "éllo");
+        assert_eq!(tail("hi", 5), "hi");
+        assert_eq!(tail("hi", 0), "");
+        assert_eq!(undouble("stopp"), "stop");
+        assert_eq!(undouble("fuzz"), "fuz");
+        assert_eq!(undouble("agree"), "agree");
+        assert_eq!(strip_root("bring", "ing"), None);
+        assert!(!is_harvestable("x") && !is_harvestable("b4"));
+    }
+
+    #[test]
+    fn harvest() {
+        let text = "The blorfing went on. We blorfed. A Zorp, x, b4 and \
+            blorfed again, with many words after it to end the context.";
+        let mut harvest = Harvest::new(1);
+        harvest.parse_text(text.as_bytes()).unwrap();
+        // names, symbols and words with digits are skipped
+        assert_eq!(harvest.len(), 2);
+        let cands = harvest.into_candidates();
+        assert_eq!(cands.len(), 1);
+        let cand = &cands[0];
+        assert_eq!(format!("{:?}", cand.lexeme()), "blorf:V");
+        assert_eq!(cand.seen(), 3);
+        assert_eq!(cand.words(), ["blorfed", "blorfing"]);
+        // one context per word, with up to 30 characters on each side
+        let mut contexts = cand.contexts().to_vec();
+        contexts.sort();
+        assert_eq!(
+            contexts,
+            [
+                "The blorfing went on. We blorfed. A Zorp,",
+                "The blorfing went on. We blorfed. A Zorp, x, b4 and blorfed \
+                again,",
+            ]
+        );
+        let mut harvest = Harvest::new(0);
+        harvest.parse_text(text.as_bytes()).unwrap();
+        assert!(harvest.into_candidates()[0].contexts().is_empty());
+    }
+
+    #[test]
+    fn lexicon() {
+        let lex = Arc::new(Lexicon::from_csv("blorf:V").unwrap());
+        let mut harvest = Harvest::with_lexicon(2, lex);
+        harvest
+            .parse_text("blorfing and snarking".as_bytes())
+            .unwrap();
+        let cands = harvest.into_candidates();
+        let words: Vec<_> = cands.iter().flat_map(|c| c.words()).collect();
+        assert_eq!(words, ["and", "snarking"]);
+        let mut harvest = Harvest::new(2);
+        harvest.parse_text("".as_bytes()).unwrap();
+        assert!(harvest.is_empty());
+    }
+}
@@ -1,28 +1,106 @@
+use crate::Result;
 use crate::kind::Kind;
-use crate::lex;
-use crate::parse::Parser;
-use crate::word::WordClass;
-use std::io::BufRead;
+use crate::lex::{self, Lexicon};
+use crate::parse::{Chunk, Parser, ParserTokenizer, Token, Tokenizer};
+use crate::word::{WordAttr, WordClass};
+use html_escape::encode_text;
+use std::io::{BufRead, Write, stdout};
+use std::sync::Arc;
 use yansi::{Paint, Style};
 
 /// Hilite text from a reader
-pub fn hilite_text<R>(reader: R) -> Result<(), std::io::Error>
+pub fn hilite_text<R>(reader: R) -> Result<()>
 where
     R: BufRead,
 {
-    for chunk in Parser::new(reader) {
-        let (_chunk, text, kind) = chunk?;
-        print!("{}", text.paint(style(kind, &text)));
+    write_hilite(reader, stdout().lock())
+}
+
+/// Hilite text from a reader to a writer
+pub fn write_hilite<R, W>(reader: R, writer: W) -> Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    write_hilite_with_lexicon(reader, writer, lex::builtin_shared())
+}
+
+/// Hilite text from a reader to a writer, using a shared lexicon
+pub fn write_hilite_with_lexicon<R, W>(
+    reader: R,
+    writer: W,
+    lex: Arc<Lexicon>,
+) -> Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    write_hilite_with_tokenizer(reader, writer, lex, &ParserTokenizer)
+}
+
+/// Hilite text from a reader to a writer, using a custom tokenizer
+pub fn write_hilite_with_tokenizer<R, W>(
+    reader: R,
+    mut writer: W,
+    lex: Arc<Lexicon>,
+    tokenizer: &dyn Tokenizer,
+) -> Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    let tokens = tokenizer.tokenize(Box::new(reader), Arc::clone(&lex));
+    for phrase in Phrases::new(tokens) {
+        let phrase = phrase?;
+        let classes = classify(&lex, &phrase);
+        for (token, wc) in phrase.iter().zip(classes) {
+            write!(writer, "{}", token.text().paint(style(token.kind(), wc)))?;
+        }
     }
-    println!();
+    writeln!(writer)?;
+    writer.flush()?;
     Ok(())
 }
 
+/// Hilite text as HTML
+///
+/// Each word or symbol is wrapped in a `span`, with a class for its kind
+/// (e.g. `booky-proper`) or word class (e.g. `booky-noun`).
+pub fn hilite_html(text: &str) -> Result<String> {
+    let lex = lex::builtin();
+    let mut html = String::with_capacity(text.len() * 2);
+    for phrase in Phrases::new(Parser::from_str(text)) {
+        let phrase = phrase?;
+        let classes = classify(lex, &phrase);
+        for (token, wc) in phrase.iter().zip(classes) {
+            if token.chunk() == Chunk::Boundary {
+                html.push_str(&encode_text(token.text()));
+            } else {
+                html.push_str(&format!(
+                    "<span class=\"booky-{}\">{}</span>",
+                    class_name(token.kind(), wc),
+                    encode_text(token.text())
+                ));
+            }
+        }
+    }
+    Ok(html)
+}
+
+/// Get HTML class name of a chunk
+fn class_name(kind: Kind, wc: Option<WordClass>) -> String {
+    let name = match (kind, wc) {
+        (Kind::Lexicon, Some(wc)) => format!("{wc:?}"),
+        _ => format!("{kind:?}"),
+    };
+    name.to_lowercase()
+}
+
 /// Get style to paint a chunk
-fn style(kind: Kind, word: &str) -> Style {
+fn style(kind: Kind, wc: Option<WordClass>) -> Style {
     match kind {
         Kind::Lexicon => {
-            let Some(wc) = word_class(word) else {
+            let Some(wc) = wc else {
                 return Style::new();
             };
             match wc {
@@ -35,24 +113,202 @@ fn style(kind: Kind, word: &str) -> Style {
             }
         }
         Kind::Foreign => Style::new().bright().bold().italic(),
-        Kind::Ordinal | Kind::Roman | Kind::Number => {
-            Style::new().bright_red().bold()
-        }
+        Kind::Ordinal
+        | Kind::Roman
+        | Kind::Number
+        | Kind::Date
+        | Kind::Time => Style::new().bright_red().bold(),
         Kind::Acronym => Style::new().bold(),
         Kind::Proper => Style::new().bright().bold(),
+        Kind::Url | Kind::Email => Style::new().cyan().underline(),
+        Kind::Hashtag | Kind::Mention => Style::new().magenta(),
+        Kind::Emoji => Style::new(),
         Kind::Symbol => Style::new().dim(),
         Kind::Unknown => Style::new().underline(),
     }
 }
 
-/// Determine word class
-fn word_class(word: &str) -> Option<WordClass> {
-    let mut ents = lex::builtin().word_entries(word);
-    if ents.len() == 1 {
-        let we = ents.pop().unwrap();
-        Some(we.word_class())
-    } else {
-        // FIXME: match sentence structure to choose word class?
-        None
+/// Iterator of phrases (chunks up to punctuation)
+struct Phrases<I> {
+    /// Token iterator
+    tokens: I,
+}
+
+impl<I> Phrases<I>
+where
+    I: Iterator<Item = std::result::Result<Token, std::io::Error>>,
+{
+    /// Create a new phrase iterator
+    fn new(tokens: I) -> Self {
+        Phrases { tokens }
+    }
+}
+
+impl<I> Iterator for Phrases<I>
+where
+    I: Iterator<Item = std::result::Result<Token, std::io::Error>>,
+{
+    type Item = Result<Vec<Token>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut phrase = Vec::new();
+        for token in self.tokens.by_ref() {
+            match token {
+                Ok(token) => {
+                    let end = token.chunk() == Chunk::Symbol;
+                    phrase.push(token);
+                    if end {
+                        break;
+                    }
+                }
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+        (!phrase.is_empty()).then_some(Ok(phrase))
+    }
+}
+
+/// Determine word classes of all chunks in a phrase
+///
+/// Words with entries in more than one class are disambiguated using the
+/// neighboring words, falling back to the most likely class.
+fn classify(lex: &Lexicon, phrase: &[Token]) -> Vec<Option<WordClass>> {
+    let words: Vec<_> = phrase
+        .iter()
+        .enumerate()
+        .filter(|(_i, token)| token.chunk() == Chunk::Text)
+        .collect();
+    let mut classes = vec![None; phrase.len()];
+    let mut prev: Option<(&str, Option<WordClass>)> = None;
+    for (n, (i, token)) in words.iter().enumerate() {
+        let word = token.text();
+        let next = words.get(n + 1).map(|(_i, t)| t.text());
+        let wc = match token.kind() {
+            Kind::Lexicon => word_class(lex, prev, word, next),
+            _ => None,
+        };
+        classes[*i] = wc;
+        prev = Some((word, wc));
+    }
+    classes
+}
+
+/// Determine word class using context
+///
+/// * `prev`: previous word and its class
+/// * `next`: next word
+fn word_class(
+    lex: &Lexicon,
+    prev: Option<(&str, Option<WordClass>)>,
+    word: &str,
+    next: Option<&str>,
+) -> Option<WordClass> {
+    let classes = word_classes(lex, word);
+    if let [wc] = classes[..] {
+        return Some(wc);
+    }
+    let has = |wc| classes.contains(&wc);
+    let (prev_word, prev_wc) = prev.unwrap_or_default();
+    match prev_wc {
+        // determiner / adjective => noun (unless another noun follows)
+        Some(WordClass::Determiner | WordClass::Adjective)
+            if has(WordClass::Noun) =>
+        {
+            let noun_next = next.is_some_and(|next| {
+                word_classes(lex, next).contains(&WordClass::Noun)
+            });
+            if noun_next && has(WordClass::Adjective) {
+                return Some(WordClass::Adjective);
+            }
+            return Some(WordClass::Noun);
+        }
+        Some(WordClass::Determiner) if has(WordClass::Adjective) => {
+            return Some(WordClass::Adjective);
+        }
+        // adverb => adjective
+        Some(WordClass::Adverb) if has(WordClass::Adjective) => {
+            return Some(WordClass::Adjective);
+        }
+        // pronoun => verb
+        Some(WordClass::Pronoun) if has(WordClass::Verb) => {
+            return Some(WordClass::Verb);
+        }
+        _ => (),
+    }
+    // "to" / auxiliary => verb
+    if has(WordClass::Verb)
+        && (prev_word.eq_ignore_ascii_case("to")
+            || is_auxiliary(lex, prev_word))
+    {
+        return Some(WordClass::Verb);
+    }
+    if has(WordClass::Determiner) && prev_wc != Some(WordClass::Determiner) {
+        return Some(WordClass::Determiner);
+    }
+    lex.most_likely_class(word)
+}
+
+/// Get the distinct word classes of a word form
+fn word_classes(lex: &Lexicon, word: &str) -> Vec<WordClass> {
+    let mut classes: Vec<_> = lex
+        .word_entries(word)
+        .iter()
+        .map(|e| e.word_class())
+        .collect();
+    classes.sort();
+    classes.dedup();
+    classes
+}
+
+/// Check if a word is an auxiliary verb
+fn is_auxiliary(lex: &Lexicon, word: &str) -> bool {
+    lex.word_entries(word)
+        .iter()
+        .any(|e| e.attributes().any(|a| a == WordAttr::Auxiliary))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn html() {
+        assert_eq!(
+            hilite_html("Dr. Jekyll & blorf").unwrap(),
+            "<span class=\"booky-noun\">Dr.</span> \
+            <span class=\"booky-proper\">Jekyll</span> \
+            <span class=\"booky-symbol\">&amp;</span> \
+            <span class=\"booky-unknown\">blorf</span>"
+        );
+        let lex = lex::builtin();
+        let text = "They run to light the bright light, a very light run.";
+        let phrases: Vec<_> = Phrases::new(Parser::new(text.as_bytes()))
+            .map(|p| p.unwrap())
+            .collect();
+        let classes: Vec<_> = phrases
+            .iter()
+            .flat_map(|phrase| {
+                classify(lex, phrase)
+                    .into_iter()
+                    .zip(phrase)
+                    .filter(|(_wc, token)| token.chunk() == Chunk::Text)
+                    .map(|(wc, _token)| wc)
+            })
+            .collect();
+        use WordClass::*;
+        assert_eq!(classes[..2], [Some(Pronoun), Some(Verb)]);
+        assert_eq!(
+            classes[3..],
+            [
+                Some(Verb),
+                Some(Determiner),
+                Some(Adjective),
+                Some(Noun),
+                Some(Determiner),
+                Some(Adjective),
+                Some(Adjective),
+                Some(Noun),
+            ]
+        );
     }
 }
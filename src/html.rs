@@ -0,0 +1,335 @@
+use crate::Result;
+use html_escape::decode_html_entities;
+use std::io::BufRead;
+
+/// Void elements (no closing tag)
+const VOID_ELEMENTS: &[&str] = &[
+    "!doctype", "area", "base", "br", "col", "embed", "hr", "img", "input",
+    "link", "meta", "source", "track", "wbr",
+];
+
+/// Raw text elements (content is never displayed)
+const RAW_TEXT_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Block elements (separated by line breaks)
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "li",
+    "main",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "td",
+    "th",
+    "tr",
+    "ul",
+];
+
+/// Elements skipped by default
+const SKIPPED_TAGS: &[&str] = &[
+    "annotation",
+    "figure",
+    "footer",
+    "form",
+    "header",
+    "label",
+    "nav",
+    "noscript",
+    "semantics",
+    "template",
+];
+
+/// HTML text extractor
+#[derive(Clone, Debug)]
+pub struct Extractor {
+    /// Tags of skipped elements
+    skip_tags: Vec<String>,
+    /// Classes of skipped elements
+    skip_classes: Vec<String>,
+}
+
+/// Open element
+struct Element {
+    /// Tag name
+    tag: String,
+    /// Content displayed
+    displayed: bool,
+}
+
+impl Default for Extractor {
+    fn default() -> Self {
+        Extractor {
+            skip_tags: SKIPPED_TAGS.iter().map(|t| t.to_string()).collect(),
+            skip_classes: Vec::new(),
+        }
+    }
+}
+
+impl Extractor {
+    /// Create a new HTML text extractor
+    ///
+    /// Navigation, header, footer, form and figure elements are skipped.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Skip elements with a tag
+    pub fn skip_tag(mut self, tag: &str) -> Self {
+        self.skip_tags.push(tag.to_lowercase());
+        self
+    }
+
+    /// Display elements with a tag (which is skipped by default)
+    pub fn display_tag(mut self, tag: &str) -> Self {
+        let tag = tag.to_lowercase();
+        self.skip_tags.retain(|t| *t != tag);
+        self
+    }
+
+    /// Skip elements with a class attribute containing a name
+    pub fn skip_class(mut self, class: &str) -> Self {
+        self.skip_classes.push(class.to_string());
+        self
+    }
+
+    /// Check if an element with given attributes is displayed
+    fn is_displayed(&self, tag: &str, attrs: &str) -> bool {
+        if self.skip_tags.iter().any(|t| t == tag) {
+            return false;
+        }
+        match class_attr(attrs) {
+            Some(classes) => !classes.split_whitespace().any(|c| {
+                self.skip_classes.iter().any(|s| c.contains(s.as_str()))
+            }),
+            None => true,
+        }
+    }
+
+    /// Extract displayed text from HTML
+    pub fn extract_text<R>(&self, mut reader: R) -> Result<String>
+    where
+        R: BufRead,
+    {
+        let mut text = String::new();
+        let mut stack: Vec<Element> = Vec::new();
+        let mut buf = Vec::with_capacity(4096);
+        loop {
+            buf.clear();
+            if reader.read_until(b'<', &mut buf)? == 0 {
+                break;
+            }
+            let tag_next = buf.last() == Some(&b'<');
+            if tag_next {
+                buf.pop();
+            }
+            if stack.iter().all(|e| e.displayed) {
+                let content = String::from_utf8_lossy(&buf);
+                text.push_str(&decode_html_entities(&content));
+            }
+            if !tag_next {
+                break;
+            }
+            buf.clear();
+            if reader.read_until(b'>', &mut buf)? == 0 {
+                break;
+            }
+            if buf.starts_with(b"!--") {
+                while !buf.ends_with(b"-->") {
+                    if reader.read_until(b'>', &mut buf)? == 0 {
+                        break;
+                    }
+                }
+                continue;
+            }
+            if buf.starts_with(b"![CDATA[") {
+                while !buf.ends_with(b"]]>") {
+                    if reader.read_until(b'>', &mut buf)? == 0 {
+                        break;
+                    }
+                }
+                if stack.iter().all(|e| e.displayed) {
+                    let content = buf[8..].strip_suffix(b"]]>").unwrap_or(&[]);
+                    text.push_str(&String::from_utf8_lossy(content));
+                }
+                continue;
+            }
+            // `>` can appear within quoted attribute values
+            while in_quotes(&buf) {
+                if reader.read_until(b'>', &mut buf)? == 0 {
+                    break;
+                }
+            }
+            let tag = String::from_utf8_lossy(&buf);
+            let tag = tag.trim_end_matches('>');
+            let self_closing = tag.ends_with('/');
+            let tag = tag.trim_end_matches('/');
+            let (name, attrs) =
+                tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            let name = name.to_lowercase();
+            if let Some(name) = name.strip_prefix('/') {
+                if BLOCK_ELEMENTS.contains(&name) {
+                    line_break(&mut text);
+                }
+                if let Some(i) = stack.iter().rposition(|e| e.tag == name) {
+                    stack.truncate(i);
+                }
+                continue;
+            }
+            if BLOCK_ELEMENTS.contains(&name.as_str()) {
+                line_break(&mut text);
+            }
+            if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                if !self_closing {
+                    skip_raw_text(&mut reader, &name)?;
+                }
+                continue;
+            }
+            if self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+                continue;
+            }
+            let displayed = self.is_displayed(&name, attrs);
+            stack.push(Element {
+                tag: name,
+                displayed,
+            });
+        }
+        Ok(text)
+    }
+}
+
+/// Add a line break to text (unless at start of a line)
+fn line_break(text: &mut String) {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+}
+
+/// Check if a tag ends within a quoted attribute value
+fn in_quotes(tag: &[u8]) -> bool {
+    let mut quote = None;
+    let mut after_eq = false;
+    for &b in tag {
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => (),
+            None if after_eq && (b == b'"' || b == b'\'') => quote = Some(b),
+            None => (),
+        }
+        if !b.is_ascii_whitespace() {
+            after_eq = b == b'=';
+        }
+    }
+    quote.is_some()
+}
+
+/// Get the value of a class attribute
+fn class_attr(attrs: &str) -> Option<&str> {
+    let lower = attrs.to_ascii_lowercase();
+    let i = lower.find("class=")?;
+    let value = &attrs[i + 6..];
+    match value.chars().next()? {
+        q @ ('"' | '\'') => {
+            let value = &value[1..];
+            Some(value.split(q).next().unwrap_or(value))
+        }
+        _ => value.split_whitespace().next(),
+    }
+}
+
+/// Skip raw text until the closing tag
+fn skip_raw_text<R>(reader: &mut R, name: &str) -> Result<()>
+where
+    R: BufRead,
+{
+    let end = format!("</{name}");
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        if reader.read_until(b'>', &mut buf)? == 0 {
+            return Ok(());
+        }
+        let text = String::from_utf8_lossy(&buf).to_ascii_lowercase();
+        if text.trim_end_matches('>').trim_end().ends_with(&end) {
+            return Ok(());
+        }
+    }
+}
+
+/// Extract displayed text from HTML (with default settings)
+pub fn extract_text<R>(reader: R) -> Result<String>
+where
+    R: BufRead,
+{
+    Extractor::new().extract_text(reader)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extract() {
+        let html = "<!DOCTYPE html><html><head><title>A &amp; B</title>\
+            <style>p { color: red; }</style></head><body>\
+            <nav><a href=\"/\">Home</a></nav>\
+            <p>Hello <b>big</b> world<br/>again</p>\
+            <script>if (a < b) { c(); }</script>\
+            <!-- a > b -->\
+            <div class=\"infobox vcard\">Skip me</div>\
+            <p title=\"a > b\" class='x'>Bye <![CDATA[x < y]]></p>\
+            </body></html>";
+        let ex = Extractor::new().skip_class("infobox");
+        let text = ex.extract_text(html.as_bytes()).unwrap();
+        assert_eq!(text, "A & B\nHello big world\nagain\nBye x < y\n");
+    }
+
+    #[test]
+    fn skip() {
+        let html = "<NAV>menu</NAV><Header>Top</Header>\
+            <div class=ad>Buy</div><p class=\"a  sidebar-x\">Side</p>\
+            <section><figure>Fig <p>nested</p></figure>Body</section>";
+        let ex = Extractor::new().skip_class("sidebar").skip_class("ad");
+        let text = ex.extract_text(html.as_bytes()).unwrap();
+        assert_eq!(text, "Body\n");
+        let ex = Extractor::new().display_tag("HEADER").skip_tag("Section");
+        let text = ex.extract_text(html.as_bytes()).unwrap();
+        assert_eq!(text, "Top\nBuy\nSide\n");
+    }
+
+    #[test]
+    fn malformed() {
+        let extract = |html: &str| extract_text(html.as_bytes()).unwrap();
+        // unterminated tags, comments and sections end the text
+        assert_eq!(extract("Hi <b"), "Hi ");
+        assert_eq!(extract("Hi <!-- never closed"), "Hi ");
+        assert_eq!(extract("Hi <![CDATA[ open"), "Hi ");
+        assert_eq!(extract("Hi <script>x()"), "Hi ");
+        assert_eq!(extract("a <img alt=\"b > c\"> d"), "a  d");
+        // unmatched close tags are ignored
+        assert_eq!(extract("</p></nav>one</b> two"), "one two");
+        // a skipped element hides the rest if never closed
+        assert_eq!(extract("one <nav> two <p>three"), "one \n");
+        assert_eq!(extract("<style/>a &lt;b&gt; &bogus;"), "a <b> &bogus;");
+        let text = extract_text(&b"caf\xE9 <p>ok"[..]).unwrap();
+        assert_eq!(text, "caf\u{FFFD} \nok");
+        assert_eq!(extract(""), "");
+    }
+}
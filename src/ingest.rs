@@ -0,0 +1,394 @@
+use crate::fountain::{self, Element};
+use crate::{
+    Result, delimited, feed, html, json, latex, markdown, org, rst, subtitle,
+};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Input text format
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Format {
+    /// Plain text
+    #[default]
+    Text,
+    /// HTML
+    Html,
+    /// RSS or Atom feed
+    Feed,
+    /// Markdown
+    Markdown,
+    /// LaTeX
+    Latex,
+    /// Org-mode
+    Org,
+    /// reStructuredText
+    Rst,
+    /// Comma-separated values
+    Csv,
+    /// Tab-separated values
+    Tsv,
+    /// JSON or JSON Lines
+    Json,
+    /// PDF document
+    #[cfg(feature = "pdf")]
+    Pdf,
+    /// Word document
+    #[cfg(feature = "docx")]
+    Docx,
+    /// EPUB book
+    #[cfg(feature = "epub")]
+    Epub,
+    /// Subtitles (SRT or WebVTT)
+    Subtitle,
+    /// Fountain screenplay (scene headings, action and dialogue)
+    Fountain,
+    /// Fountain screenplay (dialogue only)
+    FountainDialogue,
+    /// Fountain screenplay (action only)
+    FountainAction,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(val: &str) -> Result<Self, Self::Err> {
+        match val {
+            "text" => Ok(Format::Text),
+            "html" => Ok(Format::Html),
+            "feed" | "rss" | "atom" => Ok(Format::Feed),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "latex" | "tex" => Ok(Format::Latex),
+            "org" => Ok(Format::Org),
+            "rst" | "rest" => Ok(Format::Rst),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            "json" | "jsonl" => Ok(Format::Json),
+            #[cfg(feature = "pdf")]
+            "pdf" => Ok(Format::Pdf),
+            #[cfg(feature = "docx")]
+            "docx" => Ok(Format::Docx),
+            #[cfg(feature = "epub")]
+            "epub" => Ok(Format::Epub),
+            "srt" | "vtt" => Ok(Format::Subtitle),
+            "fountain" => Ok(Format::Fountain),
+            "fountain-dialogue" => Ok(Format::FountainDialogue),
+            "fountain-action" => Ok(Format::FountainAction),
+            _ => Err(format!("Unknown format: {val}")),
+        }
+    }
+}
+
+impl Format {
+    /// Detect format from a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        match ext.as_str() {
+            "txt" | "text" => Some(Format::Text),
+            "htm" | "html" | "xhtml" => Some(Format::Html),
+            "atom" | "rss" => Some(Format::Feed),
+            "markdown" | "md" => Some(Format::Markdown),
+            "latex" | "tex" => Some(Format::Latex),
+            "org" => Some(Format::Org),
+            "rest" | "rst" => Some(Format::Rst),
+            "csv" => Some(Format::Csv),
+            "tab" | "tsv" => Some(Format::Tsv),
+            "json" | "jsonl" | "ndjson" => Some(Format::Json),
+            #[cfg(feature = "pdf")]
+            "pdf" => Some(Format::Pdf),
+            #[cfg(feature = "docx")]
+            "docx" => Some(Format::Docx),
+            #[cfg(feature = "epub")]
+            "epub" => Some(Format::Epub),
+            "srt" | "vtt" => Some(Format::Subtitle),
+            "fountain" | "spmd" => Some(Format::Fountain),
+            _ => None,
+        }
+    }
+
+    /// Detect format by sniffing the start of content
+    pub fn sniff(buf: &[u8]) -> Self {
+        #[cfg(feature = "pdf")]
+        if buf.starts_with(b"%PDF") {
+            return Format::Pdf;
+        }
+        // EPUB files start with an uncompressed `mimetype` entry
+        #[cfg(feature = "epub")]
+        if buf.starts_with(b"PK\x03\x04")
+            && buf.get(30..58) == Some(b"mimetypeapplication/epub+zip")
+        {
+            return Format::Epub;
+        }
+        #[cfg(feature = "docx")]
+        if buf.starts_with(b"PK\x03\x04") {
+            return Format::Docx;
+        }
+        let text = String::from_utf8_lossy(buf);
+        let text = text.trim_start_matches('\u{FEFF}').trim_start();
+        let head = text.get(..512).unwrap_or(text).to_lowercase();
+        if head.starts_with('<') {
+            if head.contains("<rss") || head.contains("<feed") {
+                return Format::Feed;
+            }
+            if head.contains("<html") || head.starts_with("<!doctype html") {
+                return Format::Html;
+            }
+        }
+        if head.starts_with("webvtt") || is_srt(text) {
+            return Format::Subtitle;
+        }
+        if head.starts_with('{') {
+            return Format::Json;
+        }
+        if head.starts_with("\\documentclass") {
+            return Format::Latex;
+        }
+        if head.starts_with("#+title:") {
+            return Format::Org;
+        }
+        Format::Text
+    }
+
+    /// Check if the format is binary (not text)
+    pub fn is_binary(self) -> bool {
+        match self {
+            #[cfg(feature = "pdf")]
+            Format::Pdf => true,
+            #[cfg(feature = "docx")]
+            Format::Docx => true,
+            #[cfg(feature = "epub")]
+            Format::Epub => true,
+            _ => false,
+        }
+    }
+}
+
+/// Check if text starts with an SRT cue
+fn is_srt(text: &str) -> bool {
+    let mut lines = text.lines();
+    lines
+        .next()
+        .is_some_and(|l| l.trim().chars().all(|c| c.is_ascii_digit()))
+        && lines.next().is_some_and(|l| l.contains("-->"))
+}
+
+/// Text ingester (extracting prose from various formats)
+#[derive(Clone, Debug, Default)]
+pub struct Ingest {
+    /// Input format (detected if not set)
+    format: Option<Format>,
+    /// Selected columns (CSV / TSV)
+    columns: Vec<String>,
+    /// Selected field paths (JSON)
+    fields: Vec<String>,
+}
+
+impl Ingest {
+    /// Create a new ingester
+    ///
+    /// The format is detected by file extension, then content sniffing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the input format (skipping detection)
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Select a column by name or number (CSV / TSV)
+    pub fn column(mut self, column: &str) -> Self {
+        self.columns.push(column.to_string());
+        self
+    }
+
+    /// Select a field by path (JSON)
+    pub fn field(mut self, path: &str) -> Self {
+        self.fields.push(path.to_string());
+        self
+    }
+
+    /// Detect the format of a reader (without consuming any content)
+    pub fn detect<R>(&self, reader: &mut R, path: Option<&Path>) -> Format
+    where
+        R: BufRead,
+    {
+        if let Some(format) = self.format {
+            return format;
+        }
+        if let Some(format) = path.and_then(Format::from_path) {
+            return format;
+        }
+        reader.fill_buf().map_or(Format::Text, Format::sniff)
+    }
+
+    /// Read text from a reader, extracting prose
+    ///
+    /// The path (if any) is used for format detection.
+    pub fn read<'a, R>(
+        &self,
+        mut reader: R,
+        path: Option<&Path>,
+    ) -> Result<Box<dyn BufRead + 'a>>
+    where
+        R: BufRead + 'a,
+    {
+        let text = match self.detect(&mut reader, path) {
+            Format::Text => return Ok(Box::new(reader)),
+            Format::Html => html::extract_text(reader)?,
+            Format::Feed => feed::extract_text(reader)?,
+            Format::Markdown => markdown::extract_text(reader)?,
+            Format::Latex => latex::extract_text(reader)?,
+            Format::Org => org::extract_text(reader)?,
+            Format::Rst => rst::extract_text(reader)?,
+            Format::Csv => {
+                delimited::extract_columns(reader, ',', &self.columns)?
+            }
+            Format::Tsv => {
+                delimited::extract_columns(reader, '\t', &self.columns)?
+            }
+            Format::Json => json::extract_fields(reader, &self.fields)?,
+            #[cfg(feature = "pdf")]
+            Format::Pdf => crate::pdf::extract_text(reader)?,
+            #[cfg(feature = "docx")]
+            Format::Docx => crate::docx::extract_text(reader)?,
+            #[cfg(feature = "epub")]
+            Format::Epub => crate::epub::extract_text(reader)?,
+            Format::Subtitle => subtitle::extract_text(reader)?,
+            Format::Fountain => fountain::extract_text(
+                reader,
+                &[
+                    Element::SceneHeading,
+                    Element::Action,
+                    Element::Dialogue,
+                    Element::Lyric,
+                ],
+            )?,
+            Format::FountainDialogue => fountain::extract_text(
+                reader,
+                &[Element::Dialogue, Element::Lyric],
+            )?,
+            Format::FountainAction => {
+                fountain::extract_text(reader, &[Element::Action])?
+            }
+        };
+        Ok(Box::new(Cursor::new(text.into_bytes())))
+    }
+
+    /// Open a file, extracting prose
+    pub fn open(&self, path: &Path) -> Result<Box<dyn BufRead>> {
+        let reader = BufReader::new(File::open(path)?);
+        self.read(reader, Some(path))
+    }
+}
+
+/// Open a file, extracting prose (format detected automatically)
+pub fn open<P>(path: P) -> Result<Box<dyn BufRead>>
+where
+    P: AsRef<Path>,
+{
+    Ingest::new().open(path.as_ref())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Error;
+    use std::io::Read;
+
+    /// Read all text from an ingester
+    fn read_all(ingest: &Ingest, src: &str, path: Option<&str>) -> String {
+        let mut text = String::new();
+        ingest
+            .read(src.as_bytes(), path.map(Path::new))
+            .unwrap()
+            .read_to_string(&mut text)
+            .unwrap();
+        text
+    }
+
+    #[test]
+    fn detect() {
+        let path = Path::new("notes/Readme.MD");
+        assert_eq!(Format::from_path(path), Some(Format::Markdown));
+        assert_eq!(Format::from_path(Path::new("x.dat")), None);
+        assert_eq!(Format::sniff(b"<!DOCTYPE html><html>"), Format::Html);
+        assert_eq!(
+            Format::sniff(b"<?xml version=\"1.0\"?><rss>"),
+            Format::Feed
+        );
+        assert_eq!(
+            Format::sniff(b"1\n00:00:01,000 --> 00:00:02,000\n"),
+            Format::Subtitle
+        );
+        assert_eq!(Format::sniff(b"Call me Ishmael."), Format::Text);
+        let mut reader = &b"WEBVTT\n"[..];
+        assert_eq!(Ingest::new().detect(&mut reader, None), Format::Subtitle);
+        assert_eq!(reader, b"WEBVTT\n");
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!("rss".parse(), Ok(Format::Feed));
+        assert_eq!("jsonl".parse(), Ok(Format::Json));
+        assert_eq!("vtt".parse(), Ok(Format::Subtitle));
+        assert_eq!("fountain-action".parse(), Ok(Format::FountainAction));
+        assert!("HTML".parse::<Format>().is_err());
+        assert!("".parse::<Format>().is_err());
+        assert_eq!(
+            Format::from_path(Path::new("a.NDJSON")),
+            Some(Format::Json)
+        );
+        assert_eq!(Format::from_path(Path::new("Makefile")), None);
+        assert_eq!(Format::from_path(Path::new(".md")), None);
+        assert!(!Format::Html.is_binary());
+    }
+
+    #[test]
+    fn sniff() {
+        let sniffs: &[(&[u8], Format)] = &[
+            (b"\xEF\xBB\xBF  <html><body>", Format::Html),
+            (
+                b"<feed xmlns=\"http://www.w3.org/2005/Atom\">",
+                Format::Feed,
+            ),
+            (b"<p>no html element</p>", Format::Text),
+            (b"{\"text\": \"hi\"}", Format::Json),
+            (b"\\documentclass{article}", Format::Latex),
+            (b"#+TITLE: Notes", Format::Org),
+            (b"1\nnot a cue", Format::Text),
+            (b"", Format::Text),
+            (b"\xFF\xFE", Format::Text),
+        ];
+        for (buf, format) in sniffs {
+            assert_eq!(Format::sniff(buf), *format, "{buf:?}");
+        }
+    }
+
+    #[test]
+    fn read() {
+        let text = "plain <b>text</b>\n";
+        assert_eq!(read_all(&Ingest::new(), text, None), text);
+        // the extension wins over sniffing, and a set format over both
+        assert_eq!(
+            read_all(&Ingest::new(), "<html>x &amp; y</html>", Some("a.txt")),
+            "<html>x &amp; y</html>"
+        );
+        let csv = "id,body\n1,First\n2,\"Second, too\"\n";
+        let ingest = Ingest::new().format(Format::Csv).column("body");
+        let text = read_all(&ingest, csv, Some("a.txt"));
+        assert!(text.contains("First") && text.contains("Second, too"));
+        assert!(!text.contains('1'));
+        let ingest = Ingest::new().column("nope");
+        let res = ingest.read(csv.as_bytes(), Some(Path::new("a.csv")));
+        assert!(matches!(res, Err(Error::Select(_))));
+        let ingest = Ingest::new().field("a[");
+        let res = ingest.read(&b"{}"[..], Some(Path::new("a.json")));
+        assert!(matches!(res, Err(Error::Select(_))));
+        let res = open("no/such/file.txt");
+        assert!(matches!(res, Err(Error::Io(_))));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{Error, Result};
+use serde_json::{Deserializer, Value};
+use std::io::BufRead;
+
+/// Field path step
+#[derive(Clone, Debug, PartialEq)]
+enum Step {
+    /// Object member
+    Key(String),
+    /// Every array element (`[]`)
+    Each,
+    /// One array element (`[N]`)
+    Index(usize),
+}
+
+/// Parse a field path (e.g. `comments[].text`)
+fn parse_path(path: &str) -> Result<Vec<Step>> {
+    let invalid = || Error::Select(format!("invalid field path `{path}`"));
+    let mut steps = Vec::new();
+    for seg in path.split('.').filter(|s| !s.is_empty()) {
+        let (key, mut rest) = seg.split_at(seg.find('[').unwrap_or(seg.len()));
+        if !key.is_empty() {
+            steps.push(Step::Key(key.to_string()));
+        }
+        while !rest.is_empty() {
+            let (idx, r) = rest
+                .strip_prefix('[')
+                .and_then(|r| r.split_once(']'))
+                .ok_or_else(invalid)?;
+            steps.push(match idx {
+                "" => Step::Each,
+                _ => Step::Index(idx.parse().map_err(|_| invalid())?),
+            });
+            rest = r;
+        }
+    }
+    Ok(steps)
+}
+
+/// Collect all strings within a value
+fn collect_strings<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        Value::String(s) => out.push(s),
+        Value::Array(arr) => arr.iter().for_each(|v| collect_strings(v, out)),
+        Value::Object(obj) => {
+            obj.values().for_each(|v| collect_strings(v, out))
+        }
+        _ => (),
+    }
+}
+
+/// Select strings from a value by path
+fn select<'a>(value: &'a Value, steps: &[Step], out: &mut Vec<&'a str>) {
+    let Some((step, rest)) = steps.split_first() else {
+        collect_strings(value, out);
+        return;
+    };
+    match (step, value) {
+        (Step::Key(key), Value::Object(obj)) => {
+            if let Some(v) = obj.get(key) {
+                select(v, rest, out);
+            }
+        }
+        (Step::Each, Value::Array(arr)) => {
+            arr.iter().for_each(|v| select(v, rest, out));
+        }
+        (Step::Index(i), Value::Array(arr)) => {
+            if let Some(v) = arr.get(*i) {
+                select(v, rest, out);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Extract string values from JSON or JSON Lines
+///
+/// Fields are selected by path, with members separated by `.` and arrays
+/// indexed by `[N]` or `[]` (all elements), e.g. `comments[].text`.  If no
+/// fields are selected, all strings are included.  Each value is separated
+/// by a blank line.
+pub fn extract_fields<R>(reader: R, fields: &[String]) -> Result<String>
+where
+    R: BufRead,
+{
+    let paths = if fields.is_empty() {
+        vec![Vec::new()]
+    } else {
+        fields
+            .iter()
+            .map(|f| parse_path(f))
+            .collect::<Result<Vec<_>>>()?
+    };
+    let mut text = String::new();
+    for value in Deserializer::from_reader(reader).into_iter::<Value>() {
+        let value = value?;
+        let mut strings = Vec::new();
+        for steps in &paths {
+            select(&value, steps, &mut strings);
+        }
+        for s in strings {
+            text.push_str(s.trim());
+            text.push_str("\n\n");
+        }
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn path() {
+        assert_eq!(
+            parse_path("a.b[].c[2]").unwrap(),
+            [
+                Step::Key("a".into()),
+                Step::Key("b".into()),
+                Step::Each,
+                Step::Key("c".into()),
+                Step::Index(2),
+            ]
+        );
+        assert!(parse_path("a[x]").is_err());
+        assert_eq!(parse_path("").unwrap(), []);
+        assert_eq!(
+            parse_path("[]..[0]").unwrap(),
+            [Step::Each, Step::Index(0)]
+        );
+        for path in ["a[", "a[-1]", "a[0]x", "[[]]"] {
+            assert!(
+                matches!(parse_path(path), Err(Error::Select(_))),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn mismatch() {
+        let json = "{\"a\": [\"x\", {\"b\": \"y\"}], \"c\": 5}";
+        for (path, text) in [
+            ("a[0]", "x\n\n"),
+            ("a[1].b", "y\n\n"),
+            ("a[2]", ""),
+            ("a.b", ""),
+            ("c", ""),
+            ("c[]", ""),
+            ("a[][]", ""),
+            ("z", ""),
+            ("a", "x\n\ny\n\n"),
+        ] {
+            let fields = [path.to_string()];
+            let t = extract_fields(json.as_bytes(), &fields).unwrap();
+            assert_eq!(t, text, "{path}");
+        }
+        assert_eq!(extract_fields("".as_bytes(), &[]).unwrap(), "");
+        assert!(extract_fields("{\"a\": ".as_bytes(), &[]).is_err());
+        assert!(extract_fields("[1] [".as_bytes(), &[]).is_err());
+        let fields = ["a[z]".to_string()];
+        assert!(extract_fields("{}".as_bytes(), &fields).is_err());
+    }
+
+    #[test]
+    fn extract() {
+        let jsonl = "{\"id\": 1, \"comments\": [{\"text\": \"Nice one\"}, \
+            {\"text\": \"Agreed\"}]}\n{\"id\": 2, \"comments\": []}\n\
+            {\"comments\": [{\"text\": \"Last\", \"by\": \"me\"}]}\n";
+        let fields = ["comments[].text".to_string()];
+        let text = extract_fields(jsonl.as_bytes(), &fields).unwrap();
+        assert_eq!(text, "Nice one\n\nAgreed\n\nLast\n\n");
+        let json = "[\"a\", {\"b\": [\"c\"]}, 5]";
+        let text = extract_fields(json.as_bytes(), &[]).unwrap();
+        assert_eq!(text, "a\n\nc\n\n");
+    }
+}
@@ -0,0 +1,223 @@
+use crate::kind::Kind;
+use crate::lex::make_word;
+use crate::tally::WordTally;
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Built-in word frequency source (CSV)
+const BUILTIN_CSV: &str = include_str!("../res/word_freq.csv");
+
+/// Static word frequency model
+static WORD_FREQ: LazyLock<WordFreq> = LazyLock::new(|| {
+    // the embedded source is validated by tests
+    WordFreq::from_csv(BUILTIN_CSV)
+        .unwrap_or_else(|e| panic!("Built-in word frequencies: {e}"))
+});
+
+/// Get built-in word frequency model
+///
+/// This contains only common English words, so any other word is treated as
+/// rare in the reference.
+pub fn builtin() -> &'static WordFreq {
+    &WORD_FREQ
+}
+
+/// Word frequency model of a reference corpus
+#[derive(Clone, Debug, Default)]
+pub struct WordFreq {
+    /// Count of each word (lowercase)
+    counts: HashMap<String, u64>,
+    /// Total count of all words
+    total: u64,
+}
+
+/// Keyword of a document
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Keyword {
+    /// Word
+    word: String,
+    /// Seen count in document
+    seen: usize,
+    /// Keyness score (log-likelihood)
+    score: f64,
+}
+
+impl WordFreq {
+    /// Create a new empty word frequency model
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a word frequency model from source (CSV lines)
+    ///
+    /// Each line is a word and its count (e.g. `the,69971`).  Blank lines
+    /// and `#` comments are ignored.
+    pub fn from_csv(src: &str) -> Result<Self> {
+        let mut freq = WordFreq::new();
+        for (i, line) in src.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || Error::Decode(format!("line {}: `{line}`", i + 1));
+            let (word, count) = line.split_once(',').ok_or_else(invalid)?;
+            let count = count.trim().parse().map_err(|_| invalid())?;
+            freq.add(word.trim(), count);
+        }
+        Ok(freq)
+    }
+
+    /// Make a word frequency model from a tally of a reference corpus
+    pub fn from_tally(tally: &WordTally) -> Self {
+        let mut freq = WordFreq::new();
+        for entry in tally.iter() {
+            if is_term(entry.kind()) {
+                freq.add(entry.word(), entry.seen() as u64);
+            }
+        }
+        freq
+    }
+
+    /// Add to the count of a word
+    pub fn add(&mut self, word: &str, count: u64) {
+        *self.counts.entry(make_word(word)).or_default() += count;
+        self.total += count;
+    }
+
+    /// Get the count of a word
+    pub fn count(&self, word: &str) -> u64 {
+        self.counts.get(&make_word(word)).copied().unwrap_or(0)
+    }
+
+    /// Get the total count of all words
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Extract keywords from a document tally
+    ///
+    /// Words used more often in the document than in the reference are
+    /// scored by log-likelihood keyness, and sorted from most to least
+    /// distinctive.  Symbols and numbers are not considered.
+    pub fn keywords(&self, tally: &WordTally) -> Vec<Keyword> {
+        let terms: Vec<_> =
+            tally.iter().filter(|e| is_term(e.kind())).collect();
+        let doc_total: usize = terms.iter().map(|e| e.seen()).sum();
+        let doc_total = doc_total as f64;
+        // words missing from the reference are counted as seen once
+        let ref_total = (self.total + 1) as f64;
+        let mut keywords: Vec<_> = terms
+            .into_iter()
+            .filter_map(|e| {
+                let a = e.seen() as f64;
+                let b = self.count(e.word()).max(1) as f64;
+                if a / doc_total <= b / ref_total {
+                    return None;
+                }
+                Some(Keyword {
+                    word: e.word().to_string(),
+                    seen: e.seen(),
+                    score: log_likelihood(a, b, doc_total, ref_total),
+                })
+            })
+            .collect();
+        keywords.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        keywords
+    }
+}
+
+impl Keyword {
+    /// Get word
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// Get seen count in document
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// Get keyness score (log-likelihood)
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+/// Check if a word kind can be a keyword
+fn is_term(kind: Kind) -> bool {
+    matches!(
+        kind,
+        Kind::Lexicon
+            | Kind::Foreign
+            | Kind::Acronym
+            | Kind::Proper
+            | Kind::Hashtag
+            | Kind::Unknown
+    )
+}
+
+/// Calculate log-likelihood (G²) of a word's counts in two corpora
+///
+/// * `a`: count in document
+/// * `b`: count in reference
+/// * `c`: total words in document
+/// * `d`: total words in reference
+fn log_likelihood(a: f64, b: f64, c: f64, d: f64) -> f64 {
+    let e1 = c * (a + b) / (c + d);
+    let e2 = d * (a + b) / (c + d);
+    2.0 * (a * (a / e1).ln() + b * (b / e2).ln())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keywords() {
+        assert!(builtin().count("the") > builtin().count("mother"));
+        let freq = WordFreq::from_csv("the,10\n The , 5").unwrap();
+        assert_eq!(freq.count("the"), 15);
+        let mut tally = WordTally::new();
+        let text = "A whale swam past our ship, and the captain saw it. \
+            Every sailor on the ship feared that whale; the captain did not. \
+            He had hunted the whale for years.";
+        tally.parse_text(text.as_bytes()).unwrap();
+        let keywords = builtin().keywords(&tally);
+        let words: Vec<_> = keywords.iter().take(3).map(|k| k.word()).collect();
+        assert_eq!(words, ["whale", "captain", "ship"]);
+        assert_eq!(keywords[0].seen(), 3);
+        assert!(keywords.iter().all(|k| k.word() != "and"));
+    }
+
+    #[test]
+    fn models() {
+        let freq = WordFreq::from_csv("# counts\n\nthe,10\nwhale,1\n").unwrap();
+        assert_eq!(freq.count("THE"), 10);
+        assert_eq!(freq.count("ship"), 0);
+        assert_eq!(freq.total(), 11);
+        for src in ["the", "the,", "the,-1", "the,1.5", "ok,1\nthe 10"] {
+            assert!(
+                matches!(WordFreq::from_csv(src), Err(Error::Decode(_))),
+                "{src:?}"
+            );
+        }
+        assert!(WordFreq::new().keywords(&WordTally::new()).is_empty());
+        let mut tally = WordTally::new();
+        tally
+            .parse_text("The whale, the whale! 42 # NASA".as_bytes())
+            .unwrap();
+        let reference = WordFreq::from_tally(&tally);
+        assert_eq!(reference.count("whale"), 2);
+        assert_eq!(reference.count("42"), 0);
+        assert_eq!(reference.total(), 5);
+        let keywords = freq.keywords(&tally);
+        let words: Vec<_> = keywords.iter().map(|k| k.word()).collect();
+        assert_eq!(words, ["whale", "NASA"]);
+    }
+}
@@ -1,5 +1,9 @@
+use crate::roman;
+use alloc::string::String;
+
 /// Word kind
 #[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Kind {
     /// In Lexicon
     Lexicon,
@@ -15,6 +19,20 @@ pub enum Kind {
     Acronym,
     /// Proper noun (name)
     Proper,
+    /// Date ("2024-05-01", "May 1st, 2024")
+    Date,
+    /// Time of day ("3:45 PM")
+    Time,
+    /// Web address (URL)
+    Url,
+    /// Email address
+    Email,
+    /// Hashtag ("#topic")
+    Hashtag,
+    /// Handle mention ("@name")
+    Mention,
+    /// Emoji, emoticon or other pictograph
+    Emoji,
     /// Symbol or letter (punctuation, etc.)
     Symbol,
     /// Unknown / Other
@@ -26,8 +44,8 @@ impl Kind {
     pub fn all() -> &'static [Self] {
         use Kind::*;
         &[
-            Lexicon, Foreign, Ordinal, Roman, Number, Acronym, Proper, Symbol,
-            Unknown,
+            Lexicon, Foreign, Ordinal, Roman, Number, Date, Time, Acronym,
+            Proper, Url, Email, Hashtag, Mention, Emoji, Symbol, Unknown,
         ]
     }
 
@@ -40,8 +58,15 @@ impl Kind {
             Ordinal => 'o',
             Roman => 'r',
             Number => 'n',
+            Date => 'd',
+            Time => 'c',
             Acronym => 'a',
             Proper => 'p',
+            Url => 'w',
+            Email => 'm',
+            Hashtag => 'h',
+            Mention => 't',
+            Emoji => 'e',
             Symbol => 's',
             Unknown => 'u',
         }
@@ -50,11 +75,25 @@ impl Kind {
 
 impl From<&str> for Kind {
     fn from(word: &str) -> Self {
-        if is_foreign(word) {
+        if is_url(word) {
+            Kind::Url
+        } else if is_email(word) {
+            Kind::Email
+        } else if is_hashtag(word) {
+            Kind::Hashtag
+        } else if is_mention(word) {
+            Kind::Mention
+        } else if is_emoji(word) {
+            Kind::Emoji
+        } else if date_prefix(word).is_some_and(|d| d.len() == word.len()) {
+            Kind::Date
+        } else if time_prefix(word).is_some_and(|t| t.len() == word.len()) {
+            Kind::Time
+        } else if is_foreign(word) {
             Kind::Foreign
         } else if is_ordinal_number(word) {
             Kind::Ordinal
-        } else if is_roman_numeral(word) {
+        } else if roman::is_valid(word) {
             Kind::Roman
         } else if is_number(word) {
             Kind::Number
@@ -70,6 +109,129 @@ impl From<&str> for Kind {
     }
 }
 
+/// URL schemes
+const URL_SCHEMES: &[&str] = &["http://", "https://", "ftp://", "mailto:"];
+
+/// Check if a word starts with a prefix (ASCII case insensitive)
+fn starts_with_ignore_case(word: &str, prefix: &str) -> bool {
+    word.len() >= prefix.len()
+        && word.as_bytes()[..prefix.len()]
+            .eq_ignore_ascii_case(prefix.as_bytes())
+}
+
+/// Check if a word is a URL ("https://example.com" or "www.example.com")
+pub fn is_url(word: &str) -> bool {
+    if word.chars().any(char::is_whitespace) {
+        return false;
+    }
+    for scheme in URL_SCHEMES {
+        if starts_with_ignore_case(word, scheme) {
+            let rest = &word[scheme.len()..];
+            return rest.chars().next().is_some_and(char::is_alphanumeric);
+        }
+    }
+    starts_with_ignore_case(word, "www.")
+        && word[4..].split('/').next().is_some_and(is_domain)
+}
+
+/// Check if a word is a domain name ("example.com")
+fn is_domain(word: &str) -> bool {
+    let mut labels = 0;
+    for label in word.split('.') {
+        if label.is_empty()
+            || label.starts_with('-')
+            || !label.chars().all(|c| c.is_alphanumeric() || c == '-')
+        {
+            return false;
+        }
+        labels += 1;
+    }
+    labels >= 2
+        && word
+            .rsplit('.')
+            .next()
+            .is_some_and(|tld| tld.chars().all(char::is_alphabetic))
+}
+
+/// Check if a word is an email address ("name@example.com")
+pub fn is_email(word: &str) -> bool {
+    match word.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !local.starts_with('.')
+                && !local.ends_with('.')
+                && local
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "._%+-".contains(c))
+                && is_domain(domain)
+        }
+        None => false,
+    }
+}
+
+/// Check if a character can be in a hashtag or handle
+pub(crate) fn is_handle_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Check if a word is a hashtag ("#topic")
+pub fn is_hashtag(word: &str) -> bool {
+    word.strip_prefix('#').is_some_and(|tag| {
+        tag.chars().all(is_handle_char) && tag.chars().any(char::is_alphabetic)
+    })
+}
+
+/// Check if a word is a handle mention ("@name")
+pub fn is_mention(word: &str) -> bool {
+    word.strip_prefix('@').is_some_and(|name| {
+        !name.is_empty() && name.chars().all(is_handle_char)
+    })
+}
+
+/// Emoticons (made of symbols)
+pub(crate) const EMOTICONS: &[&str] = &[
+    ":)", ":-)", ":(", ":-(", ";)", ";-)", ":D", ":-D", ":P", ":-P", ":p",
+    ":-p", ":O", ":-O", ":o", ":/", ":-/", ":|", ":-|", ":'(", "<3", "</3",
+    "^_^", "^^",
+];
+
+/// Check if a character is a pictograph (starting an emoji)
+pub(crate) fn is_pictograph(c: char) -> bool {
+    matches!(c,
+        '\u{231A}'..='\u{231B}'
+        | '\u{23E9}'..='\u{23F3}'
+        | '\u{23F8}'..='\u{23FA}'
+        | '\u{25FD}'..='\u{25FE}'
+        | '\u{2600}'..='\u{27BF}'
+        | '\u{2B1B}'..='\u{2B1C}'
+        | '\u{2B50}'
+        | '\u{2B55}'
+        | '\u{1F000}'..='\u{1FAFF}'
+    ) && !is_emoji_modifier(c)
+}
+
+/// Check if a character modifies the preceding emoji
+///
+/// These are variation selectors, skin tones, the combining keycap and tag
+/// characters (for subdivision flags).
+pub(crate) fn is_emoji_modifier(c: char) -> bool {
+    matches!(c,
+        '\u{FE0E}'..='\u{FE0F}'
+        | '\u{1F3FB}'..='\u{1F3FF}'
+        | '\u{20E3}'
+        | '\u{E0020}'..='\u{E007F}'
+    )
+}
+
+/// Check if a word is an emoji (or emoticon)
+fn is_emoji(word: &str) -> bool {
+    EMOTICONS.contains(&word)
+        || (word
+            .chars()
+            .any(|c| is_pictograph(c) || matches!(c, '\u{FE0F}' | '\u{20E3}'))
+            && !word.chars().any(char::is_alphabetic))
+}
+
 /// Check if a word is foreign (not English)
 fn is_foreign(word: &str) -> bool {
     word.chars().any(|c| c.is_alphabetic() && !c.is_ascii())
@@ -91,22 +253,253 @@ fn is_ordinal_number(w: &str) -> bool {
     false
 }
 
-/// Uppercase roman numerals
-const ROMAN_UPPER: &str = "IVXLCDM";
+/// Check if a word contains a number
+fn is_number(word: &str) -> bool {
+    word.chars().any(|c| c.is_ascii_digit())
+}
+
+/// Currency symbols (before a number)
+pub(crate) const CURRENCIES: &str = "$€£¥₹";
+
+/// Get the formatted number at the start of some text
+///
+/// A number has an optional sign and currency symbol, digits grouped by
+/// commas ("1,234") or underscores ("1_000"), an optional decimal part and
+/// an optional percent sign.
+pub(crate) fn number_prefix(text: &str) -> Option<&str> {
+    let b = text.as_bytes();
+    let digits =
+        |i: usize| b[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+    let mut i = 0;
+    for prefix in ["-", "+", "−"] {
+        if text.starts_with(prefix) {
+            i = prefix.len();
+            break;
+        }
+    }
+    if let Some(c) = text[i..].chars().next()
+        && CURRENCIES.contains(c)
+    {
+        i += c.len_utf8();
+    }
+    let first = digits(i);
+    if first == 0 {
+        return None;
+    }
+    i += first;
+    if first <= 3 {
+        while b.get(i) == Some(&b',') && digits(i + 1) == 3 {
+            i += 4;
+        }
+    }
+    while b.get(i) == Some(&b'_') && digits(i + 1) > 0 {
+        i += 1 + digits(i + 1);
+    }
+    if b.get(i) == Some(&b'.') && digits(i + 1) > 0 {
+        i += 1 + digits(i + 1);
+    }
+    if b.get(i) == Some(&b'%') {
+        i += 1;
+    }
+    Some(&text[..i])
+}
+
+/// Get the value of a formatted number ("-1,234.5", "$20", "50%")
+///
+/// Currency and percent signs are ignored, so "50%" is 50.
+pub fn number_value(word: &str) -> Option<f64> {
+    if number_prefix(word)? != word {
+        return None;
+    }
+    let mut digits = String::with_capacity(word.len());
+    for c in word.chars() {
+        match c {
+            '0'..='9' | '.' => digits.push(c),
+            '-' | '−' => digits.push('-'),
+            _ => (),
+        }
+    }
+    digits.parse().ok()
+}
+
+/// Months of the year
+const MONTHS: &[&str] = &[
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
 
-/// Lowercase roman numerals
-const ROMAN_LOWER: &str = "ivxlcdm";
+/// Time of day suffixes (ante / post meridiem)
+const MERIDIEMS: &[&str] =
+    &["a.m.", "p.m.", "A.M.", "P.M.", "am", "pm", "AM", "PM"];
 
-/// Check if a string is a romal numeral
-fn is_roman_numeral(word: &str) -> bool {
-    !word.is_empty()
-        && (word.chars().all(|c| ROMAN_UPPER.contains(c))
-            || word.chars().all(|c| ROMAN_LOWER.contains(c)))
+/// Count ASCII digits in text, starting at byte `i`
+fn count_digits(text: &str, i: usize) -> usize {
+    text.as_bytes()
+        .get(i..)
+        .map_or(0, |b| b.iter().take_while(|c| c.is_ascii_digit()).count())
 }
 
-/// Check if a word contains a number
-fn is_number(word: &str) -> bool {
-    word.chars().any(|c| c.is_ascii_digit())
+/// Parse a number of `min..=max` digits at byte `i`
+///
+/// Returns the value and number of digits.
+fn parse_digits(
+    text: &str,
+    i: usize,
+    min: usize,
+    max: usize,
+) -> Option<(u32, usize)> {
+    let n = count_digits(text, i);
+    if n < min || n > max {
+        return None;
+    }
+    Some((text[i..i + n].parse().ok()?, n))
+}
+
+/// Check if text has a character at byte `i`
+fn has_char(text: &str, i: usize, c: char) -> bool {
+    text.get(i..).is_some_and(|t| t.starts_with(c))
+}
+
+/// Get the length of a capitalized month name (or abbreviation) at byte `i`
+fn month_len(text: &str, i: usize) -> Option<usize> {
+    let t = text.get(i..)?;
+    let len = t.chars().take_while(char::is_ascii_alphabetic).count();
+    let word = &t[..len];
+    for month in MONTHS {
+        if word == *month
+            || (len == 3 || word == "Sept") && month.starts_with(word)
+        {
+            // abbreviations may have a dot
+            let dot = len < month.len() && t[len..].starts_with('.');
+            return Some(len + usize::from(dot));
+        }
+    }
+    None
+}
+
+/// Get the length of a day of the month (with optional ordinal suffix)
+fn day_len(text: &str, i: usize) -> Option<usize> {
+    let (day, n) = parse_digits(text, i, 1, 2)?;
+    if !(1..=31).contains(&day) {
+        return None;
+    }
+    let suffix = text
+        .get(i + n..)
+        .and_then(|t| {
+            ["st", "nd", "rd", "th"].iter().find(|s| t.starts_with(*s))
+        })
+        .map_or(0, |s| s.len());
+    Some(n + suffix)
+}
+
+/// Get the date at the start of some text
+///
+/// Recognized formats are "2024-05-01", "5/1/2024", "May 1st, 2024",
+/// "1 May 2024" and "May 1".
+pub fn date_prefix(text: &str) -> Option<&str> {
+    let len = iso_date_len(text)
+        .or_else(|| slash_date_len(text))
+        .or_else(|| month_date_len(text))
+        .or_else(|| day_date_len(text))?;
+    Some(&text[..len])
+}
+
+/// Get the length of an ISO 8601 date ("2024-05-01")
+fn iso_date_len(text: &str) -> Option<usize> {
+    parse_digits(text, 0, 4, 4)?;
+    let (month, _) = parse_digits(text, 5, 2, 2)?;
+    let (day, _) = parse_digits(text, 8, 2, 2)?;
+    (has_char(text, 4, '-')
+        && has_char(text, 7, '-')
+        && (1..=12).contains(&month)
+        && (1..=31).contains(&day))
+    .then_some(10)
+}
+
+/// Get the length of a date with slashes ("5/1/2024")
+fn slash_date_len(text: &str) -> Option<usize> {
+    let (_, a) = parse_digits(text, 0, 1, 2)?;
+    if !has_char(text, a, '/') {
+        return None;
+    }
+    let (_, b) = parse_digits(text, a + 1, 1, 2)?;
+    let i = a + b + 1;
+    if !has_char(text, i, '/') {
+        return None;
+    }
+    let y = count_digits(text, i + 1);
+    matches!(y, 2 | 4).then_some(i + 1 + y)
+}
+
+/// Get the length of a year (after a space), or zero
+fn year_len(text: &str, i: usize) -> usize {
+    match parse_digits(text, i + 1, 4, 4) {
+        Some(_) if has_char(text, i, ' ') => 5,
+        _ => 0,
+    }
+}
+
+/// Get the length of a month-first date ("May 1st, 2024")
+fn month_date_len(text: &str) -> Option<usize> {
+    let mut i = month_len(text, 0)?;
+    if !has_char(text, i, ' ') {
+        return None;
+    }
+    i += 1 + day_len(text, i + 1)?;
+    if has_char(text, i, ',') && year_len(text, i + 1) > 0 {
+        i += 1;
+    }
+    Some(i + year_len(text, i))
+}
+
+/// Get the length of a day-first date ("1 May 2024")
+fn day_date_len(text: &str) -> Option<usize> {
+    let mut i = day_len(text, 0)?;
+    if !has_char(text, i, ' ') {
+        return None;
+    }
+    i += 1 + month_len(text, i + 1)?;
+    Some(i + year_len(text, i))
+}
+
+/// Get the time of day at the start of some text
+///
+/// Recognized formats are "15:30", "3:45:10", "3:45 PM" and "3pm".
+pub fn time_prefix(text: &str) -> Option<&str> {
+    let (hour, mut i) = parse_digits(text, 0, 1, 2)?;
+    let mut minutes = false;
+    if has_char(text, i, ':') {
+        let (min, _) = parse_digits(text, i + 1, 2, 2)?;
+        if min > 59 {
+            return None;
+        }
+        i += 3;
+        if has_char(text, i, ':')
+            && let Some((sec, _)) = parse_digits(text, i + 1, 2, 2)
+            && sec <= 59
+        {
+            i += 3;
+        }
+        minutes = true;
+    }
+    let space = usize::from(has_char(text, i, ' '));
+    match MERIDIEMS.iter().find(|m| text[i + space..].starts_with(*m)) {
+        Some(m) if (1..=12).contains(&hour) => {
+            Some(&text[..i + space + m.len()])
+        }
+        _ if minutes && hour <= 23 => Some(&text[..i]),
+        _ => None,
+    }
 }
 
 /// Check if a word is an acronym / initialism
@@ -123,3 +516,145 @@ fn is_probably_proper(word: &str) -> bool {
         _ => false,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn kinds() {
+        use Kind::*;
+        let kinds = [
+            ("https://example.com/a?b=c", Url),
+            ("www.example.com", Url),
+            ("www.example.com.", Unknown),
+            ("name@example.com", Email),
+            ("name@localhost", Unknown),
+            ("#rust_lang", Hashtag),
+            ("#123", Number),
+            ("@douglau", Mention),
+            ("👍🏽", Emoji),
+            (":-)", Emoji),
+            ("2024-05-01", Date),
+            ("2024-13-01", Number),
+            ("May", Proper),
+            ("3:45", Time),
+            ("25:00", Number),
+            ("café", Foreign),
+            ("21st", Ordinal),
+            ("st", Unknown),
+            ("XIV", Roman),
+            ("1,234", Number),
+            ("1,23", Number),
+            ("NASA", Acronym),
+            ("U.S.", Acronym),
+            ("Paris", Proper),
+            ("&", Symbol),
+            ("blorf", Unknown),
+        ];
+        for (word, kind) in kinds {
+            assert_eq!(Kind::from(word), kind, "{word}");
+        }
+    }
+
+    #[test]
+    fn codes() {
+        let mut codes: Vec<char> =
+            Kind::all().iter().map(|k| k.code()).collect();
+        assert_eq!(codes.len(), Kind::all().len());
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), Kind::all().len());
+    }
+
+    #[test]
+    fn numbers() {
+        let numbers = [
+            ("1,234", Some(1234.0)),
+            ("-1,234.5", Some(-1234.5)),
+            ("$20", Some(20.0)),
+            ("50%", Some(50.0)),
+            ("1_000_000", Some(1_000_000.0)),
+            ("1,23", None),
+            ("1234,567", None),
+            ("12.", None),
+            ("abc", None),
+        ];
+        for (word, value) in numbers {
+            assert_eq!(number_value(word), value, "{word}");
+        }
+        assert_eq!(number_prefix("1,23"), Some("1"));
+        assert_eq!(number_prefix("12.5%."), Some("12.5%"));
+        assert_eq!(number_prefix("-x"), None);
+    }
+
+    #[test]
+    fn dates() {
+        let dates = [
+            ("2024-05-01 at noon", Some("2024-05-01")),
+            ("5/1/2024.", Some("5/1/2024")),
+            ("5/1/202", None),
+            ("May 1st, 2024!", Some("May 1st, 2024")),
+            ("Sept. 30", Some("Sept. 30")),
+            ("1 May 2024", Some("1 May 2024")),
+            ("May 3, then", Some("May 3")),
+            ("may 3", None),
+            ("May 32", None),
+            ("Mayday 3", None),
+            ("2024-5-01", None),
+        ];
+        for (text, date) in dates {
+            assert_eq!(date_prefix(text), date, "{text}");
+        }
+    }
+
+    #[test]
+    fn times() {
+        let times = [
+            ("15:30 today", Some("15:30")),
+            ("3:45:10", Some("3:45:10")),
+            ("3:45 PM", Some("3:45 PM")),
+            ("3pm", Some("3pm")),
+            ("11 a.m.", Some("11 a.m.")),
+            ("13pm", None),
+            ("3:60", None),
+            ("24:00", None),
+            ("3 o'clock", None),
+        ];
+        for (text, time) in times {
+            assert_eq!(time_prefix(text), time, "{text}");
+        }
+    }
+
+    #[test]
+    fn web() {
+        let urls = [
+            ("http://example.com", true),
+            ("HTTPS://EXAMPLE.COM", true),
+            ("mailto:name@example.com", true),
+            ("www.example.co.uk/path", true),
+            ("www.example.com.", false),
+            ("www.example", false),
+            ("https://", false),
+            ("http://a b", false),
+            ("example.com", false),
+        ];
+        for (word, url) in urls {
+            assert_eq!(is_url(word), url, "{word}");
+        }
+        let emails = [
+            ("first.last+tag@example.com", true),
+            (".name@example.com", false),
+            ("name.@example.com", false),
+            ("@example.com", false),
+            ("name@example.c0m", false),
+            ("name@-example.com", false),
+        ];
+        for (word, email) in emails {
+            assert_eq!(is_email(word), email, "{word}");
+        }
+        assert!(is_hashtag("#a1") && !is_hashtag("#") && !is_hashtag("#a-b"));
+        assert!(is_mention("@_x") && !is_mention("@") && !is_mention("@a.b"));
+    }
+}
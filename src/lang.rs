@@ -0,0 +1,229 @@
+use crate::Result;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+use std::sync::LazyLock;
+
+/// Number of trigrams in a profile
+const PROFILE_LEN: usize = 300;
+
+/// Minimum number of letters needed to detect a language
+const MIN_LETTERS: usize = 30;
+
+/// Maximum number of bytes read from a document
+const MAX_SAMPLE: usize = 64 * 1024;
+
+/// Sample texts for building profiles
+const SAMPLES: &[(Language, &str)] = &[
+    (Language::Dutch, include_str!("../res/lang/nl.txt")),
+    (Language::English, include_str!("../res/lang/en.txt")),
+    (Language::French, include_str!("../res/lang/fr.txt")),
+    (Language::German, include_str!("../res/lang/de.txt")),
+    (Language::Italian, include_str!("../res/lang/it.txt")),
+    (Language::Portuguese, include_str!("../res/lang/pt.txt")),
+    (Language::Spanish, include_str!("../res/lang/es.txt")),
+];
+
+/// Static trigram profiles
+static PROFILES: LazyLock<Vec<(Language, Profile)>> = LazyLock::new(|| {
+    SAMPLES
+        .iter()
+        .map(|(lang, text)| (*lang, Profile::from_text(text)))
+        .collect()
+});
+
+/// Natural language
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Language {
+    /// Dutch
+    Dutch,
+    /// English
+    English,
+    /// French
+    French,
+    /// German
+    German,
+    /// Italian
+    Italian,
+    /// Portuguese
+    Portuguese,
+    /// Spanish
+    Spanish,
+}
+
+/// Character trigram profile (ranked by frequency)
+struct Profile {
+    /// Rank of each trigram
+    ranks: HashMap<[char; 3], usize>,
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{self:?}")
+    }
+}
+
+impl Language {
+    /// Get all languages
+    pub fn all() -> &'static [Self] {
+        use Language::*;
+        &[Dutch, English, French, German, Italian, Portuguese, Spanish]
+    }
+}
+
+impl Profile {
+    /// Build a profile from text
+    fn from_text(text: &str) -> Self {
+        let mut counts: HashMap<[char; 3], usize> = HashMap::new();
+        for word in text.split(|c: char| !c.is_alphabetic()) {
+            if word.is_empty() {
+                continue;
+            }
+            let chars: Vec<char> = std::iter::once(' ')
+                .chain(word.chars().flat_map(char::to_lowercase))
+                .chain(std::iter::once(' '))
+                .collect();
+            for tri in chars.windows(3) {
+                *counts.entry([tri[0], tri[1], tri[2]]).or_default() += 1;
+            }
+        }
+        let mut trigrams: Vec<_> = counts.into_iter().collect();
+        trigrams.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let ranks = trigrams
+            .into_iter()
+            .take(PROFILE_LEN)
+            .enumerate()
+            .map(|(rank, (tri, _n))| (tri, rank))
+            .collect();
+        Profile { ranks }
+    }
+
+    /// Get "out-of-place" distance to another profile
+    fn distance(&self, other: &Profile) -> usize {
+        self.ranks
+            .iter()
+            .map(|(tri, rank)| {
+                other
+                    .ranks
+                    .get(tri)
+                    .map_or(PROFILE_LEN, |r| r.abs_diff(*rank))
+            })
+            .sum()
+    }
+}
+
+/// Detect the language of a text
+///
+/// Character trigrams of the text (up to 64 KiB) are compared with profiles
+/// of each known language.  Returns `None` if the text has too few letters,
+/// or none of its letters are in the Latin alphabet.
+pub fn detect_language<R>(reader: R) -> Result<Option<Language>>
+where
+    R: BufRead,
+{
+    let mut text = String::new();
+    for line in reader.lines() {
+        text.push_str(&line?);
+        text.push('\n');
+        if text.len() >= MAX_SAMPLE {
+            break;
+        }
+    }
+    let letters = text.chars().filter(|c| c.is_alphabetic()).count();
+    let latin = text
+        .chars()
+        .filter(|c| c.is_alphabetic() && (*c as u32) < 0x250)
+        .count();
+    if letters < MIN_LETTERS || latin * 2 < letters {
+        return Ok(None);
+    }
+    let profile = Profile::from_text(&text);
+    Ok(PROFILES
+        .iter()
+        .min_by_key(|(_lang, p)| profile.distance(p))
+        .map(|(lang, _p)| *lang))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Detect the language of a string
+    fn detect(text: &str) -> Option<Language> {
+        detect_language(text.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn detect_languages() {
+        let samples = [
+            (
+                Language::English,
+                "The quick brown fox jumps over the lazy dog, and then it \
+                runs away into the woods.",
+            ),
+            (
+                Language::French,
+                "Le renard brun rapide saute par-dessus le chien paresseux, \
+                puis il s'enfuit dans les bois.",
+            ),
+            (
+                Language::German,
+                "Der schnelle braune Fuchs springt über den faulen Hund und \
+                läuft dann in den Wald.",
+            ),
+            (
+                Language::Spanish,
+                "El rápido zorro marrón salta sobre el perro perezoso y luego \
+                se escapa hacia el bosque.",
+            ),
+            (
+                Language::Italian,
+                "La volpe marrone veloce salta sopra il cane pigro e poi \
+                scappa nel bosco.",
+            ),
+            (
+                Language::Portuguese,
+                "A rápida raposa marrom pula sobre o cão preguiçoso e depois \
+                foge para a floresta.",
+            ),
+            (
+                Language::Dutch,
+                "De snelle bruine vos springt over de luie hond en rent dan \
+                weg naar het bos.",
+            ),
+        ];
+        for (lang, text) in samples {
+            assert_eq!(detect(text), Some(lang), "{text}");
+        }
+        assert_eq!(detect("Hello there"), None);
+        assert_eq!(
+            detect("Быстрая коричневая лиса прыгает через собаку."),
+            None
+        );
+    }
+
+    #[test]
+    fn profiles() {
+        assert_eq!(detect(""), None);
+        assert_eq!(detect(&"1234567890 ".repeat(10)), None);
+        // mostly non-Latin text, with a few Latin words
+        let text = "Быстрая коричневая лиса прыгает через ленивую собаку \
+            the fox";
+        assert_eq!(detect(text), None);
+        let profile = Profile::from_text("Aaa aaa, AAA!");
+        assert_eq!(profile.ranks.len(), 3);
+        assert_eq!(profile.ranks.get(&[' ', 'a', 'a']), Some(&0));
+        assert_eq!(profile.distance(&profile), 0);
+        let other = Profile::from_text("bbb");
+        assert_eq!(profile.distance(&other), 3 * PROFILE_LEN);
+        assert!(Profile::from_text("").ranks.is_empty());
+        let long = Profile::from_text(SAMPLES[0].1);
+        assert_eq!(long.ranks.len(), PROFILE_LEN);
+        assert!(detect_language(&[b'a', 0xFF][..]).is_err());
+        let names: Vec<_> =
+            Language::all().iter().map(|l| l.to_string()).collect();
+        assert_eq!(names[1], "English");
+        assert_eq!(names.len(), SAMPLES.len());
+    }
+}
@@ -0,0 +1,289 @@
+use crate::Result;
+use std::io::BufRead;
+
+/// Commands with all arguments dropped
+const DROP_COMMANDS: &[&str] = &[
+    "addtolength",
+    "autoref",
+    "bibliography",
+    "bibliographystyle",
+    "cite",
+    "citep",
+    "citet",
+    "cref",
+    "Cref",
+    "DeclareMathOperator",
+    "documentclass",
+    "eqref",
+    "hspace",
+    "include",
+    "includegraphics",
+    "input",
+    "label",
+    "newcommand",
+    "newenvironment",
+    "pageref",
+    "pagestyle",
+    "providecommand",
+    "ref",
+    "renewcommand",
+    "setcounter",
+    "setlength",
+    "thispagestyle",
+    "url",
+    "usepackage",
+    "vspace",
+];
+
+/// Environments with content dropped
+const DROP_ENVIRONMENTS: &[&str] = &[
+    "align",
+    "align*",
+    "alignat",
+    "alignat*",
+    "comment",
+    "displaymath",
+    "eqnarray",
+    "eqnarray*",
+    "equation",
+    "equation*",
+    "flalign",
+    "flalign*",
+    "gather",
+    "gather*",
+    "lstlisting",
+    "math",
+    "minted",
+    "multline",
+    "multline*",
+    "tikzpicture",
+    "verbatim",
+];
+
+/// Push newlines contained in skipped text (to keep line numbers)
+fn push_newlines(out: &mut String, skipped: &str) {
+    for _ in skipped.matches('\n') {
+        out.push('\n');
+    }
+}
+
+/// Skip past an end delimiter
+fn skip_past<'a>(rest: &'a str, end: &str, out: &mut String) -> &'a str {
+    match rest.find(end) {
+        Some(i) => {
+            push_newlines(out, &rest[..i]);
+            &rest[i + end.len()..]
+        }
+        None => {
+            push_newlines(out, rest);
+            ""
+        }
+    }
+}
+
+/// Split a group argument (`{...}` or `[...]`) from the start of text
+fn split_group(rest: &str) -> Option<(&str, &str)> {
+    let trimmed = rest.trim_start_matches([' ', '\t']);
+    let (open, close) = match trimmed.chars().next()? {
+        '{' => ('{', '}'),
+        '[' => ('[', ']'),
+        _ => return None,
+    };
+    let mut depth = 0;
+    for (i, c) in trimmed.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some((&trimmed[1..i], &trimmed[i + 1..]));
+            }
+        }
+    }
+    None
+}
+
+/// Skip all group arguments
+fn skip_groups<'a>(mut rest: &'a str, out: &mut String) -> &'a str {
+    while let Some((group, r)) = split_group(rest) {
+        push_newlines(out, group);
+        rest = r;
+    }
+    rest
+}
+
+/// Handle a command (starting with `\`)
+fn command<'a>(rest: &'a str, out: &mut String) -> &'a str {
+    let after = &rest[1..];
+    let Some(c) = after.chars().next() else {
+        return "";
+    };
+    let next = &after[c.len_utf8()..];
+    if !c.is_ascii_alphabetic() {
+        return match c {
+            '(' => skip_past(next, "\\)", out),
+            '[' => skip_past(next, "\\]", out),
+            '\\' => {
+                out.push('\n');
+                next
+            }
+            ',' | ';' | ':' | ' ' => {
+                out.push(' ');
+                next
+            }
+            '%' | '&' | '$' | '#' | '_' | '{' | '}' => {
+                out.push(c);
+                next
+            }
+            // accents, etc.
+            _ => next,
+        };
+    }
+    let len = after
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(after.len());
+    let name = &after[..len];
+    let rest = after[len..].strip_prefix('*').unwrap_or(&after[len..]);
+    match name {
+        "begin" => {
+            let Some((env, rest)) = split_group(rest) else {
+                return rest;
+            };
+            if DROP_ENVIRONMENTS.contains(&env) {
+                skip_past(rest, &format!("\\end{{{env}}}"), out)
+            } else {
+                skip_groups(rest, out)
+            }
+        }
+        "end" => split_group(rest).map_or(rest, |(_env, rest)| rest),
+        "href" => split_group(rest).map_or(rest, |(_url, rest)| rest),
+        _ if DROP_COMMANDS.contains(&name) => skip_groups(rest, out),
+        _ => {
+            // skip optional arguments, keeping the rest
+            let mut rest = rest;
+            while rest.starts_with('[')
+                && let Some((opt, r)) = split_group(rest)
+            {
+                push_newlines(out, opt);
+                rest = r;
+            }
+            rest
+        }
+    }
+}
+
+/// Strip LaTeX markup from text
+fn strip(src: &str, out: &mut String) {
+    let mut rest = src;
+    while let Some(c) = rest.chars().next() {
+        rest = match c {
+            '%' => match rest.find('\n') {
+                Some(i) => &rest[i..],
+                None => "",
+            },
+            '$' => {
+                let delim = if rest.starts_with("$$") { "$$" } else { "$" };
+                skip_past(&rest[delim.len()..], delim, out)
+            }
+            '\\' => command(rest, out),
+            '{' | '}' => &rest[1..],
+            '~' => {
+                out.push(' ');
+                &rest[1..]
+            }
+            _ => {
+                out.push(c);
+                &rest[c.len_utf8()..]
+            }
+        };
+    }
+}
+
+/// Extract prose text from LaTeX source
+///
+/// The preamble, comments, math and most command arguments are removed,
+/// keeping the arguments of text commands (`\emph`, `\section`, etc).
+/// Line numbers match the source.
+pub fn extract_text<R>(mut reader: R) -> Result<String>
+where
+    R: BufRead,
+{
+    let mut src = String::new();
+    reader.read_to_string(&mut src)?;
+    let mut out = String::with_capacity(src.len());
+    let begin = "\\begin{document}";
+    let start = match src.find(begin) {
+        Some(i) => {
+            push_newlines(&mut out, &src[..i]);
+            i + begin.len()
+        }
+        None => 0,
+    };
+    let end = src[start..]
+        .find("\\end{document}")
+        .map_or(src.len(), |i| start + i);
+    strip(&src[start..end], &mut out);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extract() {
+        let tex = "\\documentclass{article}\n\\usepackage{amsmath}\n\
+            \\begin{document}\n\
+            \\section{Intro} % comment\n\
+            We \\emph{show} that $x^2 > 0$ holds~\\cite{knuth}.\n\
+            \\begin{equation}\n  E = mc^2\n\\end{equation}\n\
+            See \\href{https://x.io}{the site} and 50\\% more.\n\
+            \\end{document}\n";
+        let text = extract_text(tex.as_bytes()).unwrap();
+        assert_eq!(
+            text,
+            "\n\n\nIntro \nWe show that  holds .\n\n\n\n\
+            See the site and 50% more.\n"
+        );
+        let tex = "\\end{document}\\begin{document}Late";
+        assert_eq!(extract_text(tex.as_bytes()).unwrap(), "Late");
+    }
+
+    #[test]
+    fn commands() {
+        for (tex, text) in [
+            ("", ""),
+            ("a\\", "a"),
+            ("a\\\\b", "a\nb"),
+            ("a\\,b\\ c", "a b c"),
+            ("caf\\'e \\#1 \\_x", "cafe #1 _x"),
+            ("\\(x\\) and \\[\ny\n\\] z", " and \n\n z"),
+            ("$$a$$b $c\nd", "b \n"),
+            ("\\section*[Short]{Long \\textbf{bold}}", "Long bold"),
+            ("\\item[a\nb] one", "\n one"),
+            ("\\begin{itemize}\\item One\\end{itemize}", " One"),
+            ("\\begin{verbatim}x\ny", "\n"),
+            ("\\begin{minipage}{5cm}Box\\end{minipage}", "Box"),
+            ("\\begin {tabular}", ""),
+            ("\\cite[p.~3]{a}, \\ref{b} \\url{c}.", ",  ."),
+            ("\\href{https://x.io", "https://x.io"),
+            ("\\textit{unclosed", "unclosed"),
+            ("100%\nmore", "100\nmore"),
+        ] {
+            let mut out = String::new();
+            strip(tex, &mut out);
+            assert_eq!(out, text, "{tex:?}");
+        }
+    }
+
+    #[test]
+    fn document() {
+        for (tex, text) in [
+            ("Just text", "Just text"),
+            ("\\begin{document}\ntext\n\\end{document}\nmore", "\ntext\n"),
+        ] {
+            assert_eq!(extract_text(tex.as_bytes()).unwrap(), text, "{tex:?}");
+        }
+        assert!(extract_text(&[b'\\', 0xFF][..]).is_err());
+    }
+}
@@ -1,27 +1,56 @@
-use crate::word::Lexeme;
-use std::collections::HashMap;
-use std::sync::LazyLock;
+#[cfg(feature = "std")]
+use crate::cache;
+#[cfg(feature = "std")]
+use crate::rhyme::rhyme_key;
+#[cfg(feature = "std")]
+use crate::suggest::{BkTree, Suggestion};
+use crate::word::{Lexeme, WordClass};
+use crate::{Error, Result};
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::ops::{Bound, RangeBounds};
+#[cfg(feature = "std")]
+use std::sync::{LazyLock, OnceLock};
+
+/// Built-in lexicon source (CSV)
+const BUILTIN_CSV: &str = include_str!("../res/english.csv");
 
 /// Static lexicon
-static LEXICON: LazyLock<Lexicon> = LazyLock::new(make_builtin);
+#[cfg(feature = "std")]
+static LEXICON: LazyLock<Arc<Lexicon>> = LazyLock::new(make_builtin);
 
 /// Make builtin lexicon
-fn make_builtin() -> Lexicon {
-    let mut lex = Lexicon::default();
-    for (i, line) in include_str!("../res/english.csv").lines().enumerate() {
-        match Lexeme::try_from(line) {
-            Ok(word) => lex.insert(word),
-            Err(_) => panic!("Bad word on line {}: `{line}`", i + 1),
-        }
-    }
-    lex
+#[cfg(feature = "std")]
+fn make_builtin() -> Arc<Lexicon> {
+    // the embedded source is validated by tests
+    let lex = Lexicon::from_csv(BUILTIN_CSV)
+        .unwrap_or_else(|e| panic!("Built-in lexicon: {e}"));
+    Arc::new(lex)
 }
 
 /// Get built-in lexicon
+#[cfg(feature = "std")]
 pub fn builtin() -> &'static Lexicon {
     &LEXICON
 }
 
+/// Get a shared handle to the built-in lexicon
+#[cfg(feature = "std")]
+pub fn builtin_shared() -> Arc<Lexicon> {
+    Arc::clone(&LEXICON)
+}
+
+/// Get built-in lexicon source (CSV lines)
+///
+/// Without the `std` feature, load it with `Lexicon::from_csv`.
+pub fn builtin_csv() -> &'static str {
+    BUILTIN_CSV
+}
+
 /// Check if a character is an apostrophe
 ///
 /// Unicode has several different apostrophes:
@@ -33,33 +62,134 @@ pub fn is_apostrophe(c: char) -> bool {
     c == '\u{0027}' || c == '\u{02BC}' || c == '\u{2019}' || c == '\u{FF07}'
 }
 
+/// Normalize characters of a word (lowercase, with ASCII apostrophes)
+fn normalize(word: &str) -> impl Iterator<Item = char> + '_ {
+    word.chars()
+        .flat_map(|c| if is_apostrophe(c) { '\'' } else { c }.to_lowercase())
+}
+
 /// Make word to check lexicon
 pub fn make_word(word: &str) -> String {
-    let mut w = String::with_capacity(word.len());
-    for c in word.chars() {
-        if is_apostrophe(c) {
-            w.push('\'');
-        } else {
-            for cl in c.to_lowercase() {
-                w.push(cl);
-            }
-        }
+    normalize(word).collect()
+}
+
+/// FNV-1a hash of a normalized word
+fn hash_word(word: &str) -> u32 {
+    let fnv =
+        |hash: u32, b: u8| (hash ^ u32::from(b)).wrapping_mul(0x0100_0193);
+    let hash = 0x811c_9dc5;
+    if word.is_ascii() {
+        return word.bytes().map(|b| b.to_ascii_lowercase()).fold(hash, fnv);
+    }
+    let mut buf = [0; 4];
+    normalize(word).fold(hash, |hash, c| {
+        c.encode_utf8(&mut buf).bytes().fold(hash, fnv)
+    })
+}
+
+/// Check if a word matches a normalized form
+fn matches_form(word: &str, form: &str) -> bool {
+    if word.is_ascii() {
+        word.eq_ignore_ascii_case(form)
+    } else {
+        normalize(word).eq(form.chars())
     }
-    w
 }
 
+/// End of a link chain / empty hash slot
+const NONE: u32 = u32::MAX;
+
+/// Minimum hash table size
+const MIN_TABLE: usize = 64;
+
+/// Binary cache file magic number
+#[cfg(feature = "std")]
+const CACHE_MAGIC: &[u8] = b"booky-lexicon";
+
+/// Binary cache format version
+#[cfg(feature = "std")]
+const CACHE_VERSION: u32 = 1;
+
+/// Word form record
+#[derive(Clone, Copy)]
+struct Form {
+    /// Start offset in form text
+    start: u32,
+    /// End offset in form text
+    end: u32,
+    /// First entry link
+    first: u32,
+    /// Last entry link
+    last: u32,
+}
+
+/// Policy for merging lexemes with the same lemma and word class
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MergePolicy {
+    /// Keep the existing lexeme
+    #[default]
+    KeepFirst,
+    /// Replace the existing lexeme
+    KeepLast,
+    /// Keep both lexemes (unless identical)
+    KeepBoth,
+}
+
+/// Sorted form index: form indices in sorted order
+#[cfg(all(feature = "std", not(feature = "fst")))]
+type SortedForms = Vec<u32>;
+
+/// Sorted form index: map of form to form index
+#[cfg(feature = "fst")]
+type SortedForms = fst::Map<Vec<u8>>;
+
 /// Lexicon of words
+///
+/// Word forms are packed into one text buffer, and found using an
+/// open-addressing hash table of form indices.  Lexeme indices for each form
+/// are chained through a flat table of links.  For prefix and range queries,
+/// a sorted index of forms is built on first use.
+///
+/// A lexicon is `Send` and `Sync`, so one can be shared between threads
+/// with an `Arc` (see `WordTally::with_lexicon`).
 #[derive(Default, Clone)]
 pub struct Lexicon {
     /// All lexemes
     words: Vec<Lexeme>,
+    /// Form text (normalized, concatenated)
+    text: String,
     /// All word forms
-    forms: HashMap<String, Vec<usize>>,
+    forms: Vec<Form>,
+    /// Entry links (lexeme index, next link)
+    links: Vec<(u32, u32)>,
+    /// Hash table slots (hash, form index)
+    table: Vec<(u32, u32)>,
+    /// Owned word forms, for `forms` (built on first use)
+    #[cfg(feature = "std")]
+    owned: OnceLock<Vec<String>>,
+    /// Suggestion index (built on first use)
+    #[cfg(feature = "std")]
+    suggest: OnceLock<BkTree>,
+    /// Form indices in sorted order (built on first use)
+    #[cfg(feature = "std")]
+    sorted: OnceLock<SortedForms>,
+    /// Anagram index: (sorted lemma letters, lexeme index), built on first use
+    #[cfg(feature = "std")]
+    anagrams: OnceLock<Vec<(String, u32)>>,
+    /// Rhyme index: (rhyme key, form index), built on first use
+    #[cfg(feature = "std")]
+    rhymes: OnceLock<Vec<(String, u32)>>,
 }
 
+/// Lexicons must be shareable between threads
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Lexicon>();
+};
+
 impl IntoIterator for Lexicon {
     type Item = Lexeme;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
+    type IntoIter = vec::IntoIter<Self::Item>;
 
     fn into_iter(mut self) -> Self::IntoIter {
         self.words.sort();
@@ -67,51 +197,682 @@ impl IntoIterator for Lexicon {
     }
 }
 
+/// Lexicons are serialized as a sequence of lexemes; the form index is
+/// rebuilt when deserializing
+#[cfg(feature = "serde")]
+impl serde::Serialize for Lexicon {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(&self.words)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Lexicon {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let words = Vec::<Lexeme>::deserialize(deserializer)?;
+        let mut lex = Lexicon::new();
+        for word in words {
+            lex.insert(word);
+        }
+        Ok(lex)
+    }
+}
+
 impl Lexicon {
     /// Create a new empty lexicon
     pub fn new() -> Self {
         Lexicon::default()
     }
 
+    /// Load a lexicon from source (CSV lines)
+    pub fn from_csv(src: &str) -> Result<Self> {
+        let mut lex = Lexicon::default();
+        for (i, line) in src.lines().enumerate() {
+            let word = Lexeme::try_from(line).map_err(|_| Error::Lexicon {
+                line: i + 1,
+                text: line.to_string(),
+            })?;
+            lex.insert(word);
+        }
+        Ok(lex)
+    }
+
+    /// Load a lexicon from a reader (CSV lines)
+    #[cfg(feature = "std")]
+    pub fn from_reader<R>(reader: R) -> Result<Self>
+    where
+        R: std::io::BufRead,
+    {
+        let mut lex = Lexicon::default();
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let word = Lexeme::try_from(line.as_str()).map_err(|_| {
+                Error::Lexicon {
+                    line: i + 1,
+                    text: line,
+                }
+            })?;
+            lex.insert(word);
+        }
+        Ok(lex)
+    }
+
+    /// Load a lexicon from a file (CSV lines)
+    #[cfg(feature = "std")]
+    pub fn from_path<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let file = std::fs::File::open(path)?;
+        Lexicon::from_reader(std::io::BufReader::new(file))
+    }
+
+    /// Save the lexicon to a binary cache file
+    ///
+    /// The cache includes the form index, so loading it with `from_cache`
+    /// is much faster than parsing CSV.  The format may change between
+    /// versions of this crate.
+    #[cfg(feature = "std")]
+    pub fn to_cache<P>(&self, path: P) -> Result<()>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let mut w = cache::Writer::new();
+        w.bytes(CACHE_MAGIC);
+        w.u32(CACHE_VERSION);
+        w.len(self.words.len());
+        for word in &self.words {
+            word.write_cache(&mut w);
+        }
+        w.str(&self.text);
+        w.len(self.forms.len());
+        for form in &self.forms {
+            for v in [form.start, form.end, form.first, form.last] {
+                w.u32(v);
+            }
+        }
+        w.len(self.links.len());
+        for (n, next) in &self.links {
+            w.u32(*n);
+            w.u32(*next);
+        }
+        std::fs::write(path, w.into_bytes())?;
+        Ok(())
+    }
+
+    /// Load a lexicon from a binary cache file (see `to_cache`)
+    #[cfg(feature = "std")]
+    pub fn from_cache<P>(path: P) -> Result<Self>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let buf = std::fs::read(path)?;
+        let mut r = cache::Reader::new(&buf);
+        if r.bytes(CACHE_MAGIC.len())? != CACHE_MAGIC
+            || r.u32()? != CACHE_VERSION
+        {
+            return Err(cache::invalid());
+        }
+        let mut lex = Lexicon::default();
+        let len = r.len(4)?;
+        lex.words = (0..len)
+            .map(|_i| Lexeme::read_cache(&mut r))
+            .collect::<Result<_>>()?;
+        lex.text = r.str()?.to_string();
+        let len = r.len(16)?;
+        lex.forms.reserve_exact(len);
+        for _i in 0..len {
+            lex.forms.push(Form {
+                start: r.u32()?,
+                end: r.u32()?,
+                first: r.u32()?,
+                last: r.u32()?,
+            });
+        }
+        let len = r.len(8)?;
+        lex.links.reserve_exact(len);
+        for _i in 0..len {
+            lex.links.push((r.u32()?, r.u32()?));
+        }
+        r.finish()?;
+        if !lex.is_valid() {
+            return Err(cache::invalid());
+        }
+        // hash table is smaller to rebuild than to store
+        let mut len = MIN_TABLE;
+        while len < lex.forms.len() * 2 {
+            len *= 2;
+        }
+        lex.table = vec![(0, NONE); len];
+        for f in 0..lex.forms.len() {
+            lex.place(hash_word(lex.form(f)), f as u32);
+        }
+        Ok(lex)
+    }
+
+    /// Check that the form index is consistent (so lookups cannot panic)
+    #[cfg(feature = "std")]
+    fn is_valid(&self) -> bool {
+        let links = self.links.len() as u32;
+        let forms_ok = self.forms.iter().all(|f| {
+            f.start <= f.end
+                && self.text.get(f.start as usize..f.end as usize).is_some()
+                && f.first < links
+                && f.last < links
+        });
+        // links only point forward, so chains always end
+        let links_ok = self.links.iter().enumerate().all(|(i, (n, next))| {
+            (*n as usize) < self.words.len()
+                && (*next == NONE || (*next as usize > i && *next < links))
+        });
+        forms_ok && links_ok
+    }
+
     /// Insert a lexeme (word) into the lexicon
     pub fn insert(&mut self, word: Lexeme) {
+        #[cfg(feature = "std")]
+        self.suggest.take();
+        #[cfg(feature = "std")]
+        self.sorted.take();
+        #[cfg(feature = "std")]
+        self.anagrams.take();
+        #[cfg(feature = "std")]
+        self.rhymes.take();
+        #[cfg(feature = "std")]
+        self.owned.take();
         for form in word.forms() {
             self.insert_form(form);
         }
         self.words.push(word);
     }
 
+    /// Merge another lexicon into this one
+    ///
+    /// Lexemes with the same lemma and word class as an existing entry are
+    /// handled according to the merge `policy`.
+    pub fn merge(&mut self, other: Lexicon, policy: MergePolicy) {
+        let mut replaced = false;
+        for word in other.words {
+            let found =
+                self.entry_indices(word.lemma()).into_iter().find(|n| {
+                    let entry = &self.words[*n];
+                    entry.lemma() == word.lemma()
+                        && entry.word_class() == word.word_class()
+                });
+            match (found, policy) {
+                (Some(_), MergePolicy::KeepFirst) => (),
+                (Some(n), MergePolicy::KeepLast) => {
+                    self.words[n] = word;
+                    replaced = true;
+                }
+                (Some(_), MergePolicy::KeepBoth)
+                    if self.words.contains(&word) => {}
+                _ => self.insert(word),
+            }
+        }
+        // replaced lexemes may have different forms
+        if replaced {
+            self.rebuild();
+        }
+    }
+
+    /// Rebuild the form index from all lexemes
+    fn rebuild(&mut self) {
+        let words = core::mem::take(&mut self.words);
+        *self = Lexicon::default();
+        for word in words {
+            self.insert(word);
+        }
+    }
+
     /// Insert a word form
     fn insert_form(&mut self, word: &str) {
-        let n = self.words.len();
-        if let Some(nums) = self.forms.get_mut(word) {
-            nums.push(n);
-        } else {
-            let nums = vec![n];
-            self.forms.insert(word.to_lowercase(), nums);
+        let n = self.words.len() as u32;
+        let link = self.links.len() as u32;
+        let hash = hash_word(word);
+        match self.probe(word, hash) {
+            Ok(f) => {
+                let form = &mut self.forms[f];
+                let last = &mut self.links[form.last as usize];
+                if last.0 != n {
+                    last.1 = link;
+                    form.last = link;
+                    self.links.push((n, NONE));
+                }
+            }
+            Err(slot) => {
+                let start = self.text.len() as u32;
+                self.text.extend(normalize(word));
+                let end = self.text.len() as u32;
+                self.table[slot] = (hash, self.forms.len() as u32);
+                self.forms.push(Form {
+                    start,
+                    end,
+                    first: link,
+                    last: link,
+                });
+                self.links.push((n, NONE));
+                if self.forms.len() * 2 > self.table.len() {
+                    self.grow_table();
+                }
+            }
+        }
+    }
+
+    /// Double the size of the hash table
+    fn grow_table(&mut self) {
+        let len = (self.table.len() * 2).max(MIN_TABLE);
+        let old = core::mem::replace(&mut self.table, vec![(0, NONE); len]);
+        for (hash, f) in old.into_iter().filter(|(_h, f)| *f != NONE) {
+            self.place(hash, f);
+        }
+    }
+
+    /// Place a form index into the first empty hash table slot
+    fn place(&mut self, hash: u32, f: u32) {
+        let mask = self.table.len() - 1;
+        let mut slot = hash as usize & mask;
+        while self.table[slot].1 != NONE {
+            slot = (slot + 1) & mask;
+        }
+        self.table[slot] = (hash, f);
+    }
+
+    /// Probe hash table for a word form
+    ///
+    /// Returns the form index if found, otherwise an empty slot.
+    fn probe(&mut self, word: &str, hash: u32) -> Result<usize, usize> {
+        if self.table.is_empty() {
+            self.grow_table();
+        }
+        self.find_form(word, hash).ok_or_else(|| {
+            let mask = self.table.len() - 1;
+            let mut slot = hash as usize & mask;
+            while self.table[slot].1 != NONE {
+                slot = (slot + 1) & mask;
+            }
+            slot
+        })
+    }
+
+    /// Find the index of a word form
+    fn find_form(&self, word: &str, hash: u32) -> Option<usize> {
+        if self.table.is_empty() {
+            return None;
+        }
+        let mask = self.table.len() - 1;
+        let mut slot = hash as usize & mask;
+        loop {
+            let (h, f) = self.table[slot];
+            if f == NONE {
+                return None;
+            }
+            if h == hash && matches_form(word, self.form(f as usize)) {
+                return Some(f as usize);
+            }
+            slot = (slot + 1) & mask;
         }
     }
 
+    /// Get text of a word form
+    fn form(&self, f: usize) -> &str {
+        let form = &self.forms[f];
+        &self.text[form.start as usize..form.end as usize]
+    }
+
+    /// Find a word form, ignoring the dot of a known abbreviation if needed
+    fn find_word(&self, word: &str) -> Option<usize> {
+        let found = self.find_form(word, hash_word(word));
+        #[cfg(feature = "std")]
+        let found = found.or_else(|| {
+            let stem = word.strip_suffix('.')?;
+            if !crate::abbrev::builtin().contains(word) {
+                return None;
+            }
+            self.find_form(stem, hash_word(stem))
+        });
+        found
+    }
+
     /// Check if lexicon contains a word form
+    ///
+    /// The dot of a built-in abbreviation is ignored if needed ("Dr." finds
+    /// "Dr").
     pub fn contains(&self, word: &str) -> bool {
-        self.forms.contains_key(&make_word(word))
+        self.find_word(word).is_some()
     }
 
     /// Get all lexeme entries containing a word form
+    ///
+    /// The dot of a built-in abbreviation is ignored if needed ("Dr." finds
+    /// "Dr").
     pub fn word_entries(&self, word: &str) -> Vec<&Lexeme> {
-        if let Some(indices) = self.forms.get(&make_word(word)) {
-            let mut entries = Vec::with_capacity(indices.len());
-            for i in indices {
-                entries.push(&self.words[*i]);
+        self.entry_indices(word)
+            .into_iter()
+            .map(|n| &self.words[n])
+            .collect()
+    }
+
+    /// Get indices of all lexemes containing a word form
+    fn entry_indices(&self, word: &str) -> Vec<usize> {
+        let mut indices = Vec::new();
+        if let Some(f) = self.find_word(word) {
+            let mut link = self.forms[f].first;
+            while link != NONE {
+                let (n, next) = self.links[link as usize];
+                indices.push(n as usize);
+                link = next;
+            }
+        }
+        indices
+    }
+
+    /// Get the lemmas (and word classes) of a word form
+    ///
+    /// For example, "running" returns `[("run", WordClass::Verb)]`.
+    pub fn lemmatize(&self, form: &str) -> Vec<(&str, WordClass)> {
+        let mut lemmas = Vec::new();
+        for entry in self.word_entries(form) {
+            let lemma = (entry.lemma(), entry.word_class());
+            if !lemmas.contains(&lemma) {
+                lemmas.push(lemma);
             }
-            return entries;
         }
-        vec![]
+        lemmas
+    }
+
+    /// Get the most likely word class of a word form
+    ///
+    /// When entries have more than one class, the built-in class frequency
+    /// table breaks the tie (with the `prob` feature).  Returns `None` for
+    /// unknown or ambiguous words.
+    pub fn most_likely_class(&self, word: &str) -> Option<WordClass> {
+        let mut classes: Vec<_> = self
+            .word_entries(word)
+            .iter()
+            .map(|entry| entry.word_class())
+            .collect();
+        classes.sort();
+        classes.dedup();
+        match classes[..] {
+            [] => None,
+            [wc] => Some(wc),
+            #[cfg(feature = "prob")]
+            _ => crate::prob::builtin().most_likely(word, &classes),
+            #[cfg(not(feature = "prob"))]
+            _ => None,
+        }
+    }
+
+    /// Suggest word forms similar to a (misspelled) word
+    ///
+    /// Forms within `max_distance` edits are ranked by distance, then by
+    /// length of common prefix.  An index of all forms is built on the first
+    /// call.
+    #[cfg(feature = "std")]
+    pub fn suggest(&self, word: &str, max_distance: usize) -> Vec<Suggestion> {
+        self.suggest
+            .get_or_init(|| self.iter_forms().collect())
+            .find(&make_word(word), max_distance)
+    }
+
+    /// Get form indices in sorted order
+    #[cfg(feature = "std")]
+    fn sort_forms(&self) -> Vec<u32> {
+        let mut sorted: Vec<u32> = (0..self.forms.len() as u32).collect();
+        sorted.sort_unstable_by_key(|f| self.form(*f as usize));
+        sorted
+    }
+
+    /// Get the sorted form index
+    #[cfg(all(feature = "std", not(feature = "fst")))]
+    fn sorted_forms(&self) -> &[u32] {
+        self.sorted.get_or_init(|| self.sort_forms())
+    }
+
+    /// Get the sorted form index
+    #[cfg(feature = "fst")]
+    fn sorted_forms(&self) -> &fst::Map<Vec<u8>> {
+        self.sorted.get_or_init(|| {
+            let sorted = self.sort_forms();
+            let forms = sorted
+                .iter()
+                .map(|f| (self.form(*f as usize), u64::from(*f)));
+            // forms are unique, and sorted by bytes
+            fst::Map::from_iter(forms).expect("Sorted forms")
+        })
+    }
+
+    /// Get form indices starting with a normalized prefix (sorted)
+    #[cfg(all(feature = "std", not(feature = "fst")))]
+    fn sorted_prefix(
+        &self,
+        prefix: String,
+    ) -> impl Iterator<Item = usize> + use<'_> {
+        let sorted = self.sorted_forms();
+        let start = sorted
+            .partition_point(|f| self.form(*f as usize) < prefix.as_str());
+        sorted[start..]
+            .iter()
+            .map(|f| *f as usize)
+            .take_while(move |f| self.form(*f).starts_with(&prefix))
+    }
+
+    /// Get form indices starting with a normalized prefix (sorted)
+    #[cfg(feature = "fst")]
+    fn sorted_prefix(
+        &self,
+        prefix: String,
+    ) -> impl Iterator<Item = usize> + use<'_> {
+        use fst::{Automaton, IntoStreamer};
+        let matcher = fst::automaton::Str::new(&prefix).starts_with();
+        let stream = self.sorted_forms().search(matcher).into_stream();
+        stream.into_values().into_iter().map(|f| f as usize)
+    }
+
+    /// Get form indices within a range of forms (sorted)
+    #[cfg(all(feature = "std", not(feature = "fst")))]
+    fn sorted_range(
+        &self,
+        start: Bound<&str>,
+        end: Bound<&str>,
+    ) -> impl Iterator<Item = usize> + use<'_> {
+        let sorted = self.sorted_forms();
+        let form = |f: &u32| self.form(*f as usize);
+        let start = match start {
+            Bound::Included(s) => sorted.partition_point(|f| form(f) < s),
+            Bound::Excluded(s) => sorted.partition_point(|f| form(f) <= s),
+            Bound::Unbounded => 0,
+        };
+        let end = match end {
+            Bound::Included(e) => sorted.partition_point(|f| form(f) <= e),
+            Bound::Excluded(e) => sorted.partition_point(|f| form(f) < e),
+            Bound::Unbounded => sorted.len(),
+        };
+        sorted[start..end.max(start)].iter().map(|f| *f as usize)
+    }
+
+    /// Get form indices within a range of forms (sorted)
+    #[cfg(feature = "fst")]
+    fn sorted_range(
+        &self,
+        start: Bound<&str>,
+        end: Bound<&str>,
+    ) -> impl Iterator<Item = usize> + use<'_> {
+        use fst::IntoStreamer;
+        let mut range = self.sorted_forms().range();
+        range = match start {
+            Bound::Included(s) => range.ge(s),
+            Bound::Excluded(s) => range.gt(s),
+            Bound::Unbounded => range,
+        };
+        range = match end {
+            Bound::Included(e) => range.le(e),
+            Bound::Excluded(e) => range.lt(e),
+            Bound::Unbounded => range,
+        };
+        let values = range.into_stream().into_values();
+        values.into_iter().map(|f| f as usize)
+    }
+
+    /// Get an iterator of word forms starting with a prefix (sorted)
+    ///
+    /// For example, `forms_with_prefix("un")` yields "unable", "unaware", etc.
+    #[cfg(feature = "std")]
+    pub fn forms_with_prefix(
+        &self,
+        prefix: &str,
+    ) -> impl Iterator<Item = &str> + use<'_> {
+        self.sorted_prefix(make_word(prefix)).map(|f| self.form(f))
+    }
+
+    /// Find word forms matching a glob pattern (sorted)
+    ///
+    /// In the pattern, `*` matches any characters and `?` matches one
+    /// character.  For example, `find("re*ize")` yields "realize",
+    /// "recognize", etc.
+    #[cfg(feature = "std")]
+    pub fn find(&self, pattern: &str) -> impl Iterator<Item = &str> {
+        let pattern: Vec<char> = normalize(pattern).collect();
+        let prefix: String = pattern
+            .iter()
+            .take_while(|c| !matches!(c, '*' | '?'))
+            .collect();
+        self.forms_with_prefix(&prefix)
+            .filter(move |form| glob_match(&pattern, form))
+    }
+
+    /// Get the anagram index (sorted by letters)
+    #[cfg(feature = "std")]
+    fn anagram_index(&self) -> &[(String, u32)] {
+        self.anagrams.get_or_init(|| {
+            let mut index: Vec<_> = self
+                .words
+                .iter()
+                .enumerate()
+                .map(|(n, word)| (sorted_letters(word.lemma()), n as u32))
+                .filter(|(letters, _n)| !letters.is_empty())
+                .collect();
+            index.sort_unstable();
+            index
+        })
+    }
+
+    /// Get lexemes with lemmas which are anagrams of some letters
+    ///
+    /// Only letters are compared (case, spaces and punctuation are
+    /// ignored).  For example, "listen" returns "enlist", "silent", etc.
+    #[cfg(feature = "std")]
+    pub fn anagrams(&self, letters: &str) -> Vec<&Lexeme> {
+        let letters = sorted_letters(letters);
+        let index = self.anagram_index();
+        let start = index.partition_point(|(l, _n)| *l < letters);
+        index[start..]
+            .iter()
+            .take_while(|(l, _n)| *l == letters)
+            .map(|(_l, n)| &self.words[*n as usize])
+            .collect()
+    }
+
+    /// Get lexemes with lemmas using only some of the letters (sub-anagrams)
+    ///
+    /// Each letter can be used only as many times as it appears.  Lexemes
+    /// are sorted by lemma length (longest first).
+    #[cfg(feature = "std")]
+    pub fn sub_anagrams(&self, letters: &str) -> Vec<&Lexeme> {
+        let letters = sorted_letters(letters);
+        let mut words: Vec<_> = self
+            .anagram_index()
+            .iter()
+            .filter(|(l, _n)| {
+                l.len() <= letters.len() && is_subset(l, &letters)
+            })
+            .map(|(l, n)| (l.len(), &self.words[*n as usize]))
+            .collect();
+        words.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(b.1)));
+        words.into_iter().map(|(_len, word)| word).collect()
+    }
+
+    /// Get the rhyme index (sorted by rhyme key)
+    #[cfg(feature = "std")]
+    fn rhyme_index(&self) -> &[(String, u32)] {
+        self.rhymes.get_or_init(|| {
+            let mut index: Vec<_> = (0..self.forms.len())
+                .filter_map(|f| Some((rhyme_key(self.form(f))?, f as u32)))
+                .collect();
+            index.sort_unstable();
+            index
+        })
+    }
+
+    /// Get word forms which rhyme with a word
+    ///
+    /// Rhymes are guessed from spelling (see `rhyme::rhyme_key`), and
+    /// sorted by length of common ending, so "station" comes before
+    /// "million" for "nation".
+    #[cfg(feature = "std")]
+    pub fn rhymes(&self, word: &str) -> Vec<&str> {
+        let Some(key) = rhyme_key(word) else {
+            return Vec::new();
+        };
+        let word = make_word(word);
+        let index = self.rhyme_index();
+        let start = index.partition_point(|(k, _f)| *k < key);
+        let mut forms: Vec<_> = index[start..]
+            .iter()
+            .take_while(|(k, _f)| *k == key)
+            .map(|(_k, f)| self.form(*f as usize))
+            .filter(|form| *form != word)
+            .collect();
+        forms.sort_by_cached_key(|form| {
+            let common = form
+                .chars()
+                .rev()
+                .zip(word.chars().rev())
+                .take_while(|(a, b)| a == b)
+                .count();
+            (core::cmp::Reverse(common), *form)
+        });
+        forms
+    }
+
+    /// Get an iterator of word forms within a range (sorted)
+    ///
+    /// Bounds are compared with lowercase forms, so `forms_in_range("ca".."cb")`
+    /// yields forms starting with "ca".
+    #[cfg(feature = "std")]
+    pub fn forms_in_range<'a, R>(
+        &self,
+        range: R,
+    ) -> impl Iterator<Item = &str> + use<'_, R>
+    where
+        R: RangeBounds<&'a str>,
+    {
+        let start = range.start_bound().map(|s| *s);
+        let end = range.end_bound().map(|e| *e);
+        self.sorted_range(start, end).map(|f| self.form(f))
     }
 
     /// Get an iterator of all word forms (lowercase)
+    ///
+    /// Forms are packed into one buffer, so owned copies are made on the
+    /// first call.  Use `iter_forms` to avoid that (or without `std`).
+    #[cfg(feature = "std")]
     pub fn forms(&self) -> impl Iterator<Item = &String> {
-        self.forms.keys()
+        self.owned
+            .get_or_init(|| self.iter_forms().map(String::from).collect())
+            .iter()
+    }
+
+    /// Get an iterator of all word forms (lowercase), without copying
+    pub fn iter_forms(&self) -> impl Iterator<Item = &str> {
+        (0..self.forms.len()).map(|f| self.form(f))
     }
 
     /// Get an iterator of all lexemes (words)
@@ -119,3 +880,263 @@ impl Lexicon {
         self.words.iter()
     }
 }
+
+/// Get the letters of a word (lowercase), sorted
+#[cfg(feature = "std")]
+fn sorted_letters(word: &str) -> String {
+    let mut letters: Vec<char> = word
+        .chars()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(char::to_lowercase)
+        .collect();
+    letters.sort_unstable();
+    letters.into_iter().collect()
+}
+
+/// Check if sorted letters are a subset of other sorted letters
+#[cfg(feature = "std")]
+fn is_subset(letters: &str, of: &str) -> bool {
+    let mut of = of.chars();
+    letters.chars().all(|c| of.any(|o| o == c))
+}
+
+/// Check if a word matches a glob pattern (`*` and `?` wildcards)
+#[cfg(feature = "std")]
+fn glob_match(pattern: &[char], word: &str) -> bool {
+    let word: Vec<char> = word.chars().collect();
+    let (mut p, mut w) = (0, 0);
+    // last `*` position in pattern, and word position it matched up to
+    let mut star = None;
+    while w < word.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, w));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == word[w] => {
+                p += 1;
+                w += 1;
+            }
+            _ => match star {
+                Some((sp, sw)) => {
+                    star = Some((sp, sw + 1));
+                    p = sp + 1;
+                    w = sw + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn load_csv() {
+        let lex = Lexicon::from_csv(builtin_csv()).unwrap();
+        assert!(lex.contains("Mice"));
+        assert!(!lex.contains("blorf"));
+        assert!(Lexicon::from_csv("mouse:Q").is_err());
+    }
+
+    #[test]
+    fn lookup() {
+        let mut lex =
+            Lexicon::from_csv("run:V,ran,run\nrun:N\nRome:N").unwrap();
+        lex.insert(Lexeme::try_from("o'clock:Av").unwrap());
+        let entries = lex.word_entries("RUN");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].word_class(), crate::word::WordClass::Verb);
+        assert_eq!(lex.word_entries("ran").len(), 1);
+        assert!(lex.contains("rome"));
+        assert!(lex.contains("O’Clock"));
+        assert!(!lex.contains("runs’"));
+        assert!(lex.word_entries("Rome.").is_empty());
+        let mut forms: Vec<_> = lex.iter_forms().collect();
+        forms.sort();
+        assert_eq!(forms, ["o'clock", "ran", "rome", "romes", "run", "runs"]);
+        #[cfg(feature = "std")]
+        assert!(lex.forms().eq(lex.iter_forms()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn abbreviations() {
+        let lex = Lexicon::from_csv("Dr:N\nend:N").unwrap();
+        assert!(lex.contains("Dr."));
+        assert_eq!(lex.lemmatize("DR."), [("Dr", WordClass::Noun)]);
+        // only known abbreviations drop their dot
+        assert!(!lex.contains("end."));
+        assert!(lex.word_entries("end.").is_empty());
+        assert!(lex.lemmatize("end.").is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn from_reader() {
+        let lex = Lexicon::from_reader("blorf:V\nsnark:N".as_bytes()).unwrap();
+        assert!(lex.contains("blorfed"));
+        assert!(lex.contains("snarks"));
+        assert!(matches!(
+            Lexicon::from_reader("blorf:V\nsnark:Q".as_bytes()),
+            Err(Error::Lexicon { line: 2, .. })
+        ));
+        assert!(Lexicon::from_path("no/such/lexicon.csv").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn prefix() {
+        let mut lex = Lexicon::from_csv("run:V,ran,run\nrune:N").unwrap();
+        let forms: Vec<_> = lex.forms_with_prefix("Run").collect();
+        assert_eq!(forms, ["run", "rune", "runes"]);
+        assert_eq!(lex.forms_with_prefix("x").count(), 0);
+        let forms: Vec<_> = lex.forms_in_range("ran".."rune").collect();
+        assert_eq!(forms, ["ran", "run"]);
+        lex.insert(Lexeme::try_from("rung:N").unwrap());
+        assert_eq!(lex.forms_with_prefix("rung").count(), 2);
+        assert_eq!(lex.forms_in_range(.."rz").count(), 6);
+        let forms: Vec<_> = lex.forms_in_range("run"..="rune").collect();
+        assert_eq!(forms, ["run", "rune"]);
+        let range = (Bound::Excluded("run"), Bound::Unbounded);
+        assert_eq!(lex.forms_in_range(range).next(), Some("rune"));
+        assert_eq!(lex.forms_in_range("s".."r").count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn glob() {
+        let lex = builtin();
+        let found: Vec<_> = lex.find("re*ize").collect();
+        assert!(found.contains(&"realize"));
+        assert!(found.contains(&"recognize"));
+        assert!(
+            found
+                .iter()
+                .all(|f| f.starts_with("re") && f.ends_with("ize"))
+        );
+        let found: Vec<_> = lex.find("C?T").collect();
+        assert_eq!(found, ["cat", "cot", "cut"]);
+        assert!(lex.find("*").count() > 100_000);
+        assert_eq!(lex.find("zz?zz*").count(), 0);
+        assert!(glob_match(&['a', '*', 'b', '*'], "axxbyb"));
+        assert!(!glob_match(&['a', '*', 'b'], "axxbyc"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn anagrams() {
+        let lex = builtin();
+        let found: Vec<_> =
+            lex.anagrams("Listen").iter().map(|w| w.lemma()).collect();
+        assert!(found.contains(&"silent"));
+        assert!(found.contains(&"enlist"));
+        assert!(lex.anagrams("xqzj").is_empty());
+        let found = lex.sub_anagrams("tac");
+        assert_eq!(found[0].lemma().len(), 3);
+        let lemmas: Vec<_> = found.iter().map(|w| w.lemma()).collect();
+        assert!(lemmas.contains(&"act"));
+        assert!(lemmas.contains(&"at"));
+        assert!(!lemmas.contains(&"tact"));
+        assert!(is_subset("act", "acct"));
+        assert!(!is_subset("aact", "acct"));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn rhymes() {
+        let lex = builtin();
+        let found = lex.rhymes("Nation");
+        assert!(found.iter().take(20).all(|f| f.ends_with("ation")));
+        assert!(found.contains(&"station"));
+        assert!(!found.contains(&"nation"));
+        assert!(lex.rhymes("night").contains(&"white"));
+        assert!(lex.rhymes("123").is_empty());
+    }
+
+    #[test]
+    fn merge() {
+        let base = Lexicon::from_csv("run:V,ran,run\nrun:N").unwrap();
+        let other = Lexicon::from_csv("run:V,runned,run\nblorf:N").unwrap();
+        let mut lex = base.clone();
+        lex.merge(other.clone(), MergePolicy::KeepFirst);
+        assert!(lex.contains("ran"));
+        assert!(!lex.contains("runned"));
+        assert!(lex.contains("blorfs"));
+        let mut lex = base.clone();
+        lex.merge(other.clone(), MergePolicy::KeepLast);
+        assert!(!lex.contains("ran"));
+        assert!(lex.contains("runned"));
+        assert_eq!(lex.word_entries("run").len(), 2);
+        let mut lex = base.clone();
+        lex.merge(other, MergePolicy::KeepBoth);
+        assert!(lex.contains("ran"));
+        assert_eq!(lex.word_entries("run").len(), 3);
+        lex.merge(base, MergePolicy::KeepBoth);
+        assert_eq!(lex.word_entries("run").len(), 3);
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn serde() {
+        let lex = Lexicon::from_csv("mouse:N,mice\nrun:V,ran,run").unwrap();
+        let json = serde_json::to_string(&lex).unwrap();
+        assert_eq!(json, "[\"mouse:N,mice\",\"run:V,ran,run\"]");
+        let lex = serde_json::from_str::<Lexicon>(&json).unwrap();
+        assert!(lex.contains("mice"));
+        assert_eq!(lex.word_entries("ran").len(), 1);
+        assert!(serde_json::from_str::<Lexicon>("[\"mouse:Q\"]").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn suggest() {
+        let mut lex = Lexicon::from_csv("run:V,runs,running,ran").unwrap();
+        let words = |lex: &Lexicon| -> Vec<String> {
+            lex.suggest("Runing", 1)
+                .iter()
+                .map(|s| s.word().to_string())
+                .collect()
+        };
+        assert_eq!(words(&lex), ["running"]);
+        lex.insert(Lexeme::try_from("ruin:V").unwrap());
+        assert_eq!(words(&lex), ["running", "ruining"]);
+    }
+
+    #[test]
+    fn lemmatize() {
+        let lex = Lexicon::from_csv(builtin_csv()).unwrap();
+        assert_eq!(
+            lex.lemmatize("Running"),
+            [
+                ("run", WordClass::Verb),
+                ("running", WordClass::Adjective),
+                ("running", WordClass::Noun),
+            ]
+        );
+        assert_eq!(lex.lemmatize("mice"), [("mouse", WordClass::Noun)]);
+        assert!(lex.lemmatize("blorf").is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn cache() {
+        let path = std::env::temp_dir()
+            .join(format!("booky-test-{}.cache", std::process::id()));
+        let lex = Lexicon::from_csv(builtin_csv()).unwrap();
+        lex.to_cache(&path).unwrap();
+        let cached = Lexicon::from_cache(&path).unwrap();
+        assert_eq!(cached.iter().count(), lex.iter().count());
+        assert!(cached.iter_forms().eq(lex.iter_forms()));
+        assert_eq!(cached.word_entries("Mice"), lex.word_entries("mice"));
+        assert!(!cached.contains("blorf"));
+        let mut buf = std::fs::read(&path).unwrap();
+        buf.truncate(buf.len() - 4);
+        std::fs::write(&path, &buf).unwrap();
+        assert!(matches!(Lexicon::from_cache(&path), Err(Error::Decode(_))));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
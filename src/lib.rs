@@ -1,7 +1,102 @@
-mod contractions;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod abbrev;
+#[cfg(feature = "archive")]
+pub mod archive;
+#[cfg(feature = "std")]
+mod cache;
+#[cfg(feature = "std")]
+pub mod check;
+#[cfg(feature = "std")]
+pub mod concordance;
+pub mod contractions;
+#[cfg(feature = "std")]
+pub mod delimited;
+#[cfg(feature = "docx")]
+pub mod docx;
+#[cfg(feature = "epub")]
+pub mod epub;
+mod error;
+#[cfg(feature = "std")]
+pub mod feed;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod fountain;
+#[cfg(feature = "std")]
+pub mod gazetteer;
+#[cfg(feature = "std")]
+pub mod grammar;
+#[cfg(feature = "std")]
+pub mod gutenberg;
+#[cfg(feature = "std")]
+pub mod harvest;
+#[cfg(feature = "std")]
 pub mod hilite;
+#[cfg(feature = "std")]
+pub mod html;
+#[cfg(feature = "std")]
+pub mod ingest;
+#[cfg(feature = "std")]
+pub mod json;
+#[cfg(feature = "std")]
+pub mod keyword;
 pub mod kind;
+#[cfg(feature = "std")]
+pub mod lang;
+#[cfg(feature = "std")]
+pub mod latex;
 pub mod lex;
-mod parse;
+#[cfg(feature = "std")]
+pub mod markdown;
+#[cfg(feature = "std")]
+pub mod meter;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "std")]
+pub mod ngram;
+#[cfg(feature = "std")]
+pub mod nonsense;
+pub mod numbers;
+#[cfg(feature = "std")]
+pub mod org;
+#[cfg(feature = "std")]
+pub mod parse;
+#[cfg(feature = "std")]
+pub mod passive;
+#[cfg(feature = "pdf")]
+pub mod pdf;
+pub mod penn;
+#[cfg(feature = "prob")]
+pub mod prob;
+#[cfg(feature = "std")]
+pub mod rhyme;
+pub mod roman;
+#[cfg(feature = "std")]
+pub mod rst;
+#[cfg(feature = "std")]
+pub mod sentence;
+#[cfg(feature = "std")]
+pub mod spelling;
+pub mod stem;
+#[cfg(feature = "std")]
+pub mod stopwords;
+#[cfg(feature = "std")]
+pub mod subtitle;
+#[cfg(feature = "std")]
+pub mod suggest;
+#[cfg(feature = "std")]
+pub mod summary;
+#[cfg(feature = "std")]
+pub mod tagger;
+#[cfg(feature = "std")]
 pub mod tally;
+pub mod upos;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 pub mod word;
+
+pub use error::{Error, Result};
@@ -0,0 +1,158 @@
+use crate::Result;
+use regex::Regex;
+use std::io::BufRead;
+use std::sync::LazyLock;
+
+/// Inline code spans
+static CODE_SPAN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"``.*?``|`[^`]*`").unwrap());
+
+/// Links and images (keeping text), with inline or reference destination
+static LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"!?\[([^\]]*)\](?:\([^)]*\)|\[[^\]]*\])").unwrap()
+});
+
+/// Autolinks and bare URLs
+static URL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"<[a-zA-Z][a-zA-Z0-9+.-]*:[^>\s]*>|<[^@>\s]+@[^>\s]+>|https?://\S+",
+    )
+    .unwrap()
+});
+
+/// ATX heading markers (opening and closing)
+static HEADING: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^ {0,3}#{1,6}(?:[ \t]+|$)|[ \t]+#+[ \t]*$").unwrap()
+});
+
+/// Setext heading underlines and thematic breaks
+static RULE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^ {0,3}(?:=+|(?:[-*_][ \t]*){3,})[ \t]*$").unwrap()
+});
+
+/// Link reference definitions
+static LINK_DEF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^ {0,3}\[[^\]]+\]:\s*\S+").unwrap());
+
+/// Markdown block state
+enum Block {
+    /// Prose text
+    Prose,
+    /// Front matter (with closing marker)
+    FrontMatter(&'static str),
+    /// Fenced code block (with fence marker)
+    Code(String),
+}
+
+/// Get code fence marker of a line
+fn code_fence(line: &str) -> Option<String> {
+    let line = line.trim_start();
+    let c = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let fence: String = line.chars().take_while(|f| *f == c).collect();
+    (fence.len() >= 3).then_some(fence)
+}
+
+/// Check if a line closes a code fence
+fn is_fence_end(line: &str, fence: &str) -> bool {
+    let line = line.trim();
+    line.starts_with(fence) && line.chars().all(|c| fence.starts_with(c))
+}
+
+/// Strip heading markers, inline code, link destinations and URLs from a
+/// line
+fn strip_inline(line: &str) -> String {
+    let line = HEADING.replace_all(line, "");
+    let line = CODE_SPAN.replace_all(&line, "");
+    let line = LINK.replace_all(&line, "$1");
+    URL.replace_all(&line, "").into_owned()
+}
+
+/// Extract prose text from Markdown
+///
+/// Front matter, code blocks, heading markers, rules, inline code, link
+/// destinations and URLs are removed.  Skipped lines are kept as empty lines,
+/// so line numbers match the source.
+pub fn extract_text<R>(reader: R) -> Result<String>
+where
+    R: BufRead,
+{
+    let mut text = String::new();
+    let mut block = Block::Prose;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        match &block {
+            Block::FrontMatter(end) => {
+                if line.trim_end() == *end {
+                    block = Block::Prose;
+                }
+            }
+            Block::Code(fence) => {
+                if is_fence_end(&line, fence) {
+                    block = Block::Prose;
+                }
+            }
+            Block::Prose => {
+                let marker = line.trim_end();
+                if i == 0 && (marker == "---" || marker == "+++") {
+                    let end = if marker == "---" { "---" } else { "+++" };
+                    block = Block::FrontMatter(end);
+                } else if let Some(fence) = code_fence(&line) {
+                    block = Block::Code(fence);
+                } else if !LINK_DEF.is_match(&line) && !RULE.is_match(&line) {
+                    text.push_str(&strip_inline(&line));
+                }
+            }
+        }
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extract() {
+        let md = "---\ntitle: Post\n---\n\
+            # Heading #\n\
+            Sub\n===\n\
+            Use `foo_bar()` or see [the docs](https://x.io/docs).\n\
+            ```rust\nlet x = 1;\n```\n\
+            ![A cat](cat.png) at <https://x.io> and https://y.io/z ok\n\
+            [docs]: https://x.io/docs\n";
+        let text = extract_text(md.as_bytes()).unwrap();
+        assert_eq!(
+            text,
+            "\n\n\nHeading\nSub\n\nUse  or see the docs.\n\n\n\nA cat at  and  ok\n\n"
+        );
+        // a double backtick span can contain single backticks
+        let text = extract_text("A ``b `c` d`` e".as_bytes()).unwrap();
+        assert_eq!(text, "A  e\n");
+    }
+
+    #[test]
+    fn blocks() {
+        let extract = |md: &str| extract_text(md.as_bytes()).unwrap();
+        // front matter only at the start
+        assert_eq!(extract("+++\na = 1\n+++\nHi"), "\n\n\nHi\n");
+        assert_eq!(extract("Hi\n---\nthere"), "Hi\n\nthere\n");
+        // a fence closes only with the same character, at least as long
+        assert_eq!(extract("~~~~\nx\n~~~\n```\n~~~~~\nok"), "\n\n\n\n\nok\n");
+        // an unclosed fence or front matter hides the rest
+        assert_eq!(extract("Hi\n```\ncode\nmore"), "Hi\n\n\n\n");
+        assert_eq!(extract("---\ntitle: x"), "\n\n");
+        assert!(extract_text(&b"caf\xE9"[..]).is_err());
+    }
+
+    #[test]
+    fn inline() {
+        let extract = |md: &str| extract_text(md.as_bytes()).unwrap();
+        assert_eq!(extract("#hashtag and # not"), "#hashtag and # not\n");
+        assert_eq!(extract("    # indented code"), "    # indented code\n");
+        assert_eq!(extract("## Closed ##  "), "Closed\n");
+        assert_eq!(extract("[ref][1] and [x]"), "ref and [x]\n");
+        assert_eq!(extract("mail <a@b.io> now"), "mail  now\n");
+        assert_eq!(extract("* * *"), "\n");
+    }
+}
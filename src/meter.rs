@@ -0,0 +1,609 @@
+use crate::lex::{self, Lexicon};
+use crate::parse::{Chunk, Parser};
+use crate::word::{WordClass, syllables};
+use crate::{Error, Result};
+use std::fmt;
+use std::io::BufRead;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Suffixes which put stress on the syllable before them (and their
+/// syllable counts)
+const PRE_STRESS_SUFFIXES: &[(&str, usize)] = &[
+    ("graphy", 2),
+    ("cious", 1),
+    ("tious", 1),
+    ("cial", 1),
+    ("tial", 1),
+    ("cian", 1),
+    ("tion", 1),
+    ("sion", 1),
+    ("ical", 2),
+    ("ious", 2),
+    ("logy", 2),
+    ("meter", 2),
+    ("ian", 2),
+    ("ity", 2),
+    ("ify", 2),
+    ("ics", 1),
+    ("ic", 1),
+];
+
+/// Suffixes which take stress themselves
+const STRESSED_SUFFIXES: &[&str] = &["eer", "ese", "ette", "ique", "oon"];
+
+/// Suffixes which do not change stress
+const NEUTRAL_SUFFIXES: &[&str] = &[
+    "able", "er", "est", "ful", "hood", "ible", "ing", "ism", "ist", "less",
+    "ly", "ment", "ness", "ship",
+];
+
+/// Prefixes which are unstressed in verbs
+const VERB_PREFIXES: &[&str] = &[
+    "under", "over", "with", "for", "out", "com", "con", "dis", "mis", "per",
+    "pre", "pro", "sub", "be", "de", "em", "en", "ex", "im", "in", "ob", "re",
+    "un",
+];
+
+/// Prefixes which are unstressed in any word class
+const PREFIXES: &[&str] = &["dis", "mis", "non", "be", "un"];
+
+/// Stress of a syllable
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Stress {
+    /// Unstressed (`x`)
+    Weak,
+    /// Stressed (`/`)
+    Strong,
+    /// Either (`-`), such as a monosyllable or secondary stress
+    Any,
+}
+
+/// Metrical foot
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Foot {
+    /// Weak, strong (`x/`)
+    Iamb,
+    /// Strong, weak (`/x`)
+    Trochee,
+    /// Weak, weak, strong (`xx/`)
+    Anapest,
+    /// Strong, weak, weak (`/xx`)
+    Dactyl,
+}
+
+/// Meter of a line (foot and number of feet)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Meter {
+    /// Metrical foot
+    foot: Foot,
+    /// Number of feet
+    feet: usize,
+}
+
+/// Place where a line breaks a meter
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Break {
+    /// Wrong number of syllables
+    Length {
+        /// Syllables expected
+        expected: usize,
+        /// Syllables found
+        found: usize,
+    },
+    /// Syllable stress does not match
+    Stress {
+        /// Syllable number (starting at 0)
+        syllable: usize,
+        /// Word containing syllable
+        word: String,
+        /// Stress expected
+        expected: Stress,
+    },
+}
+
+/// Scanned line of verse
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Line {
+    /// Line text
+    text: String,
+    /// Words, with stress of each syllable
+    words: Vec<(String, Vec<Stress>)>,
+}
+
+/// Verse scanner
+#[derive(Clone, Default)]
+pub struct Scanner {
+    /// Lexicon for parsing (built-in if not set)
+    lex: Option<Arc<Lexicon>>,
+}
+
+impl fmt::Display for Stress {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let mark = match self {
+            Stress::Weak => 'x',
+            Stress::Strong => '/',
+            Stress::Any => '-',
+        };
+        write!(fmt, "{mark}")
+    }
+}
+
+impl fmt::Display for Break {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Break::Length { expected, found } => {
+                write!(fmt, "{found} syllables (expected {expected})")
+            }
+            Break::Stress {
+                syllable,
+                word,
+                expected,
+            } => {
+                let stress = match expected {
+                    Stress::Strong => "stressed",
+                    _ => "unstressed",
+                };
+                write!(
+                    fmt,
+                    "syllable {} (`{word}`) should be {stress}",
+                    syllable + 1
+                )
+            }
+        }
+    }
+}
+
+impl Stress {
+    /// Check if a stress conflicts with another
+    fn conflicts(self, other: Stress) -> bool {
+        matches!(
+            (self, other),
+            (Stress::Weak, Stress::Strong) | (Stress::Strong, Stress::Weak)
+        )
+    }
+}
+
+impl Foot {
+    /// Get all feet
+    pub fn all() -> &'static [Self] {
+        use Foot::*;
+        &[Iamb, Trochee, Anapest, Dactyl]
+    }
+
+    /// Get stress pattern
+    pub fn pattern(self) -> &'static [Stress] {
+        use Stress::*;
+        match self {
+            Foot::Iamb => &[Weak, Strong],
+            Foot::Trochee => &[Strong, Weak],
+            Foot::Anapest => &[Weak, Weak, Strong],
+            Foot::Dactyl => &[Strong, Weak, Weak],
+        }
+    }
+
+    /// Get adjective (e.g. "iambic")
+    fn adjective(self) -> &'static str {
+        match self {
+            Foot::Iamb => "iambic",
+            Foot::Trochee => "trochaic",
+            Foot::Anapest => "anapestic",
+            Foot::Dactyl => "dactylic",
+        }
+    }
+
+    /// Check if the foot ends with a stressed syllable
+    fn is_rising(self) -> bool {
+        matches!(self, Foot::Iamb | Foot::Anapest)
+    }
+}
+
+/// Names of line lengths (in feet)
+const LENGTHS: &[&str] = &[
+    "monometer",
+    "dimeter",
+    "trimeter",
+    "tetrameter",
+    "pentameter",
+    "hexameter",
+    "heptameter",
+    "octameter",
+];
+
+impl fmt::Display for Meter {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let adjective = self.foot.adjective();
+        match LENGTHS.get(self.feet.wrapping_sub(1)) {
+            Some(length) => write!(fmt, "{adjective} {length}"),
+            None => write!(fmt, "{adjective} ({} feet)", self.feet),
+        }
+    }
+}
+
+impl FromStr for Meter {
+    type Err = Error;
+
+    /// Parse a meter name (e.g. `iambic pentameter` or `iambic-pentameter`)
+    fn from_str(val: &str) -> Result<Self> {
+        let invalid = || Error::Meter(val.to_string());
+        let (adjective, length) =
+            val.split_once([' ', '-']).ok_or_else(invalid)?;
+        let foot = Foot::all()
+            .iter()
+            .find(|f| f.adjective() == adjective.to_lowercase())
+            .ok_or_else(invalid)?;
+        let feet = LENGTHS
+            .iter()
+            .position(|l| *l == length.to_lowercase())
+            .ok_or_else(invalid)?;
+        Ok(Meter::new(*foot, feet + 1))
+    }
+}
+
+impl Meter {
+    /// Iambic pentameter
+    pub const IAMBIC_PENTAMETER: Self = Meter {
+        foot: Foot::Iamb,
+        feet: 5,
+    };
+
+    /// Create a new meter
+    pub fn new(foot: Foot, feet: usize) -> Self {
+        Meter { foot, feet }
+    }
+
+    /// Get metrical foot
+    pub fn foot(self) -> Foot {
+        self.foot
+    }
+
+    /// Get number of feet
+    pub fn feet(self) -> usize {
+        self.feet
+    }
+
+    /// Get stress pattern
+    pub fn pattern(self) -> Vec<Stress> {
+        self.foot.pattern().repeat(self.feet)
+    }
+}
+
+impl Line {
+    /// Get line text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the number of syllables
+    pub fn syllables(&self) -> usize {
+        self.words.iter().map(|(_w, s)| s.len()).sum()
+    }
+
+    /// Get the stress of each syllable
+    pub fn stresses(&self) -> Vec<Stress> {
+        self.words
+            .iter()
+            .flat_map(|(_w, s)| s.iter().copied())
+            .collect()
+    }
+
+    /// Get the places where the line breaks a meter
+    ///
+    /// A rising meter (iambic or anapestic) may have one extra unstressed
+    /// syllable at the end (a feminine ending).
+    pub fn breaks(&self, meter: Meter) -> Vec<Break> {
+        let pattern = meter.pattern();
+        let stresses = self.stresses();
+        let mut breaks = Vec::new();
+        let feminine = meter.foot.is_rising()
+            && stresses.len() == pattern.len() + 1
+            && stresses.last() != Some(&Stress::Strong);
+        if stresses.len() != pattern.len() && !feminine {
+            breaks.push(Break::Length {
+                expected: pattern.len(),
+                found: stresses.len(),
+            });
+        }
+        let words = self
+            .words
+            .iter()
+            .flat_map(|(w, s)| std::iter::repeat_n(w, s.len()));
+        for (syllable, ((found, expected), word)) in
+            stresses.iter().zip(&pattern).zip(words).enumerate()
+        {
+            if found.conflicts(*expected) {
+                breaks.push(Break::Stress {
+                    syllable,
+                    word: word.clone(),
+                    expected: *expected,
+                });
+            }
+        }
+        breaks
+    }
+
+    /// Check if the line fits a meter
+    pub fn fits(&self, meter: Meter) -> bool {
+        self.breaks(meter).is_empty()
+    }
+
+    /// Detect the meter which best fits the line
+    ///
+    /// Returns `None` if the line does not fit any meter.
+    pub fn detect_meter(&self) -> Option<Meter> {
+        Foot::all()
+            .iter()
+            .flat_map(|foot| {
+                (1..=LENGTHS.len()).map(|feet| Meter::new(*foot, feet))
+            })
+            .find(|meter| self.fits(*meter))
+    }
+}
+
+impl Scanner {
+    /// Create a new verse scanner
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new verse scanner with a shared lexicon
+    pub fn with_lexicon(lex: Arc<Lexicon>) -> Self {
+        Scanner { lex: Some(lex) }
+    }
+
+    /// Scan a line of verse
+    pub fn scan_line(&self, text: &str) -> Result<Line> {
+        let lex = self.lex.clone().unwrap_or_else(lex::builtin_shared);
+        let mut words = Vec::new();
+        for token in Parser::with_lexicon(text.as_bytes(), Arc::clone(&lex)) {
+            let token = token?;
+            let word = token.text();
+            if token.chunk() == Chunk::Text
+                && word.chars().any(char::is_alphabetic)
+            {
+                let stress = word_stress(&lex, word);
+                words.push((token.into_text(), stress));
+            }
+        }
+        Ok(Line {
+            text: text.to_string(),
+            words,
+        })
+    }
+
+    /// Scan lines of verse from a reader (skipping blank lines)
+    pub fn scan_text<R>(&self, reader: R) -> Result<Vec<Line>>
+    where
+        R: BufRead,
+    {
+        let mut lines = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if !line.is_empty() {
+                lines.push(self.scan_line(line)?);
+            }
+        }
+        Ok(lines)
+    }
+}
+
+/// Get the stress of each syllable of a word
+///
+/// Monosyllables can take either stress.  Polysyllables are given a primary
+/// stress (guessed from affixes), with unstressed neighbouring syllables.
+/// Where word classes disagree (e.g. "record"), either stress is allowed.
+fn word_stress(lex: &Lexicon, word: &str) -> Vec<Stress> {
+    let syllables = syllables(word);
+    if syllables == 1 {
+        return vec![Stress::Any];
+    }
+    let lower = word.to_lowercase();
+    let first = lower.chars().next();
+    // inflected forms are stressed like their lemma
+    let mut stems: Vec<_> = lex
+        .word_entries(word)
+        .iter()
+        .map(|e| (e.lemma().to_lowercase(), Some(e.word_class())))
+        .filter(|(lemma, _wc)| lemma.chars().next() == first)
+        .collect();
+    if stems.is_empty() {
+        stems.push((lower, None));
+    }
+    let mut stress: Option<Vec<Stress>> = None;
+    for (stem, class) in stems {
+        let primary = primary_stress(&stem, class).min(syllables - 1);
+        let pattern = (0..syllables).map(|i| match i.abs_diff(primary) {
+            0 => Stress::Strong,
+            1 => Stress::Weak,
+            _ => Stress::Any,
+        });
+        match &mut stress {
+            Some(stress) => {
+                for (s, p) in stress.iter_mut().zip(pattern) {
+                    if *s != p {
+                        *s = Stress::Any;
+                    }
+                }
+            }
+            None => stress = Some(pattern.collect()),
+        }
+    }
+    stress.unwrap_or_default()
+}
+
+/// Guess the syllable with primary stress in a word
+fn primary_stress(word: &str, class: Option<WordClass>) -> usize {
+    let count = syllables(word);
+    if count == 1 {
+        return 0;
+    }
+    for (suffix, n) in PRE_STRESS_SUFFIXES {
+        if word.len() > suffix.len() + 1 && word.ends_with(suffix) && count > *n
+        {
+            return count - n - 1;
+        }
+    }
+    if STRESSED_SUFFIXES.iter().any(|s| word.ends_with(s)) {
+        return count - 1;
+    }
+    for suffix in NEUTRAL_SUFFIXES {
+        if let Some(stem) = word.strip_suffix(suffix)
+            && stem.len() > 2
+            && syllables(stem) < count
+        {
+            return primary_stress(stem, class);
+        }
+    }
+    let prefixes = match class {
+        Some(WordClass::Verb) => VERB_PREFIXES,
+        _ => PREFIXES,
+    };
+    for prefix in prefixes {
+        if let Some(stem) = word.strip_prefix(prefix)
+            && stem.len() > 2
+        {
+            return syllables(prefix);
+        }
+    }
+    count.saturating_sub(3)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scan() {
+        let lex = lex::builtin();
+        let marks = |word| {
+            word_stress(lex, word)
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<String>()
+        };
+        assert_eq!(marks("summer"), "/x");
+        assert_eq!(marks("forgetting"), "x/x");
+        assert_eq!(marks("melody"), "/x-");
+        assert_eq!(marks("record"), "--");
+        assert_eq!(marks("nation"), "/x");
+        let scanner = Scanner::new();
+        let line = scanner
+            .scan_line("Shall I compare thee to a summer's day?")
+            .unwrap();
+        assert_eq!(line.syllables(), 10);
+        assert!(line.fits(Meter::IAMBIC_PENTAMETER));
+        assert_eq!(line.detect_meter(), Some(Meter::IAMBIC_PENTAMETER));
+        let line = scanner.scan_line("Summer is here").unwrap();
+        assert_eq!(
+            line.breaks(Meter::IAMBIC_PENTAMETER),
+            [
+                Break::Length {
+                    expected: 10,
+                    found: 4
+                },
+                Break::Stress {
+                    syllable: 0,
+                    word: "Summer".to_string(),
+                    expected: Stress::Weak,
+                },
+                Break::Stress {
+                    syllable: 1,
+                    word: "Summer".to_string(),
+                    expected: Stress::Strong,
+                },
+            ]
+        );
+        let meter: Meter = "trochaic tetrameter".parse().unwrap();
+        assert_eq!(meter, Meter::new(Foot::Trochee, 4));
+    }
+
+    #[test]
+    fn names() {
+        for (name, meter) in [
+            ("iambic pentameter", Some(Meter::IAMBIC_PENTAMETER)),
+            ("Dactylic-Hexameter", Some(Meter::new(Foot::Dactyl, 6))),
+            ("anapestic monometer", Some(Meter::new(Foot::Anapest, 1))),
+            ("trochaic octameter", Some(Meter::new(Foot::Trochee, 8))),
+            ("iambic", None),
+            ("iambic nonameter", None),
+            ("spondaic dimeter", None),
+            ("", None),
+        ] {
+            assert_eq!(name.parse::<Meter>().ok(), meter, "{name}");
+        }
+        assert!(matches!("iamb".parse::<Meter>(), Err(Error::Meter(_))));
+        for (meter, name) in [
+            (Meter::IAMBIC_PENTAMETER, "iambic pentameter"),
+            (Meter::new(Foot::Dactyl, 8), "dactylic octameter"),
+            (Meter::new(Foot::Trochee, 9), "trochaic (9 feet)"),
+            (Meter::new(Foot::Anapest, 0), "anapestic (0 feet)"),
+        ] {
+            assert_eq!(meter.to_string(), name);
+        }
+        assert_eq!(Meter::new(Foot::Anapest, 0).pattern(), []);
+        assert_eq!(Meter::new(Foot::Dactyl, 2).pattern().len(), 6);
+    }
+
+    #[test]
+    fn stress() {
+        for (word, class, primary) in [
+            ("photography", None, 1),
+            ("electricity", None, 2),
+            ("engineer", None, 2),
+            ("hopefulness", None, 0),
+            ("unhappy", None, 1),
+            ("record", Some(WordClass::Verb), 1),
+            ("record", Some(WordClass::Noun), 0),
+            ("cat", None, 0),
+            ("", None, 0),
+        ] {
+            assert_eq!(primary_stress(word, class), primary, "{word}");
+        }
+    }
+
+    #[test]
+    fn breaks() {
+        let scanner = Scanner::new();
+        // feminine endings are allowed only in rising meters
+        let line = scanner
+            .scan_line(
+                "To be, or not to be, that is the \
+            question",
+            )
+            .unwrap();
+        assert_eq!(line.syllables(), 11);
+        assert!(line.fits(Meter::IAMBIC_PENTAMETER));
+        assert!(!line.fits(Meter::new(Foot::Trochee, 5)));
+        let line = scanner
+            .scan_line("Tell me not, in mournful numbers")
+            .unwrap();
+        assert_eq!(line.detect_meter(), Some(Meter::new(Foot::Trochee, 4)));
+        // a feminine ending must be unstressed
+        let line = scanner.scan_line("the day is done, engineer").unwrap();
+        assert!(!line.fits(Meter::new(Foot::Iamb, 3)));
+        // numbers and punctuation are not scanned
+        let line = scanner.scan_line("42 -- !").unwrap();
+        assert_eq!(line.syllables(), 0);
+        assert_eq!(line.detect_meter(), None);
+        assert_eq!(
+            line.breaks(Meter::new(Foot::Iamb, 1)),
+            [Break::Length {
+                expected: 2,
+                found: 0
+            }]
+        );
+        let lines = scanner
+            .scan_text("  The cat \n\n\t\nsat down\n".as_bytes())
+            .unwrap();
+        let text: Vec<_> = lines.iter().map(Line::text).collect();
+        assert_eq!(text, ["The cat", "sat down"]);
+        assert!(scanner.scan_text(&[0xFF, b'\n'][..]).is_err());
+        assert!(scanner.scan_text("".as_bytes()).unwrap().is_empty());
+    }
+}
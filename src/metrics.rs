@@ -0,0 +1,239 @@
+use crate::Result;
+use crate::kind::Kind;
+use crate::lex::{self, Lexicon};
+use crate::sentence::Sentences;
+use crate::word::syllables;
+use std::io::BufRead;
+use std::sync::Arc;
+
+/// Suffixes not counted as syllables of complex words (Gunning Fog)
+const FOG_SUFFIXES: &[&str] = &["es", "ed", "ing"];
+
+/// Readability metrics of a text
+///
+/// Counts of sentences, words and syllables are accumulated by parsing
+/// text, and readability scores are calculated from them.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Readability {
+    /// Sentence count
+    sentences: usize,
+    /// Word count
+    words: usize,
+    /// Letter count
+    letters: usize,
+    /// Syllable count
+    syllables: usize,
+    /// Count of words with 3 or more syllables
+    polysyllables: usize,
+    /// Count of complex words (Gunning Fog)
+    complex: usize,
+    /// Lexicon for parsing (built-in if not set)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lex: Option<Arc<Lexicon>>,
+}
+
+impl Readability {
+    /// Create new readability metrics
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create new readability metrics, parsing with a shared lexicon
+    pub fn with_lexicon(lex: Arc<Lexicon>) -> Self {
+        Readability {
+            lex: Some(lex),
+            ..Default::default()
+        }
+    }
+
+    /// Parse text from a reader
+    pub fn parse_text<R>(&mut self, reader: R) -> Result<()>
+    where
+        R: BufRead,
+    {
+        let lex = self.lex.clone().unwrap_or_else(lex::builtin_shared);
+        for sentence in Sentences::with_lexicon(reader, lex) {
+            let sentence = sentence?;
+            let mut words = 0;
+            for (word, kind) in sentence.words() {
+                if word.chars().any(char::is_alphabetic) {
+                    self.count_word(word, kind);
+                    words += 1;
+                }
+            }
+            if words > 0 {
+                self.sentences += 1;
+            }
+        }
+        Ok(())
+    }
+
+    /// Merge counts of other metrics into these
+    pub fn merge(&mut self, other: &Readability) {
+        self.sentences += other.sentences;
+        self.words += other.words;
+        self.letters += other.letters;
+        self.syllables += other.syllables;
+        self.polysyllables += other.polysyllables;
+        self.complex += other.complex;
+    }
+
+    /// Count one word
+    fn count_word(&mut self, word: &str, kind: Kind) {
+        let syllables = syllables(word);
+        self.words += 1;
+        self.letters += word.chars().filter(|c| c.is_alphabetic()).count();
+        self.syllables += syllables;
+        if syllables >= 3 {
+            self.polysyllables += 1;
+            if is_complex(word, kind) {
+                self.complex += 1;
+            }
+        }
+    }
+
+    /// Get sentence count
+    pub fn sentences(&self) -> usize {
+        self.sentences
+    }
+
+    /// Get word count
+    pub fn words(&self) -> usize {
+        self.words
+    }
+
+    /// Get syllable count
+    pub fn syllables(&self) -> usize {
+        self.syllables
+    }
+
+    /// Get count of words with 3 or more syllables
+    pub fn polysyllables(&self) -> usize {
+        self.polysyllables
+    }
+
+    /// Get average sentence length (words)
+    pub fn avg_sentence_length(&self) -> Option<f64> {
+        ratio(self.words, self.sentences)
+    }
+
+    /// Get average word length (letters)
+    pub fn avg_word_length(&self) -> Option<f64> {
+        ratio(self.letters, self.words)
+    }
+
+    /// Get average syllables per word
+    pub fn avg_syllables(&self) -> Option<f64> {
+        ratio(self.syllables, self.words)
+    }
+
+    /// Get Flesch reading ease score (higher is easier)
+    pub fn flesch_reading_ease(&self) -> Option<f64> {
+        let wps = self.avg_sentence_length()?;
+        let spw = self.avg_syllables()?;
+        Some(206.835 - 1.015 * wps - 84.6 * spw)
+    }
+
+    /// Get Flesch-Kincaid grade level
+    pub fn flesch_kincaid(&self) -> Option<f64> {
+        let wps = self.avg_sentence_length()?;
+        let spw = self.avg_syllables()?;
+        Some(0.39 * wps + 11.8 * spw - 15.59)
+    }
+
+    /// Get Gunning Fog index (grade level)
+    ///
+    /// Complex words have 3 or more syllables, not counting proper nouns or
+    /// words which only reach 3 with an `-es`, `-ed` or `-ing` suffix.
+    pub fn gunning_fog(&self) -> Option<f64> {
+        let wps = self.avg_sentence_length()?;
+        let complex = ratio(self.complex, self.words)?;
+        Some(0.4 * (wps + 100.0 * complex))
+    }
+
+    /// Get SMOG grade level
+    ///
+    /// SMOG is intended for texts of 30 or more sentences.
+    pub fn smog(&self) -> Option<f64> {
+        let poly = ratio(self.polysyllables * 30, self.sentences)?;
+        Some(1.0430 * poly.sqrt() + 3.1291)
+    }
+}
+
+/// Calculate a ratio of two counts
+fn ratio(num: usize, den: usize) -> Option<f64> {
+    (den > 0).then(|| num as f64 / den as f64)
+}
+
+/// Check if a polysyllable is a complex word (Gunning Fog)
+fn is_complex(word: &str, kind: Kind) -> bool {
+    if kind == Kind::Proper {
+        return false;
+    }
+    let word = word.to_lowercase();
+    FOG_SUFFIXES
+        .iter()
+        .filter_map(|s| word.strip_suffix(s))
+        .all(|stem| syllables(stem) >= 3)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn readability() {
+        let text = "The cat sat on the mat. It was a beautiful, \
+            extraordinary day! Nobody complained.";
+        let mut metrics = Readability::new();
+        metrics.parse_text(text.as_bytes()).unwrap();
+        assert_eq!(metrics.sentences(), 3);
+        assert_eq!(metrics.words(), 14);
+        assert_eq!(metrics.polysyllables(), 3);
+        let wps = metrics.avg_sentence_length().unwrap();
+        assert!((wps - 14.0 / 3.0).abs() < 1e-9);
+        let grade = metrics.flesch_kincaid().unwrap();
+        assert!(grade > 0.0 && grade < 10.0, "{grade}");
+        assert!(metrics.flesch_reading_ease().unwrap() > 50.0);
+        assert!(metrics.gunning_fog().unwrap() > wps * 0.4);
+        assert!(metrics.smog().unwrap() > 3.1291);
+        assert_eq!(Readability::new().flesch_kincaid(), None);
+        let mut total = metrics.clone();
+        total.merge(&metrics);
+        assert_eq!(total.sentences(), 6);
+        assert_eq!(total.avg_sentence_length(), Some(wps));
+        let mut metrics = Readability::new();
+        metrics.parse_text("".as_bytes()).unwrap();
+        metrics.parse_text("42! ... 3.14?".as_bytes()).unwrap();
+        assert_eq!((metrics.sentences(), metrics.words()), (0, 0));
+        for value in [
+            metrics.avg_sentence_length(),
+            metrics.avg_word_length(),
+            metrics.avg_syllables(),
+            metrics.flesch_reading_ease(),
+            metrics.gunning_fog(),
+            metrics.smog(),
+        ] {
+            assert_eq!(value, None);
+        }
+        // numbers are not words
+        metrics.parse_text("Go now, 42 times".as_bytes()).unwrap();
+        assert_eq!((metrics.sentences(), metrics.words()), (1, 3));
+        assert_eq!(metrics.avg_word_length(), Some(10.0 / 3.0));
+        assert_eq!(metrics.polysyllables(), 0);
+        assert_eq!(metrics.smog(), Some(3.1291));
+        assert!(metrics.parse_text(&[b'a', 0xFF][..]).is_err());
+        for (word, kind, complex) in [
+            ("beautiful", Kind::Lexicon, true),
+            ("educated", Kind::Lexicon, true),
+            ("excited", Kind::Lexicon, false),
+            ("complicated", Kind::Lexicon, true),
+            ("Canada", Kind::Proper, false),
+            ("Canada", Kind::Lexicon, true),
+            ("amusing", Kind::Lexicon, false),
+        ] {
+            assert_eq!(is_complex(word, kind), complex, "{word}");
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::Result;
+use crate::lex::{self, Lexicon, make_word};
+use crate::sentence::Sentences;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+use std::sync::Arc;
+use yansi::Paint;
+
+/// Minimum n-gram length
+const MIN_N: usize = 2;
+
+/// Maximum n-gram length
+const MAX_N: usize = 3;
+
+/// N-gram tally entry
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NgramEntry {
+    /// Seen count
+    seen: usize,
+    /// Words
+    words: Vec<String>,
+}
+
+/// N-gram tally (bigrams and trigrams)
+///
+/// N-grams never span sentences.
+#[derive(Clone, Default)]
+pub struct NgramTally {
+    /// N-grams in tally (keyed by lowercase words)
+    ngrams: HashMap<Vec<String>, NgramEntry>,
+    /// Lexicon for parsing (built-in if not set)
+    lex: Option<Arc<Lexicon>>,
+}
+
+impl fmt::Display for NgramEntry {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "{:5} {}",
+            self.seen.bright().yellow(),
+            self.words.join(" ")
+        )
+    }
+}
+
+impl NgramEntry {
+    /// Get seen count
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// Get words
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Get number of words
+    pub fn n(&self) -> usize {
+        self.words.len()
+    }
+}
+
+impl NgramTally {
+    /// Create a new n-gram tally
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new n-gram tally, parsing with a shared lexicon
+    pub fn with_lexicon(lex: Arc<Lexicon>) -> Self {
+        NgramTally {
+            lex: Some(lex),
+            ..Default::default()
+        }
+    }
+
+    /// Parse text from a reader
+    pub fn parse_text<R>(&mut self, reader: R) -> Result<()>
+    where
+        R: BufRead,
+    {
+        let lex = self.lex.clone().unwrap_or_else(lex::builtin_shared);
+        for sentence in Sentences::with_lexicon(reader, lex) {
+            let sentence = sentence?;
+            let words: Vec<_> = sentence.words().map(|(w, _k)| w).collect();
+            for n in MIN_N..=MAX_N {
+                for words in words.windows(n) {
+                    self.tally_ngram(words);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Tally one n-gram
+    fn tally_ngram(&mut self, words: &[&str]) {
+        let key: Vec<_> = words.iter().map(|w| make_word(w)).collect();
+        self.ngrams
+            .entry(key)
+            .or_insert_with(|| NgramEntry {
+                seen: 0,
+                words: words.iter().map(|w| w.to_string()).collect(),
+            })
+            .seen += 1;
+    }
+
+    /// Merge another n-gram tally into this one
+    pub fn merge(&mut self, other: NgramTally) {
+        for (key, entry) in other.ngrams {
+            self.ngrams
+                .entry(key)
+                .and_modify(|e| e.seen += entry.seen)
+                .or_insert(entry);
+        }
+    }
+
+    /// Get the number of n-grams (all lengths)
+    pub fn len(&self) -> usize {
+        self.ngrams.len()
+    }
+
+    /// Check if n-gram tally is empty
+    pub fn is_empty(&self) -> bool {
+        self.ngrams.is_empty()
+    }
+
+    /// Get a Vec of n-gram entries with `n` words (most frequent first)
+    ///
+    /// Only bigrams (2) and trigrams (3) are tallied.
+    pub fn into_ngram_entries(self, n: usize) -> Vec<NgramEntry> {
+        let mut entries: Vec<_> =
+            self.ngrams.into_values().filter(|e| e.n() == n).collect();
+        entries.sort_by(|a, b| b.seen.cmp(&a.seen).then(a.cmp(b)));
+        entries
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ngrams() {
+        let text = "The old man sat. The old man slept. Old man river.";
+        let mut tally = NgramTally::new();
+        tally.parse_text(text.as_bytes()).unwrap();
+        let bigrams = tally.clone().into_ngram_entries(2);
+        assert_eq!(bigrams[0].words(), ["old", "man"]);
+        assert_eq!(bigrams[0].seen(), 3);
+        assert_eq!(bigrams[1].words(), ["The", "old"]);
+        assert_eq!(bigrams[1].seen(), 2);
+        // no bigrams across sentences
+        assert!(!bigrams.iter().any(|e| e.words() == ["sat", "The"]));
+        let trigrams = tally.into_ngram_entries(3);
+        assert_eq!(trigrams[0].words(), ["The", "old", "man"]);
+        assert_eq!(trigrams[0].seen(), 2);
+        assert_eq!(trigrams.len(), 4);
+        let mut tally = NgramTally::new();
+        tally.parse_text("".as_bytes()).unwrap();
+        tally.parse_text("Hello! Go. ... ?".as_bytes()).unwrap();
+        assert!(tally.is_empty());
+        // numbers are words, but punctuation is not
+        tally
+            .parse_text("In 1999, it -- rained".as_bytes())
+            .unwrap();
+        assert_eq!(tally.len(), 5);
+        let mut other = NgramTally::new();
+        other.parse_text("IN 1999 we left.".as_bytes()).unwrap();
+        tally.merge(other);
+        let bigrams = tally.clone().into_ngram_entries(2);
+        assert_eq!(bigrams[0].words(), ["In", "1999"]);
+        assert_eq!(bigrams[0].seen(), 2);
+        // ties are sorted by words
+        let words: Vec<_> =
+            bigrams[1..].iter().map(|e| e.words().join(" ")).collect();
+        assert_eq!(words, ["1999 it", "1999 we", "it rained", "we left"]);
+        assert!(tally.clone().into_ngram_entries(1).is_empty());
+        assert!(tally.into_ngram_entries(4).is_empty());
+        assert!(NgramTally::new().parse_text(&[b'a', 0xFF][..]).is_err());
+    }
+}
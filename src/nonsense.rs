@@ -0,0 +1,383 @@
+use crate::grammar::starts_with_vowel_sound;
+use crate::keyword;
+use crate::lex::{self, Lexicon};
+use crate::word::{Inflection, Lexeme, WordAttr, WordClass};
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Determiners for singular nouns (`a` becomes `an` before a vowel sound)
+const SINGULAR_DETERMINERS: &[&str] = &["the", "a", "this", "that", "every"];
+
+/// Determiners for plural nouns
+const PLURAL_DETERMINERS: &[&str] = &["the", "some", "these", "those", "many"];
+
+/// Sentence template (word classes)
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    /// Word class of each slot
+    slots: Vec<WordClass>,
+}
+
+/// Grammatical number
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Number {
+    /// Singular
+    Singular,
+    /// Plural
+    Plural,
+}
+
+/// Words of one class, weighted by frequency
+struct Pool {
+    /// Lexemes
+    words: Vec<Lexeme>,
+    /// Cumulative weights
+    weights: Vec<u64>,
+}
+
+/// Nonsense sentence generator
+///
+/// Words are chosen at random from the lexicon, weighted by their frequency
+/// in the built-in word frequency model.  Nouns and verbs are inflected to
+/// agree in number.
+pub struct Generator {
+    /// Lexicon for choosing words
+    lex: Arc<Lexicon>,
+    /// Random number generator
+    rng: fastrand::Rng,
+    /// Word pools for each class
+    pools: HashMap<WordClass, Pool>,
+}
+
+impl Default for Template {
+    fn default() -> Self {
+        use WordClass::*;
+        Template {
+            slots: vec![Determiner, Adjective, Noun, Verb, Adverb],
+        }
+    }
+}
+
+impl FromStr for Template {
+    type Err = Error;
+
+    /// Parse a template from word class codes (e.g. `D A N V Av`)
+    fn from_str(val: &str) -> Result<Self> {
+        let slots = val
+            .split_whitespace()
+            .map(WordClass::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        if slots.is_empty() {
+            return Err(Error::WordClass(val.to_string()));
+        }
+        Ok(Template { slots })
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        for (i, wc) in self.slots.iter().enumerate() {
+            if i > 0 {
+                write!(fmt, " ")?;
+            }
+            write!(fmt, "{wc}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Template {
+    /// Get word class of each slot
+    pub fn slots(&self) -> &[WordClass] {
+        &self.slots
+    }
+}
+
+impl Pool {
+    /// Make a pool of words of one class
+    fn new(lex: &Lexicon, wc: WordClass) -> Self {
+        let freq = keyword::builtin();
+        let mut words = Vec::new();
+        let mut weights = Vec::new();
+        let mut total = 0;
+        for word in lex.iter().filter(|w| is_usable(lex, w, wc)) {
+            // square root keeps common words from crowding out the rest
+            total += freq.count(word.lemma()).isqrt() + 1;
+            words.push(word.clone());
+            weights.push(total);
+        }
+        Pool { words, weights }
+    }
+
+    /// Choose a random word
+    fn choose(&self, rng: &mut fastrand::Rng) -> Option<&Lexeme> {
+        let total = *self.weights.last()?;
+        let n = rng.u64(..total);
+        let i = self.weights.partition_point(|w| *w <= n);
+        self.words.get(i)
+    }
+}
+
+/// Check if a lexeme is usable for generating sentences
+fn is_usable(lex: &Lexicon, word: &Lexeme, wc: WordClass) -> bool {
+    word.word_class() == wc
+        && word.lemma().chars().all(|c| c.is_ascii_lowercase())
+        && (!is_content(wc) || !is_function_word(lex, word.lemma()))
+        && !word.attributes().any(|a| {
+            a == WordAttr::Proper
+                || (wc == WordClass::Verb && a == WordAttr::Auxiliary)
+        })
+        // verbs like "be" have more than one present form
+        && (wc != WordClass::Verb
+            || word
+                .labeled_forms()
+                .iter()
+                .filter(|f| f.feature() == Inflection::Present)
+                .count()
+                == 1)
+}
+
+/// Check if a word class is for content words
+fn is_content(wc: WordClass) -> bool {
+    matches!(
+        wc,
+        WordClass::Noun
+            | WordClass::Verb
+            | WordClass::Adjective
+            | WordClass::Adverb
+    )
+}
+
+/// Check if a word has any function word entries (e.g. "in")
+fn is_function_word(lex: &Lexicon, word: &str) -> bool {
+    lex.word_entries(word).iter().any(|e| {
+        matches!(
+            e.word_class(),
+            WordClass::Conjunction
+                | WordClass::Determiner
+                | WordClass::Preposition
+                | WordClass::Pronoun
+        )
+    })
+}
+
+/// Get the first form of a lexeme with an inflection
+fn inflected(word: &Lexeme, feature: Inflection) -> Option<String> {
+    word.labeled_forms()
+        .into_iter()
+        .find(|f| f.feature() == feature)
+        .map(|f| f.text().to_string())
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self::with_lexicon(lex::builtin_shared())
+    }
+}
+
+impl Generator {
+    /// Create a new generator (using the built-in lexicon)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new generator with a shared lexicon
+    pub fn with_lexicon(lex: Arc<Lexicon>) -> Self {
+        Generator {
+            lex,
+            rng: fastrand::Rng::new(),
+            pools: HashMap::new(),
+        }
+    }
+
+    /// Seed the random number generator (for repeatable output)
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = fastrand::Rng::with_seed(seed);
+        self
+    }
+
+    /// Generate a sentence from a template
+    ///
+    /// A determiner sets the number of the following noun, and a verb
+    /// agrees with the previous noun.  Slots with no usable words in the
+    /// lexicon are skipped.
+    pub fn sentence(&mut self, template: &Template) -> String {
+        let past = self.rng.u8(..4) == 0;
+        let mut number = self.random_number();
+        let mut words: Vec<String> = Vec::new();
+        let mut det = None;
+        for wc in template.slots() {
+            let word = match wc {
+                WordClass::Determiner => {
+                    number = self.random_number();
+                    det = Some((words.len(), number));
+                    Some(self.determiner(number))
+                }
+                WordClass::Noun => {
+                    let noun = self.noun(&mut number);
+                    // some nouns have only one number (e.g. "pants")
+                    if let Some((i, n)) = det.take()
+                        && n != number
+                    {
+                        words[i] = self.determiner(number);
+                    }
+                    noun
+                }
+                WordClass::Verb => self.verb(number, past),
+                _ => self.choose(*wc).map(|w| w.lemma().to_string()),
+            };
+            words.extend(word);
+        }
+        for i in 1..words.len() {
+            if words[i - 1] == "a"
+                && starts_with_vowel_sound(&words[i]) == Some(true)
+            {
+                words[i - 1] = "an".to_string();
+            }
+        }
+        let mut text = words.join(" ");
+        if let Some(c) = text.chars().next() {
+            text.replace_range(..c.len_utf8(), &c.to_uppercase().to_string());
+            text.push('.');
+        }
+        text
+    }
+
+    /// Choose a random grammatical number
+    fn random_number(&mut self) -> Number {
+        if self.rng.u8(..3) == 0 {
+            Number::Plural
+        } else {
+            Number::Singular
+        }
+    }
+
+    /// Choose a random word of one class
+    fn choose(&mut self, wc: WordClass) -> Option<&Lexeme> {
+        let lex = &self.lex;
+        let pool = self.pools.entry(wc).or_insert_with(|| Pool::new(lex, wc));
+        pool.choose(&mut self.rng)
+    }
+
+    /// Choose a determiner
+    fn determiner(&mut self, number: Number) -> String {
+        let dets = match number {
+            Number::Singular => SINGULAR_DETERMINERS,
+            Number::Plural => PLURAL_DETERMINERS,
+        };
+        dets[self.rng.usize(..dets.len())].to_string()
+    }
+
+    /// Choose a noun (adjusting number if it has no plural)
+    fn noun(&mut self, number: &mut Number) -> Option<String> {
+        let word = self.choose(WordClass::Noun)?.clone();
+        if word.attributes().any(|a| a == WordAttr::PluraleTantum) {
+            *number = Number::Plural;
+            return Some(word.lemma().to_string());
+        }
+        if *number == Number::Plural
+            && let Some(plural) = inflected(&word, Inflection::Plural)
+        {
+            return Some(plural);
+        }
+        *number = Number::Singular;
+        Some(word.lemma().to_string())
+    }
+
+    /// Choose a verb (agreeing with number)
+    fn verb(&mut self, number: Number, past: bool) -> Option<String> {
+        let word = self.choose(WordClass::Verb)?.clone();
+        let feature = match (past, number) {
+            (true, _) => Inflection::PastTense,
+            (false, Number::Singular) => Inflection::Present,
+            (false, Number::Plural) => Inflection::Base,
+        };
+        inflected(&word, feature).or_else(|| Some(word.lemma().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nonsense() {
+        let lex = Lexicon::from_csv(
+            "ox:N,oxen\nowl:N\npants:N.p\nwalk:V\nbe:V,am,are,is,was,were,been,being\n\
+            old:A\nslowly:Av",
+        )
+        .unwrap();
+        let template: Template = "D A N V Av".parse().unwrap();
+        assert_eq!(template, Template::default());
+        assert_eq!(template.to_string(), "D A N V Av");
+        assert!("D X".parse::<Template>().is_err());
+        let mut generator = Generator::with_lexicon(Arc::new(lex)).seed(1);
+        let template: Template = "D N V".parse().unwrap();
+        for _i in 0..50 {
+            let text = generator.sentence(&template);
+            let words: Vec<_> = text.trim_end_matches('.').split(' ').collect();
+            assert_eq!(words.len(), 3, "{text}");
+            assert!(!text.contains("be") && !text.contains(" is"), "{text}");
+            match words[1] {
+                "ox" | "owl" => assert_ne!(words[2], "walk", "{text}"),
+                "oxen" | "owls" | "pants" => {
+                    assert_ne!(words[2], "walks", "{text}");
+                    assert!(!matches!(words[0], "A" | "An"), "{text}");
+                }
+                _ => panic!("{text}"),
+            }
+            if words[1] == "owl" {
+                assert_ne!(words[0], "A", "{text}");
+            }
+        }
+    }
+
+    #[test]
+    fn templates() {
+        let template: Template = " N\tV  Av ".parse().unwrap();
+        assert_eq!(template.to_string(), "N V Av");
+        assert_eq!(template.slots().len(), 3);
+        for val in ["", "  ", "N V x", "n"] {
+            assert!(
+                matches!(val.parse::<Template>(), Err(Error::WordClass(_))),
+                "{val:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn usable() {
+        let lex = Lexicon::from_csv(
+            "Ann:N\nbin:N\ncan:V.a,could\ndo:V\nin:N\nin:P\n\
+            jazz:N.n\nx-ray:N",
+        )
+        .unwrap();
+        let usable: Vec<_> = lex
+            .iter()
+            .filter(|w| {
+                is_usable(&lex, w, WordClass::Noun)
+                    || is_usable(&lex, w, WordClass::Verb)
+            })
+            .map(|w| w.to_string())
+            .collect();
+        assert_eq!(usable, ["bin:N", "do:V"]);
+        // slots with no usable words are skipped
+        let mut generator = Generator::with_lexicon(Arc::new(lex)).seed(7);
+        let template = "V".parse().unwrap();
+        assert_eq!(generator.sentence(&template), "Do.");
+        let template = "Av Av".parse().unwrap();
+        assert_eq!(generator.sentence(&template), "");
+        let template: Template = "D A N".parse().unwrap();
+        for _i in 0..20 {
+            let text = generator.sentence(&template);
+            assert!(text.ends_with(" bin.") || text.ends_with(" bins."));
+        }
+        let mut a = Generator::new().seed(42);
+        let mut b = Generator::new().seed(42);
+        let template = Template::default();
+        assert_eq!(a.sentence(&template), b.sentence(&template));
+    }
+}
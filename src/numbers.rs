@@ -0,0 +1,253 @@
+/// Cardinal words below twenty
+const UNITS: &[&str] = &[
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+/// Ordinal words below twenty
+const UNIT_ORDINALS: &[&str] = &[
+    "zeroth",
+    "first",
+    "second",
+    "third",
+    "fourth",
+    "fifth",
+    "sixth",
+    "seventh",
+    "eighth",
+    "ninth",
+    "tenth",
+    "eleventh",
+    "twelfth",
+    "thirteenth",
+    "fourteenth",
+    "fifteenth",
+    "sixteenth",
+    "seventeenth",
+    "eighteenth",
+    "nineteenth",
+];
+
+/// Cardinal words for tens (twenty and up)
+const TENS: &[&str] = &[
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty",
+    "ninety",
+];
+
+/// Ordinal words for tens (twenty and up)
+const TEN_ORDINALS: &[&str] = &[
+    "twentieth",
+    "thirtieth",
+    "fortieth",
+    "fiftieth",
+    "sixtieth",
+    "seventieth",
+    "eightieth",
+    "ninetieth",
+];
+
+/// Scale words, with ordinals and values
+const SCALES: &[(&str, &str, u64)] = &[
+    ("hundred", "hundredth", 100),
+    ("thousand", "thousandth", 1_000),
+    ("million", "millionth", 1_000_000),
+    ("billion", "billionth", 1_000_000_000),
+    ("trillion", "trillionth", 1_000_000_000_000),
+];
+
+/// Spelled-out number ("twenty-three", "hundredth")
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NumberWord {
+    /// Integer value
+    value: u64,
+    /// Ordinal ("third") instead of cardinal ("three")
+    ordinal: bool,
+}
+
+/// One word of a spelled-out number
+#[derive(Clone, Copy, PartialEq)]
+enum Part {
+    /// Zero to nineteen
+    Unit(u64),
+    /// Twenty, thirty, etc.
+    Tens(u64),
+    /// Hundred, thousand, etc.
+    Scale(u64),
+}
+
+impl NumberWord {
+    /// Get integer value
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    /// Check if number is ordinal ("first") instead of cardinal ("one")
+    pub fn is_ordinal(self) -> bool {
+        self.ordinal
+    }
+}
+
+/// Look up one word of a spelled-out number
+///
+/// Returns the part and whether it is ordinal.
+fn lookup(word: &str) -> Option<(Part, bool)> {
+    let find = |words: &[&str]| {
+        words.iter().position(|w| w.eq_ignore_ascii_case(word))
+    };
+    if let Some(i) = find(UNITS) {
+        return Some((Part::Unit(i as u64), false));
+    }
+    if let Some(i) = find(UNIT_ORDINALS) {
+        return Some((Part::Unit(i as u64), true));
+    }
+    if let Some(i) = find(TENS) {
+        return Some((Part::Tens(20 + 10 * i as u64), false));
+    }
+    if let Some(i) = find(TEN_ORDINALS) {
+        return Some((Part::Tens(20 + 10 * i as u64), true));
+    }
+    for (card, ord, val) in SCALES {
+        if card.eq_ignore_ascii_case(word) {
+            return Some((Part::Scale(*val), false));
+        } else if ord.eq_ignore_ascii_case(word) {
+            return Some((Part::Scale(*val), true));
+        }
+    }
+    None
+}
+
+/// Parse a spelled-out number
+///
+/// Words can be separated by spaces or hyphens, with "and" between them:
+/// "twenty-three", "first" and "one hundred and fifth" are all numbers.
+/// Only the last word can be ordinal.
+pub fn parse(text: &str) -> Option<NumberWord> {
+    let mut words = text
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .filter(|w| !w.is_empty())
+        .peekable();
+    let mut total = 0;
+    let mut group = 0;
+    let mut last: Option<Part> = None;
+    let mut last_scale = u64::MAX;
+    let mut ordinal = false;
+    let mut count = 0;
+    while let Some(word) = words.next() {
+        if ordinal {
+            return None;
+        }
+        if word.eq_ignore_ascii_case("and") {
+            if count == 0 || words.peek().is_none() {
+                return None;
+            }
+            continue;
+        }
+        let (part, ord) = lookup(word)?;
+        ordinal = ord;
+        match (last, part) {
+            // zero only stands alone
+            (_, Part::Unit(0)) if count > 0 => return None,
+            (Some(Part::Unit(0)), _) => return None,
+            (None | Some(Part::Scale(_)), Part::Unit(n) | Part::Tens(n)) => {
+                group += n
+            }
+            (Some(Part::Tens(_)), Part::Unit(n)) if n < 10 => group += n,
+            (Some(Part::Unit(_) | Part::Tens(_)), Part::Scale(100))
+                if group < 100 =>
+            {
+                group *= 100
+            }
+            (Some(Part::Unit(_) | Part::Tens(_)), Part::Scale(s))
+            | (Some(Part::Scale(100)), Part::Scale(s))
+                if s > 100 && s < last_scale =>
+            {
+                total += group * s;
+                group = 0;
+                last_scale = s;
+            }
+            // a bare scale, such as "hundred" or "thousandth"
+            (None, Part::Scale(s)) => group = s,
+            _ => return None,
+        }
+        last = Some(part);
+        count += 1;
+    }
+    (count > 0).then_some(NumberWord {
+        value: total + group,
+        ordinal,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        for (text, value, ordinal) in [
+            ("zero", 0, false),
+            ("first", 1, true),
+            ("Twelve", 12, false),
+            ("twenty-three", 23, false),
+            ("ninetieth", 90, true),
+            ("hundredth", 100, true),
+            ("one hundred and fifth", 105, true),
+            ("two thousand twenty-four", 2024, false),
+            ("three million four hundred thousand", 3_400_000, false),
+            ("nineteen hundred", 1900, false),
+            (" twenty -  one ", 21, false),
+            ("THOUSAND", 1_000, false),
+            ("millionth", 1_000_000, true),
+            ("ninety-nine trillion", 99_000_000_000_000, false),
+            ("one thousand and one", 1_001, false),
+            ("twelve hundred and twelve", 1_212, false),
+            ("one hundred thousand", 100_000, false),
+        ] {
+            let num = parse(text).unwrap();
+            assert_eq!(num.value(), value, "{text}");
+            assert_eq!(num.is_ordinal(), ordinal, "{text}");
+        }
+        for text in [
+            "",
+            "and",
+            "one and",
+            "first hundred",
+            "twenty twenty",
+            "three four",
+            "thousand million",
+            "zero one",
+            "eleven-ty",
+            "and one",
+            "one hundred and",
+            "one and and two",
+            "twenty tenth",
+            "one hundred hundred",
+            "one thousand thousand",
+            "five hundred twenty hundred",
+            "a million",
+            "3",
+            "-",
+        ] {
+            assert_eq!(parse(text), None, "{text}");
+        }
+    }
+}
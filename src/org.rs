@@ -0,0 +1,126 @@
+use crate::Result;
+use regex::Regex;
+use std::io::BufRead;
+use std::sync::LazyLock;
+
+/// Links (keeping description)
+static LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\[\[[^\]]*\]\[([^\]]*)\]\]|\[\[[^\]]*\]\]").unwrap()
+});
+
+/// Inline code / verbatim (`~code~` and `=verbatim=`)
+static CODE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(^|[\s(])([~=])[^\s~=][^~=]*?[~=]").unwrap());
+
+/// Bare URLs
+static URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"https?://\S+").unwrap());
+
+/// Blocks with content skipped
+const SKIPPED_BLOCKS: &[&str] = &["comment", "example", "export", "src"];
+
+/// Keywords with prose values
+const PROSE_KEYWORDS: &[&str] = &["title:", "subtitle:", "caption:"];
+
+/// Org block state
+enum Block {
+    /// Prose text
+    Prose,
+    /// Skipped block (with end marker, lowercase)
+    Skipped(String),
+    /// Drawer (`:NAME:` ... `:END:`)
+    Drawer,
+}
+
+/// Strip links, inline code and URLs from a line
+fn strip_inline(line: &str) -> String {
+    let line = LINK.replace_all(line, "$1");
+    let line = CODE.replace_all(&line, "$1");
+    URL.replace_all(&line, "").into_owned()
+}
+
+/// Check if a line is a drawer start (e.g. `:PROPERTIES:`)
+fn is_drawer(line: &str) -> bool {
+    line.len() > 2
+        && line.starts_with(':')
+        && line.ends_with(':')
+        && line[1..line.len() - 1]
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Extract prose text from Org-mode source
+///
+/// Source, example, export and comment blocks, drawers, comments and
+/// keyword lines are skipped, along with link targets and inline code.
+/// Line numbers match the source.
+pub fn extract_text<R>(reader: R) -> Result<String>
+where
+    R: BufRead,
+{
+    let mut text = String::new();
+    let mut block = Block::Prose;
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        let lower = trimmed.to_lowercase();
+        match &block {
+            Block::Skipped(end) => {
+                if lower.starts_with(end.as_str()) {
+                    block = Block::Prose;
+                }
+            }
+            Block::Drawer => {
+                if lower == ":end:" {
+                    block = Block::Prose;
+                }
+            }
+            Block::Prose => {
+                if let Some(name) = lower.strip_prefix("#+begin_") {
+                    let name = name.split_whitespace().next().unwrap_or(name);
+                    if SKIPPED_BLOCKS.contains(&name) {
+                        block = Block::Skipped(format!("#+end_{name}"));
+                    }
+                } else if let Some(keyword) = lower.strip_prefix("#+") {
+                    if let Some(kw) =
+                        PROSE_KEYWORDS.iter().find(|k| keyword.starts_with(*k))
+                    {
+                        text.push_str(&strip_inline(&trimmed[2 + kw.len()..]));
+                    }
+                } else if is_drawer(trimmed) {
+                    block = Block::Drawer;
+                } else if !(trimmed == "#"
+                    || trimmed.starts_with("# ")
+                    || trimmed == ":"
+                    || trimmed.starts_with(": "))
+                {
+                    text.push_str(&strip_inline(&line));
+                }
+            }
+        }
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extract() {
+        let org = "#+TITLE: My Notes\n#+OPTIONS: toc:nil\n\
+            * TODO Read [[https://x.io][the manual]]\n\
+            :PROPERTIES:\n:ID: 1234\n:END:\n\
+            Call ~foo()~ or =bar= daily. # not a comment\n\
+            # a comment\n\
+            #+BEGIN_SRC rust\nfn main() {}\n#+END_SRC\n\
+            See [[file:notes.org]] too.\n";
+        let text = extract_text(org.as_bytes()).unwrap();
+        assert_eq!(
+            text,
+            " My Notes\n\n* TODO Read the manual\n\n\n\n\
+            Call  or  daily. # not a comment\n\n\n\n\nSee  too.\n"
+        );
+    }
+}
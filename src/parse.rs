@@ -1,10 +1,31 @@
+use crate::abbrev::{self, Abbreviations};
 use crate::contractions;
-use crate::kind::Kind;
+use crate::gazetteer::Gazetteer;
+use crate::kind::{
+    CURRENCIES, EMOTICONS, Kind, date_prefix, is_email, is_emoji_modifier,
+    is_handle_char, is_hashtag, is_mention, is_pictograph, is_url,
+    number_prefix, number_value, time_prefix,
+};
 use crate::lex::{self, Lexicon, is_apostrophe};
+use crate::numbers;
+use crate::sentence::{Sentences, is_abbreviation};
+use std::collections::{HashSet, VecDeque};
 use std::io::{self, BufRead, Bytes};
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Longest run of characters checked for a URL or email address
+const MAX_RUN: usize = 256;
+
+/// Longest date or time checked ("September 30th, 2024")
+const MAX_DATE: usize = 20;
+
+/// Most names remembered by a parser (seen within sentences)
+const MAX_NAMES: usize = 4096;
 
 /// Character chunk types
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Chunk {
     /// Alphanumeric character or apostrophe text
     Text,
@@ -12,8 +33,93 @@ pub enum Chunk {
     Symbol,
     /// Word boundary character (whitespace, control, etc.)
     Boundary,
+    /// Emoji or emoticon (may be multiple characters)
+    Emoji,
 }
 
+/// Variant of a hyphenated compound found in a lexicon
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Compound {
+    /// Hyphenated form (e.g. "e-mail")
+    Hyphenated,
+    /// Closed form, without hyphens (e.g. "email")
+    Closed,
+    /// Open form, with spaces (e.g. "ice cream")
+    Open,
+}
+
+/// Token of parsed text
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Token {
+    /// Chunk type
+    chunk: Chunk,
+    /// Token text
+    text: String,
+    /// Word kind
+    kind: Kind,
+    /// Byte span in text
+    span: Range<usize>,
+    /// Line number (starting at 1)
+    line: usize,
+    /// Column number (characters, starting at 1)
+    column: usize,
+    /// Compound variant found in lexicon (hyphenated words only)
+    compound: Option<Compound>,
+}
+
+/// Token borrowing its text from parsed `&str` input
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TokenRef<'a> {
+    /// Chunk type
+    chunk: Chunk,
+    /// Token text
+    text: &'a str,
+    /// Word kind
+    kind: Kind,
+    /// Byte span in text
+    span: Range<usize>,
+    /// Line number (starting at 1)
+    line: usize,
+    /// Column number (characters, starting at 1)
+    column: usize,
+    /// Compound variant found in lexicon (hyphenated words only)
+    compound: Option<Compound>,
+}
+
+/// Iterator of borrowed tokens from in-memory text
+///
+/// This avoids allocating a `String` for each token.
+pub struct BorrowedTokens<'a> {
+    /// Text being parsed
+    text: &'a str,
+    /// Text parser
+    parser: Parser<&'a [u8]>,
+}
+
+/// Boxed iterator of tokens
+pub type Tokens<'a> = Box<dyn Iterator<Item = Result<Token, io::Error>> + 'a>;
+
+/// Text tokenizer
+///
+/// `Parser` is the built-in tokenizer.  Another can be substituted to change
+/// how text is split (e.g. keeping all hyphenated words whole), while still
+/// using `WordTally` or `hilite`.  Use `classify` to find word kinds.
+pub trait Tokenizer: Send + Sync {
+    /// Split text from a reader into tokens
+    fn tokenize<'a>(
+        &self,
+        reader: Box<dyn BufRead + 'a>,
+        lex: Arc<Lexicon>,
+    ) -> Tokens<'a>;
+}
+
+/// Built-in tokenizer, using `Parser`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ParserTokenizer;
+
 /// Splitter for separating text into characters
 struct CharSplitter<R: BufRead> {
     /// Remaining bytes of underlying reader
@@ -22,16 +128,83 @@ struct CharSplitter<R: BufRead> {
     code: Vec<u8>,
 }
 
+/// Reader over an iterator of lines
+///
+/// Each line is followed by a newline.
+pub struct LineReader<I> {
+    /// Remaining lines
+    lines: I,
+    /// Current line (with newline)
+    line: Vec<u8>,
+    /// Position in current line
+    pos: usize,
+}
+
+/// Reader which rejoins words hyphenated across line breaks
+///
+/// A word split with a hyphen at the end of a line ("inter-" / "national")
+/// is joined if the whole word is in the lexicon; otherwise the hyphen is
+/// kept.  The line break moves after the joined word, so line numbers are
+/// unchanged.
+pub struct Dehyphenate<R> {
+    /// Underlying reader
+    reader: R,
+    /// Word lexicon
+    lex: Arc<Lexicon>,
+    /// Next line (already read)
+    next: Option<String>,
+    /// Current line
+    line: String,
+    /// Position in current line
+    pos: usize,
+}
+
+/// Word with byte span in text
+pub(crate) struct SpanWord {
+    /// Word text
+    pub text: String,
+    /// Byte span in text
+    pub span: Range<usize>,
+}
+
 /// Text parser
 pub struct Parser<R: BufRead> {
     /// Word lexicon
-    lex: &'static Lexicon,
+    lex: Arc<Lexicon>,
+    /// Gazetteer of names
+    gazetteer: Option<Arc<Gazetteer>>,
+    /// Abbreviations (keeping their dots)
+    abbrevs: Arc<Abbreviations>,
     /// Text character splitter
     splitter: CharSplitter<R>,
-    /// Current text chunk
+    /// Characters read ahead of current chunk
+    ahead: VecDeque<Result<char, io::Error>>,
+    /// Current text chunk (buffer reused between chunks)
     text: String,
-    /// Processed chunks
-    chunks: Vec<Result<(Chunk, String, Kind), io::Error>>,
+    /// Scratch buffer for peeking ahead
+    scratch: String,
+    /// Processed tokens (waiting to be yielded, without text)
+    tokens: VecDeque<Result<Token, io::Error>>,
+    /// Text of processed tokens
+    arena: String,
+    /// Byte offset of arena start
+    arena_start: usize,
+    /// Byte offset of next token
+    offset: usize,
+    /// Line number of next token
+    line: usize,
+    /// Column number of next token
+    column: usize,
+    /// Next word starts a sentence
+    initial: bool,
+    /// Last word (to check for abbreviations)
+    last_word: String,
+    /// Count of consecutive newlines
+    newlines: usize,
+    /// Last character of previous token
+    last_char: char,
+    /// Proper words seen capitalized within a sentence (up to `MAX_NAMES`)
+    names: HashSet<String>,
 }
 
 impl<R> CharSplitter<R>
@@ -69,7 +242,158 @@ where
                 }
             }
         }
-        Some(Err(io::Error::other("Invalid UTF-8")))
+        Some(Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Invalid UTF-8",
+        )))
+    }
+}
+
+impl<I, S> LineReader<I>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    /// Create a new line reader
+    pub fn new<L>(lines: L) -> Self
+    where
+        L: IntoIterator<IntoIter = I>,
+    {
+        LineReader {
+            lines: lines.into_iter(),
+            line: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl<I, S> io::Read for LineReader<I>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let avail = self.fill_buf()?;
+        let len = avail.len().min(buf.len());
+        buf[..len].copy_from_slice(&avail[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<I, S> BufRead for LineReader<I>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos >= self.line.len()
+            && let Some(line) = self.lines.next()
+        {
+            self.line.clear();
+            self.line.extend_from_slice(line.as_ref().as_bytes());
+            self.line.push(b'\n');
+            self.pos = 0;
+        }
+        Ok(&self.line[self.pos.min(self.line.len())..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+impl<R> Dehyphenate<R>
+where
+    R: BufRead,
+{
+    /// Create a new dehyphenating reader
+    pub fn new(reader: R, lex: Arc<Lexicon>) -> Self {
+        Dehyphenate {
+            reader,
+            lex,
+            next: None,
+            line: String::new(),
+            pos: 0,
+        }
+    }
+
+    /// Read a line from the underlying reader
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line)? {
+            0 => Ok(None),
+            _ => Ok(Some(line)),
+        }
+    }
+
+    /// Join the next line's first word to the current line (if in lexicon)
+    fn join_next(&mut self) -> io::Result<()> {
+        let body = self.line.trim_end_matches(['\r', '\n']);
+        let Some(head) = body.strip_suffix('-') else {
+            return Ok(());
+        };
+        let first: String = head
+            .chars()
+            .rev()
+            .take_while(|c| c.is_alphabetic())
+            .collect();
+        if first.is_empty() {
+            return Ok(());
+        }
+        let hyphen = head.len();
+        self.next = self.read_line()?;
+        let Some(next) = &self.next else {
+            return Ok(());
+        };
+        let rest = next.trim_start_matches([' ', '\t']);
+        let len = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        let second = &rest[..len];
+        let word: String = first.chars().rev().chain(second.chars()).collect();
+        if second.is_empty() || !self.lex.contains(&word) {
+            return Ok(());
+        }
+        self.line.replace_range(hyphen..hyphen + 1, second);
+        let rest = &rest[len..];
+        self.next = (!rest.is_empty()).then(|| rest.to_string());
+        Ok(())
+    }
+}
+
+impl<R> io::Read for Dehyphenate<R>
+where
+    R: BufRead,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let avail = self.fill_buf()?;
+        let len = avail.len().min(buf.len());
+        buf[..len].copy_from_slice(&avail[..len]);
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl<R> BufRead for Dehyphenate<R>
+where
+    R: BufRead,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos >= self.line.len() {
+            let line = match self.next.take() {
+                Some(line) => Some(line),
+                None => self.read_line()?,
+            };
+            self.line = line.unwrap_or_default();
+            self.pos = 0;
+            self.join_next()?;
+        }
+        Ok(&self.line.as_bytes()[self.pos.min(self.line.len())..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
     }
 }
 
@@ -84,6 +408,191 @@ where
     }
 }
 
+impl Tokenizer for ParserTokenizer {
+    fn tokenize<'a>(
+        &self,
+        reader: Box<dyn BufRead + 'a>,
+        lex: Arc<Lexicon>,
+    ) -> Tokens<'a> {
+        Box::new(Parser::with_lexicon(reader, lex))
+    }
+}
+
+impl Token {
+    /// Create a new token
+    ///
+    /// The span is in bytes; line and column numbers start at 1.
+    pub fn new(
+        chunk: Chunk,
+        text: String,
+        kind: Kind,
+        span: Range<usize>,
+        line: usize,
+        column: usize,
+    ) -> Self {
+        Token {
+            chunk,
+            text,
+            kind,
+            span,
+            line,
+            column,
+            compound: None,
+        }
+    }
+
+    /// Get chunk type
+    pub fn chunk(&self) -> Chunk {
+        self.chunk
+    }
+
+    /// Get token text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get word kind
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Get byte span in text
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Get line number (starting at 1)
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get column number (characters, starting at 1)
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Get numeric value
+    ///
+    /// Formatted numbers are parsed, such as "1,234.5", "-3" or "$20", as
+    /// well as spelled-out number words ("twelve", "third").
+    pub fn value(&self) -> Option<f64> {
+        token_value(self.kind, &self.text)
+    }
+
+    /// Get compound variant found in lexicon (hyphenated words only)
+    ///
+    /// For example, "co-operate" is a `Compound::Closed` variant of
+    /// "cooperate".
+    pub fn compound(&self) -> Option<Compound> {
+        self.compound
+    }
+
+    /// Convert into token text
+    pub fn into_text(self) -> String {
+        self.text
+    }
+}
+
+impl<'a> TokenRef<'a> {
+    /// Get chunk type
+    pub fn chunk(&self) -> Chunk {
+        self.chunk
+    }
+
+    /// Get token text
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Get word kind
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Get byte span in text
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Get line number (starting at 1)
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get column number (characters, starting at 1)
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Get numeric value (see `Token::value`)
+    pub fn value(&self) -> Option<f64> {
+        token_value(self.kind, self.text)
+    }
+
+    /// Get compound variant found in lexicon (hyphenated words only)
+    pub fn compound(&self) -> Option<Compound> {
+        self.compound
+    }
+
+    /// Convert to an owned token
+    pub fn to_token(&self) -> Token {
+        Token {
+            chunk: self.chunk,
+            text: self.text.to_string(),
+            kind: self.kind,
+            span: self.span(),
+            line: self.line,
+            column: self.column,
+            compound: self.compound,
+        }
+    }
+}
+
+impl<'a> BorrowedTokens<'a> {
+    /// Create borrowed tokens from text (using the built-in lexicon)
+    pub fn new(text: &'a str) -> Self {
+        Self::with_lexicon(text, lex::builtin_shared())
+    }
+
+    /// Create borrowed tokens from text, with a shared lexicon
+    pub fn with_lexicon(text: &'a str, lex: Arc<Lexicon>) -> Self {
+        BorrowedTokens {
+            text,
+            parser: Parser::with_lexicon(text.as_bytes(), lex),
+        }
+    }
+
+    /// Use a gazetteer to find names (`Kind::Proper`)
+    pub fn gazetteer(mut self, gazetteer: Arc<Gazetteer>) -> Self {
+        self.parser = self.parser.gazetteer(gazetteer);
+        self
+    }
+
+    /// Use an abbreviation table (instead of the built-in one)
+    pub fn abbreviations(mut self, abbrevs: Arc<Abbreviations>) -> Self {
+        self.parser = self.parser.abbreviations(abbrevs);
+        self
+    }
+}
+
+impl<'a> Iterator for BorrowedTokens<'a> {
+    type Item = TokenRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // reading from a `&str` cannot fail
+        let token = self.parser.next_token()?.ok()?;
+        Some(TokenRef {
+            chunk: token.chunk,
+            text: &self.text[token.span()],
+            kind: token.kind,
+            span: token.span,
+            line: token.line,
+            column: token.column,
+            compound: token.compound,
+        })
+    }
+}
+
 impl Chunk {
     /// Determine chunk type from a single character
     fn from_char(c: char) -> Self {
@@ -91,6 +600,8 @@ impl Chunk {
             Chunk::Boundary
         } else if c.is_alphanumeric() || is_apostrophe(c) {
             Chunk::Text
+        } else if is_pictograph(c) {
+            Chunk::Emoji
         } else {
             Chunk::Symbol
         }
@@ -104,28 +615,116 @@ fn is_boundary(c: char) -> bool {
     c.is_whitespace() || c.is_control() || c == '\u{200B}' || c == '\u{FEFF}'
 }
 
-/// Check if a dot is appendable
-fn is_dot_appendable(word: &str) -> bool {
-    word.chars().count() > 0
-        && word.chars().all(|c| c.is_uppercase() || c == '.')
-        && !word.ends_with('.')
+/// Get the length (in characters) and kind of a URL or email address
+fn web_kind(run: &str) -> Option<(usize, Kind)> {
+    let word = trim_trailing(run);
+    let kind = if is_url(word) {
+        Kind::Url
+    } else if is_email(word) {
+        Kind::Email
+    } else {
+        return None;
+    };
+    Some((word.chars().count(), kind))
+}
+
+/// Get the length (in characters) and kind of a date or time
+fn date_time_kind(run: &str) -> Option<(usize, Kind)> {
+    let (found, kind) = match date_prefix(run) {
+        Some(date) => (date, Kind::Date),
+        None => (time_prefix(run)?, Kind::Time),
+    };
+    match run[found.len()..].chars().next() {
+        Some(n) if Chunk::from_char(n) == Chunk::Text => None,
+        _ => Some((found.chars().count(), kind)),
+    }
+}
+
+/// Get the length (in characters) of a formatted number
+///
+/// Plain digits are not counted.
+fn number_len(run: &str) -> Option<usize> {
+    let num = number_prefix(run)?;
+    let mut rest = run[num.len()..].chars();
+    match (rest.next(), rest.next()) {
+        (Some(a), _) if Chunk::from_char(a) == Chunk::Text => None,
+        (Some(',' | '.' | '_'), Some(d)) if d.is_ascii_digit() => None,
+        _ if num.chars().all(|c| c.is_ascii_digit()) => None,
+        _ => Some(num.chars().count()),
+    }
+}
+
+/// Trim trailing punctuation from a URL or email address
+///
+/// A closing parenthesis is kept if it matches an opening one.
+fn trim_trailing(run: &str) -> &str {
+    let mut word = run;
+    while let Some(c) = word.chars().last() {
+        let keep = match c {
+            ')' => word.matches('(').count() >= word.matches(')').count(),
+            _ => !".,;:!?'\"”’]}>".contains(c),
+        };
+        if keep {
+            break;
+        }
+        word = &word[..word.len() - c.len_utf8()];
+    }
+    word
+}
+
+/// Check if a character can start a number (sign or currency symbol)
+fn is_number_prefix(c: char) -> bool {
+    matches!(c, '-' | '+' | '−') || CURRENCIES.contains(c)
+}
+
+/// Check if a character is a regional indicator (paired for flags)
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c, '\u{1F1E6}'..='\u{1F1FF}')
+}
+
+/// Check if uppercase text with dots is an initialism ("U.S.", "J.")
+fn is_initialism(text: &str) -> bool {
+    text.chars().all(|c| c.is_uppercase() || c == '.')
+        && (text.chars().count() <= 2
+            || text.chars().filter(|c| *c == '.').count() > 1)
 }
 
 impl<R> Iterator for Parser<R>
 where
     R: BufRead,
 {
-    type Item = Result<(Chunk, String, Kind), io::Error>;
+    type Item = Result<Token, io::Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.chunks.is_empty() {
-            self.read_chunk();
-        }
-        if !self.chunks.is_empty() {
-            Some(self.chunks.remove(0))
-        } else {
-            None
+        let mut token = self.next_token()?;
+        if let Ok(token) = &mut token {
+            token.text = self.token_text(token).to_string();
         }
+        Some(token)
+    }
+}
+
+impl<'a> Parser<&'a [u8]> {
+    /// Create a new parser for in-memory text (using the built-in lexicon)
+    ///
+    /// This borrows the text, so `FromStr` cannot be implemented.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(text: &'a str) -> Self {
+        Self::new(text.as_bytes())
+    }
+}
+
+impl<I, S> Parser<LineReader<I>>
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    /// Create a new parser for lines of text (using the built-in lexicon)
+    pub fn from_lines<L>(lines: L) -> Self
+    where
+        L: IntoIterator<IntoIter = I>,
+    {
+        Self::new(LineReader::new(lines))
     }
 }
 
@@ -133,28 +732,121 @@ impl<R> Parser<R>
 where
     R: BufRead,
 {
-    /// Create a new parser
+    /// Create a new parser (using the built-in lexicon)
     pub fn new(reader: R) -> Self {
-        let lex = lex::builtin();
+        Self::with_lexicon(reader, lex::builtin_shared())
+    }
+
+    /// Create a new parser with a shared lexicon
+    pub fn with_lexicon(reader: R, lex: Arc<Lexicon>) -> Self {
         let splitter = CharSplitter::new(reader);
-        let chunks = Vec::new();
+        let tokens = VecDeque::new();
         let text = String::new();
         Parser {
             lex,
+            gazetteer: None,
+            abbrevs: abbrev::builtin_shared(),
             splitter,
+            ahead: VecDeque::new(),
             text,
-            chunks,
+            scratch: String::new(),
+            tokens,
+            arena: String::new(),
+            arena_start: 0,
+            offset: 0,
+            line: 1,
+            column: 1,
+            initial: true,
+            last_word: String::new(),
+            newlines: 0,
+            last_char: '\n',
+            names: HashSet::new(),
         }
     }
 
+    /// Use a gazetteer to find names (`Kind::Proper`)
+    pub fn gazetteer(mut self, gazetteer: Arc<Gazetteer>) -> Self {
+        self.gazetteer = Some(gazetteer);
+        self
+    }
+
+    /// Use an abbreviation table (instead of the built-in one)
+    pub fn abbreviations(mut self, abbrevs: Arc<Abbreviations>) -> Self {
+        self.abbrevs = abbrevs;
+        self
+    }
+
+    /// Check if a word was seen capitalized within a sentence (so far)
+    pub(crate) fn is_name(&self, word: &str) -> bool {
+        self.names.contains(word)
+    }
+
+    /// Read the next character
+    fn next_char(&mut self) -> Option<Result<char, io::Error>> {
+        self.ahead.pop_front().or_else(|| self.splitter.next())
+    }
+
+    /// Peek at a character ahead of the current one
+    fn peek_char(&mut self, n: usize) -> Option<char> {
+        while self.ahead.len() <= n {
+            self.ahead.push_back(self.splitter.next()?);
+        }
+        self.ahead[n].as_ref().ok().copied()
+    }
+
+    /// Get next token, without its text
+    fn next_token(&mut self) -> Option<Result<Token, io::Error>> {
+        if self.tokens.is_empty() {
+            self.arena.clear();
+            self.arena_start = self.offset;
+            self.read_chunk();
+        }
+        self.tokens.pop_front()
+    }
+
+    /// Get text of a processed token (from the arena)
+    fn token_text(&self, token: &Token) -> &str {
+        let start = token.span.start - self.arena_start;
+        &self.arena[start..start + token.span.len()]
+    }
+
     /// Read next chunk
     fn read_chunk(&mut self) {
-        while let Some(ch) = self.splitter.next() {
+        while let Some(ch) = self.next_char() {
             if let Err(e) = ch {
-                self.chunks.push(Err(e));
+                self.tokens.push_back(Err(e));
                 return;
             }
             let c = ch.unwrap();
+            if self.is_emoji_start(c) {
+                self.push_text();
+                self.push_emoji(c);
+                return;
+            }
+            if self.text.is_empty()
+                && let Some((len, kind)) = self.peek_web(c)
+            {
+                self.push_run(c, len, kind);
+                return;
+            }
+            if self.text.is_empty()
+                && let Some((len, kind)) = self.peek_date_time(c)
+            {
+                self.push_run(c, len, kind);
+                return;
+            }
+            if self.text.is_empty()
+                && let Some(len) = self.peek_number(c)
+            {
+                self.push_run(c, len, Kind::Number);
+                return;
+            }
+            if self.text.is_empty()
+                && let Some(emoticon) = self.peek_emoticon(c)
+            {
+                self.push_emoticon(emoticon);
+                return;
+            }
             match Chunk::from_char(c) {
                 Chunk::Boundary => {
                     self.push_text();
@@ -169,7 +861,7 @@ where
                             continue;
                         }
                     }
-                    if c == '.' && is_dot_appendable(&self.text) {
+                    if c == '.' && self.is_dot_appendable() {
                         self.text.push('.');
                         continue;
                     }
@@ -178,44 +870,261 @@ where
                     return;
                 }
                 Chunk::Text => self.text.push(c),
+                Chunk::Emoji => unreachable!(),
             }
         }
         self.push_text();
     }
 
+    /// Check if a character starts an emoji
+    ///
+    /// A keycap ("1️⃣") or symbol with emoji presentation ("©️") only starts
+    /// an emoji when followed by a modifier.
+    fn is_emoji_start(&mut self, c: char) -> bool {
+        match Chunk::from_char(c) {
+            Chunk::Emoji => true,
+            Chunk::Boundary => false,
+            Chunk::Text => {
+                c.is_ascii_digit()
+                    && self.text.is_empty()
+                    && matches!(
+                        self.peek_char(0),
+                        Some('\u{FE0F}' | '\u{20E3}')
+                    )
+            }
+            Chunk::Symbol => {
+                matches!(self.peek_char(0), Some('\u{FE0F}' | '\u{20E3}'))
+            }
+        }
+    }
+
+    /// Push an emoji, with all characters in its grapheme cluster
+    fn push_emoji(&mut self, c: char) {
+        let mut emoji = String::from(c);
+        if is_regional_indicator(c)
+            && let Some(r) = self.peek_char(0)
+            && is_regional_indicator(r)
+        {
+            emoji.push(r);
+            self.ahead.pop_front();
+        }
+        loop {
+            match self.peek_char(0) {
+                Some(m) if is_emoji_modifier(m) => emoji.push(m),
+                Some('\u{200D}') => match self.peek_char(1) {
+                    Some(p) if is_pictograph(p) => {
+                        emoji.push('\u{200D}');
+                        emoji.push(p);
+                        self.ahead.pop_front();
+                    }
+                    _ => break,
+                },
+                _ => break,
+            }
+            self.ahead.pop_front();
+        }
+        self.push_token(Chunk::Emoji, &emoji, Kind::Emoji);
+    }
+
+    /// Peek for a URL, email, hashtag or mention starting with a character
+    ///
+    /// Returns the number of characters and kind, if found.
+    fn peek_web(&mut self, c: char) -> Option<(usize, Kind)> {
+        if c == '#' || c == '@' {
+            let mut tag = String::from(c);
+            let mut n = 0;
+            while let Some(t) = self.peek_char(n)
+                && is_handle_char(t)
+            {
+                tag.push(t);
+                n += 1;
+            }
+            return match c {
+                '#' if is_hashtag(&tag) => Some((n + 1, Kind::Hashtag)),
+                '@' if is_mention(&tag) => Some((n + 1, Kind::Mention)),
+                _ => None,
+            };
+        }
+        if !c.is_alphabetic() {
+            return None;
+        }
+        let run = self.peek_run(c, MAX_RUN, false);
+        let found = web_kind(&run);
+        self.scratch = run;
+        found
+    }
+
+    /// Peek at a run of characters up to the next boundary
+    ///
+    /// The run is taken from the scratch buffer; put it back when done.
+    fn peek_run(&mut self, c: char, max: usize, spaces: bool) -> String {
+        let mut run = std::mem::take(&mut self.scratch);
+        run.clear();
+        run.push(c);
+        for n in 0..max {
+            match self.peek_char(n) {
+                Some(r) if (spaces && r == ' ') || !is_boundary(r) => {
+                    run.push(r)
+                }
+                _ => break,
+            }
+        }
+        run
+    }
+
+    /// Peek for a date or time starting with a character
+    ///
+    /// Returns the number of characters and kind, if found.
+    fn peek_date_time(&mut self, c: char) -> Option<(usize, Kind)> {
+        if !(c.is_ascii_digit() || c.is_ascii_uppercase())
+            || matches!(self.last_char, '.' | ',' | '_')
+        {
+            return None;
+        }
+        let run = self.peek_run(c, MAX_DATE, true);
+        let found = date_time_kind(&run);
+        self.scratch = run;
+        found
+    }
+
+    /// Peek for a formatted number starting with a character
+    ///
+    /// Plain digits are left as text, so they can be part of an ordinal
+    /// ("3rd") or compound ("10-20").  Returns the number of characters.
+    fn peek_number(&mut self, c: char) -> Option<usize> {
+        let sign = is_number_prefix(c);
+        if !(c.is_ascii_digit() || sign) {
+            return None;
+        }
+        // a sign must not follow text ("10-20")
+        if sign
+            && Chunk::from_char(self.last_char) != Chunk::Boundary
+            && !"([{\"“‘".contains(self.last_char)
+        {
+            return None;
+        }
+        // nor continue a dotted sequence ("3.14.15")
+        if matches!(self.last_char, '.' | ',' | '_') {
+            return None;
+        }
+        let run = self.peek_run(c, MAX_RUN, false);
+        let found = number_len(&run);
+        self.scratch = run;
+        found
+    }
+
+    /// Push a run of characters as one token (first character already read)
+    fn push_run(&mut self, c: char, len: usize, kind: Kind) {
+        let mut word = std::mem::take(&mut self.scratch);
+        word.clear();
+        word.push(c);
+        for _i in 1..len {
+            if let Some(Ok(c)) = self.ahead.pop_front() {
+                word.push(c);
+            }
+        }
+        self.push_token(Chunk::Text, &word, kind);
+        self.scratch = word;
+    }
+
+    /// Peek for an emoticon starting with a character
+    ///
+    /// Emoticons must not be followed by text.
+    fn peek_emoticon(&mut self, c: char) -> Option<&'static str> {
+        let mut found = None;
+        'emo: for emoticon in EMOTICONS.iter().filter(|e| e.starts_with(c)) {
+            let mut n = 0;
+            for e in emoticon.chars().skip(1) {
+                if self.peek_char(n) != Some(e) {
+                    continue 'emo;
+                }
+                n += 1;
+            }
+            if self
+                .peek_char(n)
+                .is_some_and(|c| Chunk::from_char(c) == Chunk::Text)
+            {
+                continue;
+            }
+            if found.is_none_or(|f: &str| emoticon.len() > f.len()) {
+                found = Some(emoticon);
+            }
+        }
+        found
+    }
+
+    /// Push an emoticon (first character already read)
+    fn push_emoticon(&mut self, emoticon: &str) {
+        for _c in emoticon.chars().skip(1) {
+            self.ahead.pop_front();
+        }
+        self.push_token(Chunk::Emoji, emoticon, Kind::Emoji);
+    }
+
+    /// Check if a dot is appendable to current text
+    fn is_dot_appendable(&self) -> bool {
+        let text = &self.text;
+        !text.is_empty()
+            && !text.ends_with('.')
+            && (text.chars().all(|c| c.is_uppercase() || c == '.')
+                || self.abbrevs.is_prefix(&format!("{text}.")))
+    }
+
     /// Push text chunk
     fn push_text(&mut self) {
+        if self.text.is_empty() {
+            return;
+        }
         let mut text = std::mem::take(&mut self.text);
-        if !text.is_empty() {
-            // this check doesn't work for abbreviations...
-            if text.ends_with('.')
-                && text.chars().count() > 2
-                && text.chars().filter(|c| *c == '.').count() == 1
-            {
-                text.pop();
-                self.push_chunk(Chunk::Text, text);
-                self.push_symbol('.');
-            } else {
-                self.push_chunk(Chunk::Text, text);
+        if !text.contains('.') {
+            self.push_chunk(Chunk::Text, &text);
+        } else if self.abbrevs.contains(&text) {
+            self.push_token(Chunk::Text, &text, Kind::Lexicon);
+        } else if is_initialism(&text) {
+            self.push_chunk(Chunk::Text, &text);
+        } else {
+            // not an abbreviation; split off dots
+            for (i, part) in text.split('.').enumerate() {
+                if i > 0 {
+                    self.push_symbol('.');
+                }
+                if !part.is_empty() {
+                    self.push_chunk(Chunk::Text, part);
+                }
             }
         }
+        // keep buffer for next chunk
+        text.clear();
+        self.text = text;
     }
 
     /// Push symbol chunk
     fn push_symbol(&mut self, c: char) {
-        self.push_chunk(Chunk::Symbol, String::from(c));
+        self.push_chunk(Chunk::Symbol, c.encode_utf8(&mut [0; 4]));
     }
 
     /// Push boundary chunk
     fn push_boundary(&mut self, c: char) {
-        self.push_chunk(Chunk::Boundary, String::from(c));
+        // boundaries are never in the lexicon
+        self.push_token(
+            Chunk::Boundary,
+            c.encode_utf8(&mut [0; 4]),
+            Kind::Symbol,
+        );
     }
 
     /// Push one chunk
-    fn push_chunk(&mut self, chunk: Chunk, txt: String) {
+    fn push_chunk(&mut self, chunk: Chunk, txt: &str) {
+        if let Some(compound) = resolve_compound(&self.lex, txt) {
+            self.push_token(chunk, txt, Kind::Lexicon);
+            if let Some(Ok(token)) = self.tokens.back_mut() {
+                token.compound = Some(compound);
+            }
+            return;
+        }
         if txt.chars().count() == 1
-            || self.lex.contains(&txt)
             || !txt.chars().any(is_splittable)
+            || self.lex.contains(txt)
         {
             self.push_word(chunk, txt);
             return;
@@ -224,7 +1133,7 @@ where
         let mut first = true;
         for ch in txt.split('-') {
             if !first {
-                self.push_word(Chunk::Symbol, String::from('-'));
+                self.push_word(Chunk::Symbol, "-");
             }
             self.push_word_check_contraction(ch);
             first = false;
@@ -235,15 +1144,14 @@ where
     fn push_word_check_contraction(&mut self, word: &str) {
         if !word.is_empty() {
             let kind = self.contraction_kind(word);
-            self.chunks
-                .push(Ok((Chunk::Text, String::from(word), kind)));
+            self.push_token(Chunk::Text, word, kind);
         }
     }
 
     /// Check contraction kind
     fn contraction_kind(&self, word: &str) -> Kind {
         if self.lex.contains(word) {
-            return Kind::Lexicon;
+            return self.word_kind(word);
         }
         if word.chars().any(is_apostrophe) {
             let mut kinds = Vec::new();
@@ -263,18 +1171,154 @@ where
     }
 
     /// Get word kind
+    ///
+    /// Capitalization at the start of a sentence says little, so a word
+    /// there is only `Proper` if it is a gazetteer name (and not a lexicon
+    /// word), or was already seen capitalized within a sentence.
+    ///
+    /// Tokens are classified as they are parsed, so this only looks
+    /// backward: a name which starts a sentence before it first appears
+    /// within one is `Unknown`.
     fn word_kind(&self, word: &str) -> Kind {
-        if self.lex.contains(word) {
-            Kind::Lexicon
-        } else {
-            Kind::from(word)
+        let lexicon = self.lex.contains(word);
+        if self
+            .gazetteer
+            .as_ref()
+            .is_some_and(|gaz| gaz.contains(word))
+            && !(self.initial && lexicon)
+        {
+            return Kind::Proper;
+        }
+        if lexicon {
+            return Kind::Lexicon;
+        }
+        match Kind::from(word) {
+            Kind::Proper if self.initial && !self.names.contains(word) => {
+                Kind::Unknown
+            }
+            kind => kind,
         }
     }
 
     /// Push one word
-    fn push_word(&mut self, chunk: Chunk, word: String) {
-        let kind = self.word_kind(&word);
-        self.chunks.push(Ok((chunk, word, kind)));
+    fn push_word(&mut self, chunk: Chunk, word: &str) {
+        let kind = self.word_kind(word);
+        self.push_token(chunk, word, kind);
+    }
+
+    /// Track sentence position (before a token is pushed)
+    fn track_sentence(&mut self, chunk: Chunk, text: &str, kind: Kind) {
+        match chunk {
+            Chunk::Boundary => {
+                if text == "\n" {
+                    self.newlines += 1;
+                    // a blank line always ends a sentence
+                    if self.newlines > 1 {
+                        self.initial = true;
+                    }
+                }
+                return;
+            }
+            Chunk::Text if text.chars().any(char::is_alphanumeric) => {
+                if kind == Kind::Proper
+                    && !self.initial
+                    && self.names.len() < MAX_NAMES
+                {
+                    self.names.insert(text.to_string());
+                }
+                // an abbreviation like "etc." may end a sentence
+                self.initial = is_terminal_abbreviation(text);
+                self.last_word.clear();
+                self.last_word.push_str(text);
+            }
+            Chunk::Symbol => match text {
+                "!" | "?" | "…" => self.initial = true,
+                "." => self.initial = !is_abbreviation(&self.last_word),
+                _ => (),
+            },
+            Chunk::Text | Chunk::Emoji => (),
+        }
+        self.newlines = 0;
+    }
+
+    /// Push a token (at current position)
+    fn push_token(&mut self, chunk: Chunk, text: &str, kind: Kind) {
+        self.track_sentence(chunk, text, kind);
+        if let Some(c) = text.chars().last() {
+            self.last_char = c;
+        }
+        let start = self.offset;
+        let (line, column) = (self.line, self.column);
+        self.offset += text.len();
+        for c in text.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.arena.push_str(text);
+        self.tokens.push_back(Ok(Token {
+            chunk,
+            text: String::new(),
+            kind,
+            span: start..self.offset,
+            line,
+            column,
+            compound: None,
+        }));
+    }
+}
+
+/// Get numeric value of a token
+fn token_value(kind: Kind, text: &str) -> Option<f64> {
+    match kind {
+        Kind::Number => number_value(text),
+        Kind::Lexicon => numbers::parse(text).map(|n| n.value() as f64),
+        _ => None,
+    }
+}
+
+/// Classify the kind of a word, using a lexicon
+///
+/// Unlike `Parser`, this does not depend on sentence position.
+pub fn classify(lex: &Lexicon, word: &str) -> Kind {
+    if lex.contains(word) {
+        Kind::Lexicon
+    } else {
+        Kind::from(word)
+    }
+}
+
+/// Check if a word is an abbreviation which may end a sentence ("etc.")
+///
+/// Titles ("Mr."), "e.g." and initialisms ("U.S.") do not.
+pub(crate) fn is_terminal_abbreviation(word: &str) -> bool {
+    !is_initialism(word)
+        && word
+            .strip_suffix('.')
+            .is_some_and(|stem| !is_abbreviation(stem))
+}
+
+/// Resolve a hyphenated compound word using a lexicon
+///
+/// The hyphenated, closed and open variants are looked up in that order.
+/// Returns `None` if the word has no hyphens between parts, or no variant
+/// is in the lexicon.
+pub fn resolve_compound(lex: &Lexicon, word: &str) -> Option<Compound> {
+    let parts: Vec<_> = word.split('-').collect();
+    if parts.len() < 2 || parts.iter().any(|p| p.is_empty()) {
+        return None;
+    }
+    if lex.contains(word) {
+        Some(Compound::Hyphenated)
+    } else if lex.contains(&parts.concat()) {
+        Some(Compound::Closed)
+    } else if lex.contains(&parts.join(" ")) {
+        Some(Compound::Open)
+    } else {
+        None
     }
 }
 
@@ -282,3 +1326,329 @@ where
 fn is_splittable(c: char) -> bool {
     c == '-' || is_apostrophe(c)
 }
+
+/// Split text into sentences of words, with byte spans
+pub(crate) fn span_sentences(
+    text: &str,
+    lex: Arc<Lexicon>,
+) -> crate::Result<Vec<Vec<SpanWord>>> {
+    let mut sentences = Vec::new();
+    for sentence in Sentences::with_lexicon(text.as_bytes(), lex) {
+        let words: Vec<_> = sentence?
+            .word_spans()
+            .map(|span| SpanWord {
+                text: text[span.clone()].to_string(),
+                span,
+            })
+            .collect();
+        if !words.is_empty() {
+            sentences.push(words);
+        }
+    }
+    Ok(sentences)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        let text = "Hi, “you”\nwell-read--ok.";
+        let tokens: Vec<_> =
+            Parser::new(text.as_bytes()).map(|t| t.unwrap()).collect();
+        for token in &tokens {
+            assert_eq!(&text[token.span()], token.text());
+        }
+        let pos: Vec<_> = tokens
+            .iter()
+            .filter(|t| t.chunk() == Chunk::Text)
+            .map(|t| (t.text(), t.line(), t.column()))
+            .collect();
+        assert_eq!(
+            pos,
+            [
+                ("Hi", 1, 1),
+                ("you", 1, 6),
+                ("well", 2, 1),
+                ("read", 2, 6),
+                ("ok", 2, 12),
+            ]
+        );
+    }
+
+    #[test]
+    fn abbreviations() {
+        let text = "Mr. Smith, e.g. at 3 p.m. in the U.S. or the USA. A. B";
+        let words: Vec<_> = Parser::new(text.as_bytes())
+            .map(|t| t.unwrap())
+            .filter(|t| t.chunk() != Chunk::Boundary)
+            .map(|t| t.into_text())
+            .collect();
+        assert_eq!(
+            words,
+            [
+                "Mr.", "Smith", ",", "e.g.", "at", "3 p.m.", "in", "the",
+                "U.S.", "or", "the", "USA", ".", "A.", "B",
+            ]
+        );
+        let mut abbrevs = abbrev::builtin().clone();
+        abbrevs.insert("approx");
+        abbrevs.insert("Corp");
+        let words: Vec<_> = Parser::new("Acme Corp. made a.".as_bytes())
+            .abbreviations(Arc::new(abbrevs))
+            .map(|t| t.unwrap())
+            .filter(|t| t.chunk() != Chunk::Boundary)
+            .map(|t| t.into_text())
+            .collect();
+        assert_eq!(words, ["Acme", "Corp.", "made", "a", "."]);
+    }
+
+    #[test]
+    fn compounds() {
+        let lex = Arc::new(
+            Lexicon::from_csv("e-mail:N\nicebox:N\nice cream:N\nice:N")
+                .unwrap(),
+        );
+        let text = "e-mail ice-box ice-cream ice-well -ice";
+        let tokens: Vec<_> = Parser::with_lexicon(text.as_bytes(), lex)
+            .map(|t| t.unwrap())
+            .filter(|t| t.chunk() != Chunk::Boundary)
+            .map(|t| (t.compound(), t.into_text()))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (Some(Compound::Hyphenated), "e-mail".into()),
+                (Some(Compound::Closed), "ice-box".into()),
+                (Some(Compound::Open), "ice-cream".into()),
+                (None, "ice".into()),
+                (None, "-".into()),
+                (None, "well".into()),
+                (None, "-".into()),
+                (None, "ice".into()),
+            ]
+        );
+        assert_eq!(
+            resolve_compound(lex::builtin(), "co-operate"),
+            Some(Compound::Closed)
+        );
+    }
+
+    #[test]
+    fn emoji() {
+        let text = "Hi 👋🏽! 👨\u{200D}👩\u{200D}👧 🇨🇦, 1\u{FE0F}\u{20E3} ok :-) \
+            at 3:2 <3 ❤\u{FE0F}";
+        let tokens: Vec<_> = Parser::new(text.as_bytes())
+            .map(|t| t.unwrap())
+            .filter(|t| t.chunk() != Chunk::Boundary)
+            .map(|t| (t.kind(), t.into_text()))
+            .collect();
+        let emoji: Vec<_> = tokens
+            .iter()
+            .filter(|(k, _t)| *k == Kind::Emoji)
+            .map(|(_k, t)| t.as_str())
+            .collect();
+        assert_eq!(
+            emoji,
+            [
+                "👋🏽",
+                "👨\u{200D}👩\u{200D}👧",
+                "🇨🇦",
+                "1\u{FE0F}\u{20E3}",
+                ":-)",
+                "<3",
+                "❤\u{FE0F}",
+            ]
+        );
+        assert_eq!(tokens[2], (Kind::Symbol, "!".into()));
+        assert!(tokens.iter().any(|(_k, t)| t == ":"));
+        assert_eq!(Kind::from("🎉"), Kind::Emoji);
+        assert_eq!(Kind::from(";)"), Kind::Emoji);
+    }
+
+    #[test]
+    fn web() {
+        let text = "See https://en.wikipedia.org/wiki/Rust_(language), \
+            mail bob.smith+x@example.co.uk (or www.example.com). \
+            #RustLang @ferris_1 #1 me@ x@y";
+        let tokens: Vec<_> = Parser::new(text.as_bytes())
+            .map(|t| t.unwrap())
+            .filter(|t| t.chunk() != Chunk::Boundary)
+            .map(|t| (t.kind(), t.into_text()))
+            .collect();
+        let web: Vec<_> = tokens
+            .iter()
+            .filter(|(k, _t)| {
+                matches!(
+                    k,
+                    Kind::Url | Kind::Email | Kind::Hashtag | Kind::Mention
+                )
+            })
+            .map(|(k, t)| (*k, t.as_str()))
+            .collect();
+        assert_eq!(
+            web,
+            [
+                (Kind::Url, "https://en.wikipedia.org/wiki/Rust_(language)"),
+                (Kind::Email, "bob.smith+x@example.co.uk"),
+                (Kind::Url, "www.example.com"),
+                (Kind::Hashtag, "#RustLang"),
+                (Kind::Mention, "@ferris_1"),
+            ]
+        );
+        assert_eq!(tokens[2], (Kind::Symbol, ",".into()));
+        assert_eq!(Kind::from("user@example.com"), Kind::Email);
+        assert_eq!(Kind::from("www.example"), Kind::Unknown);
+    }
+
+    #[test]
+    fn numbers() {
+        let text = "Paid $1,234.56 (-3.5%), not 1_000 or 3.14.15 on \
+            pages 10-20, 3rd 1,23 -x.";
+        let tokens: Vec<_> = Parser::new(text.as_bytes())
+            .map(|t| t.unwrap())
+            .filter(|t| t.chunk() != Chunk::Boundary)
+            .collect();
+        let words: Vec<_> = tokens.iter().map(|t| t.text()).collect();
+        assert_eq!(
+            words,
+            [
+                "Paid",
+                "$1,234.56",
+                "(",
+                "-3.5%",
+                ")",
+                ",",
+                "not",
+                "1_000",
+                "or",
+                "3",
+                ".",
+                "14",
+                ".",
+                "15",
+                "on",
+                "pages",
+                "10",
+                "-",
+                "20",
+                ",",
+                "3rd",
+                "1",
+                ",",
+                "23",
+                "-",
+                "x",
+                ".",
+            ]
+        );
+        let values: Vec<_> = tokens.iter().filter_map(|t| t.value()).collect();
+        assert_eq!(
+            values,
+            [
+                1234.56, -3.5, 1000.0, 3.0, 14.0, 15.0, 10.0, 20.0, 1.0, 23.0
+            ]
+        );
+        assert_eq!(tokens[1].kind(), Kind::Number);
+        assert_eq!(number_value("−7"), Some(-7.0));
+        assert_eq!(number_value("1,23"), None);
+    }
+
+    #[test]
+    fn dates() {
+        let text = "On May 1st, 2024 at 3:45 PM, or 2024-05-01 15:30, \
+            we met 1 Sept. 1999 by 9am; you may 3 amble at 2:61.";
+        let tokens: Vec<_> = Parser::new(text.as_bytes())
+            .map(|t| t.unwrap())
+            .filter(|t| matches!(t.kind(), Kind::Date | Kind::Time))
+            .map(|t| (t.kind(), t.into_text()))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (Kind::Date, "May 1st, 2024".into()),
+                (Kind::Time, "3:45 PM".into()),
+                (Kind::Date, "2024-05-01".into()),
+                (Kind::Time, "15:30".into()),
+                (Kind::Date, "1 Sept. 1999".into()),
+                (Kind::Time, "9am".into()),
+            ]
+        );
+        assert_eq!(Kind::from("5/1/24"), Kind::Date);
+        assert_eq!(Kind::from("12:00:30"), Kind::Time);
+    }
+
+    #[test]
+    fn dehyphenate() {
+        use std::io::Read;
+        let lex = lex::builtin_shared();
+        let text = "An inter-\n  national, well-\nknown\r\nfoo-\nbar -\nok-\n";
+        let mut out = String::new();
+        Dehyphenate::new(text.as_bytes(), lex)
+            .read_to_string(&mut out)
+            .unwrap();
+        assert_eq!(
+            out,
+            "An international\n, well-\nknown\r\nfoo-\nbar -\nok-\n"
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        let text = "Tom ran.\nSam ran too.";
+        fn words<R: BufRead>(parser: Parser<R>) -> Vec<(String, usize)> {
+            parser
+                .map(|t| t.unwrap())
+                .filter(|t| t.chunk() == Chunk::Text)
+                .map(|t| (t.text().to_string(), t.line()))
+                .collect()
+        }
+        let from_lines = words(Parser::from_lines(text.lines()));
+        assert_eq!(from_lines, words(Parser::from_str(text)));
+        assert_eq!(from_lines[3], ("ran".into(), 2));
+    }
+
+    #[test]
+    fn borrowed() {
+        let text = "Hi, “you”\nwell-read--ok at 3:45 PM, 2.5% 🎉.";
+        let owned: Vec<_> =
+            Parser::from_str(text).map(|t| t.unwrap()).collect();
+        let borrowed: Vec<_> = BorrowedTokens::new(text).collect();
+        assert_eq!(owned.len(), borrowed.len());
+        for (o, b) in owned.iter().zip(&borrowed) {
+            assert_eq!(*o, b.to_token());
+        }
+        assert_eq!(borrowed[7].text(), "well");
+        assert_eq!(borrowed[7].line(), 2);
+    }
+
+    #[test]
+    fn gazetteer() {
+        let gaz = Arc::new(Gazetteer::calendar());
+        let text = "In May, you may go to Paris on Friday's train.";
+        let kinds: Vec<_> = Parser::new(text.as_bytes())
+            .gazetteer(gaz)
+            .map(|t| t.unwrap())
+            .filter(|t| t.chunk() == Chunk::Text)
+            .map(|t| t.kind())
+            .collect();
+        assert_eq!(kinds[1], Kind::Proper);
+        assert_eq!(kinds[3], Kind::Lexicon);
+        assert_eq!(kinds[6], Kind::Proper);
+        assert_eq!(kinds[8], Kind::Proper);
+        assert_eq!(kinds[9], Kind::Lexicon);
+    }
+
+    #[test]
+    fn names() {
+        let text = "Zyxwell left. Then Zyxwell ran.\nZyxwell is back.";
+        let kinds: Vec<_> = Parser::new(text.as_bytes())
+            .map(|t| t.unwrap())
+            .filter(|t| t.text() == "Zyxwell")
+            .map(|t| t.kind())
+            .collect();
+        // only names seen before a sentence start are known there
+        assert_eq!(kinds, [Kind::Unknown, Kind::Proper, Kind::Proper]);
+    }
+}
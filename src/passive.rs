@@ -0,0 +1,184 @@
+use crate::Result;
+use crate::lex::Lexicon;
+use crate::parse::{SpanWord, span_sentences};
+use crate::penn::Penn;
+use crate::tagger::Tagger;
+use crate::word::WordClass;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Maximum number of adverbs between auxiliary and participle
+const MAX_ADVERBS: usize = 2;
+
+/// Passive voice construction ("be" + past participle)
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Passive {
+    /// Byte span in text (from auxiliary to participle)
+    span: Range<usize>,
+    /// Auxiliary verb (form of "be")
+    auxiliary: String,
+    /// Past participle
+    participle: String,
+}
+
+/// Passive voice finder
+#[derive(Clone, Default)]
+pub struct PassiveFinder {
+    /// Part-of-speech tagger
+    tagger: Tagger,
+}
+
+impl Passive {
+    /// Get byte span in text
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Get auxiliary verb (form of "be")
+    pub fn auxiliary(&self) -> &str {
+        &self.auxiliary
+    }
+
+    /// Get past participle
+    pub fn participle(&self) -> &str {
+        &self.participle
+    }
+}
+
+impl PassiveFinder {
+    /// Create a new passive voice finder (using the built-in lexicon)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new passive voice finder with a tagger
+    ///
+    /// A tagger with trained weights rules out participles used as
+    /// adjectives (e.g. "was tired") more reliably.
+    pub fn with_tagger(tagger: Tagger) -> Self {
+        PassiveFinder { tagger }
+    }
+
+    /// Find passive voice constructions in a text
+    pub fn find(&self, text: &str) -> Result<Vec<Passive>> {
+        let lex = self.tagger.lexicon();
+        let mut found = Vec::new();
+        for words in span_sentences(text, Arc::clone(lex))? {
+            self.find_sentence(lex, &words, &mut found);
+        }
+        Ok(found)
+    }
+
+    /// Find passive voice constructions in a sentence
+    fn find_sentence(
+        &self,
+        lex: &Lexicon,
+        words: &[SpanWord],
+        found: &mut Vec<Passive>,
+    ) {
+        let texts: Vec<_> = words.iter().map(|w| w.text.as_str()).collect();
+        let tags = self.tagger.tag_words(&texts);
+        let mut i = 0;
+        while i < words.len() {
+            if !is_be(lex, &words[i].text) {
+                i += 1;
+                continue;
+            }
+            let aux = i;
+            let mut adverbs = 0;
+            let mut j = i + 1;
+            while let Some(word) = words.get(j) {
+                if is_be(lex, &word.text) {
+                    j += 1;
+                } else if adverbs < MAX_ADVERBS
+                    && tags[j] == Some(WordClass::Adverb)
+                {
+                    adverbs += 1;
+                    j += 1;
+                } else {
+                    break;
+                }
+            }
+            if let Some(word) = words.get(j)
+                && tags[j].is_none_or(|wc| wc == WordClass::Verb)
+                && is_participle(lex, &word.text)
+            {
+                found.push(Passive {
+                    span: words[aux].span.start..word.span.end,
+                    auxiliary: words[aux].text.clone(),
+                    participle: word.text.clone(),
+                });
+            }
+            i = j.max(i + 1);
+        }
+    }
+}
+
+/// Check if a word is a form of "be"
+fn is_be(lex: &Lexicon, word: &str) -> bool {
+    lex.word_entries(word)
+        .iter()
+        .any(|e| e.word_class() == WordClass::Verb && e.lemma() == "be")
+}
+
+/// Check if a word is a past participle (or past form, which may be one)
+fn is_participle(lex: &Lexicon, word: &str) -> bool {
+    lex.word_entries(word).iter().any(|e| {
+        e.word_class() == WordClass::Verb
+            && e.lemma() != "be"
+            && matches!(Penn::from_lexeme(e, word), Penn::Vbn | Penn::Vbd)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn passive() {
+        let finder = PassiveFinder::new();
+        let text = "The cake was eaten. Mistakes were quickly made! \
+            It has been done, but we ate it.";
+        let found = finder.find(text).unwrap();
+        let verbs: Vec<_> = found
+            .iter()
+            .map(|p| (p.auxiliary(), p.participle()))
+            .collect();
+        assert_eq!(
+            verbs,
+            [("was", "eaten"), ("were", "made"), ("been", "done")]
+        );
+        assert_eq!(&text[found[0].span()], "was eaten");
+        assert_eq!(&text[found[1].span()], "were quickly made");
+        assert!(
+            finder
+                .find("She is happy. They were here.")
+                .unwrap()
+                .is_empty()
+        );
+        let found = |text: &str| -> Vec<String> {
+            let found = finder.find(text).unwrap();
+            found.iter().map(|p| text[p.span()].to_string()).collect()
+        };
+        assert!(found("").is_empty());
+        assert!(found("It was.").is_empty());
+        assert!(found("The end was").is_empty());
+        // only two adverbs are skipped
+        assert!(found("It was not very quickly eaten.").is_empty());
+        assert_eq!(found("It is being built."), ["is being built"]);
+        // sentence boundaries break a passive
+        assert!(found("It was. Eaten later.").is_empty());
+        assert!(found("She was eating.").is_empty());
+        let lex = Lexicon::from_csv(
+            "be:V,am,are,is,was,were,been,being\n\
+            blorf:V",
+        )
+        .unwrap();
+        let finder =
+            PassiveFinder::with_tagger(Tagger::with_lexicon(Arc::new(lex)));
+        let found = finder.find("It was blorfed, then was eaten.").unwrap();
+        let verbs: Vec<_> = found.iter().map(Passive::participle).collect();
+        assert_eq!(verbs, ["blorfed"]);
+    }
+}
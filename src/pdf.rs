@@ -0,0 +1,75 @@
+use crate::lex;
+use crate::parse::Dehyphenate;
+use crate::{Error, Result};
+use std::io::Read;
+
+/// Typographic ligatures
+const LIGATURES: &[(char, &str)] = &[
+    ('\u{FB00}', "ff"),
+    ('\u{FB01}', "fi"),
+    ('\u{FB02}', "fl"),
+    ('\u{FB03}', "ffi"),
+    ('\u{FB04}', "ffl"),
+    ('\u{FB05}', "st"),
+    ('\u{FB06}', "st"),
+];
+
+/// Normalize extracted text
+///
+/// Ligatures are expanded, and words hyphenated across line breaks are
+/// joined if the whole word is in the lexicon (see `Dehyphenate`).
+fn normalize(src: &str) -> Result<String> {
+    let mut text = String::with_capacity(src.len());
+    for line in src.lines() {
+        let mut line = line.trim_end().to_string();
+        for (lig, rep) in LIGATURES {
+            if line.contains(*lig) {
+                line = line.replace(*lig, rep);
+            }
+        }
+        text.push_str(&line);
+        text.push('\n');
+    }
+    let mut joined = String::with_capacity(text.len());
+    Dehyphenate::new(text.as_bytes(), lex::builtin_shared())
+        .read_to_string(&mut joined)?;
+    Ok(joined)
+}
+
+/// Extract text from a PDF document
+///
+/// Ligatures are expanded, and words hyphenated across line breaks are
+/// joined if the whole word is in the lexicon.
+pub fn extract_text<R>(mut reader: R) -> Result<String>
+where
+    R: Read,
+{
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf)?;
+    let text = pdf_extract::extract_text_from_mem(&buf)
+        .map_err(|e| Error::Decode(e.to_string()))?;
+    normalize(&text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalize_text() {
+        let src = "The \u{FB01}rst exam-\nple is well-\nKnown, and self-\n";
+        assert_eq!(
+            normalize(src).unwrap(),
+            "The first example\n is well-\nKnown, and self-\n"
+        );
+    }
+
+    #[test]
+    fn compound() {
+        let src = "A well-\nknown fact, in-\n side a pre-\nsented ex-\n";
+        assert_eq!(
+            normalize(src).unwrap(),
+            "A well-\nknown fact, inside\n a presented\n ex-\n"
+        );
+    }
+}
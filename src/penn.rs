@@ -0,0 +1,581 @@
+use crate::Error;
+use crate::kind::Kind;
+use crate::lex::make_word;
+use crate::word::{FormRule, Lexeme, WordAttr, WordClass};
+use alloc::string::ToString;
+use core::fmt;
+
+/// Penn Treebank part-of-speech tag
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Penn {
+    /// `CC`: Coordinating conjunction
+    Cc,
+    /// `CD`: Cardinal number
+    Cd,
+    /// `DT`: Determiner
+    Dt,
+    /// `EX`: Existential there
+    Ex,
+    /// `FW`: Foreign word
+    Fw,
+    /// `IN`: Preposition or subordinating conjunction
+    In,
+    /// `JJ`: Adjective
+    Jj,
+    /// `JJR`: Adjective, comparative
+    Jjr,
+    /// `JJS`: Adjective, superlative
+    Jjs,
+    /// `MD`: Modal
+    Md,
+    /// `NN`: Noun, singular or mass
+    Nn,
+    /// `NNS`: Noun, plural
+    Nns,
+    /// `NNP`: Proper noun, singular
+    Nnp,
+    /// `NNPS`: Proper noun, plural
+    Nnps,
+    /// `PDT`: Predeterminer
+    Pdt,
+    /// `PRP`: Personal pronoun
+    Prp,
+    /// `PRP$`: Possessive pronoun
+    PrpS,
+    /// `RB`: Adverb
+    Rb,
+    /// `RBR`: Adverb, comparative
+    Rbr,
+    /// `RBS`: Adverb, superlative
+    Rbs,
+    /// `SYM`: Symbol
+    Sym,
+    /// `TO`: to
+    To,
+    /// `UH`: Interjection
+    Uh,
+    /// `VB`: Verb, base form
+    Vb,
+    /// `VBD`: Verb, past tense
+    Vbd,
+    /// `VBG`: Verb, gerund or present participle
+    Vbg,
+    /// `VBN`: Verb, past participle
+    Vbn,
+    /// `VBP`: Verb, non-3rd person singular present
+    Vbp,
+    /// `VBZ`: Verb, 3rd person singular present
+    Vbz,
+    /// `WDT`: Wh-determiner
+    Wdt,
+    /// `WP`: Wh-pronoun
+    Wp,
+    /// `WP$`: Possessive wh-pronoun
+    WpS,
+    /// `WRB`: Wh-adverb
+    Wrb,
+    /// `.`: Sentence-final punctuation
+    Period,
+    /// `,`: Comma
+    Comma,
+    /// `:`: Colon, semicolon, dash or ellipsis
+    Colon,
+    /// ` `` `: Opening quotation mark
+    OpenQuote,
+    /// `''`: Closing quotation mark
+    CloseQuote,
+    /// `-LRB-`: Opening bracket
+    OpenParen,
+    /// `-RRB-`: Closing bracket
+    CloseParen,
+    /// `$`: Currency sign
+    Dollar,
+    /// `#`: Pound sign
+    Hash,
+}
+
+/// Coordinating conjunctions
+const COORDINATING: &[&str] = &[
+    "and", "but", "either", "neither", "nor", "or", "plus", "so", "yet",
+];
+
+/// Possessive pronouns / determiners
+const POSSESSIVE: &[&str] = &[
+    "her", "his", "its", "my", "our", "their", "thine", "thy", "your",
+];
+
+/// Wh-pronouns
+const WH_PRONOUNS: &[&str] =
+    &["what", "whatever", "who", "whoever", "whom", "whomever"];
+
+/// Wh-determiners
+const WH_DETERMINERS: &[&str] = &["what", "whatever", "which", "whichever"];
+
+/// Wh-adverbs
+const WH_ADVERBS: &[&str] = &[
+    "how", "however", "whence", "when", "whenever", "where", "wherever",
+    "whither", "why",
+];
+
+/// Predeterminers (when followed by a determiner)
+const PREDETERMINERS: &[&str] = &["all", "both", "half", "such"];
+
+/// Forms of `be` (in lexicon order)
+const BE_FORMS: &[Penn] = &[
+    Penn::Vbp, // am
+    Penn::Vbp, // are
+    Penn::Vbz, // is
+    Penn::Vbd, // was
+    Penn::Vbd, // were
+    Penn::Vbn, // been
+    Penn::Vbg, // being
+    Penn::Vbp, // art
+    Penn::Vbd, // wert
+];
+
+/// Forms of `be` and `have` (before a past participle)
+const PERFECT_PASSIVE: &[&str] = &[
+    "am", "are", "be", "been", "being", "had", "has", "have", "having", "is",
+    "was", "were",
+];
+
+/// Forms of `do` (before a base form)
+const DO_FORMS: &[&str] = &["did", "do", "does"];
+
+impl TryFrom<&str> for Penn {
+    type Error = Error;
+
+    fn try_from(tag: &str) -> Result<Self, Self::Error> {
+        Penn::all()
+            .iter()
+            .find(|p| p.tag() == tag)
+            .copied()
+            .ok_or_else(|| Error::Tag(tag.to_string()))
+    }
+}
+
+impl fmt::Display for Penn {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.tag())
+    }
+}
+
+impl Penn {
+    /// Get all tags
+    pub fn all() -> &'static [Self] {
+        use Penn::*;
+        &[
+            Cc, Cd, Dt, Ex, Fw, In, Jj, Jjr, Jjs, Md, Nn, Nns, Nnp, Nnps, Pdt,
+            Prp, PrpS, Rb, Rbr, Rbs, Sym, To, Uh, Vb, Vbd, Vbg, Vbn, Vbp, Vbz,
+            Wdt, Wp, WpS, Wrb, Period, Comma, Colon, OpenQuote, CloseQuote,
+            OpenParen, CloseParen, Dollar, Hash,
+        ]
+    }
+
+    /// Get tag name
+    pub fn tag(self) -> &'static str {
+        use Penn::*;
+        match self {
+            Cc => "CC",
+            Cd => "CD",
+            Dt => "DT",
+            Ex => "EX",
+            Fw => "FW",
+            In => "IN",
+            Jj => "JJ",
+            Jjr => "JJR",
+            Jjs => "JJS",
+            Md => "MD",
+            Nn => "NN",
+            Nns => "NNS",
+            Nnp => "NNP",
+            Nnps => "NNPS",
+            Pdt => "PDT",
+            Prp => "PRP",
+            PrpS => "PRP$",
+            Rb => "RB",
+            Rbr => "RBR",
+            Rbs => "RBS",
+            Sym => "SYM",
+            To => "TO",
+            Uh => "UH",
+            Vb => "VB",
+            Vbd => "VBD",
+            Vbg => "VBG",
+            Vbn => "VBN",
+            Vbp => "VBP",
+            Vbz => "VBZ",
+            Wdt => "WDT",
+            Wp => "WP",
+            WpS => "WP$",
+            Wrb => "WRB",
+            Period => ".",
+            Comma => ",",
+            Colon => ":",
+            OpenQuote => "``",
+            CloseQuote => "''",
+            OpenParen => "-LRB-",
+            CloseParen => "-RRB-",
+            Dollar => "$",
+            Hash => "#",
+        }
+    }
+
+    /// Get tag of a word form of a lexeme
+    pub fn from_lexeme(lex: &Lexeme, form: &str) -> Self {
+        let form = make_word(form);
+        let rule = lex.form_rule(&form);
+        let has_attr = |attr| lex.attributes().any(|a| a == attr);
+        match lex.word_class() {
+            WordClass::Adjective => match rule {
+                Some(FormRule::Comparative) => Penn::Jjr,
+                Some(FormRule::Superlative) => Penn::Jjs,
+                Some(FormRule::Irregular) if form.ends_with("st") => Penn::Jjs,
+                Some(FormRule::Irregular) => Penn::Jjr,
+                _ => Penn::Jj,
+            },
+            WordClass::Adverb => match rule {
+                Some(FormRule::Comparative) => Penn::Rbr,
+                Some(FormRule::Superlative) => Penn::Rbs,
+                Some(FormRule::Irregular) if form.ends_with("st") => Penn::Rbs,
+                Some(FormRule::Irregular) => Penn::Rbr,
+                _ if WH_ADVERBS.contains(&lex.lemma()) => Penn::Wrb,
+                _ => Penn::Rb,
+            },
+            WordClass::Conjunction if COORDINATING.contains(&lex.lemma()) => {
+                Penn::Cc
+            }
+            WordClass::Conjunction => Penn::In,
+            WordClass::Determiner => {
+                if POSSESSIVE.contains(&form.as_str()) {
+                    Penn::PrpS
+                } else if form == "whose" {
+                    Penn::WpS
+                } else if WH_DETERMINERS.contains(&lex.lemma()) {
+                    Penn::Wdt
+                } else {
+                    Penn::Dt
+                }
+            }
+            WordClass::Interjection => Penn::Uh,
+            WordClass::Noun => {
+                let plural = has_attr(WordAttr::PluraleTantum)
+                    || matches!(
+                        rule,
+                        Some(FormRule::Plural | FormRule::Irregular)
+                    );
+                match (has_attr(WordAttr::Proper), plural) {
+                    (true, true) => Penn::Nnps,
+                    (true, false) => Penn::Nnp,
+                    (false, true) => Penn::Nns,
+                    (false, false) => Penn::Nn,
+                }
+            }
+            WordClass::Preposition if lex.lemma() == "to" => Penn::To,
+            WordClass::Preposition => Penn::In,
+            WordClass::Pronoun => {
+                if POSSESSIVE.contains(&form.as_str()) {
+                    Penn::PrpS
+                } else if form == "whose" {
+                    Penn::WpS
+                } else if WH_PRONOUNS.contains(&lex.lemma()) {
+                    Penn::Wp
+                } else if lex.lemma() == "there" {
+                    Penn::Ex
+                } else {
+                    Penn::Prp
+                }
+            }
+            WordClass::Verb if has_attr(WordAttr::Auxiliary) => Penn::Md,
+            WordClass::Verb => verb_tag(lex, &form, rule),
+        }
+    }
+
+    /// Get tag of a word not in the lexicon, from its kind
+    ///
+    /// Returns `None` for `Kind::Lexicon`, which is tagged by lexeme, and
+    /// for `Kind::Unknown`.
+    pub fn from_kind(kind: Kind, word: &str) -> Option<Self> {
+        match kind {
+            Kind::Lexicon | Kind::Unknown => None,
+            Kind::Foreign => Some(Penn::Fw),
+            Kind::Ordinal => Some(Penn::Jj),
+            Kind::Roman | Kind::Number | Kind::Date | Kind::Time => {
+                Some(Penn::Cd)
+            }
+            Kind::Acronym | Kind::Proper => Some(Penn::Nnp),
+            Kind::Url | Kind::Email | Kind::Hashtag => Some(Penn::Nn),
+            Kind::Mention => Some(Penn::Nnp),
+            Kind::Emoji => Some(Penn::Sym),
+            Kind::Symbol => Some(symbol_tag(word)),
+        }
+    }
+
+    /// Check if tag is a verb
+    pub fn is_verb(self) -> bool {
+        use Penn::*;
+        matches!(self, Vb | Vbd | Vbg | Vbn | Vbp | Vbz)
+    }
+}
+
+/// Get tag of a verb form
+fn verb_tag(lex: &Lexeme, form: &str, rule: Option<FormRule>) -> Penn {
+    match rule {
+        Some(FormRule::Present) => Penn::Vbz,
+        Some(FormRule::PresentParticiple) => Penn::Vbg,
+        Some(FormRule::Past) => Penn::Vbd,
+        Some(FormRule::Irregular) => {
+            let pos = lex.irregular_position(form).unwrap_or_default();
+            if lex.lemma() == "be" {
+                return BE_FORMS.get(pos).copied().unwrap_or(Penn::Vbd);
+            }
+            match pos {
+                0 => Penn::Vbz,
+                1 => Penn::Vbg,
+                2 => Penn::Vbd,
+                _ if form.ends_with("th") => Penn::Vbz,
+                _ if form.ends_with("st") => Penn::Vbp,
+                3 => Penn::Vbn,
+                _ => Penn::Vbd,
+            }
+        }
+        _ => Penn::Vb,
+    }
+}
+
+/// Get tag of a symbol (punctuation or other)
+fn symbol_tag(word: &str) -> Penn {
+    match word.chars().next() {
+        Some('.' | '!' | '?') => Penn::Period,
+        Some(',') => Penn::Comma,
+        Some(':' | ';' | '-' | '–' | '—' | '…') => Penn::Colon,
+        Some('`' | '“' | '‘' | '«') => Penn::OpenQuote,
+        Some('"' | '\'' | '”' | '’' | '»') => Penn::CloseQuote,
+        Some('(' | '[' | '{') => Penn::OpenParen,
+        Some(')' | ']' | '}') => Penn::CloseParen,
+        Some('$' | '£' | '€' | '¥') => Penn::Dollar,
+        Some('#') => Penn::Hash,
+        _ => Penn::Sym,
+    }
+}
+
+/// Refine tags of a sentence from context
+///
+/// Base verb forms (`VB`) become `VBP`, except at the start of a sentence
+/// or after a modal, `to` or a form of `do`.  Past forms (`VBD`) become
+/// `VBN` after a form of `be` or `have`.  Predeterminers (`PDT`) are
+/// detected before another determiner.
+pub fn refine(words: &[&str], tags: &mut [Option<Penn>]) {
+    for i in 0..tags.len().min(words.len()) {
+        let word = make_word(words[i]);
+        match tags[i] {
+            Some(Penn::Vb) if !follows_base_context(words, tags, i) => {
+                tags[i] = Some(Penn::Vbp);
+            }
+            Some(Penn::Vbd) if follows_perfect_passive(words, tags, i) => {
+                tags[i] = Some(Penn::Vbn);
+            }
+            Some(Penn::Dt)
+                if PREDETERMINERS.contains(&word.as_str())
+                    && matches!(
+                        tags.get(i + 1),
+                        Some(Some(Penn::Dt | Penn::PrpS))
+                    ) =>
+            {
+                tags[i] = Some(Penn::Pdt);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Check if a past verb form follows a form of `be` or `have`
+fn follows_perfect_passive(
+    words: &[&str],
+    tags: &[Option<Penn>],
+    i: usize,
+) -> bool {
+    previous(tags, i, &[Penn::Rb, Penn::Prp]).is_some_and(|j| {
+        PERFECT_PASSIVE.contains(&make_word(words[j]).as_str())
+    })
+}
+
+/// Check if a base verb form follows a non-finite context
+fn follows_base_context(
+    words: &[&str],
+    tags: &[Option<Penn>],
+    i: usize,
+) -> bool {
+    let Some(prev) = previous(tags, i, &[Penn::Rb]) else {
+        return true;
+    };
+    let j = match tags[prev] {
+        Some(Penn::Prp | Penn::Nnp) => {
+            match previous(tags, prev, &[Penn::Rb]) {
+                Some(j) => j,
+                None => return false,
+            }
+        }
+        _ => prev,
+    };
+    matches!(tags[j], Some(Penn::Md | Penn::To))
+        || DO_FORMS.contains(&make_word(words[j]).as_str())
+}
+
+/// Find the previous tag, skipping some tags
+fn previous(tags: &[Option<Penn>], i: usize, skip: &[Penn]) -> Option<usize> {
+    (0..i)
+        .rev()
+        .find(|j| !tags[*j].is_some_and(|t| skip.contains(&t)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn penn() {
+        let go = Lexeme::try_from("go:V,goes,going,went,gone").unwrap();
+        let tags: Vec<_> = ["go", "goes", "going", "went", "gone"]
+            .iter()
+            .map(|f| Penn::from_lexeme(&go, f))
+            .collect();
+        use Penn::*;
+        assert_eq!(tags, [Vb, Vbz, Vbg, Vbd, Vbn]);
+        let cat = Lexeme::try_from("cat:N").unwrap();
+        assert_eq!(Penn::from_lexeme(&cat, "Cats"), Nns);
+        let big = Lexeme::try_from("big:A.c").unwrap();
+        assert_eq!(Penn::from_lexeme(&big, "biggest"), Jjs);
+        assert_eq!(Penn::from_kind(Kind::Number, "42"), Some(Cd));
+        assert_eq!(Penn::from_kind(Kind::Symbol, "“"), Some(OpenQuote));
+        assert_eq!(Penn::try_from("PRP$").unwrap(), PrpS);
+        assert!(Penn::try_from("NOUN").is_err());
+        let words = ["I", "have", "walked", "to", "walk"];
+        let mut tags = [Some(Prp), Some(Vb), Some(Vbd), Some(To), Some(Vb)];
+        refine(&words, &mut tags);
+        assert_eq!(tags, [Some(Prp), Some(Vbp), Some(Vbn), Some(To), Some(Vb)]);
+    }
+
+    #[test]
+    fn lexemes() {
+        use Penn::*;
+        for (lexeme, form, tag) in [
+            ("be:V,am,are,is,was,were,been,being,art,wert", "am", Vbp),
+            ("be:V,am,are,is,was,were,been,being,art,wert", "been", Vbn),
+            ("be:V,am,are,is,was,were,been,being,art,wert", "wert", Vbd),
+            ("be:V,am,are,is,was,were,been,being,art,wert", "be", Vb),
+            ("speak:V,-ks,-king,spoke,spoken,spake", "spake", Vbd),
+            ("speak:V,-ks,-king,spoke,spoken,spake", "spoken", Vbn),
+            ("walk:V", "walked", Vbd),
+            ("can:V.a,could,canst", "could", Md),
+            ("good:A.c,better,best", "better", Jjr),
+            ("good:A.c,better,best", "best", Jjs),
+            ("well:Av", "well", Rb),
+            ("where:Av", "where", Wrb),
+            ("and:C", "and", Cc),
+            ("because:C", "because", In),
+            ("to:P", "to", To),
+            ("in:P", "in", In),
+            ("hello:I", "hello", Uh),
+            ("which:D", "which", Wdt),
+            ("whose:D", "whose", WpS),
+            ("thy:D", "thy", PrpS),
+            ("all:D", "all", Dt),
+            ("who:Pn", "who", Wp),
+            ("there:Pn", "there", Ex),
+            ("I:Pn", "I", Prp),
+            ("scissors:N.p", "scissors", Nns),
+            ("Paris:N.n", "Paris", Nnp),
+            ("Alps:N.np", "Alps", Nnps),
+        ] {
+            let lex = Lexeme::try_from(lexeme).unwrap();
+            assert_eq!(Penn::from_lexeme(&lex, form), tag, "{form}");
+        }
+    }
+
+    #[test]
+    fn kinds() {
+        use Penn::*;
+        for (kind, word, tag) in [
+            (Kind::Lexicon, "cat", None),
+            (Kind::Unknown, "blorf", None),
+            (Kind::Foreign, "café", Some(Fw)),
+            (Kind::Ordinal, "3rd", Some(Jj)),
+            (Kind::Roman, "XIV", Some(Cd)),
+            (Kind::Time, "10:30", Some(Cd)),
+            (Kind::Acronym, "NASA", Some(Nnp)),
+            (Kind::Url, "https://x.org", Some(Nn)),
+            (Kind::Mention, "@me", Some(Nnp)),
+            (Kind::Emoji, "🙂", Some(Sym)),
+            (Kind::Symbol, "?", Some(Period)),
+            (Kind::Symbol, ";", Some(Colon)),
+            (Kind::Symbol, "—", Some(Colon)),
+            (Kind::Symbol, "'", Some(CloseQuote)),
+            (Kind::Symbol, "[", Some(OpenParen)),
+            (Kind::Symbol, "}", Some(CloseParen)),
+            (Kind::Symbol, "€", Some(Dollar)),
+            (Kind::Symbol, "#", Some(Hash)),
+            (Kind::Symbol, "&", Some(Sym)),
+            (Kind::Symbol, "", Some(Sym)),
+        ] {
+            assert_eq!(Penn::from_kind(kind, word), tag, "{word}");
+        }
+        for tag in Penn::all() {
+            assert_eq!(Penn::try_from(tag.tag()).ok(), Some(*tag));
+            assert_eq!(tag.to_string(), tag.tag());
+        }
+        assert!(matches!(Penn::try_from("nn"), Err(Error::Tag(_))));
+        assert!(Penn::try_from("").is_err());
+        let verbs: Vec<_> =
+            Penn::all().iter().filter(|t| t.is_verb()).collect();
+        assert_eq!(verbs, [&Vb, &Vbd, &Vbg, &Vbn, &Vbp, &Vbz]);
+    }
+
+    #[test]
+    fn refinements() {
+        use Penn::*;
+        for (words, tags, refined) in [
+            (
+                &["Walk", "home"][..],
+                &[Some(Vb), Some(Nn)][..],
+                &[Vb, Nn][..],
+            ),
+            (&["we", "walk"], &[Some(Prp), Some(Vb)], &[Prp, Vbp]),
+            (
+                &["can", "we", "walk"],
+                &[Some(Md), Some(Prp), Some(Vb)],
+                &[Md, Prp, Vb],
+            ),
+            (
+                &["did", "not", "walk"],
+                &[Some(Vbd), Some(Rb), Some(Vb)],
+                &[Vbd, Rb, Vb],
+            ),
+            (
+                &["was", "never", "seen"],
+                &[Some(Vbd), Some(Rb), Some(Vbd)],
+                &[Vbd, Rb, Vbn],
+            ),
+            (&["he", "walked"], &[Some(Prp), Some(Vbd)], &[Prp, Vbd]),
+            (
+                &["all", "the", "cats"],
+                &[Some(Dt), Some(Dt), Some(Nns)],
+                &[Pdt, Dt, Nns],
+            ),
+            (&["all", "cats"], &[Some(Dt), Some(Nns)], &[Dt, Nns]),
+            (&["both", "my"], &[Some(Dt), Some(PrpS)], &[Pdt, PrpS]),
+        ] {
+            let mut tags = tags.to_vec();
+            refine(words, &mut tags);
+            let refined: Vec<_> = refined.iter().map(|t| Some(*t)).collect();
+            assert_eq!(tags, refined, "{words:?}");
+        }
+        // mismatched lengths and unknown tags are left alone
+        let mut tags = [None, Some(Vb), Some(Vb)];
+        refine(&["the", "walk"], &mut tags);
+        assert_eq!(tags, [None, Some(Vbp), Some(Vb)]);
+        refine(&[], &mut []);
+    }
+}
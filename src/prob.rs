@@ -0,0 +1,183 @@
+use crate::lex::make_word;
+use crate::word::WordClass;
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io::BufRead;
+use std::sync::LazyLock;
+
+/// Built-in class frequency source (CSV)
+const BUILTIN_CSV: &str = include_str!("../res/class_freq.csv");
+
+/// Static class frequency table
+static CLASS_FREQ: LazyLock<ClassFreq> = LazyLock::new(|| {
+    // the embedded source is validated by tests
+    ClassFreq::from_csv(BUILTIN_CSV)
+        .unwrap_or_else(|e| panic!("Built-in class frequencies: {e}"))
+});
+
+/// Get built-in class frequency table
+pub fn builtin() -> &'static ClassFreq {
+    &CLASS_FREQ
+}
+
+/// Unigram word class frequency table
+///
+/// Records how often each word form is used as each word class.
+#[derive(Clone, Debug, Default)]
+pub struct ClassFreq {
+    /// Class counts for each form (lowercase)
+    forms: HashMap<String, Vec<(WordClass, u32)>>,
+}
+
+impl ClassFreq {
+    /// Create a new empty class frequency table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a class frequency table from source (CSV lines)
+    ///
+    /// Each line is a form, followed by `Class:count` fields (e.g.
+    /// `like,P:60,V:30`).  Blank lines and `#` comments are ignored.
+    pub fn from_csv(src: &str) -> Result<Self> {
+        let mut freq = ClassFreq::new();
+        for (i, line) in src.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || Error::Decode(format!("line {}: `{line}`", i + 1));
+            let mut fields = line.split(',');
+            let form = fields.next().ok_or_else(invalid)?;
+            for field in fields {
+                let (class, count) =
+                    field.split_once(':').ok_or_else(invalid)?;
+                let class = WordClass::try_from(class)?;
+                let count = count.parse().map_err(|_| invalid())?;
+                freq.add(form, class, count);
+            }
+        }
+        Ok(freq)
+    }
+
+    /// Count word classes in a tagged corpus
+    ///
+    /// Tokens are separated by whitespace, tagged with a word class code
+    /// (e.g. `The/D cat/N sat/V`).  Untagged and punctuation tokens are
+    /// skipped.
+    pub fn from_tagged<R>(reader: R) -> Result<Self>
+    where
+        R: BufRead,
+    {
+        let mut freq = ClassFreq::new();
+        for line in reader.lines() {
+            for token in line?.split_whitespace() {
+                if let Some((form, class)) = token.rsplit_once('/')
+                    && form.chars().any(char::is_alphanumeric)
+                {
+                    freq.add(form, WordClass::try_from(class)?, 1);
+                }
+            }
+        }
+        Ok(freq)
+    }
+
+    /// Add to the count of a form used as a word class
+    pub fn add(&mut self, form: &str, class: WordClass, count: u32) {
+        let counts = self.forms.entry(make_word(form)).or_default();
+        match counts.iter_mut().find(|(wc, _n)| *wc == class) {
+            Some((_wc, n)) => *n += count,
+            None => counts.push((class, count)),
+        }
+    }
+
+    /// Get the count of a form used as a word class
+    pub fn count(&self, form: &str, class: WordClass) -> u32 {
+        self.forms
+            .get(&make_word(form))
+            .and_then(|c| c.iter().find(|(wc, _n)| *wc == class))
+            .map_or(0, |(_wc, n)| *n)
+    }
+
+    /// Get the probability of a form being used as a word class
+    pub fn probability(&self, form: &str, class: WordClass) -> f32 {
+        let total: u32 = self
+            .forms
+            .get(&make_word(form))
+            .map_or(0, |c| c.iter().map(|(_wc, n)| n).sum());
+        if total > 0 {
+            self.count(form, class) as f32 / total as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// Get the most likely of several candidate classes for a form
+    ///
+    /// Returns `None` if no candidate has been counted, or there is a tie.
+    pub fn most_likely(
+        &self,
+        form: &str,
+        candidates: &[WordClass],
+    ) -> Option<WordClass> {
+        let mut best = None;
+        let mut best_count = 0;
+        for wc in candidates {
+            let count = self.count(form, *wc);
+            if count > best_count {
+                best = Some(*wc);
+                best_count = count;
+            } else if count == best_count {
+                best = None;
+            }
+        }
+        best
+    }
+
+    /// Write the table as CSV lines (sorted by form)
+    pub fn to_csv(&self) -> String {
+        let mut forms: Vec<_> = self.forms.iter().collect();
+        forms.sort();
+        let mut csv = String::new();
+        for (form, counts) in forms {
+            let mut counts = counts.clone();
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            csv.push_str(form);
+            for (wc, n) in counts {
+                let _ = write!(csv, ",{wc}:{n}");
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn class_freq() {
+        let freq = builtin();
+        let classes = [WordClass::Verb, WordClass::Preposition];
+        assert_eq!(
+            freq.most_likely("Like", &classes),
+            Some(WordClass::Preposition)
+        );
+        assert_eq!(freq.most_likely("blorf", &classes), None);
+        assert_eq!(freq.probability("run", WordClass::Verb), 0.8);
+        let tagged = "The/D run/N was/V long/A ./. \n runs/V run/V run/N";
+        let freq = ClassFreq::from_tagged(tagged.as_bytes()).unwrap();
+        assert_eq!(freq.count("run", WordClass::Noun), 2);
+        assert_eq!(freq.most_likely("run", &classes), Some(WordClass::Verb));
+        assert!(ClassFreq::from_tagged(&b"run/Q"[..]).is_err());
+        let csv = freq.to_csv();
+        assert_eq!(ClassFreq::from_csv(&csv).unwrap().to_csv(), csv);
+        assert!(ClassFreq::from_csv("run,V:x").is_err());
+        let lex = crate::lex::builtin();
+        assert_eq!(lex.most_likely_class("mice"), Some(WordClass::Noun));
+        assert_eq!(lex.most_likely_class("that"), Some(WordClass::Conjunction));
+        assert_eq!(lex.most_likely_class("blorf"), None);
+    }
+}
@@ -0,0 +1,471 @@
+use crate::Result;
+use crate::lex::{self, Lexicon};
+use crate::parse::{Chunk, Parser};
+use crate::word::WordClass;
+use std::io::BufRead;
+use std::sync::Arc;
+
+/// Vowel spellings and their sounds
+const VOWEL_SOUNDS: &[(&str, &str)] = &[
+    ("ai", "A"),
+    ("ay", "A"),
+    ("ei", "A"),
+    ("ey", "A"),
+    ("ea", "E"),
+    ("ee", "E"),
+    ("ie", "I"),
+    ("oa", "O"),
+    ("oe", "O"),
+    ("oo", "U"),
+    ("ew", "U"),
+    ("ue", "U"),
+    ("ou", "OU"),
+    ("ow", "OU"),
+    ("oi", "OI"),
+    ("oy", "OI"),
+];
+
+/// Open vowels at the end of a word and their sounds (e.g. "me", "go")
+const OPEN_SOUNDS: &[(&str, &str)] = &[("e", "E"), ("o", "O"), ("y", "I")];
+
+/// Long vowel sounds before a silent `e` (e.g. "date")
+const LONG_VOWELS: &[(char, &str)] =
+    &[('a', "A"), ('e', "E"), ('i', "I"), ('o', "O"), ('u', "U")];
+
+/// Initial spellings and their sounds (longest first)
+const INITIAL_SOUNDS: &[(&str, &str)] = &[
+    ("sch", "sk"),
+    ("ch", "ch"),
+    ("gn", "n"),
+    ("kn", "n"),
+    ("ph", "f"),
+    ("ps", "s"),
+    ("sh", "sh"),
+    ("th", "th"),
+    ("wh", "w"),
+    ("wr", "r"),
+    ("ce", "s"),
+    ("ci", "s"),
+    ("cy", "s"),
+    ("c", "k"),
+    ("q", "k"),
+];
+
+/// Stanza of a poem
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stanza {
+    /// Lines of text
+    lines: Vec<String>,
+    /// End-rhyme scheme (one letter per line)
+    scheme: String,
+    /// Alliterative runs
+    alliterations: Vec<Alliteration>,
+}
+
+/// Alliterative run of words in a line
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Alliteration {
+    /// Line number in stanza (starting at 0)
+    line: usize,
+    /// Initial sound
+    sound: String,
+    /// Words in run
+    words: Vec<String>,
+}
+
+/// Rhyme and alliteration analyzer
+#[derive(Clone, Default)]
+pub struct RhymeAnalyzer {
+    /// Lexicon for parsing (built-in if not set)
+    lex: Option<Arc<Lexicon>>,
+}
+
+/// Get the rhyme key of a word (its last vowel sound and following
+/// consonants)
+///
+/// The key is guessed from spelling, so some rhymes are missed (e.g.
+/// "love" / "of").  Returns `None` if the word has no vowels.
+pub fn rhyme_key(word: &str) -> Option<String> {
+    let word = word.to_lowercase();
+    let word = word.trim_end_matches("'s").trim_end_matches('\'');
+    let chars: Vec<char> = word.chars().filter(|c| c.is_alphabetic()).collect();
+    let is_vowel = |i: usize| {
+        matches!(chars[i], 'a' | 'e' | 'i' | 'o' | 'u')
+            || (chars[i] == 'y' && i > 0)
+    };
+    let n = chars.len();
+    // silent `e` after a single consonant makes a long vowel
+    if n >= 3
+        && chars[n - 1] == 'e'
+        && !is_vowel(n - 2)
+        && is_vowel(n - 3)
+        && (n == 3 || !is_vowel(n - 4))
+        && let Some((_v, sound)) =
+            LONG_VOWELS.iter().find(|(v, _s)| *v == chars[n - 3])
+    {
+        return Some(format!("{sound}{}", chars[n - 2]));
+    }
+    let end = (0..n).rev().find(|i| is_vowel(*i))? + 1;
+    let mut start = end - 1;
+    while start > 0 && is_vowel(start - 1) {
+        start -= 1;
+    }
+    let vowels: String = chars[start..end].iter().collect();
+    let mut tail: String = chars[end..].iter().collect();
+    let sound = if let Some(t) = tail.strip_prefix("gh") {
+        // "igh" / "eigh" spellings
+        let sound = match vowels.as_str() {
+            "ei" => "A",
+            v if v.ends_with('i') => "I",
+            _ => "A",
+        };
+        tail = t.to_string();
+        sound
+    } else if tail.is_empty() && (start == 0 || n <= 3 || vowels == "e") {
+        OPEN_SOUNDS
+            .iter()
+            .chain(VOWEL_SOUNDS)
+            .find(|(v, _s)| *v == vowels)
+            .map_or(vowels.as_str(), |(_v, s)| s)
+    } else {
+        VOWEL_SOUNDS
+            .iter()
+            .find(|(v, _s)| *v == vowels)
+            .map_or(vowels.as_str(), |(_v, s)| s)
+    };
+    Some(format!("{sound}{}", normalize_consonants(&tail)))
+}
+
+/// Normalize spelling of final consonants
+fn normalize_consonants(tail: &str) -> String {
+    let tail = tail.replace("ck", "k").replace("ph", "f");
+    let mut out = String::with_capacity(tail.len());
+    for c in tail.chars() {
+        if !out.ends_with(c) {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Check if two words rhyme
+pub fn rhymes(a: &str, b: &str) -> bool {
+    match (rhyme_key(a), rhyme_key(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// Get the initial consonant sound of a word
+///
+/// Returns `None` for words starting with a vowel sound.
+pub fn initial_sound(word: &str) -> Option<String> {
+    let word = word.to_lowercase();
+    let first = word.chars().next()?;
+    if !first.is_alphabetic() || matches!(first, 'a' | 'e' | 'i' | 'o' | 'u') {
+        return None;
+    }
+    if ["heir", "honest", "honor", "honour", "hour"]
+        .iter()
+        .any(|w| word.starts_with(w))
+    {
+        return None;
+    }
+    match INITIAL_SOUNDS
+        .iter()
+        .find(|(s, _sound)| word.starts_with(s))
+    {
+        Some((_s, sound)) => Some(sound.to_string()),
+        None => Some(first.to_string()),
+    }
+}
+
+impl Stanza {
+    /// Get lines of text
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+
+    /// Get end-rhyme scheme (e.g. `ABAB`)
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// Get alliterative runs
+    pub fn alliterations(&self) -> &[Alliteration] {
+        &self.alliterations
+    }
+}
+
+impl Alliteration {
+    /// Get line number in stanza (starting at 0)
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get initial sound
+    pub fn sound(&self) -> &str {
+        &self.sound
+    }
+
+    /// Get words in run
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+}
+
+impl RhymeAnalyzer {
+    /// Create a new rhyme analyzer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new rhyme analyzer with a shared lexicon
+    pub fn with_lexicon(lex: Arc<Lexicon>) -> Self {
+        RhymeAnalyzer { lex: Some(lex) }
+    }
+
+    /// Analyze a poem from a reader
+    ///
+    /// Stanzas are separated by blank lines.
+    pub fn analyze<R>(&self, reader: R) -> Result<Vec<Stanza>>
+    where
+        R: BufRead,
+    {
+        let mut stanzas = Vec::new();
+        let mut lines = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                if !lines.is_empty() {
+                    stanzas.push(self.stanza(std::mem::take(&mut lines))?);
+                }
+            } else {
+                lines.push(line.to_string());
+            }
+        }
+        if !lines.is_empty() {
+            stanzas.push(self.stanza(lines)?);
+        }
+        Ok(stanzas)
+    }
+
+    /// Analyze one stanza
+    fn stanza(&self, lines: Vec<String>) -> Result<Stanza> {
+        let lex = self.lex.clone().unwrap_or_else(lex::builtin_shared);
+        let mut keys: Vec<Option<String>> = Vec::new();
+        let mut scheme = String::new();
+        let mut alliterations = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            let words = self.words(&lex, line)?;
+            let key = words.last().and_then(|w| rhyme_key(w));
+            let letter = match &key {
+                Some(_) => match keys.iter().position(|k| *k == key) {
+                    Some(j) => scheme.chars().nth(j).unwrap_or('-'),
+                    None => next_letter(&scheme),
+                },
+                None => '-',
+            };
+            keys.push(key);
+            scheme.push(letter);
+            alliterations.extend(alliterative_runs(&lex, i, &words));
+        }
+        Ok(Stanza {
+            lines,
+            scheme,
+            alliterations,
+        })
+    }
+
+    /// Get the words of a line
+    fn words(&self, lex: &Arc<Lexicon>, line: &str) -> Result<Vec<String>> {
+        let mut words = Vec::new();
+        for token in Parser::with_lexicon(line.as_bytes(), Arc::clone(lex)) {
+            let token = token?;
+            if token.chunk() == Chunk::Text
+                && token.text().chars().any(char::is_alphabetic)
+            {
+                words.push(token.into_text());
+            }
+        }
+        Ok(words)
+    }
+}
+
+/// Get the next unused scheme letter
+fn next_letter(scheme: &str) -> char {
+    ('A'..='Z')
+        .chain('a'..='z')
+        .find(|c| !scheme.contains(*c))
+        .unwrap_or('?')
+}
+
+/// Check if a word is a function word (skipped for alliteration)
+fn is_function_word(lex: &Lexicon, word: &str) -> bool {
+    let entries = lex.word_entries(word);
+    !entries.is_empty()
+        && entries.iter().all(|e| {
+            matches!(
+                e.word_class(),
+                WordClass::Conjunction
+                    | WordClass::Determiner
+                    | WordClass::Preposition
+                    | WordClass::Pronoun
+            )
+        })
+}
+
+/// Find alliterative runs in the words of a line
+///
+/// Runs are two or more words with the same initial consonant sound, not
+/// counting function words between them.
+fn alliterative_runs(
+    lex: &Lexicon,
+    line: usize,
+    words: &[String],
+) -> Vec<Alliteration> {
+    let mut runs = Vec::new();
+    let mut run: Vec<&String> = Vec::new();
+    let mut sound = None;
+    for word in words {
+        if is_function_word(lex, word) {
+            continue;
+        }
+        let initial = initial_sound(word);
+        if initial.is_none() || initial != sound {
+            if run.len() > 1
+                && let Some(sound) = sound
+            {
+                runs.push(Alliteration {
+                    line,
+                    sound,
+                    words: run.iter().map(|w| w.to_string()).collect(),
+                });
+            }
+            run.clear();
+            sound = initial;
+        }
+        if sound.is_some() {
+            run.push(word);
+        }
+    }
+    if run.len() > 1
+        && let Some(sound) = sound
+    {
+        runs.push(Alliteration {
+            line,
+            sound,
+            words: run.iter().map(|w| w.to_string()).collect(),
+        });
+    }
+    runs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rhyme() {
+        for (a, b) in [
+            ("day", "May"),
+            ("date", "temperate"),
+            ("thee", "me"),
+            ("night", "white"),
+            ("sting", "thing"),
+            ("weigh", "day"),
+        ] {
+            assert!(rhymes(a, b), "{a} / {b}");
+        }
+        assert!(!rhymes("day", "night"));
+        assert_eq!(initial_sound("phone"), initial_sound("fish"));
+        assert_eq!(initial_sound("cent"), initial_sound("sun"));
+        assert_eq!(initial_sound("apple"), None);
+        let poem = "Shall I compare thee to a summer's day?\n\
+            Thou art more lovely and more temperate:\n\
+            Rough winds do shake the darling buds of May,\n\
+            And summer's lease hath all too short a date;\n\
+            \n\
+            Peter picked a peck of pickled peppers.\n\
+            The cat sat on the mat.\n";
+        let stanzas = RhymeAnalyzer::new().analyze(poem.as_bytes()).unwrap();
+        assert_eq!(stanzas.len(), 2);
+        assert_eq!(stanzas[0].scheme(), "ABAB");
+        assert_eq!(stanzas[1].scheme(), "AB");
+        let run = &stanzas[1].alliterations()[0];
+        assert_eq!(run.line(), 0);
+        assert_eq!(run.sound(), "p");
+        assert_eq!(run.words().len(), 5);
+    }
+
+    #[test]
+    fn keys() {
+        for (word, key) in [
+            ("cat's", Some("at")),
+            ("dogs'", Some("ogs")),
+            ("back", Some("ak")),
+            ("staff", Some("af")),
+            ("graph", Some("af")),
+            ("weigh", Some("A")),
+            ("light", Some("It")),
+            ("go", Some("O")),
+            ("be", Some("E")),
+            ("free", Some("E")),
+            ("boat", Some("Ot")),
+            ("mine", Some("In")),
+            ("rhythm", Some("ythm")),
+            ("brr", None),
+            ("42", None),
+            ("", None),
+        ] {
+            assert_eq!(rhyme_key(word).as_deref(), key, "{word}");
+        }
+        assert!(!rhymes("brr", "brr"));
+        assert!(rhymes("Knight", "kite"));
+        for (word, sound) in [
+            ("school", Some("sk")),
+            ("knight", Some("n")),
+            ("wrist", Some("r")),
+            ("Cycle", Some("s")),
+            ("queen", Some("k")),
+            ("xylophone", Some("x")),
+            ("hour", None),
+            ("Honest", None),
+            ("house", Some("h")),
+            ("'tis", None),
+            ("", None),
+        ] {
+            assert_eq!(initial_sound(word).as_deref(), sound, "{word}");
+        }
+    }
+
+    #[test]
+    fn stanzas() {
+        let analyzer = RhymeAnalyzer::new();
+        assert!(analyzer.analyze("".as_bytes()).unwrap().is_empty());
+        assert!(analyzer.analyze("\n \n\t\n".as_bytes()).unwrap().is_empty());
+        assert!(analyzer.analyze(&[0xFF, b'\n'][..]).is_err());
+        let poem = "\n\nOne fine day\n1999\n\n\n\nbrr\nthey play\n\
+            the sky\nand May\n";
+        let stanzas = analyzer.analyze(poem.as_bytes()).unwrap();
+        let schemes: Vec<_> = stanzas.iter().map(Stanza::scheme).collect();
+        assert_eq!(schemes, ["A-", "-ABA"]);
+        assert_eq!(
+            stanzas[1].lines(),
+            ["brr", "they play", "the sky", "and May"]
+        );
+        // function words do not break a run, but other words do
+        let stanzas = analyzer
+            .analyze("Big and bold, with a red, bright bell".as_bytes())
+            .unwrap();
+        let runs: Vec<_> = stanzas[0]
+            .alliterations()
+            .iter()
+            .map(|r| r.words().join(" "))
+            .collect();
+        assert_eq!(runs, ["Big bold", "bright bell"]);
+    }
+}
@@ -0,0 +1,95 @@
+use alloc::string::String;
+
+/// Numeral symbols with values, including subtractive pairs
+const NUMERALS: &[(&str, u32)] = &[
+    ("M", 1000),
+    ("CM", 900),
+    ("D", 500),
+    ("CD", 400),
+    ("C", 100),
+    ("XC", 90),
+    ("L", 50),
+    ("XL", 40),
+    ("X", 10),
+    ("IX", 9),
+    ("V", 5),
+    ("IV", 4),
+    ("I", 1),
+];
+
+/// Largest value with a standard roman numeral
+pub const MAX: u32 = 3999;
+
+/// Convert an integer to an uppercase roman numeral
+///
+/// Returns `None` unless `n` is between 1 and `MAX`.
+pub fn from_int(mut n: u32) -> Option<String> {
+    if !(1..=MAX).contains(&n) {
+        return None;
+    }
+    let mut numeral = String::new();
+    for (sym, val) in NUMERALS {
+        while n >= *val {
+            numeral.push_str(sym);
+            n -= val;
+        }
+    }
+    Some(numeral)
+}
+
+/// Convert a roman numeral to an integer
+///
+/// The numeral must be all uppercase or all lowercase, in standard form:
+/// "XIV" is 14, but "XIIII" and "XM" are not valid.
+pub fn to_int(numeral: &str) -> Option<u32> {
+    let upper = numeral.chars().all(|c| c.is_ascii_uppercase());
+    let lower = numeral.chars().all(|c| c.is_ascii_lowercase());
+    if numeral.is_empty() || !(upper || lower) {
+        return None;
+    }
+    let mut rest = numeral;
+    let mut n = 0;
+    for (sym, val) in NUMERALS {
+        while rest.len() >= sym.len()
+            && rest[..sym.len()].eq_ignore_ascii_case(sym)
+        {
+            rest = &rest[sym.len()..];
+            n += val;
+        }
+    }
+    // only the standard form round-trips ("IIII" would become "IV")
+    let canonical = from_int(n)?;
+    (rest.is_empty() && canonical.eq_ignore_ascii_case(numeral)).then_some(n)
+}
+
+/// Check if a word is a valid roman numeral
+pub fn is_valid(word: &str) -> bool {
+    to_int(word).is_some()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn numerals() {
+        for (numeral, n) in [
+            ("I", 1),
+            ("iv", 4),
+            ("IX", 9),
+            ("XIV", 14),
+            ("XLII", 42),
+            ("MCMXCIX", 1999),
+            ("MMMCMXCIX", 3999),
+        ] {
+            assert_eq!(to_int(numeral), Some(n), "{numeral}");
+            assert_eq!(from_int(n).unwrap(), numeral.to_uppercase());
+        }
+        for numeral in ["", "IIII", "XM", "VV", "IC", "did", "Xiv", "MMMM"] {
+            assert_eq!(to_int(numeral), None, "{numeral}");
+        }
+        assert_eq!(to_int("mix"), Some(1009));
+        assert_eq!(from_int(0), None);
+        assert_eq!(from_int(MAX + 1), None);
+    }
+}
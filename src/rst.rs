@@ -0,0 +1,132 @@
+use crate::Result;
+use regex::Regex;
+use std::io::BufRead;
+use std::sync::LazyLock;
+
+/// Inline literals and math roles
+static LITERAL: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"``[^`]*``|:(?:code|math|samp|kbd|file):`[^`]*`").unwrap()
+});
+
+/// Hyperlink references and roles with targets (keeping text)
+static REFERENCE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?::[\w:.-]+:)?`([^`<]*?)\s*(?:<[^>]*>)?`_{0,2}").unwrap()
+});
+
+/// Bare URLs
+static URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"https?://\S+").unwrap());
+
+/// Directives with prose content
+const PROSE_DIRECTIVES: &[&str] = &[
+    "admonition",
+    "attention",
+    "caution",
+    "danger",
+    "epigraph",
+    "error",
+    "hint",
+    "important",
+    "note",
+    "rubric",
+    "seealso",
+    "sidebar",
+    "tip",
+    "topic",
+    "warning",
+];
+
+/// Strip literals, link targets and URLs from a line
+fn strip_inline(line: &str) -> String {
+    let line = LITERAL.replace_all(line, "");
+    let line = REFERENCE.replace_all(&line, "$1");
+    URL.replace_all(&line, "").into_owned()
+}
+
+/// Check if a line is a section adornment (e.g. `=====`)
+fn is_adornment(line: &str) -> bool {
+    let mut chars = line.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_punctuation() => {
+            line.len() >= 2 && chars.all(|x| x == c)
+        }
+        _ => false,
+    }
+}
+
+/// Get indentation width of a line
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Extract prose text from reStructuredText source
+///
+/// Directives (other than admonitions), comments, link targets, literal
+/// blocks and inline literals are skipped.  Line numbers match the source.
+pub fn extract_text<R>(reader: R) -> Result<String>
+where
+    R: BufRead,
+{
+    let mut text = String::new();
+    // indentation of line introducing a skipped block
+    let mut skip: Option<usize> = None;
+    for line in reader.lines() {
+        let line = line?;
+        let trimmed = line.trim();
+        if let Some(ind) = skip {
+            if trimmed.is_empty() || indent(&line) > ind {
+                text.push('\n');
+                continue;
+            }
+            skip = None;
+        }
+        if trimmed == ".." || trimmed.starts_with(".. ") {
+            // directive, comment, link target or substitution
+            match trimmed[2..].split_once("::") {
+                Some((name, arg))
+                    if PROSE_DIRECTIVES.contains(&name.trim()) =>
+                {
+                    text.push_str(&strip_inline(arg.trim_start()));
+                }
+                _ => skip = Some(indent(&line)),
+            }
+        } else if !is_adornment(trimmed) {
+            let mut line = strip_inline(&line);
+            if let Some(prose) = line.trim_end().strip_suffix("::") {
+                // following indented block is literal
+                skip = Some(indent(&line));
+                let prose = match prose.strip_suffix(' ') {
+                    Some(p) => p.trim_end().to_string(),
+                    None if prose.trim().is_empty() => String::new(),
+                    None => format!("{prose}:"),
+                };
+                line = prose;
+            }
+            text.push_str(&line);
+        }
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extract() {
+        let rst = "Title\n=====\n\n\
+            Use ``foo()`` and see `the docs <https://x.io>`_ or :ref:`intro`.\n\n\
+            .. code-block:: python\n\n   print(1)\n\n\
+            .. note:: Be careful.\n\n\
+            Example::\n\n    let x = 1;\n\n\
+            Done.\n\
+            .. _target: https://x.io\n";
+        let text = extract_text(rst.as_bytes()).unwrap();
+        assert_eq!(
+            text,
+            "Title\n\n\nUse  and see the docs or intro.\n\n\n\n\n\n\
+             Be careful.\n\nExample:\n\n\n\nDone.\n\n"
+        );
+    }
+}
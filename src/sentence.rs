@@ -0,0 +1,331 @@
+use crate::Result;
+use crate::kind::Kind;
+use crate::lex::{self, Lexicon, is_apostrophe};
+use crate::parse::{Chunk, Parser, is_terminal_abbreviation};
+use std::io::BufRead;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Abbreviations which do not end a sentence (lowercase)
+const ABBREVIATIONS: &[&str] = &[
+    "capt", "cf", "col", "dr", "e.g", "fig", "gen", "gov", "hon", "i.e", "jr",
+    "lt", "messrs", "mme", "mlle", "mr", "mrs", "ms", "mt", "no", "prof",
+    "rev", "sgt", "sr", "st", "vs",
+];
+
+/// Sentence of text
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sentence {
+    /// Byte span in text
+    span: Range<usize>,
+    /// Sentence text (including trailing whitespace)
+    text: String,
+    /// Word spans (in text) and kinds
+    words: Vec<(Range<usize>, Kind)>,
+}
+
+/// Sentence boundary state
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum State {
+    /// Within a sentence
+    Open,
+    /// After terminal punctuation
+    Closing,
+    /// After terminal punctuation and whitespace
+    Spaced,
+    /// After a blank line
+    Break,
+}
+
+/// Iterator of sentences from a reader
+///
+/// Sentences end with `.`, `!`, `?` or `…`, followed by whitespace and a
+/// word which is not lowercase.  Closing quotes and brackets after the
+/// punctuation are included.  Common abbreviations (e.g. "Mr.") and single
+/// letters do not end a sentence.  A blank line always ends a sentence.
+pub struct Sentences<R: BufRead> {
+    /// Text parser
+    parser: Parser<R>,
+    /// Chunks of current sentence (with byte spans)
+    chunks: Vec<(Chunk, String, Kind, Range<usize>)>,
+    /// Boundary state
+    state: State,
+    /// Count of consecutive newlines
+    newlines: usize,
+}
+
+impl Sentence {
+    /// Get byte span in text
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Get sentence text (including trailing whitespace)
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get an iterator of words (and kinds)
+    pub fn words(&self) -> impl Iterator<Item = (&str, Kind)> {
+        self.words.iter().map(|(span, kind)| {
+            let start = span.start - self.span.start;
+            let end = span.end - self.span.start;
+            (&self.text[start..end], *kind)
+        })
+    }
+
+    /// Get count of words
+    pub fn word_count(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Get word byte spans (in text)
+    pub(crate) fn word_spans(&self) -> impl Iterator<Item = Range<usize>> {
+        self.words.iter().map(|(span, _kind)| span.clone())
+    }
+}
+
+impl<R> Iterator for Sentences<R>
+where
+    R: BufRead,
+{
+    type Item = Result<Sentence>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(token) = self.parser.next() {
+            let token = match token {
+                Ok(token) => token,
+                Err(e) => return Some(Err(e.into())),
+            };
+            let (chunk, kind, span) =
+                (token.chunk(), token.kind(), token.span());
+            let txt = token.into_text();
+            if self.is_end(chunk, &txt) {
+                let sentence = self.take_sentence();
+                self.push(chunk, txt, kind, span);
+                return sentence.map(Ok);
+            }
+            self.push(chunk, txt, kind, span);
+        }
+        self.take_sentence().map(Ok)
+    }
+}
+
+impl<R> Sentences<R>
+where
+    R: BufRead,
+{
+    /// Create a new sentence iterator (using the built-in lexicon)
+    pub fn new(reader: R) -> Self {
+        Self::with_lexicon(reader, lex::builtin_shared())
+    }
+
+    /// Create a new sentence iterator with a shared lexicon
+    pub fn with_lexicon(reader: R, lex: Arc<Lexicon>) -> Self {
+        Sentences {
+            parser: Parser::with_lexicon(reader, lex),
+            chunks: Vec::new(),
+            state: State::Open,
+            newlines: 0,
+        }
+    }
+
+    /// Check if a chunk starts a new sentence
+    fn is_end(&self, chunk: Chunk, txt: &str) -> bool {
+        match self.state {
+            State::Spaced => {
+                chunk != Chunk::Boundary
+                    && !txt.starts_with(|c: char| c.is_lowercase())
+            }
+            State::Break => chunk != Chunk::Boundary,
+            _ => false,
+        }
+    }
+
+    /// Push a chunk to the current sentence
+    fn push(
+        &mut self,
+        chunk: Chunk,
+        txt: String,
+        kind: Kind,
+        span: Range<usize>,
+    ) {
+        if chunk == Chunk::Boundary {
+            if txt == "\n" {
+                self.newlines += 1;
+            }
+            if self.newlines > 1 && self.has_words() {
+                self.state = State::Break;
+            } else if self.state == State::Closing {
+                self.state = State::Spaced;
+            }
+        } else {
+            self.newlines = 0;
+            self.state = match self.state {
+                State::Closing if is_closing(chunk, &txt) => State::Closing,
+                _ if self.is_terminal(&txt) => State::Closing,
+                _ if chunk == Chunk::Text && is_terminal_abbreviation(&txt) => {
+                    State::Closing
+                }
+                _ => State::Open,
+            };
+        }
+        self.chunks.push((chunk, txt, kind, span));
+    }
+
+    /// Check if a chunk is terminal punctuation
+    fn is_terminal(&self, txt: &str) -> bool {
+        match txt {
+            "!" | "?" | "…" => true,
+            "." => {
+                // repeated dots make an ellipsis
+                if self.state == State::Closing {
+                    return true;
+                }
+                match self.chunks.last() {
+                    Some((Chunk::Text, word, _kind, _span)) => {
+                        !is_abbreviation(word)
+                    }
+                    _ => true,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// Check if the current sentence has any words
+    fn has_words(&self) -> bool {
+        self.chunks.iter().any(|(c, ..)| *c == Chunk::Text)
+    }
+
+    /// Take the current sentence
+    fn take_sentence(&mut self) -> Option<Sentence> {
+        self.state = State::Open;
+        self.newlines = 0;
+        let chunks = std::mem::take(&mut self.chunks);
+        if !chunks.iter().any(|(c, ..)| *c != Chunk::Boundary) {
+            return None;
+        }
+        let start = chunks.first().map_or(0, |(.., span)| span.start);
+        let end = chunks.last().map_or(0, |(.., span)| span.end);
+        let mut text = String::with_capacity(end - start);
+        let mut words = Vec::new();
+        for (chunk, txt, kind, span) in chunks {
+            if chunk == Chunk::Text && txt.chars().any(char::is_alphanumeric) {
+                words.push((span, kind));
+            }
+            text.push_str(&txt);
+        }
+        Some(Sentence {
+            span: start..end,
+            text,
+            words,
+        })
+    }
+}
+
+/// Check if a chunk is closing punctuation (quote or bracket)
+fn is_closing(chunk: Chunk, txt: &str) -> bool {
+    match chunk {
+        Chunk::Text => txt.chars().all(is_apostrophe),
+        Chunk::Symbol => {
+            matches!(txt, "\"" | "”" | "»" | ")" | "]" | "}" | "!" | "?")
+        }
+        Chunk::Boundary | Chunk::Emoji => false,
+    }
+}
+
+/// Check if a word is an abbreviation (or single letter)
+pub(crate) fn is_abbreviation(word: &str) -> bool {
+    let word = word.to_lowercase();
+    word.chars().count() == 1 || ABBREVIATIONS.contains(&word.as_str())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn split(text: &str) -> Vec<String> {
+        Sentences::new(text.as_bytes())
+            .map(|s| s.unwrap().text().trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn sentences() {
+        assert_eq!(
+            split("Mr. Smith met Dr. J. R. Jones at 3.15 p.m. today. Why?"),
+            ["Mr. Smith met Dr. J. R. Jones at 3.15 p.m. today.", "Why?"]
+        );
+        assert_eq!(
+            split("\"Stop!\" she cried. \"Wait...\" He waited... and left."),
+            [
+                "\"Stop!\" she cried.",
+                "\"Wait...\"",
+                "He waited... and left."
+            ]
+        );
+        assert_eq!(
+            split("We ate pears, e.g. Bosc, etc. Then we left at 5 p.m. Bye."),
+            [
+                "We ate pears, e.g. Bosc, etc.",
+                "Then we left at 5 p.m.",
+                "Bye."
+            ]
+        );
+        assert_eq!(
+            split("Chapter One\n\nIt was dark"),
+            ["Chapter One", "It was dark"]
+        );
+        let text = "It rained.  The end.\n";
+        let sentences: Vec<_> = Sentences::new(text.as_bytes())
+            .map(|s| s.unwrap())
+            .collect();
+        assert_eq!(sentences[1].span(), 12..text.len());
+        assert_eq!(sentences[1].text(), "The end.\n");
+        let words: Vec<_> = sentences[0].words().map(|(w, _k)| w).collect();
+        assert_eq!(words, ["It", "rained"]);
+        assert_eq!(sentences[1].word_count(), 2);
+        for (text, sentences) in [
+            ("", &[][..]),
+            (" \n\n\t", &[]),
+            ("...", &["..."]),
+            ("no end", &["no end"]),
+            (
+                "It ended. then it went on.",
+                &["It ended. then it went on."],
+            ),
+            (
+                "Wait!? (Yes.) Really…  Done",
+                &["Wait!?", "(Yes.)", "Really…", "Done"],
+            ),
+            (
+                "See St. Paul. Plan A. Go.",
+                &["See St. Paul.", "Plan A. Go."],
+            ),
+            ("It's 'over.' Next", &["It's 'over.'", "Next"]),
+            ("one\ntwo\n\n\nthree", &["one\ntwo", "three"]),
+            // leading whitespace stays with the first sentence
+            ("\n\nIt starts late.", &["\n\nIt starts late."]),
+        ] {
+            assert_eq!(split(text), sentences, "{text:?}");
+        }
+        assert!(Sentences::new(&[b'a', 0xFF][..]).any(|s| s.is_err()));
+        // numbers are words, but punctuation is not
+        let text = "In 1999 -- odd & new!";
+        let sentence = Sentences::new(text.as_bytes()).next().unwrap().unwrap();
+        let words: Vec<_> = sentence.words().map(|(w, _k)| w).collect();
+        assert_eq!(words, ["In", "1999", "odd", "new"]);
+        assert_eq!(sentence.span(), 0..text.len());
+        for (word, abbrev) in [
+            ("Mr", true),
+            ("MRS", true),
+            ("x", true),
+            ("Ms.", false),
+            ("", false),
+        ] {
+            assert_eq!(is_abbreviation(word), abbrev, "{word}");
+        }
+    }
+}
@@ -0,0 +1,361 @@
+use crate::Result;
+use crate::kind::Kind;
+use crate::lex::{self, Lexicon, make_word};
+use crate::parse::{Chunk, Parser};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io::BufRead;
+use std::sync::Arc;
+
+/// Spelling substitutions (British, American, minimum prefix length)
+///
+/// A substitution only applies when both spellings are forms of one lexeme,
+/// or of lexemes with the same class whose lemmas follow the same rule.
+const RULES: &[(&str, &str, usize)] = &[
+    ("our", "or", 2),
+    ("tre", "ter", 2),
+    ("bre", "ber", 2),
+    ("ence", "ense", 2),
+    ("ogue", "og", 3),
+    ("lled", "led", 2),
+    ("lling", "ling", 2),
+    ("ller", "ler", 2),
+    ("ae", "e", 1),
+    ("ise", "ize", 2),
+    ("isa", "iza", 2),
+    ("isi", "izi", 2),
+    ("yse", "yze", 2),
+    ("ysi", "yzi", 2),
+    ("aluminium", "aluminum", 0),
+    ("ageing", "aging", 0),
+    ("grey", "gray", 0),
+    ("jewellery", "jewelry", 0),
+    ("mould", "mold", 0),
+    ("plough", "plow", 0),
+    ("pyjama", "pajama", 0),
+    ("sceptic", "skeptic", 0),
+];
+
+/// Spelling region
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Region {
+    /// British spelling (e.g. `colour`)
+    British,
+    /// American spelling (e.g. `color`)
+    American,
+}
+
+/// Regional spelling of a word
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spelling {
+    /// Region of the spelling
+    region: Region,
+    /// Spelling in the other region
+    counterpart: String,
+    /// Lemmas (British, American) of the spelling pair
+    lemmas: (String, String),
+}
+
+/// Regional spelling tally entry
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpellingEntry {
+    /// Seen count
+    seen: usize,
+    /// Word (lowercase)
+    word: String,
+    /// Regional spelling
+    spelling: Spelling,
+}
+
+/// Tally of regional spellings (British vs American)
+#[derive(Clone, Default)]
+pub struct SpellingTally {
+    /// Regional spelling entries
+    words: HashMap<String, SpellingEntry>,
+    /// Words checked which are not regional spellings
+    neutral: HashSet<String>,
+    /// Lexicon for parsing (built-in if not set)
+    lex: Option<Arc<Lexicon>>,
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{self:?}")
+    }
+}
+
+impl Spelling {
+    /// Get the region of a word's spelling
+    ///
+    /// Returns `None` if the word is not a regional spelling.
+    pub fn from_word(lex: &Lexicon, word: &str) -> Option<Self> {
+        let word = make_word(word);
+        for (uk, us, min) in RULES {
+            for (uk_word, us_word) in substitutions(&word, uk, us, *min) {
+                if let Some(lemmas) =
+                    pair_lemmas(lex, &uk_word, &us_word, uk, us)
+                {
+                    return Some(Spelling {
+                        region: Region::British,
+                        counterpart: us_word,
+                        lemmas,
+                    });
+                }
+            }
+            for (us_word, uk_word) in substitutions(&word, us, uk, *min) {
+                if let Some(lemmas) =
+                    pair_lemmas(lex, &uk_word, &us_word, uk, us)
+                {
+                    return Some(Spelling {
+                        region: Region::American,
+                        counterpart: uk_word,
+                        lemmas,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Get the region
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    /// Get the spelling in the other region
+    pub fn counterpart(&self) -> &str {
+        &self.counterpart
+    }
+}
+
+/// Make spellings with a substring replaced
+///
+/// Returns a list of (original, replaced) pairs.
+fn substitutions(
+    word: &str,
+    from: &str,
+    to: &str,
+    min: usize,
+) -> Vec<(String, String)> {
+    word.match_indices(from)
+        .filter(|(i, _m)| *i >= min)
+        .map(|(i, _m)| {
+            let mut replaced = String::with_capacity(word.len() + to.len());
+            replaced.push_str(&word[..i]);
+            replaced.push_str(to);
+            replaced.push_str(&word[i + from.len()..]);
+            (word.to_string(), replaced)
+        })
+        .collect()
+}
+
+/// Get lemmas of a British / American spelling pair (if valid)
+fn pair_lemmas(
+    lex: &Lexicon,
+    uk_word: &str,
+    us_word: &str,
+    uk: &str,
+    us: &str,
+) -> Option<(String, String)> {
+    let us_entries = lex.word_entries(us_word);
+    if us_entries.is_empty() {
+        return None;
+    }
+    for e1 in lex.word_entries(uk_word) {
+        for e2 in &us_entries {
+            if e1.word_class() != e2.word_class() {
+                continue;
+            }
+            if e1 == *e2 {
+                let lemma = e1.lemma();
+                return Some((lemma.replace(us, uk), lemma.to_string()));
+            }
+            if e1.lemma().replace(uk, us) == e2.lemma() {
+                return Some((e1.lemma().to_string(), e2.lemma().to_string()));
+            }
+        }
+    }
+    None
+}
+
+impl SpellingEntry {
+    /// Get seen count
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// Get word
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// Get regional spelling
+    pub fn spelling(&self) -> &Spelling {
+        &self.spelling
+    }
+}
+
+impl SpellingTally {
+    /// Create a new regional spelling tally
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new regional spelling tally with a shared lexicon
+    pub fn with_lexicon(lex: Arc<Lexicon>) -> Self {
+        SpellingTally {
+            lex: Some(lex),
+            ..Default::default()
+        }
+    }
+
+    /// Parse text from a reader
+    pub fn parse_text<R>(&mut self, reader: R) -> Result<()>
+    where
+        R: BufRead,
+    {
+        let lex = self.lex.clone().unwrap_or_else(lex::builtin_shared);
+        for token in Parser::with_lexicon(reader, Arc::clone(&lex)) {
+            let token = token?;
+            if token.chunk() == Chunk::Text && token.kind() == Kind::Lexicon {
+                self.tally_word(&lex, token.text());
+            }
+        }
+        Ok(())
+    }
+
+    /// Tally one word (found in the lexicon)
+    pub(crate) fn tally_word(&mut self, lex: &Lexicon, word: &str) {
+        let word = make_word(word);
+        if let Some(entry) = self.words.get_mut(&word) {
+            entry.seen += 1;
+        } else if self.neutral.contains(&word) {
+            // not a regional spelling
+        } else if let Some(spelling) = Spelling::from_word(lex, &word) {
+            let entry = SpellingEntry {
+                seen: 1,
+                word: word.clone(),
+                spelling,
+            };
+            self.words.insert(word, entry);
+        } else {
+            self.neutral.insert(word);
+        }
+    }
+
+    /// Merge another regional spelling tally into this one
+    pub fn merge(&mut self, other: SpellingTally) {
+        for (word, entry) in other.words {
+            match self.words.get_mut(&word) {
+                Some(e) => e.seen += entry.seen,
+                None => {
+                    self.words.insert(word, entry);
+                }
+            }
+        }
+        self.neutral.extend(other.neutral);
+    }
+
+    /// Count words seen with a region's spelling
+    pub fn count(&self, region: Region) -> usize {
+        self.words
+            .values()
+            .filter(|e| e.spelling.region == region)
+            .map(|e| e.seen)
+            .sum()
+    }
+
+    /// Get the region which the spelling leans toward
+    ///
+    /// Returns `None` if no regional spellings were seen, or there is a tie.
+    pub fn lean(&self) -> Option<Region> {
+        let uk = self.count(Region::British);
+        let us = self.count(Region::American);
+        match uk.cmp(&us) {
+            std::cmp::Ordering::Greater => Some(Region::British),
+            std::cmp::Ordering::Less => Some(Region::American),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// Get entries (sorted by seen count, descending)
+    pub fn entries(&self) -> Vec<&SpellingEntry> {
+        let mut entries: Vec<_> = self.words.values().collect();
+        entries.sort_by(|a, b| b.seen.cmp(&a.seen).then(a.word.cmp(&b.word)));
+        entries
+    }
+
+    /// Get inconsistencies (words spelled both ways)
+    ///
+    /// Returns a list of (British, American) word pairs, using the most
+    /// common word of each lemma pair.
+    pub fn inconsistencies(&self) -> Vec<(&str, &str)> {
+        let mut pairs = Vec::new();
+        let mut done = Vec::new();
+        let entries = self.entries();
+        for entry in &entries {
+            let lemmas = &entry.spelling.lemmas;
+            if done.contains(&lemmas) {
+                continue;
+            }
+            done.push(lemmas);
+            let find = |region| {
+                entries.iter().find(|e| {
+                    e.spelling.region == region && e.spelling.lemmas == *lemmas
+                })
+            };
+            if let (Some(uk), Some(us)) =
+                (find(Region::British), find(Region::American))
+            {
+                pairs.push((uk.word(), us.word()));
+            }
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn spelling() {
+        let lex = lex::builtin();
+        for (word, region, counterpart) in [
+            ("colours", Region::British, "colors"),
+            ("Center", Region::American, "centre"),
+            ("realised", Region::British, "realized"),
+            ("canceled", Region::American, "cancelled"),
+            ("grey", Region::British, "gray"),
+        ] {
+            let spelling = Spelling::from_word(lex, word).unwrap();
+            assert_eq!(spelling.region(), region, "{word}");
+            assert_eq!(spelling.counterpart(), counterpart);
+        }
+        for word in ["filled", "four", "poet", "cat"] {
+            assert_eq!(Spelling::from_word(lex, word), None, "{word}");
+        }
+        let mut tally = SpellingTally::new();
+        let text = "The colour of the theatre was grey, and the color faded.";
+        tally.parse_text(text.as_bytes()).unwrap();
+        assert_eq!(tally.count(Region::British), 3);
+        assert_eq!(tally.count(Region::American), 1);
+        assert_eq!(tally.lean(), Some(Region::British));
+        assert_eq!(tally.inconsistencies(), [("colour", "color")]);
+    }
+
+    #[test]
+    fn merge() {
+        let mut first = SpellingTally::new();
+        first.parse_text("The colour was grey.".as_bytes()).unwrap();
+        let mut second = SpellingTally::new();
+        second.parse_text("A color, a colour.".as_bytes()).unwrap();
+        first.merge(second);
+        assert_eq!(first.count(Region::British), 3);
+        assert_eq!(first.count(Region::American), 1);
+        assert_eq!(first.inconsistencies(), [("colour", "color")]);
+    }
+}
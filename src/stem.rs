@@ -0,0 +1,276 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Step 2 suffix replacements (stem measure > 0)
+const STEP_2: &[(&str, &str)] = &[
+    ("ational", "ate"),
+    ("tional", "tion"),
+    ("enci", "ence"),
+    ("anci", "ance"),
+    ("izer", "ize"),
+    ("abli", "able"),
+    ("alli", "al"),
+    ("entli", "ent"),
+    ("eli", "e"),
+    ("ousli", "ous"),
+    ("ization", "ize"),
+    ("ation", "ate"),
+    ("ator", "ate"),
+    ("alism", "al"),
+    ("iveness", "ive"),
+    ("fulness", "ful"),
+    ("ousness", "ous"),
+    ("aliti", "al"),
+    ("iviti", "ive"),
+    ("biliti", "ble"),
+];
+
+/// Step 3 suffix replacements (stem measure > 0)
+const STEP_3: &[(&str, &str)] = &[
+    ("icate", "ic"),
+    ("ative", ""),
+    ("alize", "al"),
+    ("iciti", "ic"),
+    ("ical", "ic"),
+    ("ful", ""),
+    ("ness", ""),
+];
+
+/// Step 4 suffixes removed (stem measure > 1)
+const STEP_4: &[&str] = &[
+    "al", "ance", "ence", "er", "ic", "able", "ible", "ant", "ement", "ment",
+    "ent", "ion", "ou", "ism", "ate", "iti", "ous", "ive", "ize",
+];
+
+/// Reduce a word to its stem
+///
+/// This uses the Porter stemming algorithm, which removes common English
+/// suffixes using rules only (no lexicon).  A stem is not always a word:
+/// "happiness" becomes "happi".  Words which are not plain ASCII letters
+/// are only lowercased.
+pub fn stem(word: &str) -> String {
+    let word = word.to_lowercase();
+    if word.len() <= 2 || !word.bytes().all(|b| b.is_ascii_lowercase()) {
+        return word;
+    }
+    let mut b = word.into_bytes();
+    step_1a(&mut b);
+    step_1b(&mut b);
+    step_1c(&mut b);
+    replace_suffix(&mut b, STEP_2, 0);
+    replace_suffix(&mut b, STEP_3, 0);
+    step_4(&mut b);
+    step_5(&mut b);
+    // only ASCII letters were removed or replaced
+    String::from_utf8(b).unwrap_or_default()
+}
+
+/// Check if a letter is a consonant
+fn is_consonant(b: &[u8], i: usize) -> bool {
+    match b[i] {
+        b'a' | b'e' | b'i' | b'o' | b'u' => false,
+        // "y" after a consonant is a vowel
+        b'y' => i == 0 || !is_consonant(b, i - 1),
+        _ => true,
+    }
+}
+
+/// Calculate the measure of a stem (count of vowel-consonant sequences)
+fn measure(b: &[u8]) -> usize {
+    let mut i = 0;
+    while i < b.len() && is_consonant(b, i) {
+        i += 1;
+    }
+    let mut m = 0;
+    loop {
+        while i < b.len() && !is_consonant(b, i) {
+            i += 1;
+        }
+        if i >= b.len() {
+            return m;
+        }
+        while i < b.len() && is_consonant(b, i) {
+            i += 1;
+        }
+        m += 1;
+    }
+}
+
+/// Check if a stem contains a vowel
+fn has_vowel(b: &[u8]) -> bool {
+    (0..b.len()).any(|i| !is_consonant(b, i))
+}
+
+/// Check if a stem ends with a double consonant
+fn ends_double(b: &[u8]) -> bool {
+    let n = b.len();
+    n >= 2 && b[n - 1] == b[n - 2] && is_consonant(b, n - 1)
+}
+
+/// Check if a stem ends consonant-vowel-consonant (last not w, x or y)
+fn ends_cvc(b: &[u8]) -> bool {
+    let n = b.len();
+    n >= 3
+        && is_consonant(b, n - 3)
+        && !is_consonant(b, n - 2)
+        && is_consonant(b, n - 1)
+        && !matches!(b[n - 1], b'w' | b'x' | b'y')
+}
+
+/// Replace the first matching suffix, if stem measure is above a minimum
+fn replace_suffix(b: &mut Vec<u8>, rules: &[(&str, &str)], min: usize) {
+    if let Some((suffix, rep)) =
+        rules.iter().find(|(s, _r)| b.ends_with(s.as_bytes()))
+    {
+        let len = b.len() - suffix.len();
+        if measure(&b[..len]) > min {
+            b.truncate(len);
+            b.extend_from_slice(rep.as_bytes());
+        }
+    }
+}
+
+/// Step 1a: plurals
+fn step_1a(b: &mut Vec<u8>) {
+    if b.ends_with(b"sses") || b.ends_with(b"ies") {
+        b.truncate(b.len() - 2);
+    } else if b.ends_with(b"s") && !b.ends_with(b"ss") {
+        b.pop();
+    }
+}
+
+/// Step 1b: past tense and progressive
+fn step_1b(b: &mut Vec<u8>) {
+    if b.ends_with(b"eed") {
+        if measure(&b[..b.len() - 3]) > 0 {
+            b.pop();
+        }
+        return;
+    }
+    let len = if b.ends_with(b"ed") {
+        b.len() - 2
+    } else if b.ends_with(b"ing") {
+        b.len() - 3
+    } else {
+        return;
+    };
+    if !has_vowel(&b[..len]) {
+        return;
+    }
+    b.truncate(len);
+    if b.ends_with(b"at") || b.ends_with(b"bl") || b.ends_with(b"iz") {
+        b.push(b'e');
+    } else if ends_double(b) && !matches!(b[len - 1], b'l' | b's' | b'z') {
+        b.pop();
+    } else if measure(b) == 1 && ends_cvc(b) {
+        b.push(b'e');
+    }
+}
+
+/// Step 1c: final "y" after a vowel in stem
+fn step_1c(b: &mut [u8]) {
+    let n = b.len();
+    if b.ends_with(b"y") && has_vowel(&b[..n - 1]) {
+        b[n - 1] = b'i';
+    }
+}
+
+/// Step 4: remove suffixes from long stems
+fn step_4(b: &mut Vec<u8>) {
+    if let Some(suffix) = STEP_4.iter().find(|s| b.ends_with(s.as_bytes())) {
+        let len = b.len() - suffix.len();
+        if measure(&b[..len]) > 1
+            && (*suffix != "ion"
+                || matches!(b[..len].last(), Some(b's' | b't')))
+        {
+            b.truncate(len);
+        }
+    }
+}
+
+/// Step 5: final "e" and double "l"
+fn step_5(b: &mut Vec<u8>) {
+    if b.ends_with(b"e") {
+        let stem = &b[..b.len() - 1];
+        let m = measure(stem);
+        if m > 1 || (m == 1 && !ends_cvc(stem)) {
+            b.pop();
+        }
+    }
+    if b.ends_with(b"ll") && measure(b) > 1 {
+        b.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stems() {
+        for (word, st) in [
+            ("caresses", "caress"),
+            ("ponies", "poni"),
+            ("cats", "cat"),
+            ("feed", "feed"),
+            ("agreed", "agre"),
+            ("plastered", "plaster"),
+            ("motoring", "motor"),
+            ("sing", "sing"),
+            ("conflated", "conflat"),
+            ("hopping", "hop"),
+            ("filing", "file"),
+            ("happy", "happi"),
+            ("relational", "relat"),
+            ("Generalizations", "gener"),
+            ("connection", "connect"),
+            ("controlling", "control"),
+            ("adoption", "adopt"),
+            ("is", "is"),
+            ("naïve", "naïve"),
+            ("", ""),
+            ("AS", "as"),
+            ("ties", "ti"),
+            ("bled", "bled"),
+            ("falling", "fall"),
+            ("hissing", "hiss"),
+            ("fizzed", "fizz"),
+            ("sky", "sky"),
+            ("rate", "rate"),
+            ("controll", "control"),
+            ("electricity", "electr"),
+            ("x-ray", "x-ray"),
+            ("Éclair", "éclair"),
+        ] {
+            assert_eq!(stem(word), st, "{word}");
+        }
+    }
+
+    #[test]
+    fn measures() {
+        for (stem, m) in [
+            ("tr", 0),
+            ("ee", 0),
+            ("tree", 0),
+            ("y", 0),
+            ("by", 0),
+            ("trouble", 1),
+            ("oats", 1),
+            ("trees", 1),
+            ("ivy", 1),
+            ("troubles", 2),
+            ("private", 2),
+            ("oaten", 2),
+            ("syzygy", 2),
+        ] {
+            assert_eq!(measure(stem.as_bytes()), m, "{stem}");
+        }
+        assert!(!has_vowel(b"tsk") && has_vowel(b"rhythm"));
+        assert!(ends_double(b"fizz") && !ends_double(b"fee"));
+        for (stem, cvc) in
+            [("hop", true), ("fil", true), ("sow", false), ("box", false)]
+        {
+            assert_eq!(ends_cvc(stem.as_bytes()), cvc, "{stem}");
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::Result;
+use crate::lex::{self, Lexicon, make_word};
+use crate::word::WordClass;
+use std::collections::HashSet;
+use std::io::BufRead;
+use std::sync::{Arc, LazyLock};
+
+/// Static stop word list
+static STOPWORDS: LazyLock<Arc<StopWords>> =
+    LazyLock::new(|| Arc::new(StopWords::from_lexicon(lex::builtin())));
+
+/// List of stop words, skipped by `WordTally`
+///
+/// Stop words are common function words ("the", "of", "and") which would
+/// otherwise dominate a frequency list.
+#[derive(Clone, Debug, Default)]
+pub struct StopWords {
+    /// Stop words (normalized)
+    words: HashSet<String>,
+}
+
+/// Get the built-in stop word list
+///
+/// All forms of determiners, conjunctions, prepositions and pronouns in the
+/// built-in lexicon are included.
+pub fn builtin() -> &'static StopWords {
+    &STOPWORDS
+}
+
+/// Get a shared handle to the built-in stop word list
+pub fn builtin_shared() -> Arc<StopWords> {
+    Arc::clone(&STOPWORDS)
+}
+
+/// Check if a word class contains stop words
+fn is_stop_class(wc: WordClass) -> bool {
+    matches!(
+        wc,
+        WordClass::Conjunction
+            | WordClass::Determiner
+            | WordClass::Preposition
+            | WordClass::Pronoun
+    )
+}
+
+impl StopWords {
+    /// Create an empty stop word list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a stop word list from function words of a lexicon
+    pub fn from_lexicon(lex: &Lexicon) -> Self {
+        let mut stop = StopWords::new();
+        for word in lex.iter().filter(|w| is_stop_class(w.word_class())) {
+            stop.insert(word.lemma());
+            for form in word.forms() {
+                stop.insert(form);
+            }
+        }
+        stop
+    }
+
+    /// Load more stop words from a reader
+    ///
+    /// Each line has one word.  Blank lines and lines starting with `#` are
+    /// skipped.
+    pub fn load<R>(&mut self, reader: R) -> Result<()>
+    where
+        R: BufRead,
+    {
+        for line in reader.lines() {
+            let line = line?;
+            let word = line.trim();
+            if !word.is_empty() && !word.starts_with('#') {
+                self.insert(word);
+            }
+        }
+        Ok(())
+    }
+
+    /// Insert a stop word
+    pub fn insert(&mut self, word: &str) {
+        self.words.insert(make_word(word));
+    }
+
+    /// Remove a stop word
+    pub fn remove(&mut self, word: &str) {
+        self.words.remove(&make_word(word));
+    }
+
+    /// Check if a word is a stop word (case insensitive)
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&make_word(word))
+    }
+
+    /// Get the number of stop words
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Check if the stop word list is empty
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stopwords() {
+        let mut stop = builtin().clone();
+        for word in ["the", "Of", "and", "them", "their"] {
+            assert!(stop.contains(word), "{word}");
+        }
+        assert!(!stop.contains("cat"));
+        stop.load("# more\nsaid\n\n".as_bytes()).unwrap();
+        assert!(stop.contains("Said"));
+        stop.remove("the");
+        assert!(!stop.contains("the"));
+        assert_eq!(stop.len(), builtin().len());
+    }
+}
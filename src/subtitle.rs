@@ -0,0 +1,122 @@
+use crate::Result;
+use regex::Regex;
+use std::io::BufRead;
+use std::sync::LazyLock;
+
+/// Formatting tags (HTML-like and ASS override tags)
+static TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<[^>]*>|\{\\[^}]*\}").unwrap());
+
+/// Subtitle cue
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cue {
+    /// Start time
+    start: String,
+    /// End time
+    end: String,
+    /// Spoken text (formatting removed)
+    text: String,
+}
+
+impl Cue {
+    /// Get start time (as written)
+    pub fn start(&self) -> &str {
+        &self.start
+    }
+
+    /// Get end time (as written)
+    pub fn end(&self) -> &str {
+        &self.end
+    }
+
+    /// Get spoken text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Make a cue from a block of lines (SRT or WebVTT)
+fn make_cue(block: &[String]) -> Option<Cue> {
+    // timing line may follow a cue number / identifier
+    let i = block.iter().position(|line| line.contains("-->"))?;
+    let (start, end) = block[i].split_once("-->")?;
+    let start = start.trim().to_string();
+    let end = end
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let mut text = String::new();
+    for line in &block[i + 1..] {
+        let line = TAG.replace_all(line, "");
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(line.trim());
+    }
+    Some(Cue { start, end, text })
+}
+
+/// Parse subtitle cues (SRT or WebVTT)
+///
+/// Headers, notes and style blocks are skipped.
+pub fn parse_cues<R>(reader: R) -> Result<Vec<Cue>>
+where
+    R: BufRead,
+{
+    let mut cues = Vec::new();
+    let mut block = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim_start_matches('\u{FEFF}').trim_end();
+        if line.is_empty() {
+            cues.extend(make_cue(&block));
+            block.clear();
+        } else {
+            block.push(line.to_string());
+        }
+    }
+    cues.extend(make_cue(&block));
+    Ok(cues)
+}
+
+/// Extract spoken text from subtitles (SRT or WebVTT)
+///
+/// Each cue is separated by a blank line.
+pub fn extract_text<R>(reader: R) -> Result<String>
+where
+    R: BufRead,
+{
+    let mut text = String::new();
+    for cue in parse_cues(reader)? {
+        text.push_str(cue.text());
+        text.push_str("\n\n");
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn srt() {
+        let srt = "1\n00:00:01,000 --> 00:00:04,000\n<i>Hello</i> there!\n\n\
+            2\n00:00:05,000 --> 00:00:06,500\n{\\an8}Bye.\nSee you.\n";
+        let cues = parse_cues(srt.as_bytes()).unwrap();
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].start(), "00:00:01,000");
+        assert_eq!(cues[0].end(), "00:00:04,000");
+        assert_eq!(cues[0].text(), "Hello there!");
+        assert_eq!(cues[1].text(), "Bye.\nSee you.");
+    }
+
+    #[test]
+    fn vtt() {
+        let vtt = "WEBVTT\n\nNOTE a comment\n\n\
+            intro\n00:01.000 --> 00:02.000 align:start\n<v Bob>Hi <b>all</b>\n";
+        let text = extract_text(vtt.as_bytes()).unwrap();
+        assert_eq!(text, "Hi all\n\n");
+    }
+}
@@ -0,0 +1,209 @@
+use std::cmp::Reverse;
+
+/// Spelling suggestion
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Suggestion {
+    /// Suggested word form
+    word: String,
+    /// Edit distance from misspelled word
+    distance: usize,
+}
+
+/// Node of a BK-tree
+#[derive(Clone, Debug)]
+struct Node {
+    /// Word index
+    word: u32,
+    /// Child nodes (edit distance, node index)
+    children: Vec<(u32, u32)>,
+}
+
+/// BK-tree index of words, for finding words within an edit distance
+#[derive(Clone, Debug, Default)]
+pub struct BkTree {
+    /// All words
+    words: Vec<String>,
+    /// Tree nodes (first is root)
+    nodes: Vec<Node>,
+}
+
+impl Suggestion {
+    /// Get suggested word form
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// Get edit distance from misspelled word
+    pub fn distance(&self) -> usize {
+        self.distance
+    }
+}
+
+impl<'a> FromIterator<&'a str> for BkTree {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        let mut tree = BkTree::default();
+        for word in iter {
+            tree.insert(word);
+        }
+        tree
+    }
+}
+
+impl BkTree {
+    /// Create a new empty BK-tree
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of words
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Check if the tree is empty
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Insert a word
+    pub fn insert(&mut self, word: &str) {
+        let chars: Vec<char> = word.chars().collect();
+        let new = Node {
+            word: self.words.len() as u32,
+            children: Vec::new(),
+        };
+        let mut n = 0;
+        while n < self.nodes.len() {
+            let node = &self.nodes[n];
+            let d = edit_distance(&chars, &self.words[node.word as usize]);
+            if d == 0 {
+                return;
+            }
+            let d = d as u32;
+            match node.children.iter().find(|(cd, _c)| *cd == d) {
+                Some((_cd, child)) => n = *child as usize,
+                None => {
+                    let child = self.nodes.len() as u32;
+                    self.nodes[n].children.push((d, child));
+                    break;
+                }
+            }
+        }
+        self.words.push(word.to_string());
+        self.nodes.push(new);
+    }
+
+    /// Find words within an edit distance
+    ///
+    /// Suggestions are ranked by distance, then by length of common prefix.
+    pub fn find(&self, word: &str, max_distance: usize) -> Vec<Suggestion> {
+        let chars: Vec<char> = word.chars().collect();
+        let mut found = Vec::new();
+        let mut pending = Vec::new();
+        if !self.nodes.is_empty() {
+            pending.push(0);
+        }
+        while let Some(n) = pending.pop() {
+            let node: &Node = &self.nodes[n];
+            let form = &self.words[node.word as usize];
+            let d = edit_distance(&chars, form);
+            if d <= max_distance {
+                let prefix = common_prefix(&chars, form);
+                found.push((d, Reverse(prefix), form));
+            }
+            let lo = d.saturating_sub(max_distance) as u32;
+            let hi = (d + max_distance) as u32;
+            for (cd, child) in &node.children {
+                if (lo..=hi).contains(cd) {
+                    pending.push(*child as usize);
+                }
+            }
+        }
+        found.sort();
+        found
+            .into_iter()
+            .map(|(distance, _p, form)| Suggestion {
+                word: form.to_string(),
+                distance,
+            })
+            .collect()
+    }
+}
+
+/// Count common prefix characters of two words
+fn common_prefix(a: &[char], b: &str) -> usize {
+    a.iter()
+        .zip(b.chars())
+        .take_while(|(a, b)| **a == *b)
+        .count()
+}
+
+/// Calculate edit (Levenshtein) distance between two words
+fn edit_distance(a: &[char], b: &str) -> usize {
+    let mut row: Vec<usize> = (0..=a.len()).collect();
+    for (j, cb) in b.chars().enumerate() {
+        let mut diag = row[0];
+        row[0] = j + 1;
+        for (i, ca) in a.iter().enumerate() {
+            let cost = if *ca == cb { diag } else { diag + 1 };
+            diag = row[i + 1];
+            row[i + 1] = cost.min(row[i] + 1).min(diag + 1);
+        }
+    }
+    row[a.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn suggest() {
+        let chars = |w: &str| w.chars().collect::<Vec<_>>();
+        assert_eq!(edit_distance(&chars("kitten"), "sitting"), 3);
+        assert_eq!(edit_distance(&chars(""), "abc"), 3);
+        let tree: BkTree = ["running", "ruing", "rung", "run", "cat", "run"]
+            .into_iter()
+            .collect();
+        assert_eq!(tree.len(), 5);
+        let words: Vec<_> = tree
+            .find("runing", 2)
+            .into_iter()
+            .map(|s| (s.word().to_string(), s.distance()))
+            .collect();
+        assert_eq!(
+            words,
+            [
+                ("running".to_string(), 1),
+                ("ruing".to_string(), 1),
+                ("rung".to_string(), 2),
+            ]
+        );
+        assert!(tree.find("dog", 1).is_empty());
+        for (a, b, d) in [
+            ("", "", 0),
+            ("abc", "", 3),
+            ("flaw", "lawn", 2),
+            ("naïve", "naive", 1),
+            ("ab", "ba", 2),
+        ] {
+            assert_eq!(edit_distance(&chars(a), b), d, "{a} / {b}");
+        }
+        assert_eq!(common_prefix(&chars("café"), "cafe"), 3);
+        assert_eq!(common_prefix(&chars(""), "cat"), 0);
+        let tree = BkTree::new();
+        assert!(tree.is_empty());
+        assert!(tree.find("cat", 5).is_empty());
+        let tree: BkTree =
+            ["cat", "cart", "car", "cut", "at"].into_iter().collect();
+        let words: Vec<_> =
+            tree.find("cat", 0).into_iter().map(|s| s.word).collect();
+        assert_eq!(words, ["cat"]);
+        // ties are sorted by longest common prefix, then alphabetically
+        let words: Vec<_> =
+            tree.find("cat", 1).into_iter().map(|s| s.word).collect();
+        assert_eq!(words, ["cat", "car", "cart", "cut", "at"]);
+        assert_eq!(tree.find("", 3).len(), 4);
+    }
+}
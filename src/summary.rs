@@ -0,0 +1,136 @@
+use crate::Result;
+use crate::keyword;
+use crate::lex::{self, Lexicon, make_word};
+use crate::parse::span_sentences;
+use crate::tally::WordTally;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Score bonus for the first sentence (decreasing to none for the last)
+const POSITION_BONUS: f64 = 0.5;
+
+/// Sentence chosen for a summary
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SummarySentence {
+    /// Byte span in text
+    span: Range<usize>,
+    /// Sentence number (starting at 0)
+    number: usize,
+    /// Score
+    score: f64,
+}
+
+/// Extractive summarizer
+#[derive(Clone, Default)]
+pub struct Summarizer {
+    /// Lexicon for parsing (built-in if not set)
+    lex: Option<Arc<Lexicon>>,
+}
+
+impl SummarySentence {
+    /// Get byte span in text
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Get sentence number (starting at 0)
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    /// Get score
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+impl Summarizer {
+    /// Create a new summarizer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new summarizer with a shared lexicon
+    pub fn with_lexicon(lex: Arc<Lexicon>) -> Self {
+        Summarizer { lex: Some(lex) }
+    }
+
+    /// Summarize a text
+    ///
+    /// Each sentence is scored by the average keyness of its words, with a
+    /// bonus for sentences near the start.  Returns up to `n` top scoring
+    /// sentences, in document order.
+    pub fn summarize(
+        &self,
+        text: &str,
+        n: usize,
+    ) -> Result<Vec<SummarySentence>> {
+        let lex = self.lex.clone().unwrap_or_else(lex::builtin_shared);
+        let mut tally = WordTally::with_lexicon(Arc::clone(&lex));
+        tally.parse_text(text.as_bytes())?;
+        let weights: HashMap<_, _> = keyword::builtin()
+            .keywords(&tally)
+            .into_iter()
+            .map(|k| (make_word(k.word()), k.score()))
+            .collect();
+        let sentences = span_sentences(text, lex)?;
+        let count = sentences.len() as f64;
+        let mut scored: Vec<_> = sentences
+            .iter()
+            .enumerate()
+            .filter_map(|(number, words)| {
+                let first = words.first()?;
+                let last = words.last()?;
+                let weight: f64 = words
+                    .iter()
+                    .filter_map(|w| weights.get(&make_word(&w.text)))
+                    .sum();
+                let position = 1.0 - number as f64 / count;
+                let score = weight / words.len() as f64
+                    * (1.0 + POSITION_BONUS * position);
+                Some(SummarySentence {
+                    span: first.span.start..sentence_end(text, last.span.end),
+                    number,
+                    score,
+                })
+            })
+            .collect();
+        scored.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scored.truncate(n);
+        scored.sort_by_key(|s| s.number);
+        Ok(scored)
+    }
+}
+
+/// Find the end of a sentence, including closing punctuation
+fn sentence_end(text: &str, end: usize) -> usize {
+    let rest = &text[end..];
+    end + rest.find(char::is_whitespace).unwrap_or(rest.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn summarize() {
+        let text = "The lighthouse keeper lived alone. Every night, the \
+            keeper lit the lighthouse lamp for passing ships. It was quiet. \
+            One stormy night, a ship struck the rocks below the lighthouse. \
+            He had tea.";
+        let summary = Summarizer::new().summarize(text, 2).unwrap();
+        let sentences: Vec<_> =
+            summary.iter().map(|s| &text[s.span()]).collect();
+        assert_eq!(
+            sentences,
+            [
+                "The lighthouse keeper lived alone.",
+                "Every night, the keeper lit the lighthouse lamp for \
+                passing ships."
+            ]
+        );
+        assert_eq!(summary[1].number(), 1);
+    }
+}
@@ -0,0 +1,559 @@
+use crate::contractions;
+use crate::kind::Kind;
+use crate::lex::{self, Lexicon, is_apostrophe, make_word};
+use crate::parse::{Chunk, Parser};
+use crate::penn::{self, Penn};
+use crate::word::WordClass;
+use crate::{Error, Result};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io::BufRead;
+use std::sync::Arc;
+
+/// Number of word classes
+const CLASSES: usize = 9;
+
+/// Feature weights (indexed by word class)
+type Weights = [f32; CLASSES];
+
+/// Words of a sentence, with tagged word classes
+pub type Sentence = Vec<(String, Option<WordClass>)>;
+
+/// Word token in context
+struct Token {
+    /// Normalized word
+    word: String,
+    /// Word kind
+    kind: Kind,
+    /// Candidate classes
+    candidates: Vec<WordClass>,
+}
+
+/// Averaged perceptron training state
+#[derive(Default)]
+struct Trainer {
+    /// Number of training instances
+    instances: u32,
+    /// Accumulated weight totals
+    totals: HashMap<String, Weights>,
+    /// Instance of last update for each weight
+    stamps: HashMap<String, [u32; CLASSES]>,
+}
+
+/// Part-of-speech tagger (averaged perceptron)
+///
+/// One word class is assigned to each word in context, chosen from the
+/// lexicon's candidate classes.  Without trained weights, ambiguous words are
+/// resolved by `Lexicon::most_likely_class`.
+#[derive(Clone)]
+pub struct Tagger {
+    /// Word lexicon
+    lex: Arc<Lexicon>,
+    /// Feature weights
+    weights: HashMap<String, Weights>,
+}
+
+/// Get the last few characters of a word
+fn suffix(word: &str) -> &str {
+    let start = word.char_indices().rev().nth(2).map_or(0, |(i, _c)| i);
+    &word[start..]
+}
+
+/// Get the code of an optional word class
+fn class_code(wc: Option<WordClass>) -> String {
+    wc.map_or_else(|| "-".to_string(), |wc| wc.to_string())
+}
+
+/// Check if a token is tagged (contains any alphanumeric character)
+fn is_word(word: &str) -> bool {
+    word.chars().any(char::is_alphanumeric)
+}
+
+impl Trainer {
+    /// Update weights of features
+    fn update(
+        &mut self,
+        weights: &mut HashMap<String, Weights>,
+        feats: &[String],
+        truth: WordClass,
+        guess: WordClass,
+    ) {
+        self.instances += 1;
+        if truth == guess {
+            return;
+        }
+        for feat in feats {
+            for (wc, delta) in [(truth, 1.0), (guess, -1.0)] {
+                let c = wc as usize;
+                let w = weights.entry(feat.clone()).or_default();
+                let total = self.totals.entry(feat.clone()).or_default();
+                let stamp = self.stamps.entry(feat.clone()).or_default();
+                total[c] += (self.instances - stamp[c]) as f32 * w[c];
+                stamp[c] = self.instances;
+                w[c] += delta;
+            }
+        }
+    }
+
+    /// Average weights over all training instances
+    fn average(&self, weights: &mut HashMap<String, Weights>) {
+        let instances = self.instances.max(1) as f32;
+        for (feat, w) in weights.iter_mut() {
+            let total = self.totals.get(feat).copied().unwrap_or_default();
+            let stamp = self.stamps.get(feat).copied().unwrap_or_default();
+            for c in 0..CLASSES {
+                let t = total[c] + (self.instances - stamp[c]) as f32 * w[c];
+                w[c] = t / instances;
+            }
+        }
+        weights.retain(|_f, w| w.iter().any(|v| *v != 0.0));
+    }
+}
+
+impl Default for Tagger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tagger {
+    /// Create a new tagger (using the built-in lexicon)
+    pub fn new() -> Self {
+        Self::with_lexicon(lex::builtin_shared())
+    }
+
+    /// Create a new tagger with a shared lexicon
+    pub fn with_lexicon(lex: Arc<Lexicon>) -> Self {
+        Tagger {
+            lex,
+            weights: HashMap::new(),
+        }
+    }
+
+    /// Get the lexicon
+    pub fn lexicon(&self) -> &Arc<Lexicon> {
+        &self.lex
+    }
+
+    /// Load trained weights (from `save_weights`)
+    pub fn load_weights(mut self, src: &str) -> Result<Self> {
+        for (i, line) in src.lines().enumerate() {
+            let invalid = || Error::Decode(format!("line {}: `{line}`", i + 1));
+            let mut fields = line.split('\t');
+            let feat = fields.next().ok_or_else(invalid)?;
+            let mut w = Weights::default();
+            for field in fields {
+                let (class, val) = field.split_once(':').ok_or_else(invalid)?;
+                let wc = WordClass::try_from(class)?;
+                w[wc as usize] = val.parse().map_err(|_| invalid())?;
+            }
+            self.weights.insert(feat.to_string(), w);
+        }
+        Ok(self)
+    }
+
+    /// Save trained weights (tab-separated lines)
+    pub fn save_weights(&self) -> String {
+        let mut feats: Vec<_> = self.weights.iter().collect();
+        feats.sort_by(|a, b| a.0.cmp(b.0));
+        let mut src = String::new();
+        for (feat, w) in feats {
+            src.push_str(feat);
+            for (wc, val) in WordClass::all().iter().zip(w) {
+                if *val != 0.0 {
+                    let _ = write!(src, "\t{wc}:{val}");
+                }
+            }
+            src.push('\n');
+        }
+        src
+    }
+
+    /// Make a token from a word
+    fn token(&self, word: &str) -> Token {
+        let mut candidates: Vec<_> = self
+            .lex
+            .word_entries(word)
+            .iter()
+            .map(|entry| entry.word_class())
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        let kind = if candidates.is_empty() {
+            Kind::from(word)
+        } else {
+            Kind::Lexicon
+        };
+        if matches!(kind, Kind::Proper | Kind::Acronym) {
+            candidates.push(WordClass::Noun);
+        }
+        Token {
+            word: make_word(word),
+            kind,
+            candidates,
+        }
+    }
+
+    /// Get features of a token in context
+    fn features(
+        tokens: &[Token],
+        i: usize,
+        prev: Option<WordClass>,
+        prev2: Option<WordClass>,
+    ) -> Vec<String> {
+        let word = &tokens[i].word;
+        let (p, pp) = (class_code(prev), class_code(prev2));
+        let mut feats = vec![
+            "bias".to_string(),
+            format!("w {word}"),
+            format!("s {}", suffix(word)),
+            format!("k {}", tokens[i].kind.code()),
+            format!("t {p}"),
+            format!("tt {pp} {p}"),
+            format!("tw {p} {word}"),
+        ];
+        match i.checked_sub(1).map(|j| &tokens[j].word) {
+            Some(w) => {
+                feats.push(format!("w-1 {w}"));
+                feats.push(format!("s-1 {}", suffix(w)));
+            }
+            None => feats.push("w-1 -".to_string()),
+        }
+        match tokens.get(i + 1).map(|t| &t.word) {
+            Some(w) => {
+                feats.push(format!("w+1 {w}"));
+                feats.push(format!("s+1 {}", suffix(w)));
+            }
+            None => feats.push("w+1 -".to_string()),
+        }
+        feats
+    }
+
+    /// Score candidate classes of features
+    fn score(
+        &self,
+        feats: &[String],
+        options: &[WordClass],
+    ) -> Option<WordClass> {
+        let mut scores = Weights::default();
+        for feat in feats {
+            if let Some(w) = self.weights.get(feat) {
+                for (s, v) in scores.iter_mut().zip(w) {
+                    *s += v;
+                }
+            }
+        }
+        let mut best = None;
+        let mut best_score = 0.0;
+        for wc in options {
+            let s = scores[*wc as usize];
+            if best.is_none() || s > best_score {
+                best = Some(*wc);
+                best_score = s;
+            }
+        }
+        best
+    }
+
+    /// Predict the class of a token
+    fn predict(&self, token: &Token, feats: &[String]) -> Option<WordClass> {
+        match token.candidates[..] {
+            [wc] => return Some(wc),
+            [] if token.kind != Kind::Unknown => return None,
+            _ => (),
+        }
+        if self.weights.is_empty() {
+            return self.lex.most_likely_class(&token.word);
+        }
+        if token.candidates.is_empty() {
+            self.score(feats, WordClass::all())
+        } else {
+            self.score(feats, &token.candidates)
+        }
+    }
+
+    /// Tag a sentence of tokens
+    fn tag_tokens(&self, tokens: &[Token]) -> Vec<Option<WordClass>> {
+        let mut tags = Vec::with_capacity(tokens.len());
+        let (mut prev, mut prev2) = (None, None);
+        for i in 0..tokens.len() {
+            let feats = Self::features(tokens, i, prev, prev2);
+            let wc = self.predict(&tokens[i], &feats);
+            tags.push(wc);
+            prev2 = prev;
+            prev = wc;
+        }
+        tags
+    }
+
+    /// Tag a sentence of words
+    pub fn tag_words(&self, words: &[&str]) -> Vec<Option<WordClass>> {
+        let tokens: Vec<_> = words.iter().map(|w| self.token(w)).collect();
+        self.tag_tokens(&tokens)
+    }
+
+    /// Tag words of a text
+    ///
+    /// Contractions not in the lexicon are expanded (e.g. `they’d` into
+    /// `they` and `would`), and sentences end at `.`, `!` or `?`.
+    pub fn tag_text<R>(
+        &self,
+        reader: R,
+    ) -> Result<Vec<(String, Option<WordClass>)>>
+    where
+        R: BufRead,
+    {
+        Ok(self.tag_sentences(reader)?.into_iter().flatten().collect())
+    }
+
+    /// Tag words of a text, split into sentences
+    ///
+    /// Words are split as with `tag_text`.
+    pub fn tag_sentences<R>(&self, reader: R) -> Result<Vec<Sentence>>
+    where
+        R: BufRead,
+    {
+        let mut tagged = Vec::new();
+        for words in self.sentences(reader)? {
+            let tokens: Vec<_> = words.iter().map(|w| self.token(w)).collect();
+            let tags = self.tag_tokens(&tokens);
+            tagged.push(words.into_iter().zip(tags).collect());
+        }
+        Ok(tagged)
+    }
+
+    /// Tag words of a text with Penn Treebank tags
+    ///
+    /// Words are split as with `tag_text`, and each tag is derived from the
+    /// word form of the lexeme with the tagged class, then refined in
+    /// context (see `penn::refine`).
+    pub fn tag_text_penn<R>(
+        &self,
+        reader: R,
+    ) -> Result<Vec<(String, Option<Penn>)>>
+    where
+        R: BufRead,
+    {
+        let mut tagged = Vec::new();
+        for words in self.sentences(reader)? {
+            let tokens: Vec<_> = words.iter().map(|w| self.token(w)).collect();
+            let mut tags: Vec<_> = words
+                .iter()
+                .zip(self.tag_tokens(&tokens))
+                .map(|(w, wc)| self.penn_tag(w, wc))
+                .collect();
+            let refs: Vec<_> = words.iter().map(String::as_str).collect();
+            penn::refine(&refs, &mut tags);
+            tagged.extend(words.into_iter().zip(tags));
+        }
+        Ok(tagged)
+    }
+
+    /// Get the Penn Treebank tag of a word with a word class
+    fn penn_tag(&self, word: &str, wc: Option<WordClass>) -> Option<Penn> {
+        let entries = self.lex.word_entries(word);
+        if entries.is_empty() {
+            return Penn::from_kind(Kind::from(word), word);
+        }
+        let wc = wc?;
+        entries
+            .iter()
+            .find(|entry| entry.word_class() == wc)
+            .map(|entry| Penn::from_lexeme(entry, word))
+    }
+
+    /// Split a text into sentences of words
+    fn sentences<R>(&self, reader: R) -> Result<Vec<Vec<String>>>
+    where
+        R: BufRead,
+    {
+        let mut sentences = Vec::new();
+        let mut words = Vec::new();
+        for token in Parser::with_lexicon(reader, Arc::clone(&self.lex)) {
+            let token = token?;
+            match token.chunk() {
+                Chunk::Text => {
+                    let text = token.into_text();
+                    if text.chars().any(is_apostrophe)
+                        && !self.lex.contains(&text)
+                    {
+                        words.extend(
+                            contractions::split(&text)
+                                .into_iter()
+                                .filter(|w| is_word(w)),
+                        );
+                    } else {
+                        words.push(text);
+                    }
+                }
+                Chunk::Symbol
+                    if matches!(token.text(), "." | "!" | "?")
+                        && !words.is_empty() =>
+                {
+                    sentences.push(std::mem::take(&mut words));
+                }
+                _ => (),
+            }
+        }
+        if !words.is_empty() {
+            sentences.push(words);
+        }
+        Ok(sentences)
+    }
+
+    /// Train weights from a tagged corpus
+    ///
+    /// Each line is a sentence, with words tagged by word class code (e.g.
+    /// `The/D cat/N sat/V`).  Punctuation tokens are skipped.
+    pub fn train_tagged<R>(
+        &mut self,
+        reader: R,
+        iterations: usize,
+    ) -> Result<()>
+    where
+        R: BufRead,
+    {
+        let mut sentences = Vec::new();
+        for line in reader.lines() {
+            let (mut tokens, mut truth) = (Vec::new(), Vec::new());
+            for token in line?.split_whitespace() {
+                if let Some((word, class)) = token.rsplit_once('/')
+                    && is_word(word)
+                {
+                    truth.push(WordClass::try_from(class)?);
+                    tokens.push(self.token(word));
+                }
+            }
+            if !tokens.is_empty() {
+                sentences.push((tokens, truth));
+            }
+        }
+        let mut trainer = Trainer::default();
+        let mut rng = fastrand::Rng::with_seed(0);
+        for _ in 0..iterations {
+            rng.shuffle(&mut sentences);
+            for (tokens, truth) in &sentences {
+                let (mut prev, mut prev2) = (None, None);
+                for (i, wc) in truth.iter().enumerate() {
+                    let feats = Self::features(tokens, i, prev, prev2);
+                    let options = if tokens[i].candidates.is_empty() {
+                        WordClass::all()
+                    } else {
+                        &tokens[i].candidates[..]
+                    };
+                    let guess = self.score(&feats, options).unwrap_or(*wc);
+                    trainer.update(&mut self.weights, &feats, *wc, guess);
+                    prev2 = prev;
+                    prev = Some(guess);
+                }
+            }
+        }
+        trainer.average(&mut self.weights);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Small tagged corpus
+    const CORPUS: &str = "I/Pn can/V swim/V ./.
+        The/D can/N is/V red/A
+        We/Pn can/V go/V home/Av
+        He/Pn kicked/V a/D can/N
+        You/Pn can/V see/V the/D can/N
+        They/Pn can/V run/V fast/Av";
+
+    #[test]
+    fn tagger() {
+        let tagger = Tagger::new();
+        let tags = tagger.tag_words(&["mice", "blorf"]);
+        assert_eq!(tags, [Some(WordClass::Noun), None]);
+        let mut tagger = Tagger::new();
+        tagger.train_tagged(CORPUS.as_bytes(), 20).unwrap();
+        let tagged = tagger
+            .tag_text("Sam can’t see. He kicked the can.".as_bytes())
+            .unwrap();
+        let classes: Vec<_> = tagged.iter().map(|(_w, wc)| *wc).collect();
+        assert_eq!(tagged[1].0, "can");
+        let sentences = tagger
+            .tag_sentences("Sam can’t see. He kicked the can.".as_bytes())
+            .unwrap();
+        assert_eq!(sentences.len(), 2);
+        assert_eq!(sentences.concat(), tagged);
+        assert_eq!(tagged[2].0, "not");
+        assert_eq!(classes[1], Some(WordClass::Verb));
+        assert_eq!(classes[7], Some(WordClass::Noun));
+        let weights = tagger.save_weights();
+        let loaded = Tagger::new().load_weights(&weights).unwrap();
+        assert_eq!(loaded.save_weights(), weights);
+        assert!(Tagger::new().load_weights("bias\tQ:1").is_err());
+        let tagged = tagger
+            .tag_text_penn("We can see the mice.".as_bytes())
+            .unwrap();
+        let tags: Vec<_> = tagged.iter().map(|(_w, t)| *t).collect();
+        assert_eq!(
+            tags,
+            [
+                Some(Penn::Prp),
+                Some(Penn::Md),
+                Some(Penn::Vb),
+                Some(Penn::Dt),
+                Some(Penn::Nns)
+            ]
+        );
+    }
+
+    #[test]
+    fn untrained() {
+        use WordClass::*;
+        let tagger = Tagger::new();
+        // proper names and acronyms are nouns; numbers are not tagged
+        let tags = tagger.tag_words(&["Zyxwell", "NASA", "42", "blorf", ""]);
+        assert_eq!(tags, [Some(Noun), Some(Noun), None, None, None]);
+        assert!(tagger.tag_words(&[]).is_empty());
+        let lex = Arc::new(Lexicon::from_csv("blorf:V").unwrap());
+        let tagger = Tagger::with_lexicon(lex);
+        assert_eq!(tagger.tag_words(&["blorfed", "mice"]), [Some(Verb), None]);
+        assert!(tagger.lexicon().contains("blorfs"));
+    }
+
+    #[test]
+    fn sentences() {
+        let tagger = Tagger::new();
+        let words = |text: &str| -> Vec<Vec<String>> {
+            tagger.sentences(text.as_bytes()).unwrap()
+        };
+        assert!(words("").is_empty());
+        assert!(words("... ?! .").is_empty());
+        assert_eq!(
+            words("Go! Who? We'd stay... and then"),
+            [
+                vec!["Go"],
+                vec!["Who"],
+                vec!["We", "would", "stay"],
+                vec!["and", "then"],
+            ]
+        );
+        let sentences = tagger.tag_sentences("Hi, you.".as_bytes()).unwrap();
+        assert_eq!(sentences.len(), 1);
+        assert_eq!(sentences[0].len(), 2);
+    }
+
+    #[test]
+    fn weights() {
+        let mut tagger = Tagger::new();
+        tagger.train_tagged(CORPUS.as_bytes(), 0).unwrap();
+        assert_eq!(tagger.save_weights(), "");
+        assert!(tagger.train_tagged("a/D cat/Q".as_bytes(), 1).is_err());
+        let load = |src: &str| Tagger::new().load_weights(src);
+        assert!(load("").is_ok());
+        assert!(load("bias\tN:1.5\tV:-2").is_ok());
+        assert!(matches!(load("bias\tN"), Err(Error::Decode(_))));
+        assert!(matches!(load("bias\tN:x"), Err(Error::Decode(_))));
+        assert!(matches!(load("bias\tZ:1"), Err(Error::WordClass(_))));
+        let saved = load("w cat\tN:0.5\tV:0\nbias\tD:1").unwrap();
+        assert_eq!(saved.save_weights(), "bias\tD:1\nw cat\tN:0.5\n");
+    }
+}
@@ -1,13 +1,22 @@
+use crate::Result;
+use crate::gazetteer::Gazetteer;
 use crate::kind::Kind;
-use crate::lex::make_word;
-use crate::parse::{Chunk, Parser};
-use std::collections::HashMap;
+use crate::lex::{self, Lexicon, make_word};
+use crate::parse::{Chunk, Dehyphenate, Parser, Tokenizer};
+use crate::stem::stem;
+use crate::stopwords::StopWords;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
 use std::fmt;
 use std::io::BufRead;
+#[cfg(feature = "rayon")]
+use std::path::Path;
+use std::sync::Arc;
 use yansi::Paint;
 
 /// Word tally entry
 #[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WordEntry {
     /// Seen count
     seen: usize,
@@ -17,11 +26,78 @@ pub struct WordEntry {
     kind: Kind,
 }
 
+/// Difference of one word between two tallies
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiffEntry {
+    /// Word
+    word: String,
+    /// Kind grouping
+    kind: Kind,
+    /// Seen count in left tally
+    left: usize,
+    /// Seen count in right tally
+    right: usize,
+}
+
+/// Policy for tallying words which differ only by case
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CasePolicy {
+    /// Tally each case variant apart ("Apple" / "apple" / "APPLE")
+    Preserve,
+    /// Tally all variants together, as lowercase
+    Lowercase,
+    /// Tally all variants together, using the one with fewest uppercase
+    /// characters (names with a gazetteer are kept apart)
+    #[default]
+    Smart,
+}
+
+/// Tokens between vocabulary growth samples
+const GROWTH_STEP: usize = 1000;
+
+/// Vocabulary growth, sampled while parsing
+#[derive(Clone, Default)]
+struct Growth {
+    /// Word tokens parsed
+    tokens: usize,
+    /// Word types parsed
+    types: usize,
+    /// Samples of (tokens, types)
+    samples: Vec<(usize, usize)>,
+}
+
 /// Word tally list
-#[derive(Default)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct WordTally {
     /// Words in list
     words: HashMap<String, WordEntry>,
+    /// Lexicon for parsing (built-in if not set)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    lex: Option<Arc<Lexicon>>,
+    /// Gazetteer of names (for parsing)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    gazetteer: Option<Arc<Gazetteer>>,
+    /// Group unknown words by stem
+    #[cfg_attr(feature = "serde", serde(skip))]
+    stem_unknown: bool,
+    /// Stop words (skipped)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    stopwords: Option<Arc<StopWords>>,
+    /// Case folding policy
+    #[cfg_attr(feature = "serde", serde(skip))]
+    case_policy: CasePolicy,
+    /// Rejoin words hyphenated across line breaks
+    #[cfg_attr(feature = "serde", serde(skip))]
+    dehyphenate: bool,
+    /// Tokenizer (`Parser` if not set)
+    #[cfg_attr(feature = "serde", serde(skip))]
+    tokenizer: Option<Arc<dyn Tokenizer>>,
+    /// Vocabulary growth
+    #[cfg_attr(feature = "serde", serde(skip))]
+    growth: Growth,
 }
 
 impl fmt::Display for WordEntry {
@@ -37,6 +113,58 @@ impl fmt::Display for WordEntry {
     }
 }
 
+impl fmt::Display for DiffEntry {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let delta = format!("{:+}", self.delta());
+        write!(
+            fmt,
+            "{:5} {:5} {:>6} {} {}",
+            self.left.bright().yellow(),
+            self.right.bright().yellow(),
+            delta.bright(),
+            self.kind.code().yellow(),
+            self.word
+        )
+    }
+}
+
+impl DiffEntry {
+    /// Get word
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// Get kind grouping
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Get seen count in left tally (`self` of `WordTally::diff`)
+    pub fn left(&self) -> usize {
+        self.left
+    }
+
+    /// Get seen count in right tally (`other` of `WordTally::diff`)
+    pub fn right(&self) -> usize {
+        self.right
+    }
+
+    /// Get change in seen count (right minus left)
+    pub fn delta(&self) -> i64 {
+        self.right as i64 - self.left as i64
+    }
+
+    /// Check if word is only in the left tally
+    pub fn is_left_only(&self) -> bool {
+        self.right == 0
+    }
+
+    /// Check if word is only in the right tally
+    pub fn is_right_only(&self) -> bool {
+        self.left == 0
+    }
+}
+
 impl WordEntry {
     /// Create a new word entry
     fn new(seen: usize, word: String, kind: Kind) -> Self {
@@ -64,47 +192,279 @@ fn count_uppercase(word: &str) -> usize {
     word.chars().filter(|c| c.is_uppercase()).count()
 }
 
+/// Capitalize a lowercase word
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
 impl WordTally {
     /// Create a new word tally
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Create a new word tally, parsing with a shared lexicon
+    pub fn with_lexicon(lex: Arc<Lexicon>) -> Self {
+        WordTally {
+            lex: Some(lex),
+            ..Default::default()
+        }
+    }
+
+    /// Group `Unknown` words by stem (e.g. "blorfs" with "blorfing")
+    ///
+    /// Each group uses the first variant seen (with fewest uppercase
+    /// characters) as its word.
+    pub fn stem_unknown(mut self, stem_unknown: bool) -> Self {
+        self.stem_unknown = stem_unknown;
+        self
+    }
+
+    /// Skip stop words (see `stopwords::builtin_shared`)
+    pub fn stopwords(mut self, stopwords: Arc<StopWords>) -> Self {
+        self.stopwords = Some(stopwords);
+        self
+    }
+
+    /// Set the policy for words which differ only by case
+    pub fn case_policy(mut self, case_policy: CasePolicy) -> Self {
+        self.case_policy = case_policy;
+        self
+    }
+
+    /// Use a gazetteer to find names (`Kind::Proper`) when parsing
+    pub fn gazetteer(mut self, gazetteer: Arc<Gazetteer>) -> Self {
+        self.gazetteer = Some(gazetteer);
+        self
+    }
+
+    /// Rejoin words hyphenated across line breaks (see `Dehyphenate`)
+    pub fn dehyphenate(mut self, dehyphenate: bool) -> Self {
+        self.dehyphenate = dehyphenate;
+        self
+    }
+
+    /// Use a custom tokenizer when parsing
+    ///
+    /// A gazetteer is only used by the built-in `Parser`.
+    pub fn tokenizer(mut self, tokenizer: Arc<dyn Tokenizer>) -> Self {
+        self.tokenizer = Some(tokenizer);
+        self
+    }
+
     /// Parse text from a reader
-    pub fn parse_text<R>(&mut self, reader: R) -> Result<(), std::io::Error>
+    pub fn parse_text<R>(&mut self, reader: R) -> Result<()>
+    where
+        R: BufRead,
+    {
+        let lex = self.lex.clone().unwrap_or_else(lex::builtin_shared);
+        if self.dehyphenate {
+            let reader = Dehyphenate::new(reader, Arc::clone(&lex));
+            self.parse_reader(reader, lex)
+        } else {
+            self.parse_reader(reader, lex)
+        }
+    }
+
+    /// Parse text from a reader, with a lexicon
+    fn parse_reader<R>(&mut self, reader: R, lex: Arc<Lexicon>) -> Result<()>
     where
         R: BufRead,
     {
-        for chunk in Parser::new(reader) {
-            let (chunk, text, kind) = chunk?;
-            if chunk != Chunk::Boundary {
-                self.tally_word(text, kind);
+        let tokens = match &self.tokenizer {
+            Some(tokenizer) => tokenizer.tokenize(Box::new(reader), lex),
+            None => {
+                let mut parser = Parser::with_lexicon(reader, lex);
+                if let Some(gaz) = &self.gazetteer {
+                    parser = parser.gazetteer(Arc::clone(gaz));
+                }
+                Box::new(parser)
+            }
+        };
+        for token in tokens {
+            let token = token?;
+            if token.chunk() != Chunk::Boundary {
+                let kind = token.kind();
+                self.tally_word(token.into_text(), kind);
             }
         }
         Ok(())
     }
 
     /// Tally a word
-    fn tally_word(&mut self, word: String, kind: Kind) {
-        let key = make_word(&word);
-        let we = WordEntry::new(1, word, kind);
+    fn tally_word(&mut self, mut word: String, kind: Kind) {
+        if self.stopwords.as_ref().is_some_and(|sw| sw.contains(&word)) {
+            return;
+        }
+        let mut key = make_word(&word);
+        if self.stem_unknown && kind == Kind::Unknown {
+            key = stem(&key);
+        } else if self.case_policy == CasePolicy::Preserve {
+            key = word.clone();
+        }
+        if self.case_policy == CasePolicy::Lowercase {
+            word = word.to_lowercase();
+        }
+        // names are tallied apart from common words ("Bill" / "bill")
+        if kind == Kind::Proper
+            && self.case_policy == CasePolicy::Smart
+            && self
+                .gazetteer
+                .as_ref()
+                .is_some_and(|gaz| gaz.contains(&word))
+        {
+            key = capitalize(&key);
+        }
+        if kind != Kind::Symbol {
+            let growth = &mut self.growth;
+            growth.tokens += 1;
+            if !self.words.contains_key(&key) {
+                growth.types += 1;
+            }
+            if growth.tokens.is_multiple_of(GROWTH_STEP) {
+                growth.samples.push((growth.tokens, growth.types));
+            }
+        }
+        self.tally_entry(key, WordEntry::new(1, word, kind));
+    }
+
+    /// Tally a word entry
+    fn tally_entry(&mut self, key: String, we: WordEntry) {
         match self.words.get_mut(&key) {
             Some(e) => {
                 // use variant with fewest uppercase characters
                 if count_uppercase(we.word()) < count_uppercase(e.word()) {
                     e.word = we.word;
                     e.kind = we.kind;
+                } else if e.kind == Kind::Unknown
+                    && we.kind == Kind::Proper
+                    && e.word == we.word
+                {
+                    // first seen at the start of a sentence
+                    e.kind = Kind::Proper;
                 }
-                e.seen += 1;
+                e.seen += we.seen;
             }
             None => {
-                let mut we = we;
-                we.seen = 1;
                 self.words.insert(key, we);
             }
         }
     }
 
+    /// Parse in-memory text
+    pub fn parse_str(&mut self, text: &str) -> Result<()> {
+        self.parse_text(text.as_bytes())
+    }
+
+    /// Parse text files in parallel (using rayon)
+    ///
+    /// Each file is tallied on its own thread, using the same lexicon and
+    /// options, and merged into this tally.
+    #[cfg(feature = "rayon")]
+    pub fn parse_files_parallel<P>(&mut self, paths: &[P]) -> Result<()>
+    where
+        P: AsRef<Path> + Sync,
+    {
+        let tallies = self.tally_each(paths, |path, tally| {
+            let file = std::fs::File::open(path)?;
+            tally.parse_text(std::io::BufReader::new(file))
+        });
+        for tally in tallies {
+            self.merge(tally?);
+        }
+        Ok(())
+    }
+
+    /// Tally each of a list of inputs separately
+    ///
+    /// Every input is parsed by a closure into an empty tally, using the same
+    /// lexicon and options.  With the `rayon` feature, inputs are tallied in
+    /// parallel.  Results are in the same order as the inputs.
+    pub fn tally_each<T, E, F>(
+        &self,
+        inputs: &[T],
+        parse: F,
+    ) -> Vec<Result<WordTally, E>>
+    where
+        T: Sync,
+        E: Send,
+        F: Fn(&T, &mut WordTally) -> Result<(), E> + Sync,
+    {
+        let tally_one = |input| {
+            let mut tally = self.empty_clone();
+            parse(input, &mut tally)?;
+            Ok(tally)
+        };
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            inputs.par_iter().map(tally_one).collect()
+        }
+        #[cfg(not(feature = "rayon"))]
+        inputs.iter().map(tally_one).collect()
+    }
+
+    /// Clone tally options, without any words
+    fn empty_clone(&self) -> Self {
+        WordTally {
+            words: HashMap::new(),
+            lex: self.lex.clone(),
+            gazetteer: self.gazetteer.clone(),
+            stem_unknown: self.stem_unknown,
+            stopwords: self.stopwords.clone(),
+            case_policy: self.case_policy,
+            dehyphenate: self.dehyphenate,
+            tokenizer: self.tokenizer.clone(),
+            growth: Growth::default(),
+        }
+    }
+
+    /// Merge another word tally into this one
+    pub fn merge(&mut self, other: WordTally) {
+        for (key, we) in other.words {
+            self.tally_entry(key, we);
+        }
+    }
+
+    /// Compare with another word tally
+    ///
+    /// Words with different seen counts are listed, largest change first.
+    pub fn diff(&self, other: &WordTally) -> Vec<DiffEntry> {
+        let mut entries = Vec::new();
+        for (key, we) in &self.words {
+            let right = other.words.get(key).map_or(0, |e| e.seen);
+            if right != we.seen {
+                entries.push(DiffEntry {
+                    word: we.word.clone(),
+                    kind: we.kind,
+                    left: we.seen,
+                    right,
+                });
+            }
+        }
+        for (key, we) in &other.words {
+            if !self.words.contains_key(key) {
+                entries.push(DiffEntry {
+                    word: we.word.clone(),
+                    kind: we.kind,
+                    left: 0,
+                    right: we.seen,
+                });
+            }
+        }
+        entries.sort_by(|a, b| {
+            b.delta()
+                .abs()
+                .cmp(&a.delta().abs())
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        entries
+    }
+
     /// Get the number of words
     pub fn len(&self) -> usize {
         self.words.len()
@@ -123,10 +483,486 @@ impl WordTally {
             .count()
     }
 
+    /// Get an iterator of word entries, not counting symbols
+    fn word_entries(&self) -> impl Iterator<Item = &WordEntry> {
+        self.words.values().filter(|we| we.kind != Kind::Symbol)
+    }
+
+    /// Count word tokens (all kinds except `Symbol`)
+    pub fn token_count(&self) -> usize {
+        self.word_entries().map(|we| we.seen).sum()
+    }
+
+    /// Count word types (all kinds except `Symbol`)
+    pub fn type_count(&self) -> usize {
+        self.word_entries().count()
+    }
+
+    /// Count hapax legomena (words seen only once)
+    pub fn hapax_count(&self) -> usize {
+        self.word_entries().filter(|we| we.seen == 1).count()
+    }
+
+    /// Get the type-token ratio (word types per token)
+    pub fn type_token_ratio(&self) -> Option<f64> {
+        match self.token_count() {
+            0 => None,
+            tokens => Some(self.type_count() as f64 / tokens as f64),
+        }
+    }
+
+    /// Get the vocabulary growth curve, as (tokens, types) pairs
+    ///
+    /// Samples are taken every 1000 word tokens while parsing, with a final
+    /// sample at the end.  Merged tallies are not included.
+    pub fn vocabulary_growth(&self) -> Vec<(usize, usize)> {
+        let growth = &self.growth;
+        let mut samples = growth.samples.clone();
+        if !growth.tokens.is_multiple_of(GROWTH_STEP) {
+            samples.push((growth.tokens, growth.types));
+        }
+        samples
+    }
+
+    /// Get word entries with ranks, most frequent first
+    ///
+    /// Ties are ranked in word order.  Symbols are not included.
+    pub fn rank_frequency(&self) -> Vec<(usize, &WordEntry)> {
+        let mut entries: Vec<_> = self.word_entries().collect();
+        entries.sort_by(|a, b| b.seen.cmp(&a.seen).then(a.word.cmp(&b.word)));
+        entries
+            .into_iter()
+            .enumerate()
+            .map(|(i, we)| (i + 1, we))
+            .collect()
+    }
+
+    /// Get an iterator of word entries (unsorted)
+    pub fn iter(&self) -> impl Iterator<Item = &WordEntry> {
+        self.words.values()
+    }
+
+    /// Get an iterator of word entries, in the same order as `into_entries`
+    pub fn entries(&self) -> impl Iterator<Item = &WordEntry> {
+        let mut entries: Vec<_> = self.words.values().collect();
+        entries.sort();
+        entries.into_iter()
+    }
+
+    /// Get an iterator of word entries of a given kind (sorted)
+    pub fn entries_of_kind(
+        &self,
+        kind: Kind,
+    ) -> impl Iterator<Item = &WordEntry> {
+        self.entries().filter(move |we| we.kind() == kind)
+    }
+
     /// Get a Vec of word entries
     pub fn into_entries(self) -> Vec<WordEntry> {
         let mut entries: Vec<_> = self.words.into_values().collect();
         entries.sort();
         entries
     }
+
+    /// Get the `k` most frequent word entries of some kinds (all if empty)
+    ///
+    /// Entries are in the reverse order of `into_entries`, without sorting
+    /// the whole tally.
+    pub fn top_k(&self, k: usize, kinds: &[Kind]) -> Vec<&WordEntry> {
+        let mut heap = BinaryHeap::with_capacity(k.min(self.words.len()) + 1);
+        for we in self.words.values() {
+            if !kinds.is_empty() && !kinds.contains(&we.kind) {
+                continue;
+            }
+            if heap.len() < k {
+                heap.push(Reverse(we));
+            } else if let Some(mut min) = heap.peek_mut()
+                && we > min.0
+            {
+                *min = Reverse(we);
+            }
+        }
+        heap.into_sorted_vec().into_iter().map(|we| we.0).collect()
+    }
+
+    /// Get a Vec of word entries, grouped by lemma
+    ///
+    /// Lexicon words are tallied by their lemma ("run", "runs", "ran" and
+    /// "running" are all "run").  For words with more than one lemma, the
+    /// most likely word class is used.  Other words are not grouped.
+    pub fn into_lemma_entries(self) -> Vec<WordEntry> {
+        let lex = self.lex.clone().unwrap_or_else(lex::builtin_shared);
+        let mut tally = WordTally::new();
+        for (key, mut we) in self.words {
+            if we.kind != Kind::Lexicon {
+                tally.tally_entry(key, we);
+                continue;
+            }
+            let lemmas = lex.lemmatize(&we.word);
+            let wc = lex.most_likely_class(&we.word);
+            let lemma = lemmas
+                .iter()
+                .find(|(_lemma, c)| Some(*c) == wc)
+                .or(lemmas.first());
+            match lemma {
+                Some((lemma, _wc)) => {
+                    we.word = lemma.to_string();
+                    tally.tally_entry(make_word(lemma), we);
+                }
+                None => tally.tally_entry(key, we),
+            }
+        }
+        tally.into_entries()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse::{Token, Tokens, classify};
+    use std::thread;
+
+    #[test]
+    fn shared_lexicon() {
+        let lex = Arc::new(Lexicon::from_csv("blorf:V").unwrap());
+        let handles: Vec<_> = ["The cat blorfed.", "A blorf"]
+            .into_iter()
+            .map(|text| {
+                let lex = Arc::clone(&lex);
+                thread::spawn(move || {
+                    let mut tally = WordTally::with_lexicon(lex);
+                    tally.parse_text(text.as_bytes()).unwrap();
+                    tally
+                })
+            })
+            .collect();
+        let mut tally = WordTally::new();
+        for handle in handles {
+            tally.merge(handle.join().unwrap());
+        }
+        assert_eq!(tally.count_kind(Kind::Lexicon), 2);
+        // "The" starts a sentence, so it is not `Proper`
+        assert_eq!(tally.count_kind(Kind::Unknown), 2);
+        assert_eq!(tally.len(), 6);
+    }
+
+    #[test]
+    fn lemma_entries() {
+        let mut tally = WordTally::new();
+        tally
+            .parse_str("Run! She runs, he ran; they were running.")
+            .unwrap();
+        let entries = tally.into_lemma_entries();
+        let run = entries.iter().find(|e| e.word() == "run").unwrap();
+        assert_eq!(run.seen(), 4);
+        assert_eq!(run.kind(), Kind::Lexicon);
+        assert!(entries.iter().any(|e| e.word() == "be"));
+        assert!(!entries.iter().any(|e| e.word() == "running"));
+    }
+
+    #[test]
+    fn entries() {
+        let mut tally = WordTally::new();
+        tally.parse_str("The cat saw the blorf.").unwrap();
+        let words: Vec<_> = tally.entries().map(|e| e.word()).collect();
+        assert_eq!(words, [".", "blorf", "cat", "saw", "the"]);
+        let unknown: Vec<_> = tally
+            .entries_of_kind(Kind::Unknown)
+            .map(|e| e.word())
+            .collect();
+        assert_eq!(unknown, ["blorf"]);
+        assert_eq!(tally.count_kind(Kind::Lexicon), 3);
+        assert_eq!(tally.into_entries().len(), 5);
+    }
+
+    #[test]
+    fn top_k() {
+        let mut tally = WordTally::new();
+        tally
+            .parse_str("The cat saw the other cat and the dog, 2 3 3.")
+            .unwrap();
+        let top: Vec<_> = tally
+            .top_k(3, &[])
+            .into_iter()
+            .map(|e| (e.word(), e.seen()))
+            .collect();
+        assert_eq!(top, [("the", 3), ("cat", 2), ("3", 2)]);
+        let top: Vec<_> = tally
+            .top_k(2, &[Kind::Lexicon])
+            .into_iter()
+            .map(|e| e.word())
+            .collect();
+        assert_eq!(top, ["the", "cat"]);
+        assert!(tally.top_k(0, &[]).is_empty());
+        let all: Vec<_> = tally.top_k(100, &[]).into_iter().cloned().collect();
+        let mut entries = tally.into_entries();
+        entries.reverse();
+        assert!(all == entries);
+    }
+
+    #[test]
+    fn vocabulary() {
+        let mut tally = WordTally::new();
+        assert_eq!(tally.type_token_ratio(), None);
+        tally
+            .parse_str("The cat saw the other cat, and a dog.")
+            .unwrap();
+        assert_eq!(tally.token_count(), 9);
+        assert_eq!(tally.type_count(), 7);
+        assert_eq!(tally.hapax_count(), 5);
+        assert_eq!(tally.type_token_ratio(), Some(7.0 / 9.0));
+        let ranks: Vec<_> = tally
+            .rank_frequency()
+            .into_iter()
+            .map(|(rank, we)| (rank, we.word().to_string(), we.seen()))
+            .take(3)
+            .collect();
+        assert_eq!(
+            ranks,
+            [
+                (1, "cat".to_string(), 2),
+                (2, "the".to_string(), 2),
+                (3, "a".to_string(), 1),
+            ]
+        );
+        assert_eq!(tally.vocabulary_growth(), [(9, 7)]);
+        let text = "one two three four five ".repeat(300);
+        let mut tally = WordTally::new();
+        tally.parse_str(&text).unwrap();
+        assert_eq!(tally.vocabulary_growth(), [(1000, 5), (1500, 5)]);
+    }
+
+    #[test]
+    fn dehyphenate() {
+        let text = "An inter-\nnational blorf-\nwidget.";
+        let mut tally = WordTally::new().dehyphenate(true);
+        tally.parse_str(text).unwrap();
+        let mut words: Vec<_> = tally.iter().map(|e| e.word()).collect();
+        words.sort();
+        assert_eq!(words, ["-", ".", "An", "blorf", "international", "widget"]);
+    }
+
+    #[test]
+    fn stopwords() {
+        let mut stop = StopWords::new();
+        stop.insert("blorf");
+        let text = "The cat and the blorf sat on a mat.";
+        let mut tally = WordTally::new().stopwords(Arc::new(stop));
+        tally.parse_str(text).unwrap();
+        assert_eq!(tally.len(), 8);
+        let mut tally =
+            WordTally::new().stopwords(crate::stopwords::builtin_shared());
+        tally.parse_str(text).unwrap();
+        let mut words: Vec<_> = tally.iter().map(|e| e.word()).collect();
+        words.sort();
+        assert_eq!(words, [".", "blorf", "cat", "mat", "sat"]);
+    }
+
+    #[test]
+    fn case_policy() {
+        let text = "NASA and Nasa and nasa.  Apple, apple, IBM.";
+        let seen = |policy| {
+            let mut tally = WordTally::new().case_policy(policy);
+            tally.parse_str(text).unwrap();
+            let mut seen: Vec<_> = tally
+                .into_entries()
+                .into_iter()
+                .filter(|e| e.word().chars().any(char::is_alphabetic))
+                .map(|e| (e.word().to_string(), e.seen()))
+                .collect();
+            seen.sort();
+            seen
+        };
+        let seen_of = |words: &[(&str, usize)]| {
+            words
+                .iter()
+                .map(|(w, n)| (w.to_string(), *n))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            seen(CasePolicy::Preserve),
+            seen_of(&[
+                ("Apple", 1),
+                ("IBM", 1),
+                ("NASA", 1),
+                ("Nasa", 1),
+                ("and", 2),
+                ("apple", 1),
+                ("nasa", 1),
+            ])
+        );
+        assert_eq!(
+            seen(CasePolicy::Lowercase),
+            seen_of(&[("and", 2), ("apple", 2), ("ibm", 1), ("nasa", 3)])
+        );
+        assert_eq!(
+            seen(CasePolicy::Smart),
+            seen_of(&[("IBM", 1), ("and", 2), ("apple", 2), ("nasa", 3)])
+        );
+    }
+
+    #[test]
+    fn stem_unknown() {
+        let text = "The blorfs blorfed a blorfing blorf.";
+        let mut tally = WordTally::new().stem_unknown(true);
+        tally.parse_str(text).unwrap();
+        assert_eq!(tally.count_kind(Kind::Unknown), 1);
+        let entry = tally.iter().find(|e| e.kind() == Kind::Unknown).unwrap();
+        assert_eq!(entry.seen(), 4);
+        let mut tally = WordTally::new();
+        tally.parse_text(text.as_bytes()).unwrap();
+        assert_eq!(tally.count_kind(Kind::Unknown), 4);
+    }
+
+    #[test]
+    fn gazetteer() {
+        let mut gaz = Gazetteer::calendar();
+        gaz.insert("Bill", crate::gazetteer::Entity::Person);
+        let mut tally = WordTally::new().gazetteer(Arc::new(gaz.clone()));
+        let text = "Then Bill paid the bill in May.  You may go, BILL.";
+        tally.parse_text(text.as_bytes()).unwrap();
+        let entries = tally.into_entries();
+        let find = |w: &str| entries.iter().find(|e| e.word() == w).unwrap();
+        assert_eq!(find("Bill").kind(), Kind::Proper);
+        assert_eq!(find("Bill").seen(), 2);
+        assert_eq!(find("bill").kind(), Kind::Lexicon);
+        assert_eq!(find("May").kind(), Kind::Proper);
+        assert_eq!(find("may").seen(), 1);
+        // sentence-initial names which are also lexicon words
+        let mut tally = WordTally::new().gazetteer(Arc::new(gaz));
+        tally.parse_text("May I go?".as_bytes()).unwrap();
+        assert_eq!(tally.count_kind(Kind::Proper), 0);
+    }
+
+    #[test]
+    fn sentence_initial() {
+        let text = "Zyx went home.  Later, Zyx slept.\n\nQwv?  Blorf.";
+        let mut tally = WordTally::new();
+        tally.parse_text(text.as_bytes()).unwrap();
+        let entries = tally.into_entries();
+        let find = |w: &str| entries.iter().find(|e| e.word() == w).unwrap();
+        assert_eq!(find("Zyx").kind(), Kind::Proper);
+        assert_eq!(find("Zyx").seen(), 2);
+        assert_eq!(find("Qwv").kind(), Kind::Unknown);
+        assert_eq!(find("Blorf").kind(), Kind::Unknown);
+    }
+
+    /// Tokenizer splitting only on whitespace
+    struct WhitespaceTokenizer;
+
+    impl Tokenizer for WhitespaceTokenizer {
+        fn tokenize<'a>(
+            &self,
+            mut reader: Box<dyn BufRead + 'a>,
+            lex: Arc<Lexicon>,
+        ) -> Tokens<'a> {
+            let mut text = String::new();
+            if let Err(e) = reader.read_to_string(&mut text) {
+                return Box::new(std::iter::once(Err(e)));
+            }
+            let mut tokens = Vec::new();
+            for word in text.split_whitespace() {
+                let start = word.as_ptr() as usize - text.as_ptr() as usize;
+                let kind = classify(&lex, word);
+                let span = start..start + word.len();
+                tokens.push(Ok(Token::new(
+                    Chunk::Text,
+                    word.to_string(),
+                    kind,
+                    span,
+                    1,
+                    start + 1,
+                )));
+            }
+            Box::new(tokens.into_iter())
+        }
+    }
+
+    #[test]
+    fn tokenizer() {
+        let text = "well-read blorf-ish well-read";
+        let mut tally =
+            WordTally::new().tokenizer(Arc::new(WhitespaceTokenizer));
+        tally.parse_text(text.as_bytes()).unwrap();
+        assert_eq!(tally.len(), 2);
+        let entries = tally.into_entries();
+        let find = |w: &str| entries.iter().find(|e| e.word() == w).unwrap();
+        assert_eq!(find("well-read").seen(), 2);
+        assert_eq!(find("blorf-ish").kind(), Kind::Unknown);
+    }
+
+    #[test]
+    fn tally_each() {
+        let texts = ["The cat sat.", "", "A dog, a dog."];
+        let tally = WordTally::new().case_policy(CasePolicy::Lowercase);
+        let tallies = tally.tally_each(&texts, |text, tally| {
+            if text.is_empty() {
+                return Err("empty");
+            }
+            tally.parse_str(text).map_err(|_| "parse")
+        });
+        assert_eq!(tallies.len(), 3);
+        let first = tallies[0].as_ref().unwrap();
+        assert_eq!(first.token_count(), 3);
+        assert!(first.entries().all(|e| e.word() != "The"));
+        assert_eq!(tallies[1].as_ref().err(), Some(&"empty"));
+        assert_eq!(tallies[2].as_ref().unwrap().type_count(), 2);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel() {
+        let dir = std::env::temp_dir().join("booky-tally-parallel");
+        std::fs::create_dir_all(&dir).unwrap();
+        let paths: Vec<_> = ["The cat sat.", "A cat ran.", "The dog sat."]
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let path = dir.join(format!("{i}.txt"));
+                std::fs::write(&path, text).unwrap();
+                path
+            })
+            .collect();
+        let mut tally = WordTally::new();
+        tally.parse_files_parallel(&paths).unwrap();
+        let mut serial = WordTally::new();
+        for path in &paths {
+            serial
+                .parse_text(std::fs::read_to_string(path).unwrap().as_bytes())
+                .unwrap();
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+        let entries = |tally: WordTally| -> Vec<_> {
+            tally
+                .into_entries()
+                .iter()
+                .map(|e| (e.word().to_string(), e.seen()))
+                .collect()
+        };
+        assert_eq!(entries(tally), entries(serial));
+        let mut tally = WordTally::new();
+        assert!(tally.parse_files_parallel(&[dir.join("none")]).is_err());
+    }
+
+    #[test]
+    fn diff() {
+        let mut first = WordTally::new();
+        first.parse_str("The cat sat on the mat.").unwrap();
+        let mut second = WordTally::new();
+        second
+            .parse_str("The dog sat on the mat. The dog ran.")
+            .unwrap();
+        let diff = first.diff(&second);
+        let words: Vec<_> =
+            diff.iter().map(|d| (d.word(), d.delta())).collect();
+        assert_eq!(
+            words,
+            [("dog", 2), (".", 1), ("cat", -1), ("ran", 1), ("the", 1)]
+        );
+        assert!(diff[0].is_right_only());
+        assert!(diff[2].is_left_only());
+        assert!(first.diff(&first).is_empty());
+        first.merge(second);
+        assert_eq!(first.iter().find(|e| e.word() == "dog").unwrap().seen(), 2);
+    }
 }
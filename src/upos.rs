@@ -0,0 +1,263 @@
+use crate::Error;
+use crate::kind::Kind;
+use crate::word::{Lexeme, WordAttr, WordClass};
+use alloc::string::ToString;
+use core::fmt;
+
+/// Universal Dependencies part-of-speech tag (UPOS)
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Upos {
+    /// `ADJ`: Adjective
+    Adj,
+    /// `ADP`: Adposition
+    Adp,
+    /// `ADV`: Adverb
+    Adv,
+    /// `AUX`: Auxiliary
+    Aux,
+    /// `CCONJ`: Coordinating conjunction
+    Cconj,
+    /// `DET`: Determiner
+    Det,
+    /// `INTJ`: Interjection
+    Intj,
+    /// `NOUN`: Noun
+    Noun,
+    /// `NUM`: Numeral
+    Num,
+    /// `PART`: Particle
+    Part,
+    /// `PRON`: Pronoun
+    Pron,
+    /// `PROPN`: Proper noun
+    Propn,
+    /// `PUNCT`: Punctuation
+    Punct,
+    /// `SCONJ`: Subordinating conjunction
+    Sconj,
+    /// `SYM`: Symbol
+    Sym,
+    /// `VERB`: Verb
+    Verb,
+    /// `X`: Other
+    X,
+}
+
+/// ASCII punctuation which is tagged as a symbol
+const ASCII_SYMBOLS: &str = "#$%&*+<=>@^|~";
+
+/// Non-ASCII punctuation
+const PUNCTUATION: &str = "‘’“”«»‹›–—―…¡¿·•§¶";
+
+impl TryFrom<&str> for Upos {
+    type Error = Error;
+
+    fn try_from(tag: &str) -> Result<Self, Self::Error> {
+        Upos::all()
+            .iter()
+            .find(|u| u.tag() == tag)
+            .copied()
+            .ok_or_else(|| Error::Tag(tag.to_string()))
+    }
+}
+
+impl fmt::Display for Upos {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}", self.tag())
+    }
+}
+
+impl From<WordClass> for Upos {
+    fn from(wc: WordClass) -> Self {
+        match wc {
+            WordClass::Adjective => Upos::Adj,
+            WordClass::Adverb => Upos::Adv,
+            WordClass::Conjunction => Upos::Cconj,
+            WordClass::Determiner => Upos::Det,
+            WordClass::Interjection => Upos::Intj,
+            WordClass::Noun => Upos::Noun,
+            WordClass::Preposition => Upos::Adp,
+            WordClass::Pronoun => Upos::Pron,
+            WordClass::Verb => Upos::Verb,
+        }
+    }
+}
+
+impl From<&Lexeme> for Upos {
+    fn from(lex: &Lexeme) -> Self {
+        let mut attrs = lex.attributes();
+        match lex.word_class() {
+            WordClass::Verb if attrs.any(|a| a == WordAttr::Auxiliary) => {
+                Upos::Aux
+            }
+            WordClass::Noun if attrs.any(|a| a == WordAttr::Proper) => {
+                Upos::Propn
+            }
+            wc => Upos::from(wc),
+        }
+    }
+}
+
+impl Upos {
+    /// Get all tags
+    pub fn all() -> &'static [Self] {
+        use Upos::*;
+        &[
+            Adj, Adp, Adv, Aux, Cconj, Det, Intj, Noun, Num, Part, Pron, Propn,
+            Punct, Sconj, Sym, Verb, X,
+        ]
+    }
+
+    /// Get tag name
+    pub fn tag(self) -> &'static str {
+        use Upos::*;
+        match self {
+            Adj => "ADJ",
+            Adp => "ADP",
+            Adv => "ADV",
+            Aux => "AUX",
+            Cconj => "CCONJ",
+            Det => "DET",
+            Intj => "INTJ",
+            Noun => "NOUN",
+            Num => "NUM",
+            Part => "PART",
+            Pron => "PRON",
+            Propn => "PROPN",
+            Punct => "PUNCT",
+            Sconj => "SCONJ",
+            Sym => "SYM",
+            Verb => "VERB",
+            X => "X",
+        }
+    }
+
+    /// Get tag of a word not in the lexicon, from its kind
+    ///
+    /// Returns `None` for `Kind::Lexicon`, which is tagged by word class.
+    pub fn from_kind(kind: Kind, word: &str) -> Option<Self> {
+        match kind {
+            Kind::Lexicon => None,
+            Kind::Ordinal => Some(Upos::Adj),
+            Kind::Roman | Kind::Number | Kind::Date | Kind::Time => {
+                Some(Upos::Num)
+            }
+            Kind::Acronym | Kind::Proper | Kind::Mention => Some(Upos::Propn),
+            Kind::Emoji => Some(Upos::Sym),
+            Kind::Symbol => Some(symbol_tag(word)),
+            Kind::Url
+            | Kind::Email
+            | Kind::Hashtag
+            | Kind::Foreign
+            | Kind::Unknown => Some(Upos::X),
+        }
+    }
+
+    /// Get the closest word class
+    pub fn word_class(self) -> Option<WordClass> {
+        use Upos::*;
+        match self {
+            Adj => Some(WordClass::Adjective),
+            Adp => Some(WordClass::Preposition),
+            Adv => Some(WordClass::Adverb),
+            Aux | Verb => Some(WordClass::Verb),
+            Cconj | Sconj => Some(WordClass::Conjunction),
+            Det => Some(WordClass::Determiner),
+            Intj => Some(WordClass::Interjection),
+            Noun | Propn => Some(WordClass::Noun),
+            Pron => Some(WordClass::Pronoun),
+            Num | Part | Punct | Sym | X => None,
+        }
+    }
+
+    /// Get the closest word kind
+    pub fn kind(self) -> Kind {
+        match self {
+            Upos::Num => Kind::Number,
+            Upos::Propn => Kind::Proper,
+            Upos::Punct | Upos::Sym => Kind::Symbol,
+            Upos::X => Kind::Unknown,
+            _ => Kind::Lexicon,
+        }
+    }
+}
+
+/// Get tag of a symbol (punctuation or other)
+fn symbol_tag(word: &str) -> Upos {
+    match word.chars().next() {
+        Some(c) if c.is_ascii_punctuation() && !ASCII_SYMBOLS.contains(c) => {
+            Upos::Punct
+        }
+        Some(c) if PUNCTUATION.contains(c) => Upos::Punct,
+        Some(c) if c.is_alphanumeric() => Upos::X,
+        _ => Upos::Sym,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn upos() {
+        assert_eq!(Upos::from(WordClass::Preposition), Upos::Adp);
+        let lex = Lexeme::try_from("can:V.a,could").unwrap();
+        assert_eq!(Upos::from(&lex), Upos::Aux);
+        assert_eq!(Upos::from_kind(Kind::Number, "42"), Some(Upos::Num));
+        assert_eq!(Upos::from_kind(Kind::Symbol, ","), Some(Upos::Punct));
+        assert_eq!(Upos::from_kind(Kind::Symbol, "$"), Some(Upos::Sym));
+        assert_eq!(Upos::from_kind(Kind::Lexicon, "cat"), None);
+        assert_eq!(Upos::try_from("SCONJ").unwrap(), Upos::Sconj);
+        assert!(Upos::try_from("NN").is_err());
+        for upos in Upos::all() {
+            if let Some(wc) = upos.word_class()
+                && !matches!(upos, Upos::Aux | Upos::Propn | Upos::Sconj)
+            {
+                assert_eq!(Upos::from(wc), *upos);
+            }
+        }
+    }
+
+    #[test]
+    fn kinds() {
+        use Upos::*;
+        for (kind, word, upos) in [
+            (Kind::Unknown, "blorf", X),
+            (Kind::Foreign, "café", X),
+            (Kind::Url, "https://x.org", X),
+            (Kind::Ordinal, "2nd", Adj),
+            (Kind::Date, "2024-01-01", Num),
+            (Kind::Mention, "@me", Propn),
+            (Kind::Emoji, "🙂", Sym),
+            (Kind::Symbol, "(", Punct),
+            (Kind::Symbol, "—", Punct),
+            (Kind::Symbol, "¿", Punct),
+            (Kind::Symbol, "%", Sym),
+            (Kind::Symbol, "©", Sym),
+            (Kind::Symbol, "x", X),
+            (Kind::Symbol, "", Sym),
+        ] {
+            assert_eq!(Upos::from_kind(kind, word), Some(upos), "{word}");
+        }
+        for upos in Upos::all() {
+            assert_eq!(Upos::try_from(upos.tag()).ok(), Some(*upos));
+            assert_eq!(upos.to_string(), upos.tag());
+        }
+        assert!(matches!(Upos::try_from("noun"), Err(Error::Tag(_))));
+        for (upos, kind) in [
+            (Num, Kind::Number),
+            (Propn, Kind::Proper),
+            (Punct, Kind::Symbol),
+            (Sym, Kind::Symbol),
+            (X, Kind::Unknown),
+            (Part, Kind::Lexicon),
+        ] {
+            assert_eq!(upos.kind(), kind, "{upos}");
+        }
+        let paris = Lexeme::try_from("Paris:N.n").unwrap();
+        assert_eq!(Upos::from(&paris), Propn);
+        let walk = Lexeme::try_from("walk:V").unwrap();
+        assert_eq!(Upos::from(&walk), Verb);
+    }
+}
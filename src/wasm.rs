@@ -0,0 +1,64 @@
+use crate::hilite;
+use crate::kind::Kind;
+use crate::lex;
+use crate::parse::{Chunk, Parser};
+use wasm_bindgen::prelude::*;
+
+/// Split text into words
+#[wasm_bindgen]
+pub fn tokenize(text: &str) -> Vec<String> {
+    Parser::new(text.as_bytes())
+        .filter_map(|token| match token {
+            Ok(token) if token.chunk() == Chunk::Text => {
+                Some(token.into_text())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Classify a word by kind (`Lexicon`, `Proper`, `Unknown`, etc.)
+#[wasm_bindgen]
+pub fn classify(word: &str) -> String {
+    let kind = if lex::builtin().contains(word) {
+        Kind::Lexicon
+    } else {
+        Kind::from(word)
+    };
+    format!("{kind:?}")
+}
+
+/// Get lemmas of a word form from the built-in lexicon
+#[wasm_bindgen]
+pub fn lemmatize(word: &str) -> Vec<String> {
+    let mut lemmas: Vec<String> = lex::builtin()
+        .word_entries(word)
+        .iter()
+        .map(|lex| lex.lemma().to_string())
+        .collect();
+    lemmas.dedup();
+    lemmas
+}
+
+/// Hilite text as HTML (see `booky::hilite::hilite_html`)
+#[wasm_bindgen(js_name = hiliteHtml)]
+pub fn hilite_html(text: &str) -> Result<String, JsError> {
+    hilite::hilite_html(text).map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bindings() {
+        assert_eq!(
+            tokenize("The cat’s hat, Sam!"),
+            ["The", "cat’s", "hat", "Sam"]
+        );
+        assert_eq!(classify("Sam"), "Proper");
+        assert_eq!(classify("mice"), "Lexicon");
+        assert_eq!(lemmatize("mice"), ["mouse"]);
+        assert!(lemmatize("blorf").is_empty());
+    }
+}
@@ -1,8 +1,15 @@
+use crate::Error;
+#[cfg(feature = "std")]
+use crate::cache;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
 use deunicode::deunicode_char;
-use std::fmt;
 
 /// Word class
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WordClass {
     /// `A`: Adjective
     Adjective,
@@ -27,6 +34,7 @@ pub enum WordClass {
 
 /// Word attributes
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WordAttr {
     /// `a`: Auxiliary verb (e.g. "cannot")
     Auxiliary,
@@ -44,6 +52,75 @@ pub enum WordAttr {
     AlternateZ,
 }
 
+/// Rule which produces a word form
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FormRule {
+    /// Lemma form
+    Lemma,
+    /// Variant spelling of lemma
+    Variant,
+    /// Irregular form (listed in lexicon)
+    Irregular,
+    /// Regular noun plural
+    Plural,
+    /// Regular verb present (third person singular)
+    Present,
+    /// Regular verb present participle
+    PresentParticiple,
+    /// Regular verb past
+    Past,
+    /// Regular adjective comparative
+    Comparative,
+    /// Regular adjective superlative
+    Superlative,
+}
+
+/// Inflectional feature of a word form
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Inflection {
+    /// Base form (lemma or variant spelling)
+    Base,
+    /// Noun plural
+    Plural,
+    /// Verb present (third person singular)
+    Present,
+    /// Verb present participle
+    PresentParticiple,
+    /// Verb past tense
+    PastTense,
+    /// Verb past participle
+    PastParticiple,
+    /// Adjective comparative
+    Comparative,
+    /// Adjective superlative
+    Superlative,
+    /// Other irregular form (e.g. archaic "hast")
+    Other,
+}
+
+/// Inflections of irregular forms of "be", by position
+const BE_INFLECTIONS: &[Inflection] = &[
+    Inflection::Present,
+    Inflection::Present,
+    Inflection::Present,
+    Inflection::PastTense,
+    Inflection::PastTense,
+    Inflection::PastParticiple,
+    Inflection::PresentParticiple,
+];
+
+/// Word form labeled with its inflection
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InflectedForm {
+    /// Form text
+    text: String,
+    /// Inflectional feature
+    feature: Inflection,
+}
+
 /// Word Lexeme
 #[derive(Clone, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Lexeme {
@@ -60,7 +137,7 @@ pub struct Lexeme {
 }
 
 impl TryFrom<&str> for WordClass {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(cl: &str) -> Result<Self, Self::Error> {
         match cl {
@@ -73,7 +150,7 @@ impl TryFrom<&str> for WordClass {
             "C" => Ok(WordClass::Conjunction),
             "D" => Ok(WordClass::Determiner),
             "I" => Ok(WordClass::Interjection),
-            _ => Err(()),
+            _ => Err(Error::WordClass(cl.to_string())),
         }
     }
 }
@@ -96,21 +173,50 @@ impl fmt::Display for WordClass {
 }
 
 impl WordClass {
+    /// Get all word classes
+    pub fn all() -> &'static [Self] {
+        use WordClass::*;
+        &[
+            Adjective,
+            Adverb,
+            Conjunction,
+            Determiner,
+            Interjection,
+            Noun,
+            Preposition,
+            Pronoun,
+            Verb,
+        ]
+    }
+
     /// Build regular inflected forms
-    fn build_regular_forms(self, lex: &Lexeme, lemma: &str) -> Vec<String> {
+    fn build_regular_forms(
+        self,
+        lex: &Lexeme,
+        lemma: &str,
+    ) -> Vec<(FormRule, String)> {
         let mut forms = Vec::new();
         match self {
             WordClass::Adjective => {
-                forms.push(adjective_comparative(lemma));
-                forms.push(adjective_superlative(lemma));
+                forms.push((
+                    FormRule::Comparative,
+                    adjective_comparative(lemma),
+                ));
+                forms.push((
+                    FormRule::Superlative,
+                    adjective_superlative(lemma),
+                ));
             }
             WordClass::Noun if lex.has_plural() => {
-                forms.push(noun_plural(lemma));
+                forms.push((FormRule::Plural, noun_plural(lemma)));
             }
             WordClass::Verb => {
-                forms.push(verb_present(lemma));
-                forms.push(verb_present_participle(lemma));
-                forms.push(verb_past(lemma));
+                forms.push((FormRule::Present, verb_present(lemma)));
+                forms.push((
+                    FormRule::PresentParticiple,
+                    verb_present_participle(lemma),
+                ));
+                forms.push((FormRule::Past, verb_past(lemma)));
             }
             _ => (),
         }
@@ -118,8 +224,72 @@ impl WordClass {
     }
 }
 
+impl fmt::Display for FormRule {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let rule = match self {
+            FormRule::Lemma => "lemma",
+            FormRule::Variant => "variant spelling",
+            FormRule::Irregular => "irregular",
+            FormRule::Plural => "regular plural",
+            FormRule::Present => "regular present",
+            FormRule::PresentParticiple => "regular present participle",
+            FormRule::Past => "regular past",
+            FormRule::Comparative => "regular comparative",
+            FormRule::Superlative => "regular superlative",
+        };
+        write!(fmt, "{rule}")
+    }
+}
+
+impl fmt::Display for Inflection {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let feature = match self {
+            Inflection::Base => "base",
+            Inflection::Plural => "plural",
+            Inflection::Present => "present",
+            Inflection::PresentParticiple => "present participle",
+            Inflection::PastTense => "past tense",
+            Inflection::PastParticiple => "past participle",
+            Inflection::Comparative => "comparative",
+            Inflection::Superlative => "superlative",
+            Inflection::Other => "other",
+        };
+        write!(fmt, "{feature}")
+    }
+}
+
+impl Inflection {
+    /// Get inflections produced by a regular form rule
+    fn from_rule(rule: FormRule) -> &'static [Self] {
+        match rule {
+            FormRule::Lemma | FormRule::Variant => &[Inflection::Base],
+            FormRule::Plural => &[Inflection::Plural],
+            FormRule::Present => &[Inflection::Present],
+            FormRule::PresentParticiple => &[Inflection::PresentParticiple],
+            FormRule::Past => {
+                &[Inflection::PastTense, Inflection::PastParticiple]
+            }
+            FormRule::Comparative => &[Inflection::Comparative],
+            FormRule::Superlative => &[Inflection::Superlative],
+            FormRule::Irregular => &[Inflection::Other],
+        }
+    }
+}
+
+impl InflectedForm {
+    /// Get form text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get inflectional feature
+    pub fn feature(&self) -> Inflection {
+        self.feature
+    }
+}
+
 impl TryFrom<char> for WordAttr {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(val: char) -> Result<Self, Self::Error> {
         match val {
@@ -130,25 +300,28 @@ impl TryFrom<char> for WordAttr {
             's' => Ok(Self::SingulareTantum),
             't' => Ok(Self::Transitive),
             'z' => Ok(Self::AlternateZ),
-            _ => Err(()),
+            _ => Err(Error::WordAttr(val)),
         }
     }
 }
 
 impl TryFrom<&str> for Lexeme {
-    type Error = ();
+    type Error = Error;
 
     fn try_from(line: &str) -> Result<Self, Self::Error> {
+        let invalid = |_| Error::Lexeme(line.to_string());
         let mut vals = line.split(',');
-        let lemma = vals.next().filter(|v| !v.is_empty()).ok_or(())?;
-        let (lemma, cla) = lemma.split_once(':').ok_or(())?;
+        let lemma = vals.next().filter(|v| !v.is_empty()).ok_or(());
+        let (lemma, cla) = lemma
+            .and_then(|l| l.split_once(':').ok_or(()))
+            .map_err(invalid)?;
         let lemma = lemma.to_string();
         let (wc, a) = cla.split_once('.').unwrap_or((cla, ""));
         let word_class = WordClass::try_from(wc)?;
         let attr = a.to_string();
         let mut irregular_forms = Vec::new();
         for form in vals {
-            let form = decode_irregular(&lemma, form)?;
+            let form = decode_irregular(&lemma, form).map_err(invalid)?;
             let form = encode_irregular(&lemma, &form);
             irregular_forms.push(form);
         }
@@ -160,7 +333,7 @@ impl TryFrom<&str> for Lexeme {
             irregular_forms,
             forms,
         };
-        word.build_inflected_forms()?;
+        word.build_inflected_forms().map_err(invalid)?;
         Ok(word)
     }
 }
@@ -243,7 +416,51 @@ impl fmt::Display for Lexeme {
     }
 }
 
+/// Lexemes are serialized as lexicon lines (e.g. `mouse:N,mice`)
+#[cfg(feature = "serde")]
+impl serde::Serialize for Lexeme {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(&format_args!("{self:?}"))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Lexeme {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let line = String::deserialize(deserializer)?;
+        Lexeme::try_from(line.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
 impl Lexeme {
+    /// Write lexeme to a binary cache
+    #[cfg(feature = "std")]
+    pub(crate) fn write_cache(&self, w: &mut cache::Writer) {
+        w.str(&self.lemma);
+        w.str(&self.word_class.to_string());
+        w.str(&self.attr);
+        w.strs(&self.irregular_forms);
+        w.strs(&self.forms);
+    }
+
+    /// Read lexeme from a binary cache
+    #[cfg(feature = "std")]
+    pub(crate) fn read_cache(r: &mut cache::Reader) -> Result<Self, Error> {
+        Ok(Lexeme {
+            lemma: r.str()?.to_string(),
+            word_class: WordClass::try_from(r.str()?)?,
+            attr: r.str()?.to_string(),
+            irregular_forms: r.strs()?,
+            forms: r.strs()?,
+        })
+    }
+
     /// Get lemma as a string slice
     pub fn lemma(&self) -> &str {
         &self.lemma
@@ -259,6 +476,143 @@ impl Lexeme {
         &self.forms[..]
     }
 
+    /// Get all forms, labeled with their inflections
+    ///
+    /// A form may appear more than once with different inflections (e.g.
+    /// "walked" is both past tense and past participle).
+    pub fn labeled_forms(&self) -> Vec<InflectedForm> {
+        let mut forms = Vec::new();
+        let mut push = |text: &str, features: &[Inflection]| {
+            for feature in features {
+                forms.push(InflectedForm {
+                    text: text.to_string(),
+                    feature: *feature,
+                });
+            }
+        };
+        for variant in self.variant_spellings() {
+            push(&variant, &[Inflection::Base]);
+            if self.irregular_forms.is_empty() {
+                if self.has_inflected_forms() {
+                    let regular =
+                        self.word_class.build_regular_forms(self, &variant);
+                    for (rule, f) in regular {
+                        push(&f, Inflection::from_rule(rule));
+                    }
+                }
+                continue;
+            }
+            // verbs with only two irregular forms have the same past forms
+            // as the lemma (e.g. "cut")
+            if self.irregular_forms.len() == 2
+                && self.word_class == WordClass::Verb
+                && !self.is_auxiliary()
+            {
+                push(
+                    &variant,
+                    &[Inflection::PastTense, Inflection::PastParticiple],
+                );
+            }
+            for (i, f) in self.irregular_forms.iter().enumerate() {
+                if let Ok(f) = decode_irregular(&variant, f)
+                    && f != variant
+                {
+                    push(&f, self.irregular_inflections(i));
+                }
+            }
+        }
+        forms
+    }
+
+    /// Get inflections of an irregular form, by position
+    fn irregular_inflections(&self, i: usize) -> &'static [Inflection] {
+        let n = self.irregular_forms.len();
+        match self.word_class {
+            WordClass::Noun => &[Inflection::Plural],
+            WordClass::Adjective => match i {
+                0 => &[Inflection::Comparative],
+                1 => &[Inflection::Superlative],
+                _ => &[Inflection::Other],
+            },
+            WordClass::Verb if self.lemma == "be" => {
+                BE_INFLECTIONS.get(i..=i).unwrap_or(&[Inflection::Other])
+            }
+            WordClass::Verb if n >= 2 && !self.is_auxiliary() => match i {
+                0 => &[Inflection::Present],
+                1 => &[Inflection::PresentParticiple],
+                // past participle is the same as past tense if missing
+                2 if n == 3 => {
+                    &[Inflection::PastTense, Inflection::PastParticiple]
+                }
+                2 => &[Inflection::PastTense],
+                3 => &[Inflection::PastParticiple],
+                _ => &[Inflection::Other],
+            },
+            _ => &[Inflection::Other],
+        }
+    }
+
+    /// Check if a word is an auxiliary verb
+    fn is_auxiliary(&self) -> bool {
+        self.attributes().any(|a| a == WordAttr::Auxiliary)
+    }
+
+    /// Get word attributes
+    pub fn attributes(&self) -> impl Iterator<Item = WordAttr> {
+        self.attr.chars().filter_map(|a| WordAttr::try_from(a).ok())
+    }
+
+    /// Check if a word has irregular forms
+    pub fn is_irregular(&self) -> bool {
+        !self.irregular_forms.is_empty()
+    }
+
+    /// Get the rule which produces a word form
+    pub fn form_rule(&self, form: &str) -> Option<FormRule> {
+        for variant in self.variant_spellings() {
+            if variant == form {
+                return if variant == self.lemma {
+                    Some(FormRule::Lemma)
+                } else {
+                    Some(FormRule::Variant)
+                };
+            }
+            if self.irregular_forms.is_empty() {
+                if self.has_inflected_forms() {
+                    let forms =
+                        self.word_class.build_regular_forms(self, &variant);
+                    for (rule, f) in forms {
+                        if f == form {
+                            return Some(rule);
+                        }
+                    }
+                }
+            } else {
+                for f in &self.irregular_forms {
+                    if decode_irregular(&variant, f).is_ok_and(|f| f == form) {
+                        return Some(FormRule::Irregular);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Get the position of an irregular form in the lexicon entry
+    ///
+    /// For verbs, the positions are: present, present participle, past and
+    /// past participle (e.g. `go:V,goes,going,went,gone`).
+    pub fn irregular_position(&self, form: &str) -> Option<usize> {
+        for variant in self.variant_spellings() {
+            for (i, f) in self.irregular_forms.iter().enumerate() {
+                if decode_irregular(&variant, f).is_ok_and(|f| f == form) {
+                    return Some(i);
+                }
+            }
+        }
+        None
+    }
+
     /// Check if a word has inflected forms
     fn has_inflected_forms(&self) -> bool {
         match self.word_class() {
@@ -347,8 +701,8 @@ impl Lexeme {
         self.forms.push(lemma.to_string());
         if self.irregular_forms.is_empty() {
             if self.has_inflected_forms() {
-                self.forms
-                    .extend(self.word_class.build_regular_forms(self, lemma));
+                let forms = self.word_class.build_regular_forms(self, lemma);
+                self.forms.extend(forms.into_iter().map(|(_r, f)| f));
             }
         } else {
             for form in &self.irregular_forms {
@@ -384,11 +738,132 @@ fn noun_plural(lemma: &str) -> String {
     }
 }
 
+/// Syllable counts of words which do not follow the rules (sorted)
+const SYLLABLE_EXCEPTIONS: &[(&str, usize)] = &[
+    ("acne", 2),
+    ("apostrophe", 4),
+    ("area", 3),
+    ("being", 2),
+    ("business", 2),
+    ("cafe", 2),
+    ("catastrophe", 4),
+    ("colonel", 2),
+    ("coyote", 3),
+    ("create", 2),
+    ("created", 3),
+    ("diet", 2),
+    ("every", 2),
+    ("eye", 1),
+    ("eyes", 1),
+    ("giant", 2),
+    ("hyperbole", 4),
+    ("idea", 3),
+    ("karate", 3),
+    ("maybe", 2),
+    ("naive", 2),
+    ("people", 2),
+    ("poem", 2),
+    ("poet", 2),
+    ("quiet", 2),
+    ("recipe", 3),
+    ("science", 2),
+    ("simile", 3),
+    ("society", 4),
+    ("wednesday", 2),
+];
+
+/// Vowel pairs pronounced as two syllables, except after some letters
+/// (e.g. "lion" and "various", but not "nation" or "religion")
+const HIATUS: &[(&str, &str)] = &[
+    ("eo", "g"),
+    ("ia", "cgstx"),
+    ("io", "cgstx"),
+    ("iu", "cgstx"),
+    ("ua", "gq"),
+    ("uo", "gq"),
+];
+
 /// Check if a character is a vowel
 fn is_vowel(c: char) -> bool {
     matches!(c, 'a' | 'e' | 'i' | 'o' | 'u' | 'y')
 }
 
+/// Count syllables in a word
+///
+/// Each group of adjacent vowels is counted, except a final silent `e`
+/// (including `-ed` and `-es` endings, and before suffixes like `-ly`).
+/// A consonant before `-le` makes a syllable, as does `-sm`, and some vowel
+/// pairs are split (e.g. "piano").  Words which break these rules are
+/// looked up in a table of exceptions.
+pub fn syllables(word: &str) -> usize {
+    let word = word.to_lowercase();
+    if let Ok(i) =
+        SYLLABLE_EXCEPTIONS.binary_search_by_key(&word.as_str(), |(w, _c)| w)
+    {
+        return SYLLABLE_EXCEPTIONS[i].1;
+    }
+    for suffix in ["ful", "less", "ly", "ment", "ness"] {
+        if let Some(stem) = word.strip_suffix(suffix)
+            && stem.ends_with('e')
+            && stem.len() > 2
+        {
+            return syllables(stem) + 1;
+        }
+    }
+    let chars: Vec<char> = word.chars().collect();
+    let mut count = 0;
+    let mut prev = false;
+    for i in 0..chars.len() {
+        let vowel = is_vowel_at(&chars, i);
+        if vowel && (!prev || is_hiatus(&chars, i)) {
+            count += 1;
+        }
+        prev = vowel;
+    }
+    if count > 1 && has_silent_e(&word) {
+        count -= 1;
+    }
+    if count > 0 && word.ends_with("sm") {
+        count += 1;
+    }
+    count.max(1)
+}
+
+/// Check if a character in a word is a vowel
+fn is_vowel_at(chars: &[char], i: usize) -> bool {
+    match chars[i] {
+        // "y" before a vowel is a consonant (e.g. "yes", "player")
+        'y' => !chars.get(i + 1).is_some_and(|c| *c != 'y' && is_vowel(*c)),
+        c => is_vowel(c),
+    }
+}
+
+/// Check if a vowel starts a syllable after the previous vowel
+fn is_hiatus(chars: &[char], i: usize) -> bool {
+    i > 0
+        && HIATUS.iter().any(|(pair, before)| {
+            pair.chars().eq(chars[i - 1..=i].iter().copied())
+                && !(i > 1 && before.contains(chars[i - 2]))
+        })
+}
+
+/// Check if a word ends with a silent `e` (or `-ed` / `-es`)
+fn has_silent_e(word: &str) -> bool {
+    let chars: Vec<char> = word.chars().rev().take(4).collect();
+    match chars.as_slice() {
+        ['e', 'l', c, ..] => is_vowel(*c),
+        ['s' | 'd', 'e', 'l', c, ..] => is_vowel(*c),
+        ['e', 'e', ..] => false,
+        ['e', c, ..] => !is_vowel(*c),
+        ['d', 'e', c, ..] => !is_vowel(*c) && !matches!(c, 't' | 'd'),
+        ['s', 'e', 'h' | 'g', ..] => false,
+        ['s', 'e', c, ..] => {
+            !is_vowel(*c) && !matches!(c, 'c' | 's' | 'x' | 'z')
+        }
+        _ => false,
+    }
+}
+
 /// Check if a word ends with a consonant which should repeat
 fn consonant_end_repeat(s: &str) -> Option<char> {
     // consonant doubling rules (as far as I can tell):
@@ -518,6 +993,7 @@ fn adjective_superlative(lemma: &str) -> String {
 #[cfg(test)]
 mod test {
     use super::*;
+    use alloc::vec;
 
     #[test]
     fn variants() {
@@ -541,10 +1017,134 @@ mod test {
         );
     }
 
+    #[test]
+    fn labeled_forms() {
+        let labeled = |line: &str| -> Vec<(String, Inflection)> {
+            Lexeme::try_from(line)
+                .unwrap()
+                .labeled_forms()
+                .into_iter()
+                .map(|f| (f.text().to_string(), f.feature()))
+                .collect()
+        };
+        let form = |text: &str, feature| (text.to_string(), feature);
+        use Inflection::*;
+        assert_eq!(
+            labeled("walk:V"),
+            [
+                form("walk", Base),
+                form("walks", Present),
+                form("walking", PresentParticiple),
+                form("walked", PastTense),
+                form("walked", PastParticiple),
+            ]
+        );
+        assert_eq!(
+            labeled("go:V,goes,going,went,gone")[3..],
+            [form("went", PastTense), form("gone", PastParticiple)]
+        );
+        assert_eq!(
+            labeled("cut:V,cuts,cutting")[..3],
+            [
+                form("cut", Base),
+                form("cut", PastTense),
+                form("cut", PastParticiple)
+            ]
+        );
+        assert_eq!(labeled("mouse:N,mice")[1], form("mice", Plural));
+        assert_eq!(
+            labeled("good:A.c,better,best")[1..],
+            [form("better", Comparative), form("best", Superlative)]
+        );
+        assert_eq!(
+            labeled("be:V,am,are,is,was,were,been,being,art")[5..],
+            [
+                form("were", PastTense),
+                form("been", PastParticiple),
+                form("being", PresentParticiple),
+                form("art", Other),
+            ]
+        );
+    }
+
+    #[cfg(all(feature = "serde", feature = "std"))]
+    #[test]
+    fn serde() {
+        let lex = Lexeme::try_from("mouse:N,mice").unwrap();
+        let json = serde_json::to_string(&lex).unwrap();
+        assert_eq!(json, "\"mouse:N,mice\"");
+        assert_eq!(serde_json::from_str::<Lexeme>(&json).unwrap(), lex);
+        let json = serde_json::to_string(&WordClass::Noun).unwrap();
+        assert_eq!(json, "\"Noun\"");
+        assert!(serde_json::from_str::<Lexeme>("\"mouse\"").is_err());
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(Lexeme::try_from("stop"), Err(Error::Lexeme(_))));
+        assert!(matches!(
+            Lexeme::try_from("stop:X"),
+            Err(Error::WordClass(wc)) if wc == "X"
+        ));
+        assert!(matches!(WordAttr::try_from('q'), Err(Error::WordAttr('q'))));
+    }
+
+    #[test]
+    fn form_rules() {
+        let lex = Lexeme::try_from("stop:V").unwrap();
+        assert_eq!(lex.form_rule("stop"), Some(FormRule::Lemma));
+        assert_eq!(lex.form_rule("stopped"), Some(FormRule::Past));
+        assert_eq!(
+            lex.form_rule("stopping"),
+            Some(FormRule::PresentParticiple)
+        );
+        assert_eq!(lex.form_rule("stoped"), None);
+        let lex = Lexeme::try_from("café:N").unwrap();
+        assert_eq!(lex.form_rule("cafes"), Some(FormRule::Plural));
+        assert_eq!(lex.form_rule("cafe"), Some(FormRule::Variant));
+        let lex = Lexeme::try_from("mouse:N,mice").unwrap();
+        assert_eq!(lex.form_rule("mice"), Some(FormRule::Irregular));
+    }
+
     #[test]
     fn irregular() {
         let a = decode_irregular("addendum", "-da").unwrap();
         let form = encode_irregular("addendum", &a);
         assert_eq!(form, "-da");
     }
+
+    #[test]
+    fn count_syllables() {
+        for (word, count) in [
+            ("cat", 1),
+            ("table", 2),
+            ("whole", 1),
+            ("changed", 1),
+            ("wanted", 2),
+            ("makes", 1),
+            ("horses", 2),
+            ("watches", 2),
+            ("summer's", 2),
+            ("melody", 3),
+            ("lovely", 2),
+            ("homeless", 2),
+            ("tables", 2),
+            ("tabled", 2),
+            ("miles", 1),
+            ("piano", 3),
+            ("various", 3),
+            ("nation", 2),
+            ("religion", 3),
+            ("actual", 3),
+            ("quality", 3),
+            ("player", 2),
+            ("beyond", 2),
+            ("prism", 2),
+            ("people", 2),
+            ("Idea", 3),
+        ] {
+            assert_eq!(syllables(word), count, "{word}");
+        }
+        assert!(SYLLABLE_EXCEPTIONS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
 }
@@ -0,0 +1,248 @@
+use serde_json::Value;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+/// Run the booky binary with arguments and stdin
+fn booky(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_booky"))
+        .args(args)
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // the binary may exit without reading stdin
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+/// Run the booky binary with a cache directory (no stdin)
+fn booky_cached(args: &[&str], cache: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_booky"))
+        .args(args)
+        .env("NO_COLOR", "1")
+        .env("XDG_CACHE_HOME", cache)
+        .env_remove("BOOKY_NO_CACHE")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap()
+}
+
+/// Get the exit code of a run
+fn code(out: &Output) -> i32 {
+    out.status.code().unwrap()
+}
+
+/// Parse JSON output
+fn json(bytes: &[u8]) -> Value {
+    serde_json::from_slice(bytes).unwrap()
+}
+
+/// Make an empty scratch directory for one test
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("booky-cli-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn check() {
+    let out = booky(&["--json", "check"], "The colour is red.\n");
+    assert_eq!(code(&out), 0);
+    assert_eq!(json(&out.stdout)["misspellings"], Value::Array(vec![]));
+    let out = booky(&["--json", "check"], "The colur is red.\n");
+    assert_eq!(code(&out), 1);
+    let doc = json(&out.stdout);
+    let miss = &doc["misspellings"][0];
+    assert_eq!(miss["word"], "colur");
+    assert_eq!(
+        (miss["line"].as_u64(), miss["column"].as_u64()),
+        (Some(1), Some(5))
+    );
+    assert!(out.stderr.is_empty());
+}
+
+#[test]
+fn lexicon_validate() {
+    let dir = scratch("validate");
+    let good = dir.join("good.csv");
+    fs::write(&good, "blorf:V\nsnark:N\n").unwrap();
+    let args = ["lexicon", "validate", "-f", good.to_str().unwrap()];
+    let out = booky(&args, "");
+    assert_eq!(code(&out), 0);
+    let bad = dir.join("bad.csv");
+    fs::write(&bad, "snark:N\nblorf:V\nblorf:V\nxx:Q\n").unwrap();
+    let args = ["--json", "lexicon", "validate", "-f", bad.to_str().unwrap()];
+    let out = booky(&args, "");
+    assert_eq!(code(&out), 1);
+    let problems: Vec<_> = json(&out.stdout)["problems"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| (p["line"].as_u64().unwrap(), p["problem"].clone()))
+        .collect();
+    assert_eq!(
+        problems,
+        [
+            (2, "out of order".into()),
+            (3, "duplicate".into()),
+            (4, "parse error".into())
+        ]
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn usage_errors() {
+    let out = booky(&["--bogus"], "");
+    assert_eq!(code(&out), 2);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("--bogus"));
+    let out = booky(&["--json", "--bogus"], "");
+    assert_eq!(code(&out), 2);
+    let err = &json(&out.stderr)["error"];
+    assert_eq!(
+        (err["code"].as_u64(), &err["kind"]),
+        (Some(2), &"usage".into())
+    );
+    let out = booky(&["--json", "read", "-k", "z"], "text");
+    assert_eq!(code(&out), 2);
+    assert!(out.stdout.is_empty());
+    // kinds are no longer positional
+    let out = booky(&["read", "l,u"], "text");
+    assert_eq!(code(&out), 2);
+    assert!(String::from_utf8_lossy(&out.stderr).contains("-k l,u"));
+}
+
+#[test]
+fn missing_file() {
+    let dir = scratch("missing");
+    let path = dir.join("missing.txt");
+    let out = booky(&["read", path.to_str().unwrap()], "");
+    assert_eq!(code(&out), 3);
+    let out = booky(&["--json", "read", path.to_str().unwrap()], "");
+    assert_eq!(code(&out), 3);
+    let err = &json(&out.stderr)["error"];
+    assert_eq!(
+        (err["code"].as_u64(), &err["kind"]),
+        (Some(3), &"io".into())
+    );
+    assert!(!err["causes"].as_array().unwrap().is_empty());
+    let pattern = dir.join("*.txt");
+    let out = booky(&["--json", "read", pattern.to_str().unwrap()], "");
+    assert_eq!(code(&out), 3);
+    let err = &json(&out.stderr)["error"];
+    assert!(
+        err["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("No files match")
+    );
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn input_expansion() {
+    let dir = scratch("expand");
+    fs::create_dir(dir.join("sub")).unwrap();
+    fs::write(dir.join("a.txt"), "teh colur\n").unwrap();
+    fs::write(dir.join("b.md"), "wrld\n").unwrap();
+    fs::write(dir.join("sub").join("c.txt"), "Hello wrld\n").unwrap();
+    let words = |args: &[&str]| -> Vec<String> {
+        let out = booky(args, "");
+        assert_eq!(code(&out), 1, "{args:?}");
+        json(&out.stdout)["misspellings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["word"].as_str().unwrap().to_string())
+            .collect()
+    };
+    let pattern = dir.join("*.txt");
+    let pattern = pattern.to_str().unwrap();
+    assert_eq!(words(&["--json", "check", pattern]), ["teh", "colur"]);
+    let dir_arg = dir.to_str().unwrap();
+    assert_eq!(
+        words(&["--json", "check", "-R", dir_arg]),
+        ["teh", "colur", "wrld", "wrld"]
+    );
+    // a directory without --recursive is a usage error
+    let out = booky(&["check", dir_arg], "");
+    assert_eq!(code(&out), 2);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn lexicon_cache() {
+    let dir = scratch("cache");
+    let text = dir.join("text.txt");
+    fs::write(&text, "The colour is red.\n").unwrap();
+    let args = ["check", text.to_str().unwrap()];
+    // a cache from another version or lexicon is never read
+    let stale = dir.join("booky").join("lexicon-0.0.0-00000000.bin");
+    fs::create_dir_all(stale.parent().unwrap()).unwrap();
+    fs::write(&stale, "garbage").unwrap();
+    assert_eq!(code(&booky_cached(&args, &dir)), 0);
+    let cache: Vec<_> = fs::read_dir(dir.join("booky"))
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| *p != stale)
+        .collect();
+    assert_eq!(cache.len(), 1);
+    let len = fs::metadata(&cache[0]).unwrap().len();
+    assert!(len > 0);
+    // a truncated cache falls back to the built-in lexicon, and is rewritten
+    let buf = fs::read(&cache[0]).unwrap();
+    fs::write(&cache[0], &buf[..buf.len() / 2]).unwrap();
+    let out = booky_cached(&args, &dir);
+    assert_eq!(code(&out), 0);
+    assert!(out.stderr.is_empty());
+    assert_eq!(fs::metadata(&cache[0]).unwrap().len(), len);
+    assert_eq!(fs::read(&stale).unwrap(), b"garbage");
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn read_lemmas() {
+    let text = "The runner runs and ran";
+    let args = ["read", "-k", "l", "-f", "csv", "--sort", "alpha"];
+    let out = booky(&[&args[..], &["--by-lemma"]].concat(), text);
+    assert_eq!(code(&out), 0);
+    let csv = String::from_utf8(out.stdout).unwrap();
+    assert!(csv.contains("\n2,run,l\n"));
+    let out = booky(&[&args[..], &["--lemmas"]].concat(), text);
+    assert_eq!(code(&out), 0);
+    let csv = String::from_utf8(out.stdout).unwrap();
+    assert!(csv.starts_with("seen,word,kind,lemma\n"));
+    assert!(csv.contains("\n1,ran,l,run\n"));
+    assert_eq!(code(&booky(&["read", "--lemma"], text)), 2);
+}
+
+#[test]
+fn stats_vocab() {
+    let dir = scratch("vocab");
+    let first = dir.join("first.txt");
+    let second = dir.join("second.txt");
+    fs::write(&first, "The cat sat on the mat.\n").unwrap();
+    fs::write(&second, "A dog ran far away today.\n").unwrap();
+    let args = [
+        "--json",
+        "stats",
+        "--vocab",
+        first.to_str().unwrap(),
+        second.to_str().unwrap(),
+    ];
+    let out = booky(&args, "");
+    assert_eq!(code(&out), 0);
+    let doc = json(&out.stdout);
+    assert_eq!(doc["files"][0]["growth"], serde_json::json!([[6, 5]]));
+    assert_eq!(doc["files"][1]["growth"], serde_json::json!([[6, 6]]));
+    // the total grows over both files in order
+    assert_eq!(doc["total"]["growth"], serde_json::json!([[12, 11]]));
+    assert_eq!(doc["total"]["tokens"], 12);
+    fs::remove_dir_all(dir).unwrap();
+}